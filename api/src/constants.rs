@@ -13,3 +13,8 @@ pub const GATEWAY_CLASS_PARAMETERS_CRD_KIND: &str = "GatewayClassParameters";
 pub const GATEWAY_PARAMETERS_CRD_KIND: &str = "GatewayParameters";
 
 pub const GATEWAY_CLASS_CONTROLLER_NAME: &str = "vale-gateway.whitefamily.in/control-plane";
+
+/// Option of a `BackendTLSPolicy` naming the `kubernetes.io/tls` Secret, in the namespace of the
+/// policy, with the client certificate presented to the backend
+pub const BACKEND_TLS_CLIENT_CERTIFICATE_OPTION: &str =
+    "vale-gateway.whitefamily.in/client-certificate-secret";
@@ -145,6 +145,9 @@ pub struct GatewayConfiguration {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub client_addresses: Option<ClientAddresses>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_logs: Option<AccessLogs>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, Default, PartialEq)]
@@ -152,6 +155,18 @@ pub struct GatewayConfiguration {
 pub struct GatewayInstrumentation {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub open_telemetry: Option<GatewayInstrumentationOpenTelemetry>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prometheus: Option<GatewayInstrumentationPrometheus>,
+}
+
+/// Exposes the metrics of the gateway on `/metrics` of its admin port, for Prometheus to
+/// scrape
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayInstrumentationPrometheus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, Default, PartialEq)]
@@ -250,6 +265,118 @@ pub struct ProblemDetailErrorResponse {
     pub authority: Option<String>,
 }
 
+#[derive(Default, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum AccessLogFormat {
+    #[default]
+    Json,
+    /// The NCSA common log format
+    Common,
+    /// The common log format followed by the referer and user agent
+    Combined,
+    /// Lines rendered from `template`
+    Template,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum AccessLogSink {
+    #[default]
+    Stdout,
+    /// A file on a volume mounted into the gateway, rotated by size
+    File,
+    /// The OpenTelemetry logs exporter of the gateway
+    OpenTelemetry,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum AccessLogStatusClass {
+    /// `1xx` responses
+    Informational,
+    /// `2xx` responses
+    Successful,
+    /// `3xx` responses
+    Redirection,
+    /// `4xx` responses
+    ClientError,
+    /// `5xx` responses
+    ServerError,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogs {
+    #[serde(default)]
+    pub format: AccessLogFormat,
+
+    /// Template of the lines when `format` is `Template`, with `${field}` placeholders
+    /// such as `${method}`, `${path}`, `${status}` or `${duration_ms}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    #[serde(default)]
+    pub sink: AccessLogSink,
+
+    /// Required when `sink` is `File`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<AccessLogFile>,
+
+    /// Fraction of the requests to log, from 0 to 1. All requests are logged by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_ratio: Option<f64>,
+
+    /// Only requests answered with a status in one of these classes are logged. All
+    /// requests are logged when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_classes: Vec<AccessLogStatusClass>,
+
+    /// Sampling and status classes of the requests matching specific routes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<AccessLogRoute>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogFile {
+    pub path: String,
+
+    /// The file is rotated once it grows larger
+    #[serde(default = "access_log_file_max_size_bytes_default")]
+    pub max_size_bytes: u64,
+
+    /// Number of rotated files kept besides the current one
+    #[serde(default = "access_log_file_max_files_default")]
+    pub max_files: u32,
+}
+
+fn access_log_file_max_size_bytes_default() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn access_log_file_max_files_default() -> u32 {
+    5
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogRoute {
+    /// Name of the `HTTPRoute`
+    pub name: String,
+
+    /// Namespace of the `HTTPRoute`, the namespace of the gateway by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Overrides the sample ratio of the gateway for the route
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_ratio: Option<f64>,
+
+    /// Overrides the status classes of the gateway for the route
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_classes: Vec<AccessLogStatusClass>,
+}
+
 #[derive(Default, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema, IntoStaticStr)]
 #[serde(rename_all = "PascalCase")]
 #[strum(serialize_all = "PascalCase")]
@@ -530,3 +657,1088 @@ impl AccessControlFilterConditionReason {
         }
     }
 }
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "RateLimitFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "ratelimitfilter",
+    plural = "ratelimitfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "RateLimitFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitFilterSpec {
+    /// Where the token bucket key for a request comes from
+    pub key: RateLimitFilterKey,
+
+    /// Number of requests allowed per period
+    pub requests: u32,
+
+    /// Length of the period in seconds that `requests` are replenished over
+    #[serde(default = "rate_limit_filter_period_seconds_default")]
+    pub period_seconds: u32,
+
+    /// Maximum number of requests that may be made at once, defaults to `requests`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+
+    /// Whether the limit applies to each gateway pod or to all of them together
+    #[serde(default)]
+    pub mode: RateLimitFilterMode,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum RateLimitFilterMode {
+    /// Each gateway pod enforces the limit on its own
+    #[default]
+    Local,
+    /// Gateway pods share their consumption through the control plane, approximating
+    /// a limit across all replicas. Pods fall back to local limits when the control
+    /// plane is unreachable.
+    Global,
+}
+
+fn rate_limit_filter_period_seconds_default() -> u32 {
+    1
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitFilterKey {
+    pub source: RateLimitFilterKeySource,
+
+    /// Header to read the key from when `source` is `Header`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum RateLimitFilterKeySource {
+    ClientAddress,
+    Header,
+    Route,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitFilterStatus {
+    /// Conditions describe the current conditions of the `RateLimitFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `RateLimitFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to limit requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl RateLimitFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `RateLimitFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to limit requests
+    Ready,
+    /// `NotReady` - The filter is not ready to limit requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl RateLimitFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "JwtAuthFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "jwtauthfilter",
+    plural = "jwtauthfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "JwtAuthFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct JwtAuthFilterSpec {
+    /// Required value of the `iss` claim
+    pub issuer: String,
+
+    /// Accepted values of the `aud` claim, any audience is accepted when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audiences: Vec<String>,
+
+    /// Where the JSON Web Key Set used to verify signatures is stored
+    pub jwks: JwtAuthFilterJwks,
+
+    /// Tolerance in seconds applied when checking the `exp` and `nbf` claims
+    #[serde(default = "jwt_auth_filter_clock_skew_seconds_default")]
+    pub clock_skew_seconds: u32,
+
+    /// Claims of a verified token copied into headers of the upstream request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub claim_headers: Vec<JwtAuthFilterClaimHeader>,
+
+    /// Realm reported in the `WWW-Authenticate` header of rejected requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+}
+
+fn jwt_auth_filter_clock_skew_seconds_default() -> u32 {
+    60
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtAuthFilterJwks {
+    pub source: JwtAuthFilterJwksSource,
+
+    /// Name of the `Secret` or `ConfigMap`, in the namespace of the filter
+    pub name: String,
+
+    /// Key of the entry holding the JWKS document
+    #[serde(default = "jwt_auth_filter_jwks_key_default")]
+    pub key: String,
+}
+
+fn jwt_auth_filter_jwks_key_default() -> String {
+    "jwks.json".to_string()
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum JwtAuthFilterJwksSource {
+    Secret,
+    ConfigMap,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtAuthFilterClaimHeader {
+    /// Name of a top-level claim of the token
+    pub claim: String,
+
+    /// Name of the upstream request header to set to the claim value
+    pub header: String,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtAuthFilterStatus {
+    /// Conditions describe the current conditions of the `JwtAuthFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `JwtAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum JwtAuthFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to authenticate requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl JwtAuthFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `JwtAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum JwtAuthFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to authenticate requests
+    Ready,
+    /// `NotReady` - The filter is not ready to authenticate requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl JwtAuthFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "ExternalAuthFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "externalauthfilter",
+    plural = "externalauthfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "ExternalAuthFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAuthFilterSpec {
+    /// The authorization service, which receives the method, path and selected headers of
+    /// each request and allows it with a 2xx response
+    pub backend_ref: ExternalAuthFilterBackendRef,
+
+    /// Prefix prepended to the request path when calling the authorization service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
+
+    /// Request headers forwarded to the authorization service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_headers: Vec<String>,
+
+    /// Headers of an allowing response copied into the upstream request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upstream_headers: Vec<String>,
+
+    /// Whether requests are allowed or rejected when the authorization service cannot be
+    /// reached, times out or fails with a 5xx response
+    #[serde(default)]
+    pub failure_mode: ExternalAuthFilterFailureMode,
+
+    /// How long to wait for the authorization service
+    #[serde(default = "external_auth_filter_timeout_milliseconds_default")]
+    pub timeout_milliseconds: u32,
+
+    /// Caches decisions of the authorization service by the value of a request header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<ExternalAuthFilterCache>,
+}
+
+fn external_auth_filter_timeout_milliseconds_default() -> u32 {
+    1000
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAuthFilterBackendRef {
+    /// Name of the `Service`, in the namespace of the filter
+    pub name: String,
+
+    pub port: u16,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum ExternalAuthFilterFailureMode {
+    /// Requests are rejected while the authorization service is unavailable
+    #[default]
+    Closed,
+    /// Requests are let through while the authorization service is unavailable
+    Open,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAuthFilterCache {
+    /// Request header whose value decisions are cached by, such as `Authorization`.
+    /// Requests without the header are never cached.
+    pub key_header: String,
+
+    /// How long a decision is reused
+    #[serde(default = "external_auth_filter_cache_ttl_seconds_default")]
+    pub ttl_seconds: u32,
+}
+
+fn external_auth_filter_cache_ttl_seconds_default() -> u32 {
+    60
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAuthFilterStatus {
+    /// Conditions describe the current conditions of the `ExternalAuthFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `ExternalAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalAuthFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to authorize requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl ExternalAuthFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `ExternalAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalAuthFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to authorize requests
+    Ready,
+    /// `NotReady` - The filter is not ready to authorize requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl ExternalAuthFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "BasicAuthFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "basicauthfilter",
+    plural = "basicauthfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "BasicAuthFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct BasicAuthFilterSpec {
+    /// `Secret` holding the accepted credentials in htpasswd format, with bcrypt,
+    /// `{SHA}` or argon2 password hashes
+    pub secret_ref: BasicAuthFilterSecretRef,
+
+    /// Removes the `Authorization` header from requests before they are proxied
+    #[serde(default)]
+    pub strip_authorization: bool,
+
+    /// Name of the upstream request header set to the authenticated username
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username_header: Option<String>,
+
+    /// Realm reported in the `WWW-Authenticate` header of rejected requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicAuthFilterSecretRef {
+    /// Name of the `Secret`, in the namespace of the filter
+    pub name: String,
+
+    /// Key of the entry holding the htpasswd file
+    #[serde(default = "basic_auth_filter_secret_key_default")]
+    pub key: String,
+}
+
+fn basic_auth_filter_secret_key_default() -> String {
+    "htpasswd".to_string()
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicAuthFilterStatus {
+    /// Conditions describe the current conditions of the `BasicAuthFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `BasicAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum BasicAuthFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to authenticate requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl BasicAuthFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `BasicAuthFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum BasicAuthFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to authenticate requests
+    Ready,
+    /// `NotReady` - The filter is not ready to authenticate requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl BasicAuthFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "CorsFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "corsfilter",
+    plural = "corsfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "CorsFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct CorsFilterSpec {
+    /// Origins allowed to make cross-origin requests
+    pub allow_origins: Vec<CorsFilterOrigin>,
+
+    /// Methods allowed in preflight requests, `*` allows any method. Only the
+    /// CORS-safelisted methods are allowed when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_methods: Vec<String>,
+
+    /// Request headers allowed in preflight requests, `*` allows any header
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_headers: Vec<String>,
+
+    /// Response headers exposed to the scripts of the allowed origins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expose_headers: Vec<String>,
+
+    /// Whether requests may include cookies and other credentials
+    #[serde(default)]
+    pub allow_credentials: bool,
+
+    /// How long browsers may cache the result of a preflight request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_seconds: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsFilterOrigin {
+    #[serde(rename = "type", default)]
+    pub type_: CorsFilterOriginMatchType,
+
+    /// The origin, such as `https://app.example.com`. `*` in a `Wildcard` origin
+    /// matches any characters other than `/`, and on its own matches any origin
+    pub value: String,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum CorsFilterOriginMatchType {
+    #[default]
+    Exact,
+    Wildcard,
+    RegularExpression,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsFilterStatus {
+    /// Conditions describe the current conditions of the `CorsFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `CorsFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to handle cross-origin requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl CorsFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `CorsFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to handle cross-origin requests
+    Ready,
+    /// `NotReady` - The filter is not ready to handle cross-origin requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl CorsFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "CompressionFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "compressionfilter",
+    plural = "compressionfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "CompressionFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct CompressionFilterSpec {
+    /// Algorithms in order of preference, used when the client accepts several of them
+    /// equally
+    #[serde(default = "compression_filter_algorithms_default")]
+    pub algorithms: Vec<CompressionFilterAlgorithm>,
+
+    /// Media types of the responses to compress, such as `application/json`. A
+    /// `type/*` entry matches any subtype
+    #[serde(default = "compression_filter_content_types_default")]
+    pub content_types: Vec<String>,
+
+    /// Responses with a smaller `Content-Length` are sent uncompressed. Responses
+    /// without one are always compressed
+    #[serde(default = "compression_filter_min_size_bytes_default")]
+    pub min_size_bytes: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum CompressionFilterAlgorithm {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+fn compression_filter_algorithms_default() -> Vec<CompressionFilterAlgorithm> {
+    vec![
+        CompressionFilterAlgorithm::Zstd,
+        CompressionFilterAlgorithm::Brotli,
+        CompressionFilterAlgorithm::Gzip,
+    ]
+}
+
+fn compression_filter_content_types_default() -> Vec<String> {
+    [
+        "text/*",
+        "application/javascript",
+        "application/json",
+        "application/xml",
+        "image/svg+xml",
+    ]
+    .into_iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+fn compression_filter_min_size_bytes_default() -> u32 {
+    1024
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressionFilterStatus {
+    /// Conditions describe the current conditions of the `CompressionFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `CompressionFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompressionFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to compress responses
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl CompressionFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `CompressionFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompressionFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to compress responses
+    Ready,
+    /// `NotReady` - The filter is not ready to compress responses
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl CompressionFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "CacheFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "cachefilter",
+    plural = "cachefilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "CacheFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct CacheFilterSpec {
+    /// Responses with a larger body are passed through without being cached
+    #[serde(default = "cache_filter_max_object_size_bytes_default")]
+    pub max_object_size_bytes: u32,
+
+    /// How long a stale response may be served while it is revalidated in the background,
+    /// used when the upstream does not set `stale-while-revalidate` itself
+    #[serde(default)]
+    pub stale_while_revalidate_seconds: u32,
+
+    /// How long a stale response may be served when the upstream fails, used when the
+    /// upstream does not set `stale-if-error` itself
+    #[serde(default)]
+    pub stale_if_error_seconds: u32,
+}
+
+fn cache_filter_max_object_size_bytes_default() -> u32 {
+    1024 * 1024
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheFilterStatus {
+    /// Conditions describe the current conditions of the `CacheFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `CacheFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to cache responses
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl CacheFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `CacheFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to cache responses
+    Ready,
+    /// `NotReady` - The filter is not ready to cache responses
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl CacheFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "RequestLimitsFilter",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "requestlimitsfilter",
+    plural = "requestlimitsfilters"
+)]
+#[kube(derive = "PartialEq")]
+#[kube(status = "RequestLimitsFilterStatus")]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLimitsFilterSpec {
+    /// Requests with a larger body are rejected with `413 Content Too Large`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_body_bytes: Option<u64>,
+
+    /// Requests whose header fields, names and values together, take more space are
+    /// rejected with `431 Request Header Fields Too Large`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_headers_bytes: Option<u32>,
+
+    /// Whether the request body is streamed to the upstream as it arrives, or buffered
+    /// in full first so it can be replayed. Buffering requires `maxRequestBodyBytes`
+    #[serde(default)]
+    pub body_mode: RequestLimitsFilterBodyMode,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum RequestLimitsFilterBodyMode {
+    #[default]
+    Streaming,
+    Buffered,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLimitsFilterStatus {
+    /// Conditions describe the current conditions of the `RequestLimitsFilter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+
+    /// `AttachedRoutes` indicates the number of routes that are using this filter
+    #[serde(default)]
+    pub attached_routes: i32,
+
+    /// `LastUpdated` indicates when the status was last updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<Time>,
+}
+
+/// Condition types for `RequestLimitsFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestLimitsFilterConditionType {
+    /// Accepted indicates whether the filter configuration is valid and accepted
+    Accepted,
+    /// Ready indicates whether the filter is ready to limit requests
+    Ready,
+    /// Attached indicates whether the filter is attached to any routes
+    Attached,
+}
+
+impl RequestLimitsFilterConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Ready => "Ready",
+            Self::Attached => "Attached",
+        }
+    }
+}
+
+/// Condition reasons for `RequestLimitsFilter` status
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestLimitsFilterConditionReason {
+    /// Accepted - The filter configuration is valid
+    Accepted,
+    /// `InvalidConfiguration` - The filter configuration is invalid
+    InvalidConfiguration,
+    /// Ready - The filter is ready to limit requests
+    Ready,
+    /// `NotReady` - The filter is not ready to limit requests
+    NotReady,
+    /// `AttachedToRoute` - The filter is attached to one or more routes
+    AttachedToRoute,
+    /// `NotAttached` - The filter is not attached to any routes
+    NotAttached,
+}
+
+impl RequestLimitsFilterConditionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+            Self::Ready => "Ready",
+            Self::NotReady => "NotReady",
+            Self::AttachedToRoute => "AttachedToRoute",
+            Self::NotAttached => "NotAttached",
+        }
+    }
+}
+
+/// A host outside of the cluster, such as a SaaS API, that `HTTPRoute`s can send requests to
+/// with a `backendRef` of this kind
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "Backend",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "backend",
+    plural = "backends"
+)]
+#[kube(derive = "PartialEq")]
+#[serde(rename_all = "camelCase")]
+pub struct BackendSpec {
+    /// Hostname or IP address of the host. Hostnames are resolved by the gateways, again as
+    /// their DNS records expire
+    pub host: String,
+
+    /// Port of the host, unless the `backendRef` sets one
+    pub port: u16,
+
+    /// Which Host header requests carry to the host
+    #[serde(default)]
+    pub host_header: BackendHostHeader,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+pub enum BackendHostHeader {
+    /// The hostname of the backend, which virtually hosted services require
+    #[default]
+    Rewrite,
+    /// The Host header of the client
+    Preserve,
+}
+
+/// How the gateways send traffic to the `Service`s and `Backend`s it targets, in the namespace
+/// of the policy
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[kube(
+    kind = "BackendTrafficPolicy",
+    group = "vale-gateway.whitefamily.in",
+    version = "v1alpha1",
+    namespaced,
+    singular = "backendtrafficpolicy",
+    plural = "backendtrafficpolicies"
+)]
+#[kube(derive = "PartialEq")]
+#[serde(rename_all = "camelCase")]
+pub struct BackendTrafficPolicySpec {
+    pub target_refs: Vec<BackendTrafficPolicyTargetRef>,
+
+    /// Ramps up the share of requests sent to endpoints that just became ready
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_start: Option<BackendTrafficPolicySlowStart>,
+
+    /// Rejects requests with `503 Service Unavailable` while the backend has too much work
+    /// in flight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<BackendTrafficPolicyCircuitBreaker>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendTrafficPolicyTargetRef {
+    /// `""` for a `Service`, `vale-gateway.whitefamily.in` for a `Backend`
+    #[serde(default)]
+    pub group: String,
+
+    pub kind: String,
+
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendTrafficPolicySlowStart {
+    /// How long the weight of a new endpoint takes to reach its full weight
+    pub window_seconds: u32,
+
+    /// Weight of a new endpoint when it becomes ready, in percent of its full weight
+    #[serde(default = "backend_traffic_policy_slow_start_min_weight_percent_default")]
+    pub min_weight_percent: u8,
+}
+
+fn backend_traffic_policy_slow_start_min_weight_percent_default() -> u8 {
+    10
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendTrafficPolicyCircuitBreaker {
+    /// Maximum number of in-flight requests to the backend, unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u32>,
+
+    /// Maximum number of requests waiting for a connection to the backend, unlimited when
+    /// unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pending_connections: Option<u32>,
+}
@@ -46,6 +46,16 @@ fn write_crds(out_dir: &Path) {
         GatewayParameters::crd(),
         AccessControlFilter::crd(),
         StaticResponseFilter::crd(),
+        RateLimitFilter::crd(),
+        JwtAuthFilter::crd(),
+        ExternalAuthFilter::crd(),
+        BasicAuthFilter::crd(),
+        CorsFilter::crd(),
+        CompressionFilter::crd(),
+        CacheFilter::crd(),
+        RequestLimitsFilter::crd(),
+        Backend::crd(),
+        BackendTrafficPolicy::crd(),
     ]
     .iter()
     .fold(file, |mut output, crd| {
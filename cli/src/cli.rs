@@ -109,6 +109,15 @@ pub enum Commands {
         #[arg(last = true)]
         command: Vec<String>,
     },
+    /// Purge the responses cached by gateway instances
+    #[command(name = "purge-cache")]
+    PurgeCache {
+        /// Gateway instance name
+        name: String,
+        /// Only purge responses cached for this hostname
+        #[arg(long)]
+        hostname: Option<String>,
+    },
     /// Show status of gateway resources
     Status {
         /// Resource type to show status for
@@ -5,6 +5,7 @@ pub mod exec;
 pub mod get;
 pub mod logs;
 pub mod port_forward;
+pub mod purge_cache;
 pub mod status;
 
 use anyhow::Result;
@@ -73,6 +74,12 @@ pub async fn handle_command(client: &Client, cli: &Cli) -> Result<()> {
         } => {
             exec::handle_exec_command(client, name, container.as_deref(), command, cli).await?;
         }
+        Commands::PurgeCache {
+            ref name,
+            ref hostname,
+        } => {
+            purge_cache::handle_purge_cache_command(client, name, hostname.as_deref(), cli).await?;
+        }
         Commands::Status {
             ref resource_type,
             ref name,
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use gateway_api::gateways::Gateway;
+use kube::api::{Patch, PatchParams};
+use kube::{Api, Client};
+use serde_json::json;
+use vg_core::ipc::cache::{CachePurgeRequest, CACHE_PURGE_ANNOTATION};
+
+use crate::cli::Cli;
+
+/// Annotate a gateway with a new cache purge request, which the control plane forwards to
+/// every pod of the gateway
+pub async fn purge_cache(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    hostname: Option<&str>,
+) -> Result<()> {
+    let request = CachePurgeRequest::builder()
+        .requested_at(Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true))
+        .hostname(hostname.map(str::to_string))
+        .build();
+    let patch = json!({
+        "metadata": {
+            "annotations": {
+                CACHE_PURGE_ANNOTATION: serde_json::to_string(&request)?,
+            },
+        },
+    });
+
+    let api: Api<Gateway> = Api::namespaced(client.clone(), namespace);
+    api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+    Ok(())
+}
+
+/// Handle purge-cache command
+pub async fn handle_purge_cache_command(
+    client: &Client,
+    name: &str,
+    hostname: Option<&str>,
+    cli: &Cli,
+) -> Result<()> {
+    let namespace = cli.namespace.as_deref().unwrap_or("default");
+    purge_cache(client, namespace, name, hostname).await?;
+
+    println!(
+        "Requested a purge of the cached responses of gateway {}/{} for {}",
+        namespace,
+        name,
+        hostname.unwrap_or("all hostnames")
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::{Method, Request, Response};
+    use kube::client::Body;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_purge_cache_annotates_gateway() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let service = {
+            let requests = requests.clone();
+            tower::service_fn(move |request: Request<Body>| {
+                let requests = requests.clone();
+                async move {
+                    let (parts, body) = request.into_parts();
+                    let body = body.collect_bytes().await.unwrap();
+                    requests.lock().unwrap().push((parts, body));
+
+                    let gateway = json!({
+                        "apiVersion": "gateway.networking.k8s.io/v1",
+                        "kind": "Gateway",
+                        "metadata": { "name": "gateway", "namespace": "apps" },
+                        "spec": { "gatewayClassName": "vale-gateway", "listeners": [] },
+                    });
+                    Ok::<_, std::convert::Infallible>(Response::new(Body::from(
+                        serde_json::to_vec(&gateway).unwrap(),
+                    )))
+                }
+            })
+        };
+        let client = Client::new(service, "default");
+
+        purge_cache(&client, "apps", "gateway", Some("echo.example.com"))
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let [(parts, body)] = requests.as_slice() else {
+            panic!("Expected a single request, got {}", requests.len());
+        };
+        assert_eq!(parts.method, Method::PATCH);
+        assert_eq!(
+            parts.uri.path(),
+            "/apis/gateway.networking.k8s.io/v1/namespaces/apps/gateways/gateway"
+        );
+
+        // The control plane reads the request back from the annotation
+        let patch: serde_json::Value = serde_json::from_slice(body).unwrap();
+        let annotation = patch["metadata"]["annotations"][CACHE_PURGE_ANNOTATION]
+            .as_str()
+            .unwrap();
+        let request: CachePurgeRequest = serde_json::from_str(annotation).unwrap();
+        assert_eq!(request.hostname().as_deref(), Some("echo.example.com"));
+        assert!(!request.requested_at().is_empty());
+    }
+}
@@ -10,6 +10,9 @@ use kube::{Api, Client};
 use serde::Serialize;
 use tabled::{Table, Tabled};
 use vg_api::v1alpha1::StaticResponseFilter;
+use vg_core::ipc::configuration::{
+    ConfigurationDrift, ConfigurationRollout, CONFIGURATION_ROLLOUT_ANNOTATION,
+};
 
 #[derive(Debug, Clone, clap::ValueEnum)]
 pub enum StatusResourceType {
@@ -21,6 +24,8 @@ pub enum StatusResourceType {
     GatewayClass,
     #[value(name = "staticresponsefilter", alias = "srf")]
     StaticResponseFilter,
+    #[value(name = "configuration", alias = "config")]
+    Configuration,
 }
 
 impl std::str::FromStr for StatusResourceType {
@@ -32,6 +37,7 @@ impl std::str::FromStr for StatusResourceType {
             "httproute" | "route" => Ok(StatusResourceType::HTTPRoute),
             "gatewayclass" | "gwc" => Ok(StatusResourceType::GatewayClass),
             "staticresponsefilter" | "srf" => Ok(StatusResourceType::StaticResponseFilter),
+            "configuration" | "config" => Ok(StatusResourceType::Configuration),
             _ => Err(anyhow::anyhow!("Invalid resource type: {}", s)),
         }
     }
@@ -113,6 +119,24 @@ struct StaticResponseFilterStatusRow {
     age: String,
 }
 
+#[derive(Tabled, Serialize)]
+struct ConfigurationStatusRow {
+    #[tabled(rename = "GATEWAY")]
+    gateway: String,
+    #[tabled(rename = "NAMESPACE")]
+    namespace: String,
+    #[tabled(rename = "POD")]
+    pod: String,
+    #[tabled(rename = "STATE")]
+    state: String,
+    #[tabled(rename = "APPLIED")]
+    applied_version: String,
+    #[tabled(rename = "LATEST")]
+    latest_version: String,
+    #[tabled(rename = "ERROR")]
+    error: String,
+}
+
 pub async fn handle_status_command(
     client: &Client,
     resource_type: &StatusResourceType,
@@ -132,6 +156,9 @@ pub async fn handle_status_command(
         StatusResourceType::StaticResponseFilter => {
             show_staticresponsefilter_status(client, name, cli).await?
         }
+        StatusResourceType::Configuration => {
+            show_configuration_status(client, name, &cli.output, cli.namespace.as_deref()).await?
+        }
     }
     Ok(())
 }
@@ -599,6 +626,135 @@ async fn show_staticresponsefilter_status(
     Ok(())
 }
 
+/// Shows which configuration version every gateway pod runs, to spot drift across replicas
+async fn show_configuration_status(
+    client: &Client,
+    name: Option<&str>,
+    format: &OutputFormat,
+    namespace: Option<&str>,
+) -> Result<()> {
+    let gateways = match (namespace, name) {
+        (Some(ns), Some(name)) => {
+            let api: Api<Gateway> = Api::namespaced(client.clone(), ns);
+            vec![api.get(name).await.context("Failed to get Gateway")?]
+        }
+        (Some(ns), None) => {
+            let api: Api<Gateway> = Api::namespaced(client.clone(), ns);
+            api.list(&Default::default())
+                .await
+                .context("Failed to list Gateways")?
+                .items
+        }
+        (None, Some(_)) => {
+            return Err(anyhow::anyhow!(
+                "Specify namespace when getting a specific Gateway"
+            ));
+        }
+        (None, None) => {
+            let api: Api<Gateway> = Api::all(client.clone());
+            api.list(&Default::default())
+                .await
+                .context("Failed to list Gateways")?
+                .items
+        }
+    };
+
+    if gateways.is_empty() {
+        println!("No Gateways found.");
+        return Ok(());
+    }
+
+    let mut rows: Vec<ConfigurationStatusRow> = Vec::new();
+
+    for gw in gateways {
+        let gateway = gw.metadata.name.unwrap_or_default();
+        let namespace = gw.metadata.namespace.unwrap_or_default();
+
+        let Some(rollout) = get_configuration_rollout(client, &gateway, &namespace).await else {
+            rows.push(ConfigurationStatusRow {
+                gateway,
+                namespace,
+                pod: "None".to_string(),
+                state: "Unknown".to_string(),
+                applied_version: "None".to_string(),
+                latest_version: "None".to_string(),
+                error: String::new(),
+            });
+            continue;
+        };
+
+        if rollout.pods().is_empty() {
+            rows.push(ConfigurationStatusRow {
+                gateway: gateway.clone(),
+                namespace: namespace.clone(),
+                pod: "None".to_string(),
+                state: "Pending".to_string(),
+                applied_version: "None".to_string(),
+                latest_version: rollout.version().to_string(),
+                error: String::new(),
+            });
+        }
+
+        for (pod, state) in rollout.pods() {
+            let drift = match state.drift(rollout.version()) {
+                ConfigurationDrift::InSync => "In Sync",
+                ConfigurationDrift::Pending => "Pending",
+                ConfigurationDrift::Rejected => "Rejected",
+            };
+            rows.push(ConfigurationStatusRow {
+                gateway: gateway.clone(),
+                namespace: namespace.clone(),
+                pod: pod.clone(),
+                state: drift.to_string(),
+                applied_version: state
+                    .applied_version()
+                    .map(|version| version.to_string())
+                    .unwrap_or_else(|| "None".to_string()),
+                latest_version: rollout.version().to_string(),
+                error: state.error().clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    let mut table = match format {
+        OutputFormat::Table => TableTheme::apply_status(Table::new(rows)),
+        OutputFormat::TableEmoji => TableTheme::apply_status_with_emoji(Table::new(rows)),
+        OutputFormat::Kubectl => TableTheme::apply_kubectl(Table::new(rows)),
+        OutputFormat::Wide | OutputFormat::WideEmoji => TableTheme::apply_status(Table::new(rows)),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&rows).unwrap());
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&rows).unwrap());
+            return Ok(());
+        }
+    };
+
+    if matches!(format, OutputFormat::TableEmoji | OutputFormat::WideEmoji) {
+        table = EmojiFormatter::apply_to_column(table, 3); // State
+    }
+
+    println!("{}", table);
+    Ok(())
+}
+
+/// The rollout the control plane recorded on the Deployment of a Gateway
+async fn get_configuration_rollout(
+    client: &Client,
+    name: &str,
+    namespace: &str,
+) -> Option<ConfigurationRollout> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    let deployment = api.get(name).await.ok()?;
+    let rollout = deployment
+        .metadata
+        .annotations
+        .as_ref()?
+        .get(CONFIGURATION_ROLLOUT_ANNOTATION)?;
+    serde_json::from_str(rollout).ok()
+}
+
 fn format_age(
     creation_timestamp: Option<&k8s_openapi::apimachinery::pkg::apis::meta::v1::Time>,
 ) -> String {
@@ -37,6 +37,7 @@ impl EmojiFormatter {
             "accepted" => "✅ Accepted".to_string(),
             "rejected" => "❌ Rejected".to_string(),
             "programmed" => "🔧 Programmed".to_string(),
+            "in sync" => "✅ In Sync".to_string(),
             "not programmed" => "⚠️ Not Programmed".to_string(),
             "resolved" => "✅ Resolved".to_string(),
             "not resolved" => "❌ Not Resolved".to_string(),
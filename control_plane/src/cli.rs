@@ -1,5 +1,8 @@
+use crate::ipc::auth::IpcAuthentication;
+use crate::ipc::tls::IpcTls;
 use clap::Parser;
 use getset::{CopyGetters, Getters};
+use std::net::IpAddr;
 
 #[derive(Parser, Getters, CopyGetters)]
 #[command(about = "A Kubernetes control plane for Vale Gateway", long_about = None)]
@@ -16,7 +19,38 @@ pub struct Cli {
     #[arg(env = "POD_NAME", long = "pod-name")]
     pod_name: String,
 
+    /// The address gateways reach this instance on when it is primary, which its IPC
+    /// certificate is issued for
+    #[getset(get_copy = "pub")]
+    #[arg(env = "POD_IP", long = "pod-ip")]
+    pod_ip: Option<IpAddr>,
+
     #[getset(get = "pub")]
     #[arg(env = "VALE_GATEWAY_INSTANCE", long = "instance")]
     instance_name: String,
+
+    /// Serves the metrics of the control plane on `/metrics` of the IPC port
+    #[getset(get_copy = "pub")]
+    #[arg(env = "VALE_GATEWAY_PROMETHEUS_ENABLED", long = "prometheus-enabled")]
+    prometheus_enabled: bool,
+
+    /// How gateways authenticate to the IPC endpoint
+    #[getset(get_copy = "pub")]
+    #[arg(
+        default_value = "service-account-token",
+        env = "VALE_GATEWAY_IPC_AUTHENTICATION",
+        long = "ipc-authentication",
+        value_enum
+    )]
+    ipc_authentication: IpcAuthentication,
+
+    /// How the IPC endpoint protects what it serves on the network
+    #[getset(get_copy = "pub")]
+    #[arg(
+        default_value = "control-plane-ca",
+        env = "VALE_GATEWAY_IPC_TLS",
+        long = "ipc-tls",
+        value_enum
+    )]
+    ipc_tls: IpcTls,
 }
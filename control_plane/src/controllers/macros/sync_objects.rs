@@ -68,7 +68,7 @@ macro_rules! sync_objects {
 
         gtmpl_fn!(
             fn quote(s: String) -> Result<String, FuncError> {
-                Ok(format!("\"{}\"", s.replace("\"", "\\\"")))
+                Ok(format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")))
             }
         );
 
@@ -125,7 +125,8 @@ macro_rules! sync_objects {
                 object_ref.namespace().as_ref().expect("Missing namespace"),
             );
 
-            let exists = api.get_metadata(object_ref.name()).await.is_ok();
+            let existing_metadata = api.get_metadata(object_ref.name()).await.ok().map(|object| object.metadata);
+            let exists = existing_metadata.is_some();
 
             trace!(
                 "Processing action: {:?} for object: {} {}",
@@ -144,7 +145,23 @@ macro_rules! sync_objects {
                         .ok();
                 }
                 (Upsert(_, parent_ref, value, object_overrides), true) => {
-                    let object = render_object(&template, &object_ref, parent_ref, value, object_overrides);
+                    let object = render_object(&template, &object_ref, parent_ref.clone(), value, object_overrides);
+                    // Objects rendered with a controller are only patched while that controller still
+                    // owns them, so an existing object that happens to have the same name is left alone
+                    let controller_uid = |metadata: &ObjectMeta| {
+                        metadata
+                            .owner_references
+                            .iter()
+                            .flatten()
+                            .find(|owner_reference| owner_reference.controller == Some(true))
+                            .map(|owner_reference| owner_reference.uid.clone())
+                    };
+                    if let Some(desired_controller_uid) = controller_uid(&object.metadata)
+                        && existing_metadata.as_ref().and_then(controller_uid) != Some(desired_controller_uid)
+                    {
+                        warn!("Not patching object {} as it is not controlled by {}", object_ref, parent_ref);
+                        return;
+                    }
                     info!("Patching object: {}", object_ref);
                     api.patch(object_ref.name(), &Default::default(), &Patch::Strategic(object))
                         .await
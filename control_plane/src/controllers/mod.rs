@@ -9,14 +9,16 @@ use self::filters::{
     filter_gateways, filter_http_routes,
 };
 use self::sync::{
-    sync_gateway_class_status, sync_gateway_configmaps, sync_gateway_deployments,
-    sync_gateway_services, sync_gateway_status, sync_http_route_status, sync_static_response_filter_status,
+    sync_gateway_cache_purges, sync_gateway_class_status, sync_gateway_configmaps,
+    sync_gateway_deployments, sync_gateway_service_accounts, sync_gateway_services,
+    sync_gateway_status, sync_http_route_status, sync_static_response_filter_status,
     SyncGatewayConfigmapsParams,
 };
 use self::transformers::{
-    bind_static_responses_cache, collect_extension_filters_by_gateway, collect_gateway_instances,
-    collect_http_route_backends, collect_http_routes_by_gateway, collect_service_backends,
-    determine_route_attachment_states,
+    bind_secrets_cache, bind_static_responses_cache, collect_backend_tls, collect_backend_traffic,
+    collect_extension_filters_by_gateway, collect_external_auth_backends,
+    collect_external_backends, collect_gateway_instances, collect_http_route_backends,
+    collect_http_routes_by_gateway, collect_service_backends, determine_route_attachment_states,
 };
 use crate::controllers::instances::{determine_instance_role, watch_leader_instance_ip_addr};
 use crate::ipc::IpcServices;
@@ -24,17 +26,25 @@ use crate::kubernetes::KubeClientCell;
 use crate::options::Options;
 use crate::watch_objects;
 use anyhow::Result;
+use gateway_api::apis::experimental::backendtlspolicies::BackendTLSPolicy;
 use gateway_api::apis::standard::gatewayclasses::GatewayClass;
 use gateway_api::apis::standard::gateways::Gateway;
 use gateway_api::apis::standard::httproutes::HTTPRoute;
 use getset::{CloneGetters, Getters};
+use k8s_openapi::api::core::v1::{ConfigMap, Service};
 use k8s_openapi::api::discovery::v1::EndpointSlice;
 use std::sync::Arc;
+pub use sync::gateway_service_account_name;
 use thiserror::Error;
-pub use transformers::StaticResponsesCache;
+pub use transformers::{
+    ClientCertificatesCache, HtpasswdCache, JwksCache, SecretSource, SecretsCache,
+    StaticResponsesCache,
+};
 use typed_builder::TypedBuilder;
 use vg_api::v1alpha1::{
-    AccessControlFilter, GatewayClassParameters, GatewayParameters, StaticResponseFilter,
+    AccessControlFilter, Backend, BackendTrafficPolicy, BasicAuthFilter, CacheFilter,
+    CompressionFilter, CorsFilter, ExternalAuthFilter, GatewayClassParameters, GatewayParameters,
+    JwtAuthFilter, RateLimitFilter, RequestLimitsFilter, StaticResponseFilter,
 };
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
@@ -52,6 +62,12 @@ pub struct SpawnControllersParams {
     instance_name: String,
     #[getset(get = "pub")]
     static_responses_cache: StaticResponsesCache,
+    #[getset(get = "pub")]
+    jwks_cache: JwksCache,
+    #[getset(get = "pub")]
+    htpasswd_cache: HtpasswdCache,
+    #[getset(get = "pub")]
+    client_certificates_cache: ClientCertificatesCache,
 }
 
 pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersParams) {
@@ -75,7 +91,14 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
     let gateway_classes_rx = watch_objects!(options, task_builder, GatewayClass, kube_client_rx);
     let gateways_rx = watch_objects!(options, task_builder, Gateway, kube_client_rx);
     let http_routes_rx = watch_objects!(options, task_builder, HTTPRoute, kube_client_rx);
+    let services_rx = watch_objects!(options, task_builder, Service, kube_client_rx);
     let endpoint_slices_rx = watch_objects!(options, task_builder, EndpointSlice, kube_client_rx);
+    let external_backends_rx = watch_objects!(options, task_builder, Backend, kube_client_rx);
+    let backend_tls_policies_rx =
+        watch_objects!(options, task_builder, BackendTLSPolicy, kube_client_rx);
+    let config_maps_rx = watch_objects!(options, task_builder, ConfigMap, kube_client_rx);
+    let backend_traffic_policies_rx =
+        watch_objects!(options, task_builder, BackendTrafficPolicy, kube_client_rx);
     let gateway_class_parameters_rx = watch_objects!(
         options,
         task_builder,
@@ -88,6 +111,19 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         watch_objects!(options, task_builder, StaticResponseFilter, kube_client_rx);
     let access_control_filters_rx =
         watch_objects!(options, task_builder, AccessControlFilter, kube_client_rx);
+    let rate_limit_filters_rx =
+        watch_objects!(options, task_builder, RateLimitFilter, kube_client_rx);
+    let jwt_auth_filters_rx = watch_objects!(options, task_builder, JwtAuthFilter, kube_client_rx);
+    let external_auth_filters_rx =
+        watch_objects!(options, task_builder, ExternalAuthFilter, kube_client_rx);
+    let basic_auth_filters_rx =
+        watch_objects!(options, task_builder, BasicAuthFilter, kube_client_rx);
+    let cors_filters_rx = watch_objects!(options, task_builder, CorsFilter, kube_client_rx);
+    let compression_filters_rx =
+        watch_objects!(options, task_builder, CompressionFilter, kube_client_rx);
+    let cache_filters_rx = watch_objects!(options, task_builder, CacheFilter, kube_client_rx);
+    let request_limits_filters_rx =
+        watch_objects!(options, task_builder, RequestLimitsFilter, kube_client_rx);
 
     let gateway_class_rx = filter_gateway_classes(task_builder, &gateway_classes_rx);
     let gateway_class_parameters_rx = filter_gateway_class_parameters(
@@ -125,8 +161,12 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         &kube_client_rx,
         &instance_role_rx,
         &gateways_rx,
+        params.ipc_services.configuration_rollouts_rx(),
     );
 
+    // Purge the caches of gateways when their Gateway asks for it
+    sync_gateway_cache_purges(task_builder, &gateways_rx, params.ipc_services.clone());
+
     // Add HTTPRoute status controller
     sync_http_route_status(
         task_builder,
@@ -151,15 +191,109 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         &kube_client_rx,
     );
 
+    // Add RateLimitFilter status controller
+    sync::sync_rate_limit_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &rate_limit_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add JwtAuthFilter status controller
+    sync::sync_jwt_auth_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &jwt_auth_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add ExternalAuthFilter status controller
+    sync::sync_external_auth_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &external_auth_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add BasicAuthFilter status controller
+    sync::sync_basic_auth_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &basic_auth_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add CorsFilter status controller
+    sync::sync_cors_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &cors_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add CompressionFilter status controller
+    sync::sync_compression_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &compression_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add CacheFilter status controller
+    sync::sync_cache_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &cache_filters_rx,
+        &http_routes_rx,
+    );
+
+    // Add RequestLimitsFilter status controller
+    sync::sync_request_limits_filter_status(
+        task_builder,
+        &kube_client_rx,
+        &request_limits_filters_rx,
+        &http_routes_rx,
+    );
+
     let http_routes_by_gateway_rx = collect_http_routes_by_gateway(task_builder, &http_routes_rx);
     let service_backends_rx = collect_http_route_backends(task_builder, &http_routes_rx);
-    let backends_rx =
-        collect_service_backends(task_builder, &service_backends_rx, &endpoint_slices_rx);
+    let service_backends_rx = collect_external_auth_backends(
+        task_builder,
+        &service_backends_rx,
+        &external_auth_filters_rx,
+    );
+    let backend_tls_rx =
+        collect_backend_tls(task_builder, &backend_tls_policies_rx, &config_maps_rx);
+    let backend_traffic_rx = collect_backend_traffic(task_builder, &backend_traffic_policies_rx);
+    let backends_rx = collect_service_backends(
+        task_builder,
+        &service_backends_rx,
+        &services_rx,
+        &endpoint_slices_rx,
+        &backend_tls_rx,
+        &backend_traffic_rx,
+    );
+    let backends_rx = collect_external_backends(
+        task_builder,
+        &service_backends_rx,
+        &external_backends_rx,
+        &backend_tls_rx,
+        &backend_traffic_rx,
+        &backends_rx,
+    );
     let extension_filters_rx = collect_extension_filters_by_gateway(
         task_builder,
         &http_routes_by_gateway_rx,
         &static_response_filters_rx,
         &access_control_filters_rx,
+        &rate_limit_filters_rx,
+        &jwt_auth_filters_rx,
+        &external_auth_filters_rx,
+        &basic_auth_filters_rx,
+        &cors_filters_rx,
+        &compression_filters_rx,
+        &cache_filters_rx,
+        &request_limits_filters_rx,
     );
 
     bind_static_responses_cache(
@@ -168,6 +302,27 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         params.static_responses_cache,
     );
 
+    bind_secrets_cache(
+        task_builder,
+        &jwt_auth_filters_rx,
+        &kube_client_rx,
+        params.jwks_cache,
+    );
+
+    bind_secrets_cache(
+        task_builder,
+        &basic_auth_filters_rx,
+        &kube_client_rx,
+        params.htpasswd_cache,
+    );
+
+    bind_secrets_cache(
+        task_builder,
+        &backend_tls_policies_rx,
+        &kube_client_rx,
+        params.client_certificates_cache,
+    );
+
     {
         let params = SyncGatewayConfigmapsParams::builder()
             .options(options.clone())
@@ -184,6 +339,13 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         sync_gateway_configmaps(task_builder, params);
     }
 
+    sync_gateway_service_accounts(
+        params.options.clone(),
+        task_builder,
+        kube_client_rx.clone(),
+        instance_role_rx.clone(),
+        &gateway_instances_rx,
+    );
     sync_gateway_services(
         params.options.clone(),
         task_builder,
@@ -197,5 +359,6 @@ pub fn spawn_controllers(task_builder: &TaskBuilder, params: SpawnControllersPar
         &kube_client_rx,
         &instance_role_rx,
         &gateway_instances_rx,
+        params.ipc_services.configuration_rollouts_rx(),
     );
 }
@@ -0,0 +1,342 @@
+use crate::kubernetes::objects::Objects;
+use crate::kubernetes::KubeClientCell;
+use anyhow::{Context, Result};
+use gateway_api::apis::standard::httproutes::HTTPRoute;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::chrono::Utc;
+use kube::api::PostParams;
+use kube::{Api, Client};
+use std::ops::Deref;
+use tracing::{debug, info, info_span, warn, Instrument};
+use vg_api::v1alpha1::{
+    CacheFilter, CacheFilterConditionReason, CacheFilterConditionType, CacheFilterStatus,
+};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, ReadyState};
+
+/// Controller for managing `CacheFilter` status updates
+pub fn sync_cache_filter_status(
+    task_builder: &TaskBuilder,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    cache_filters_rx: &Receiver<Objects<CacheFilter>>,
+    http_routes_rx: &Receiver<Objects<HTTPRoute>>,
+) {
+    let kube_client_rx = kube_client_rx.clone();
+    let cache_filters_rx = cache_filters_rx.clone();
+    let http_routes_rx = http_routes_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_cache_filter_status))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((kube_client, cache_filters, http_routes)) =
+                    await_ready!(kube_client_rx, cache_filters_rx, http_routes_rx)
+                {
+                    info!("Syncing status for CacheFilters");
+
+                    // Iterate through all cache filters
+                    for (filter_ref, _, filter) in cache_filters.iter() {
+                        debug!("Processing CacheFilter: {}", filter_ref);
+
+                        let attached_routes = count_attached_routes(&filter, http_routes);
+                        let status = create_filter_status(&filter.spec, attached_routes);
+
+                        if let Err(e) =
+                            update_filter_status(kube_client.deref().clone(), &filter, status).await
+                        {
+                            warn!(
+                                "Failed to update status for CacheFilter {}: {}",
+                                filter_ref, e
+                            );
+                        }
+                    }
+                }
+
+                vg_core::continue_on!(
+                    cache_filters_rx.changed(),
+                    http_routes_rx.changed(),
+                    kube_client_rx.changed()
+                );
+            }
+        });
+}
+
+/// Count how many routes are using this cache filter
+fn count_attached_routes(filter: &CacheFilter, http_routes: &Objects<HTTPRoute>) -> i32 {
+    let default_name = String::new();
+    let default_namespace = String::new();
+    let filter_name = filter.metadata.name.as_ref().unwrap_or(&default_name);
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .unwrap_or(&default_namespace);
+
+    let mut count = 0;
+    for (_, _, route) in http_routes.iter() {
+        if is_filter_attached_to_route(filter_name, filter_namespace, &route) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Check if a cache filter is attached to a specific HTTP route
+fn is_filter_attached_to_route(
+    filter_name: &str,
+    filter_namespace: &str,
+    route: &HTTPRoute,
+) -> bool {
+    if let Some(rules) = &route.spec.rules {
+        for rule in rules {
+            if let Some(filters) = &rule.filters {
+                for filter in filters {
+                    if let Some(extension_ref) = &filter.extension_ref {
+                        // Check if this is a reference to our CacheFilter
+                        if extension_ref.group == "vale-gateway.whitefamily.in"
+                            && extension_ref.kind == "CacheFilter"
+                            && extension_ref.name == filter_name
+                        {
+                            // For extension refs, we assume same namespace as the route since
+                            // the HTTPRoute extension ref doesn't have a namespace field
+                            let route_namespace = route.metadata.namespace.as_deref();
+                            if route_namespace == Some(filter_namespace) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Create status for a `CacheFilter` based on its spec and attachment info
+fn create_filter_status(
+    spec: &vg_api::v1alpha1::CacheFilterSpec,
+    attached_routes: i32,
+) -> CacheFilterStatus {
+    let now = Time(Utc::now());
+    let mut conditions = Vec::new();
+
+    // Accepted condition - validate the filter configuration
+    let accepted_condition = if is_valid_spec(spec) {
+        Condition {
+            type_: CacheFilterConditionType::Accepted.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CacheFilterConditionReason::Accepted.as_str().to_string(),
+            message: "CacheFilter configuration is valid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CacheFilterConditionType::Accepted.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CacheFilterConditionReason::InvalidConfiguration
+                .as_str()
+                .to_string(),
+            message: "CacheFilter configuration is invalid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(accepted_condition);
+
+    // Ready condition - filter is ready if it's accepted
+    let ready_condition = if conditions[0].status == "True" {
+        Condition {
+            type_: CacheFilterConditionType::Ready.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CacheFilterConditionReason::Ready.as_str().to_string(),
+            message: "CacheFilter is ready to cache responses".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CacheFilterConditionType::Ready.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CacheFilterConditionReason::NotReady.as_str().to_string(),
+            message: "CacheFilter is not ready due to invalid configuration".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(ready_condition);
+
+    // Attached condition - whether the filter is attached to any routes
+    let attached_condition = if attached_routes > 0 {
+        Condition {
+            type_: CacheFilterConditionType::Attached.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CacheFilterConditionReason::AttachedToRoute
+                .as_str()
+                .to_string(),
+            message: format!("CacheFilter is attached to {attached_routes} route(s)"),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CacheFilterConditionType::Attached.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CacheFilterConditionReason::NotAttached.as_str().to_string(),
+            message: "CacheFilter is not attached to any routes".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(attached_condition);
+
+    CacheFilterStatus {
+        conditions: Some(conditions),
+        attached_routes,
+        last_updated: Some(now),
+    }
+}
+
+/// Validate that responses of some size can be cached
+pub(super) fn is_valid_spec(spec: &vg_api::v1alpha1::CacheFilterSpec) -> bool {
+    spec.max_object_size_bytes > 0
+}
+
+/// Update the status of a `CacheFilter`
+async fn update_filter_status(
+    client: Client,
+    filter: &CacheFilter,
+    status: CacheFilterStatus,
+) -> Result<()> {
+    let filter_name = filter
+        .metadata
+        .name
+        .as_ref()
+        .context("Filter name not found")?;
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .context("Filter namespace not found")?;
+
+    let api: Api<CacheFilter> = Api::namespaced(client, filter_namespace);
+
+    debug!(
+        "Updating status for CacheFilter {}/{}",
+        filter_namespace, filter_name
+    );
+
+    // Retry mechanism to handle conflicts (optimistic concurrency control)
+    let max_retries = 5;
+    let mut attempt = 0;
+
+    while attempt < max_retries {
+        attempt += 1;
+
+        // Get the latest version of the filter
+        let current_filter = api
+            .get_status(filter_name)
+            .instrument(info_span!("get_cache_filter_status"))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get current status of CacheFilter {filter_namespace}/{filter_name}"
+                )
+            })?;
+
+        // Check if the status actually needs to be updated
+        if let Some(existing_status) = &current_filter.status
+            && existing_status == &status
+        {
+            debug!(
+                "Status for CacheFilter {}/{} is already up to date",
+                filter_namespace, filter_name
+            );
+            return Ok(());
+        }
+
+        // Create a new version with updated status
+        let mut updated_filter = current_filter.clone();
+        updated_filter.status = Some(status.clone());
+
+        // Attempt to update the status
+        match api
+            .replace_status(
+                filter_name,
+                &PostParams::default(),
+                serde_json::to_vec(&updated_filter)?,
+            )
+            .instrument(info_span!("replace_cache_filter_status"))
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    "Successfully updated status for CacheFilter {}/{} on attempt {}",
+                    filter_namespace, filter_name, attempt
+                );
+                return Ok(());
+            }
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                // Conflict error - resource was modified, retry
+                warn!(
+                    "Conflict updating CacheFilter {}/{} status on attempt {}, retrying...",
+                    filter_namespace, filter_name, attempt
+                );
+                if attempt >= max_retries {
+                    return Err(anyhow::anyhow!(
+                        "Failed to update status after {} attempts due to conflicts: {}",
+                        max_retries,
+                        api_error
+                    ));
+                }
+                // Brief delay before retry to avoid tight retry loops
+                tokio::time::sleep(tokio::time::Duration::from_millis(100 * attempt as u64)).await;
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to update status of CacheFilter {}/{}: {}",
+                    filter_namespace,
+                    filter_name,
+                    e
+                ));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Exhausted all {} retry attempts for CacheFilter {}/{}",
+        max_retries,
+        filter_namespace,
+        filter_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_api::v1alpha1::CacheFilterSpec;
+
+    fn spec() -> CacheFilterSpec {
+        CacheFilterSpec {
+            max_object_size_bytes: 1024 * 1024,
+            stale_while_revalidate_seconds: 30,
+            stale_if_error_seconds: 300,
+        }
+    }
+
+    #[test]
+    fn test_is_valid_spec() {
+        assert!(is_valid_spec(&spec()));
+
+        let mut no_stale = spec();
+        no_stale.stale_while_revalidate_seconds = 0;
+        no_stale.stale_if_error_seconds = 0;
+        assert!(is_valid_spec(&no_stale));
+
+        let mut empty_objects = spec();
+        empty_objects.max_object_size_bytes = 0;
+        assert!(!is_valid_spec(&empty_objects));
+    }
+}
@@ -0,0 +1,405 @@
+use crate::kubernetes::objects::Objects;
+use crate::kubernetes::KubeClientCell;
+use anyhow::{Context, Result};
+use gateway_api::apis::standard::httproutes::HTTPRoute;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::chrono::Utc;
+use kube::api::PostParams;
+use kube::{Api, Client};
+use std::ops::Deref;
+use tracing::{debug, info, info_span, warn, Instrument};
+use vg_api::v1alpha1::{
+    CompressionFilter, CompressionFilterConditionReason, CompressionFilterConditionType,
+    CompressionFilterStatus,
+};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, ReadyState};
+
+/// Controller for managing `CompressionFilter` status updates
+pub fn sync_compression_filter_status(
+    task_builder: &TaskBuilder,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    compression_filters_rx: &Receiver<Objects<CompressionFilter>>,
+    http_routes_rx: &Receiver<Objects<HTTPRoute>>,
+) {
+    let kube_client_rx = kube_client_rx.clone();
+    let compression_filters_rx = compression_filters_rx.clone();
+    let http_routes_rx = http_routes_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_compression_filter_status))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((kube_client, compression_filters, http_routes)) =
+                    await_ready!(kube_client_rx, compression_filters_rx, http_routes_rx)
+                {
+                    info!("Syncing status for CompressionFilters");
+
+                    // Iterate through all compression filters
+                    for (filter_ref, _, filter) in compression_filters.iter() {
+                        debug!("Processing CompressionFilter: {}", filter_ref);
+
+                        let attached_routes = count_attached_routes(&filter, http_routes);
+                        let status = create_filter_status(&filter.spec, attached_routes);
+
+                        if let Err(e) =
+                            update_filter_status(kube_client.deref().clone(), &filter, status).await
+                        {
+                            warn!(
+                                "Failed to update status for CompressionFilter {}: {}",
+                                filter_ref, e
+                            );
+                        }
+                    }
+                }
+
+                vg_core::continue_on!(
+                    compression_filters_rx.changed(),
+                    http_routes_rx.changed(),
+                    kube_client_rx.changed()
+                );
+            }
+        });
+}
+
+/// Count how many routes are using this compression filter
+fn count_attached_routes(filter: &CompressionFilter, http_routes: &Objects<HTTPRoute>) -> i32 {
+    let default_name = String::new();
+    let default_namespace = String::new();
+    let filter_name = filter.metadata.name.as_ref().unwrap_or(&default_name);
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .unwrap_or(&default_namespace);
+
+    let mut count = 0;
+    for (_, _, route) in http_routes.iter() {
+        if is_filter_attached_to_route(filter_name, filter_namespace, &route) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Check if a compression filter is attached to a specific HTTP route
+fn is_filter_attached_to_route(
+    filter_name: &str,
+    filter_namespace: &str,
+    route: &HTTPRoute,
+) -> bool {
+    if let Some(rules) = &route.spec.rules {
+        for rule in rules {
+            if let Some(filters) = &rule.filters {
+                for filter in filters {
+                    if let Some(extension_ref) = &filter.extension_ref {
+                        // Check if this is a reference to our CompressionFilter
+                        if extension_ref.group == "vale-gateway.whitefamily.in"
+                            && extension_ref.kind == "CompressionFilter"
+                            && extension_ref.name == filter_name
+                        {
+                            // For extension refs, we assume same namespace as the route since
+                            // the HTTPRoute extension ref doesn't have a namespace field
+                            let route_namespace = route.metadata.namespace.as_deref();
+                            if route_namespace == Some(filter_namespace) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Create status for a `CompressionFilter` based on its spec and attachment info
+fn create_filter_status(
+    spec: &vg_api::v1alpha1::CompressionFilterSpec,
+    attached_routes: i32,
+) -> CompressionFilterStatus {
+    let now = Time(Utc::now());
+    let mut conditions = Vec::new();
+
+    // Accepted condition - validate the filter configuration
+    let accepted_condition = if is_valid_spec(spec) {
+        Condition {
+            type_: CompressionFilterConditionType::Accepted
+                .as_str()
+                .to_string(),
+            status: "True".to_string(),
+            reason: CompressionFilterConditionReason::Accepted
+                .as_str()
+                .to_string(),
+            message: "CompressionFilter configuration is valid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CompressionFilterConditionType::Accepted
+                .as_str()
+                .to_string(),
+            status: "False".to_string(),
+            reason: CompressionFilterConditionReason::InvalidConfiguration
+                .as_str()
+                .to_string(),
+            message: "CompressionFilter configuration is invalid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(accepted_condition);
+
+    // Ready condition - filter is ready if it's accepted
+    let ready_condition = if conditions[0].status == "True" {
+        Condition {
+            type_: CompressionFilterConditionType::Ready.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CompressionFilterConditionReason::Ready.as_str().to_string(),
+            message: "CompressionFilter is ready to compress responses".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CompressionFilterConditionType::Ready.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CompressionFilterConditionReason::NotReady
+                .as_str()
+                .to_string(),
+            message: "CompressionFilter is not ready due to invalid configuration".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(ready_condition);
+
+    // Attached condition - whether the filter is attached to any routes
+    let attached_condition = if attached_routes > 0 {
+        Condition {
+            type_: CompressionFilterConditionType::Attached
+                .as_str()
+                .to_string(),
+            status: "True".to_string(),
+            reason: CompressionFilterConditionReason::AttachedToRoute
+                .as_str()
+                .to_string(),
+            message: format!("CompressionFilter is attached to {attached_routes} route(s)"),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CompressionFilterConditionType::Attached
+                .as_str()
+                .to_string(),
+            status: "False".to_string(),
+            reason: CompressionFilterConditionReason::NotAttached
+                .as_str()
+                .to_string(),
+            message: "CompressionFilter is not attached to any routes".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(attached_condition);
+
+    CompressionFilterStatus {
+        conditions: Some(conditions),
+        attached_routes,
+        last_updated: Some(now),
+    }
+}
+
+/// Validate that at least one algorithm and one media type are given, without
+/// duplicate algorithms
+pub(super) fn is_valid_spec(spec: &vg_api::v1alpha1::CompressionFilterSpec) -> bool {
+    let algorithms_are_unique = spec
+        .algorithms
+        .iter()
+        .enumerate()
+        .all(|(i, algorithm)| !spec.algorithms[..i].contains(algorithm));
+
+    !spec.algorithms.is_empty()
+        && algorithms_are_unique
+        && !spec.content_types.is_empty()
+        && spec
+            .content_types
+            .iter()
+            .all(|content_type| is_valid_media_range(content_type))
+}
+
+/// A `type/subtype` or `type/*` media type without parameters
+fn is_valid_media_range(value: &str) -> bool {
+    let is_token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    match value.split_once('/') {
+        Some((type_, subtype)) => is_token(type_) && (subtype == "*" || is_token(subtype)),
+        None => false,
+    }
+}
+
+/// Update the status of a `CompressionFilter`
+async fn update_filter_status(
+    client: Client,
+    filter: &CompressionFilter,
+    status: CompressionFilterStatus,
+) -> Result<()> {
+    let filter_name = filter
+        .metadata
+        .name
+        .as_ref()
+        .context("Filter name not found")?;
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .context("Filter namespace not found")?;
+
+    let api: Api<CompressionFilter> = Api::namespaced(client, filter_namespace);
+
+    debug!(
+        "Updating status for CompressionFilter {}/{}",
+        filter_namespace, filter_name
+    );
+
+    // Retry mechanism to handle conflicts (optimistic concurrency control)
+    let max_retries = 5;
+    let mut attempt = 0;
+
+    while attempt < max_retries {
+        attempt += 1;
+
+        // Get the latest version of the filter
+        let current_filter = api
+            .get_status(filter_name)
+            .instrument(info_span!("get_compression_filter_status"))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get current status of CompressionFilter {filter_namespace}/{filter_name}"
+                )
+            })?;
+
+        // Check if the status actually needs to be updated
+        if let Some(existing_status) = &current_filter.status
+            && existing_status == &status
+        {
+            debug!(
+                "Status for CompressionFilter {}/{} is already up to date",
+                filter_namespace, filter_name
+            );
+            return Ok(());
+        }
+
+        // Create a new version with updated status
+        let mut updated_filter = current_filter.clone();
+        updated_filter.status = Some(status.clone());
+
+        // Attempt to update the status
+        match api
+            .replace_status(
+                filter_name,
+                &PostParams::default(),
+                serde_json::to_vec(&updated_filter)?,
+            )
+            .instrument(info_span!("replace_compression_filter_status"))
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    "Successfully updated status for CompressionFilter {}/{} on attempt {}",
+                    filter_namespace, filter_name, attempt
+                );
+                return Ok(());
+            }
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                // Conflict error - resource was modified, retry
+                warn!(
+                    "Conflict updating CompressionFilter {}/{} status on attempt {}, retrying...",
+                    filter_namespace, filter_name, attempt
+                );
+                if attempt >= max_retries {
+                    return Err(anyhow::anyhow!(
+                        "Failed to update status after {} attempts due to conflicts: {}",
+                        max_retries,
+                        api_error
+                    ));
+                }
+                // Brief delay before retry to avoid tight retry loops
+                tokio::time::sleep(tokio::time::Duration::from_millis(100 * attempt as u64)).await;
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to update status of CompressionFilter {}/{}: {}",
+                    filter_namespace,
+                    filter_name,
+                    e
+                ));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Exhausted all {} retry attempts for CompressionFilter {}/{}",
+        max_retries,
+        filter_namespace,
+        filter_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_api::v1alpha1::{CompressionFilterAlgorithm, CompressionFilterSpec};
+
+    fn spec() -> CompressionFilterSpec {
+        CompressionFilterSpec {
+            algorithms: vec![
+                CompressionFilterAlgorithm::Brotli,
+                CompressionFilterAlgorithm::Gzip,
+            ],
+            content_types: vec!["text/*".to_string(), "application/json".to_string()],
+            min_size_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_is_valid_spec() {
+        assert!(is_valid_spec(&spec()));
+
+        let mut no_algorithms = spec();
+        no_algorithms.algorithms.clear();
+        assert!(!is_valid_spec(&no_algorithms));
+
+        let mut duplicate_algorithms = spec();
+        duplicate_algorithms
+            .algorithms
+            .push(CompressionFilterAlgorithm::Brotli);
+        assert!(!is_valid_spec(&duplicate_algorithms));
+
+        let mut no_content_types = spec();
+        no_content_types.content_types.clear();
+        assert!(!is_valid_spec(&no_content_types));
+
+        for content_type in ["json", "*/*", "text/", "text/html; charset=utf-8"] {
+            let mut invalid_content_type = spec();
+            invalid_content_type.content_types = vec![content_type.to_string()];
+            assert!(
+                !is_valid_spec(&invalid_content_type),
+                "{content_type} should be invalid"
+            );
+        }
+
+        let mut image = spec();
+        image.content_types = vec!["image/svg+xml".to_string()];
+        assert!(is_valid_spec(&image));
+    }
+}
@@ -0,0 +1,394 @@
+use super::rate_limit_filter_status::is_valid_header_name;
+use crate::kubernetes::objects::Objects;
+use crate::kubernetes::KubeClientCell;
+use anyhow::{Context, Result};
+use gateway_api::apis::standard::httproutes::HTTPRoute;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::chrono::Utc;
+use kube::api::PostParams;
+use kube::{Api, Client};
+use regex::Regex;
+use std::ops::Deref;
+use tracing::{debug, info, info_span, warn, Instrument};
+use vg_api::v1alpha1::{
+    CorsFilter, CorsFilterConditionReason, CorsFilterConditionType, CorsFilterOrigin,
+    CorsFilterOriginMatchType, CorsFilterStatus,
+};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, ReadyState};
+
+/// Controller for managing `CorsFilter` status updates
+pub fn sync_cors_filter_status(
+    task_builder: &TaskBuilder,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    cors_filters_rx: &Receiver<Objects<CorsFilter>>,
+    http_routes_rx: &Receiver<Objects<HTTPRoute>>,
+) {
+    let kube_client_rx = kube_client_rx.clone();
+    let cors_filters_rx = cors_filters_rx.clone();
+    let http_routes_rx = http_routes_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_cors_filter_status))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((kube_client, cors_filters, http_routes)) =
+                    await_ready!(kube_client_rx, cors_filters_rx, http_routes_rx)
+                {
+                    info!("Syncing status for CorsFilters");
+
+                    // Iterate through all CORS filters
+                    for (filter_ref, _, filter) in cors_filters.iter() {
+                        debug!("Processing CorsFilter: {}", filter_ref);
+
+                        let attached_routes = count_attached_routes(&filter, http_routes);
+                        let status = create_filter_status(&filter.spec, attached_routes);
+
+                        if let Err(e) =
+                            update_filter_status(kube_client.deref().clone(), &filter, status).await
+                        {
+                            warn!(
+                                "Failed to update status for CorsFilter {}: {}",
+                                filter_ref, e
+                            );
+                        }
+                    }
+                }
+
+                vg_core::continue_on!(
+                    cors_filters_rx.changed(),
+                    http_routes_rx.changed(),
+                    kube_client_rx.changed()
+                );
+            }
+        });
+}
+
+/// Count how many routes are using this CORS filter
+fn count_attached_routes(filter: &CorsFilter, http_routes: &Objects<HTTPRoute>) -> i32 {
+    let default_name = String::new();
+    let default_namespace = String::new();
+    let filter_name = filter.metadata.name.as_ref().unwrap_or(&default_name);
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .unwrap_or(&default_namespace);
+
+    let mut count = 0;
+    for (_, _, route) in http_routes.iter() {
+        if is_filter_attached_to_route(filter_name, filter_namespace, &route) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Check if a CORS filter is attached to a specific HTTP route
+fn is_filter_attached_to_route(
+    filter_name: &str,
+    filter_namespace: &str,
+    route: &HTTPRoute,
+) -> bool {
+    if let Some(rules) = &route.spec.rules {
+        for rule in rules {
+            if let Some(filters) = &rule.filters {
+                for filter in filters {
+                    if let Some(extension_ref) = &filter.extension_ref {
+                        // Check if this is a reference to our CorsFilter
+                        if extension_ref.group == "vale-gateway.whitefamily.in"
+                            && extension_ref.kind == "CorsFilter"
+                            && extension_ref.name == filter_name
+                        {
+                            // For extension refs, we assume same namespace as the route since
+                            // the HTTPRoute extension ref doesn't have a namespace field
+                            let route_namespace = route.metadata.namespace.as_deref();
+                            if route_namespace == Some(filter_namespace) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Create status for a `CorsFilter` based on its spec and attachment info
+fn create_filter_status(
+    spec: &vg_api::v1alpha1::CorsFilterSpec,
+    attached_routes: i32,
+) -> CorsFilterStatus {
+    let now = Time(Utc::now());
+    let mut conditions = Vec::new();
+
+    // Accepted condition - validate the filter configuration
+    let accepted_condition = if is_valid_spec(spec) {
+        Condition {
+            type_: CorsFilterConditionType::Accepted.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CorsFilterConditionReason::Accepted.as_str().to_string(),
+            message: "CorsFilter configuration is valid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CorsFilterConditionType::Accepted.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CorsFilterConditionReason::InvalidConfiguration
+                .as_str()
+                .to_string(),
+            message: "CorsFilter configuration is invalid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(accepted_condition);
+
+    // Ready condition - filter is ready if it's accepted
+    let ready_condition = if conditions[0].status == "True" {
+        Condition {
+            type_: CorsFilterConditionType::Ready.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CorsFilterConditionReason::Ready.as_str().to_string(),
+            message: "CorsFilter is ready to handle cross-origin requests".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CorsFilterConditionType::Ready.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CorsFilterConditionReason::NotReady.as_str().to_string(),
+            message: "CorsFilter is not ready due to invalid configuration".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(ready_condition);
+
+    // Attached condition - whether the filter is attached to any routes
+    let attached_condition = if attached_routes > 0 {
+        Condition {
+            type_: CorsFilterConditionType::Attached.as_str().to_string(),
+            status: "True".to_string(),
+            reason: CorsFilterConditionReason::AttachedToRoute
+                .as_str()
+                .to_string(),
+            message: format!("CorsFilter is attached to {attached_routes} route(s)"),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: CorsFilterConditionType::Attached.as_str().to_string(),
+            status: "False".to_string(),
+            reason: CorsFilterConditionReason::NotAttached.as_str().to_string(),
+            message: "CorsFilter is not attached to any routes".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(attached_condition);
+
+    CorsFilterStatus {
+        conditions: Some(conditions),
+        attached_routes,
+        last_updated: Some(now),
+    }
+}
+
+/// Validate that origins are given and can be matched, and that methods and headers are
+/// valid names
+pub(super) fn is_valid_spec(spec: &vg_api::v1alpha1::CorsFilterSpec) -> bool {
+    !spec.allow_origins.is_empty()
+        && spec
+            .allow_origins
+            .iter()
+            .all(|origin| is_valid_origin(origin, spec.allow_credentials))
+        && spec
+            .allow_methods
+            .iter()
+            .chain(spec.allow_headers.iter())
+            .chain(spec.expose_headers.iter())
+            .all(|name| is_valid_header_name(name))
+}
+
+/// Any origin cannot be combined with credentials, since every site could then read
+/// responses made with the cookies of its visitors
+fn is_valid_origin(origin: &CorsFilterOrigin, allow_credentials: bool) -> bool {
+    match origin.type_ {
+        CorsFilterOriginMatchType::Exact | CorsFilterOriginMatchType::Wildcard => {
+            !origin.value.is_empty() && !(allow_credentials && origin.value == "*")
+        }
+        CorsFilterOriginMatchType::RegularExpression => Regex::new(&origin.value).is_ok(),
+    }
+}
+
+/// Update the status of a `CorsFilter`
+async fn update_filter_status(
+    client: Client,
+    filter: &CorsFilter,
+    status: CorsFilterStatus,
+) -> Result<()> {
+    let filter_name = filter
+        .metadata
+        .name
+        .as_ref()
+        .context("Filter name not found")?;
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .context("Filter namespace not found")?;
+
+    let api: Api<CorsFilter> = Api::namespaced(client, filter_namespace);
+
+    debug!(
+        "Updating status for CorsFilter {}/{}",
+        filter_namespace, filter_name
+    );
+
+    // Retry mechanism to handle conflicts (optimistic concurrency control)
+    let max_retries = 5;
+    let mut attempt = 0;
+
+    while attempt < max_retries {
+        attempt += 1;
+
+        // Get the latest version of the filter
+        let current_filter = api
+            .get_status(filter_name)
+            .instrument(info_span!("get_cors_filter_status"))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get current status of CorsFilter {filter_namespace}/{filter_name}"
+                )
+            })?;
+
+        // Check if the status actually needs to be updated
+        if let Some(existing_status) = &current_filter.status
+            && existing_status == &status
+        {
+            debug!(
+                "Status for CorsFilter {}/{} is already up to date",
+                filter_namespace, filter_name
+            );
+            return Ok(());
+        }
+
+        // Create a new version with updated status
+        let mut updated_filter = current_filter.clone();
+        updated_filter.status = Some(status.clone());
+
+        // Attempt to update the status
+        match api
+            .replace_status(
+                filter_name,
+                &PostParams::default(),
+                serde_json::to_vec(&updated_filter)?,
+            )
+            .instrument(info_span!("replace_cors_filter_status"))
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    "Successfully updated status for CorsFilter {}/{} on attempt {}",
+                    filter_namespace, filter_name, attempt
+                );
+                return Ok(());
+            }
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                // Conflict error - resource was modified, retry
+                warn!(
+                    "Conflict updating CorsFilter {}/{} status on attempt {}, retrying...",
+                    filter_namespace, filter_name, attempt
+                );
+                if attempt >= max_retries {
+                    return Err(anyhow::anyhow!(
+                        "Failed to update status after {} attempts due to conflicts: {}",
+                        max_retries,
+                        api_error
+                    ));
+                }
+                // Brief delay before retry to avoid tight retry loops
+                tokio::time::sleep(tokio::time::Duration::from_millis(100 * attempt as u64)).await;
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to update status of CorsFilter {}/{}: {}",
+                    filter_namespace,
+                    filter_name,
+                    e
+                ));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Exhausted all {} retry attempts for CorsFilter {}/{}",
+        max_retries,
+        filter_namespace,
+        filter_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_api::v1alpha1::CorsFilterSpec;
+
+    fn origin(type_: CorsFilterOriginMatchType, value: &str) -> CorsFilterOrigin {
+        CorsFilterOrigin {
+            type_,
+            value: value.to_string(),
+        }
+    }
+
+    fn spec() -> CorsFilterSpec {
+        CorsFilterSpec {
+            allow_origins: vec![
+                origin(CorsFilterOriginMatchType::Exact, "https://app.example.com"),
+                origin(CorsFilterOriginMatchType::Wildcard, "https://*.example.com"),
+                origin(
+                    CorsFilterOriginMatchType::RegularExpression,
+                    r"https://preview-\d+\.example\.dev",
+                ),
+            ],
+            allow_methods: vec!["GET".to_string(), "POST".to_string()],
+            allow_headers: vec!["content-type".to_string()],
+            expose_headers: vec!["x-request-id".to_string()],
+            allow_credentials: true,
+            max_age_seconds: Some(600),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_spec() {
+        assert!(is_valid_spec(&spec()));
+
+        let mut no_origins = spec();
+        no_origins.allow_origins.clear();
+        assert!(!is_valid_spec(&no_origins));
+
+        let mut invalid_regex = spec();
+        invalid_regex.allow_origins[2].value = "https://(".to_string();
+        assert!(!is_valid_spec(&invalid_regex));
+
+        let mut invalid_header = spec();
+        invalid_header.allow_headers[0] = "not a header".to_string();
+        assert!(!is_valid_spec(&invalid_header));
+
+        let mut any_origin = spec();
+        any_origin.allow_origins = vec![origin(CorsFilterOriginMatchType::Wildcard, "*")];
+        assert!(!is_valid_spec(&any_origin));
+
+        any_origin.allow_credentials = false;
+        assert!(is_valid_spec(&any_origin));
+    }
+}
@@ -0,0 +1,395 @@
+use super::rate_limit_filter_status::is_valid_header_name;
+use crate::kubernetes::KubeClientCell;
+use crate::kubernetes::objects::Objects;
+use anyhow::{Context, Result};
+use gateway_api::apis::standard::httproutes::HTTPRoute;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::chrono::Utc;
+use kube::api::PostParams;
+use kube::{Api, Client};
+use std::ops::Deref;
+use tracing::{Instrument, debug, info, info_span, warn};
+use vg_api::v1alpha1::{
+    ExternalAuthFilter, ExternalAuthFilterConditionReason, ExternalAuthFilterConditionType,
+    ExternalAuthFilterStatus,
+};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready};
+
+/// Controller for managing `ExternalAuthFilter` status updates
+pub fn sync_external_auth_filter_status(
+    task_builder: &TaskBuilder,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    external_auth_filters_rx: &Receiver<Objects<ExternalAuthFilter>>,
+    http_routes_rx: &Receiver<Objects<HTTPRoute>>,
+) {
+    let kube_client_rx = kube_client_rx.clone();
+    let external_auth_filters_rx = external_auth_filters_rx.clone();
+    let http_routes_rx = http_routes_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_external_auth_filter_status))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((kube_client, external_auth_filters, http_routes)) =
+                    await_ready!(kube_client_rx, external_auth_filters_rx, http_routes_rx)
+                {
+                    info!("Syncing status for ExternalAuthFilters");
+
+                    // Iterate through all external auth filters
+                    for (filter_ref, _, filter) in external_auth_filters.iter() {
+                        debug!("Processing ExternalAuthFilter: {}", filter_ref);
+
+                        let attached_routes = count_attached_routes(&filter, http_routes);
+                        let status = create_filter_status(&filter.spec, attached_routes);
+
+                        if let Err(e) =
+                            update_filter_status(kube_client.deref().clone(), &filter, status).await
+                        {
+                            warn!(
+                                "Failed to update status for ExternalAuthFilter {}: {}",
+                                filter_ref, e
+                            );
+                        }
+                    }
+                }
+
+                vg_core::continue_on!(
+                    external_auth_filters_rx.changed(),
+                    http_routes_rx.changed(),
+                    kube_client_rx.changed()
+                );
+            }
+        });
+}
+
+/// Count how many routes are using this external auth filter
+fn count_attached_routes(filter: &ExternalAuthFilter, http_routes: &Objects<HTTPRoute>) -> i32 {
+    let default_name = String::new();
+    let default_namespace = String::new();
+    let filter_name = filter.metadata.name.as_ref().unwrap_or(&default_name);
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .unwrap_or(&default_namespace);
+
+    let mut count = 0;
+    for (_, _, route) in http_routes.iter() {
+        if is_filter_attached_to_route(filter_name, filter_namespace, &route) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Check if a external auth filter is attached to a specific HTTP route
+fn is_filter_attached_to_route(
+    filter_name: &str,
+    filter_namespace: &str,
+    route: &HTTPRoute,
+) -> bool {
+    if let Some(rules) = &route.spec.rules {
+        for rule in rules {
+            if let Some(filters) = &rule.filters {
+                for filter in filters {
+                    if let Some(extension_ref) = &filter.extension_ref {
+                        // Check if this is a reference to our ExternalAuthFilter
+                        if extension_ref.group == "vale-gateway.whitefamily.in"
+                            && extension_ref.kind == "ExternalAuthFilter"
+                            && extension_ref.name == filter_name
+                        {
+                            // For extension refs, we assume same namespace as the route since
+                            // the HTTPRoute extension ref doesn't have a namespace field
+                            let route_namespace = route.metadata.namespace.as_deref();
+                            if route_namespace == Some(filter_namespace) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Create status for a `ExternalAuthFilter` based on its spec and attachment info
+fn create_filter_status(
+    spec: &vg_api::v1alpha1::ExternalAuthFilterSpec,
+    attached_routes: i32,
+) -> ExternalAuthFilterStatus {
+    let now = Time(Utc::now());
+    let mut conditions = Vec::new();
+
+    // Accepted condition - validate the filter configuration
+    let accepted_condition = if is_valid_spec(spec) {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Accepted
+                .as_str()
+                .to_string(),
+            status: "True".to_string(),
+            reason: ExternalAuthFilterConditionReason::Accepted
+                .as_str()
+                .to_string(),
+            message: "ExternalAuthFilter configuration is valid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Accepted
+                .as_str()
+                .to_string(),
+            status: "False".to_string(),
+            reason: ExternalAuthFilterConditionReason::InvalidConfiguration
+                .as_str()
+                .to_string(),
+            message: "ExternalAuthFilter configuration is invalid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(accepted_condition);
+
+    // Ready condition - filter is ready if it's accepted
+    let ready_condition = if conditions[0].status == "True" {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Ready.as_str().to_string(),
+            status: "True".to_string(),
+            reason: ExternalAuthFilterConditionReason::Ready
+                .as_str()
+                .to_string(),
+            message: "ExternalAuthFilter is ready to authorize requests".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Ready.as_str().to_string(),
+            status: "False".to_string(),
+            reason: ExternalAuthFilterConditionReason::NotReady
+                .as_str()
+                .to_string(),
+            message: "ExternalAuthFilter is not ready due to invalid configuration".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(ready_condition);
+
+    // Attached condition - whether the filter is attached to any routes
+    let attached_condition = if attached_routes > 0 {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Attached
+                .as_str()
+                .to_string(),
+            status: "True".to_string(),
+            reason: ExternalAuthFilterConditionReason::AttachedToRoute
+                .as_str()
+                .to_string(),
+            message: format!("ExternalAuthFilter is attached to {attached_routes} route(s)"),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: ExternalAuthFilterConditionType::Attached
+                .as_str()
+                .to_string(),
+            status: "False".to_string(),
+            reason: ExternalAuthFilterConditionReason::NotAttached
+                .as_str()
+                .to_string(),
+            message: "ExternalAuthFilter is not attached to any routes".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(attached_condition);
+
+    ExternalAuthFilterStatus {
+        conditions: Some(conditions),
+        attached_routes,
+        last_updated: Some(now),
+    }
+}
+
+/// Validate that the authorization service is named and that every header is a valid name
+pub(super) fn is_valid_spec(spec: &vg_api::v1alpha1::ExternalAuthFilterSpec) -> bool {
+    !spec.backend_ref.name.is_empty()
+        && spec.backend_ref.port != 0
+        && spec.timeout_milliseconds > 0
+        && spec
+            .path_prefix
+            .as_ref()
+            .is_none_or(|path_prefix| path_prefix.starts_with('/'))
+        && spec
+            .request_headers
+            .iter()
+            .chain(spec.upstream_headers.iter())
+            .chain(spec.cache.iter().map(|cache| &cache.key_header))
+            .all(|header| is_valid_header_name(header))
+}
+
+/// Update the status of a `ExternalAuthFilter`
+async fn update_filter_status(
+    client: Client,
+    filter: &ExternalAuthFilter,
+    status: ExternalAuthFilterStatus,
+) -> Result<()> {
+    let filter_name = filter
+        .metadata
+        .name
+        .as_ref()
+        .context("Filter name not found")?;
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .context("Filter namespace not found")?;
+
+    let api: Api<ExternalAuthFilter> = Api::namespaced(client, filter_namespace);
+
+    debug!(
+        "Updating status for ExternalAuthFilter {}/{}",
+        filter_namespace, filter_name
+    );
+
+    // Retry mechanism to handle conflicts (optimistic concurrency control)
+    let max_retries = 5;
+    let mut attempt = 0;
+
+    while attempt < max_retries {
+        attempt += 1;
+
+        // Get the latest version of the filter
+        let current_filter = api
+            .get_status(filter_name)
+            .instrument(info_span!("get_external_auth_filter_status"))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get current status of ExternalAuthFilter {filter_namespace}/{filter_name}"
+                )
+            })?;
+
+        // Check if the status actually needs to be updated
+        if let Some(existing_status) = &current_filter.status
+            && existing_status == &status
+        {
+            debug!(
+                "Status for ExternalAuthFilter {}/{} is already up to date",
+                filter_namespace, filter_name
+            );
+            return Ok(());
+        }
+
+        // Create a new version with updated status
+        let mut updated_filter = current_filter.clone();
+        updated_filter.status = Some(status.clone());
+
+        // Attempt to update the status
+        match api
+            .replace_status(
+                filter_name,
+                &PostParams::default(),
+                serde_json::to_vec(&updated_filter)?,
+            )
+            .instrument(info_span!("replace_external_auth_filter_status"))
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    "Successfully updated status for ExternalAuthFilter {}/{} on attempt {}",
+                    filter_namespace, filter_name, attempt
+                );
+                return Ok(());
+            }
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                // Conflict error - resource was modified, retry
+                warn!(
+                    "Conflict updating ExternalAuthFilter {}/{} status on attempt {}, retrying...",
+                    filter_namespace, filter_name, attempt
+                );
+                if attempt >= max_retries {
+                    return Err(anyhow::anyhow!(
+                        "Failed to update status after {} attempts due to conflicts: {}",
+                        max_retries,
+                        api_error
+                    ));
+                }
+                // Brief delay before retry to avoid tight retry loops
+                tokio::time::sleep(tokio::time::Duration::from_millis(100 * attempt as u64)).await;
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to update status of ExternalAuthFilter {}/{}: {}",
+                    filter_namespace,
+                    filter_name,
+                    e
+                ));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Exhausted all {} retry attempts for ExternalAuthFilter {}/{}",
+        max_retries,
+        filter_namespace,
+        filter_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_api::v1alpha1::{
+        ExternalAuthFilterBackendRef, ExternalAuthFilterCache, ExternalAuthFilterFailureMode,
+        ExternalAuthFilterSpec,
+    };
+
+    fn spec() -> ExternalAuthFilterSpec {
+        ExternalAuthFilterSpec {
+            backend_ref: ExternalAuthFilterBackendRef {
+                name: "authz".to_string(),
+                port: 8080,
+            },
+            path_prefix: Some("/check".to_string()),
+            request_headers: vec!["authorization".to_string()],
+            upstream_headers: vec!["x-user-id".to_string()],
+            failure_mode: ExternalAuthFilterFailureMode::Closed,
+            timeout_milliseconds: 500,
+            cache: Some(ExternalAuthFilterCache {
+                key_header: "authorization".to_string(),
+                ttl_seconds: 30,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_spec() {
+        assert!(is_valid_spec(&spec()));
+
+        let mut missing_backend = spec();
+        missing_backend.backend_ref.name = String::new();
+        assert!(!is_valid_spec(&missing_backend));
+
+        let mut relative_prefix = spec();
+        relative_prefix.path_prefix = Some("check".to_string());
+        assert!(!is_valid_spec(&relative_prefix));
+
+        let mut invalid_header = spec();
+        invalid_header.upstream_headers[0] = "not a header".to_string();
+        assert!(!is_valid_spec(&invalid_header));
+
+        let mut invalid_cache_header = spec();
+        invalid_cache_header.cache = Some(ExternalAuthFilterCache {
+            key_header: String::new(),
+            ttl_seconds: 30,
+        });
+        assert!(!is_valid_spec(&invalid_cache_header));
+    }
+}
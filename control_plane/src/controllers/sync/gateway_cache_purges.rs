@@ -0,0 +1,179 @@
+use crate::ipc::IpcServices;
+use crate::kubernetes::objects::{ObjectRef, Objects};
+use gateway_api::apis::standard::gateways::Gateway;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use vg_core::ipc::cache::{CACHE_PURGE_ANNOTATION, CachePurgeRequest};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// Purges the cached responses of a gateway whenever the cache purge annotation of its Gateway
+/// changes. Only those allowed to update the Gateway can request a purge this way.
+pub fn sync_gateway_cache_purges(
+    task_builder: &TaskBuilder,
+    gateways_rx: &Receiver<Objects<Gateway>>,
+    ipc_services: Arc<IpcServices>,
+) {
+    let gateways_rx = gateways_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_gateway_cache_purges))
+        .spawn(async move {
+            let mut requests = CachePurgeRequests::default();
+            loop {
+                if let ReadyState::Ready(gateways) = await_ready!(gateways_rx) {
+                    for (gateway_ref, request) in requests.update(&gateways) {
+                        info!(
+                            "Purging cached responses of {} for {}",
+                            gateway_ref,
+                            request.hostname().as_deref().unwrap_or("all hostnames")
+                        );
+                        let _ = ipc_services
+                            .purge_gateway_cache(&gateway_ref, request.hostname().clone())
+                            .inspect_err(|e| {
+                                warn!("Failed to purge cache of {}: {}", gateway_ref, e);
+                            });
+                    }
+                }
+
+                continue_on!(gateways_rx.changed());
+            }
+        });
+}
+
+/// The last cache purge annotation seen on each Gateway
+#[derive(Debug, Default)]
+struct CachePurgeRequests {
+    seen: Option<HashMap<ObjectRef, String>>,
+}
+
+impl CachePurgeRequests {
+    /// Returns the purges requested since the last update. Annotations present when the control
+    /// plane starts were already acted on by the instance before it, so they are only recorded.
+    fn update(&mut self, gateways: &Objects<Gateway>) -> Vec<(ObjectRef, CachePurgeRequest)> {
+        let annotations: HashMap<_, _> = gateways
+            .iter()
+            .filter_map(|(gateway_ref, _, gateway)| {
+                gateway
+                    .metadata
+                    .annotations
+                    .as_ref()
+                    .and_then(|annotations| annotations.get(CACHE_PURGE_ANNOTATION))
+                    .map(|annotation| (gateway_ref, annotation.clone()))
+            })
+            .collect();
+
+        let Some(seen) = self.seen.replace(annotations.clone()) else {
+            return vec![];
+        };
+
+        annotations
+            .into_iter()
+            .filter(|(gateway_ref, annotation)| seen.get(gateway_ref) != Some(annotation))
+            .filter_map(|(gateway_ref, annotation)| {
+                match serde_json::from_str::<CachePurgeRequest>(&annotation) {
+                    Ok(request) => {
+                        let hostname = request.hostname().as_deref().map(str::to_ascii_lowercase);
+                        let request = CachePurgeRequest::builder()
+                            .requested_at(request.requested_at())
+                            .hostname(hostname)
+                            .build();
+                        Some((gateway_ref, request))
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Ignoring invalid {} annotation of {}: {}",
+                            CACHE_PURGE_ANNOTATION, gateway_ref, e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gateways(annotations: &[(&str, Option<&str>)]) -> Objects<Gateway> {
+        let mut gateways = Objects::default();
+        for (name, annotation) in annotations {
+            let mut gateway: Gateway = serde_json::from_value(json!({
+                "apiVersion": "gateway.networking.k8s.io/v1",
+                "kind": "Gateway",
+                "metadata": { "name": name, "namespace": "default", "uid": format!("{name}-uid") },
+                "spec": { "gatewayClassName": "vale-gateway", "listeners": [] },
+            }))
+            .unwrap();
+            if let Some(annotation) = annotation {
+                gateway.metadata.annotations =
+                    Some([(CACHE_PURGE_ANNOTATION.to_string(), annotation.to_string())].into());
+            }
+            gateways.insert(Arc::new(gateway)).unwrap();
+        }
+        gateways
+    }
+
+    fn gateway_ref(name: &str) -> ObjectRef {
+        ObjectRef::of_kind::<Gateway>()
+            .namespace(Some("default".to_string()))
+            .name(name)
+            .build()
+    }
+
+    #[test]
+    fn test_purges_once_per_request() {
+        let mut requests = CachePurgeRequests::default();
+        let first = r#"{"requestedAt":"2026-10-18T16:25:20Z"}"#;
+        let second = r#"{"requestedAt":"2026-10-18T16:30:00Z","hostname":"Echo.example.com"}"#;
+
+        // Requests made before the control plane started are not repeated
+        assert!(
+            requests
+                .update(&gateways(&[("a", Some(first)), ("b", None)]))
+                .is_empty()
+        );
+        assert!(
+            requests
+                .update(&gateways(&[("a", Some(first)), ("b", None)]))
+                .is_empty()
+        );
+
+        assert_eq!(
+            requests.update(&gateways(&[("a", Some(first)), ("b", Some(second))])),
+            vec![(
+                gateway_ref("b"),
+                CachePurgeRequest::builder()
+                    .requested_at("2026-10-18T16:30:00Z")
+                    .hostname(Some("echo.example.com".to_string()))
+                    .build()
+            )]
+        );
+        assert!(
+            requests
+                .update(&gateways(&[("a", Some(first)), ("b", Some(second))]))
+                .is_empty()
+        );
+
+        // A Gateway created with the annotation has nothing cached yet, purging it is harmless
+        assert_eq!(
+            requests
+                .update(&gateways(&[("a", Some(first)), ("c", Some(first))]))
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_ignores_invalid_requests() {
+        let mut requests = CachePurgeRequests::default();
+        requests.update(&gateways(&[("a", None)]));
+
+        assert!(requests.update(&gateways(&[("a", Some("now"))])).is_empty());
+    }
+}
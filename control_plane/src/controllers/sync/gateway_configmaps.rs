@@ -1,6 +1,15 @@
 use crate::controllers::instances::InstanceRole;
+use crate::controllers::sync::basic_auth_filter_status::is_valid_spec as is_valid_basic_auth_spec;
+use crate::controllers::sync::cache_filter_status::is_valid_spec as is_valid_cache_spec;
+use crate::controllers::sync::compression_filter_status::is_valid_spec as is_valid_compression_spec;
+use crate::controllers::sync::cors_filter_status::is_valid_spec as is_valid_cors_spec;
+use crate::controllers::sync::external_auth_filter_status::is_valid_spec as is_valid_external_auth_spec;
+use crate::controllers::sync::jwt_auth_filter_status::is_valid_spec as is_valid_jwt_auth_spec;
+use crate::controllers::sync::rate_limit_filter_status::is_valid_spec as is_valid_rate_limit_spec;
+use crate::controllers::sync::request_limits_filter_status::is_valid_spec as is_valid_request_limits_spec;
 use crate::controllers::transformers::{
-    Backend, ExtensionFilterKind, ExtensionFilters, GatewayInstanceConfiguration,
+    backend_ref_object_ref, Backend, ExtensionFilterKind, ExtensionFilters,
+    GatewayInstanceConfiguration,
 };
 use crate::ipc::IpcServices;
 use crate::kubernetes::objects::{ObjectRef, SyncObjectAction};
@@ -26,12 +35,18 @@ use tokio::sync::mpsc::UnboundedSender;
 use tracing::{debug, error, info, warn};
 use typed_builder::TypedBuilder;
 use vg_api::v1alpha1::{
-    AccessControlFilter, AccessControlFilterEffect, ClientAddressesSource, ErrorResponseKind,
-    ProxyIpAddressHeaders, StaticResponseFilter,
+    AccessControlFilter, AccessControlFilterEffect, AccessLogFormat, AccessLogSink,
+    AccessLogStatusClass, BasicAuthFilter, CacheFilter, ClientAddressesSource, CompressionFilter,
+    CompressionFilterAlgorithm, CorsFilter, CorsFilterOriginMatchType, ErrorResponseKind,
+    ExternalAuthFilter, ExternalAuthFilterFailureMode, JwtAuthFilter, ProxyIpAddressHeaders,
+    RateLimitFilter, RateLimitFilterKeySource, RateLimitFilterMode, RequestLimitsFilter,
+    RequestLimitsFilterBodyMode, StaticResponseFilter,
 };
 use vg_core::config::gateway::types::http::filters::{
-    ExtAccessControlRef, ExtStaticResponseRef, HTTPHeader, HttpRouteFilter, HttpRouteFilterType,
-    RequestHeaderModifier, ResponseHeaderModifier,
+    ExtAccessControlRef, ExtBasicAuthRef, ExtCacheRef, ExtCompressionRef, ExtCorsRef,
+    ExtExternalAuthRef, ExtJwtAuthRef, ExtRateLimitRef, ExtRequestLimitsRef, ExtStaticResponseRef,
+    HTTPHeader, HttpRouteFilter, HttpRouteFilterType, RequestHeaderModifier,
+    RequestHeaderModifierBuilder, ResponseHeaderModifier,
 };
 use vg_core::config::gateway::types::http::router::{
     HttpMethodMatch, HttpRouteBuilder, HttpRouteRuleBuilder, HttpRouteRuleMatchesBuilder,
@@ -39,9 +54,21 @@ use vg_core::config::gateway::types::http::router::{
 use vg_core::config::gateway::types::net::{
     AccessControlFilter as ConfigAccessControlFilter,
     AccessControlFilterClientMatches as ConfigAccessControlFilterClientMatches,
-    AccessControlFilterEffect as ConfigAccessControlEffect,
-    ErrorResponseKind as ConfigErrorResponseKind, ErrorResponses as ConfigErrorResponses,
-    ProblemDetailErrorResponse, ProxyHeaders, StaticResponse, StaticResponseBody,
+    AccessControlFilterEffect as ConfigAccessControlEffect, AccessLogFile as ConfigAccessLogFile,
+    AccessLogFormat as ConfigAccessLogFormat, AccessLogRoute as ConfigAccessLogRoute,
+    AccessLogSink as ConfigAccessLogSink, AccessLogs as ConfigAccessLogs, BackendBuilder,
+    BasicAuthFilter as ConfigBasicAuthFilter, CacheFilter as ConfigCacheFilter,
+    CompressionAlgorithm as ConfigCompressionAlgorithm,
+    CompressionFilter as ConfigCompressionFilter, CorsFilter as ConfigCorsFilter,
+    CorsOrigin as ConfigCorsOrigin, ErrorResponseKind as ConfigErrorResponseKind,
+    ErrorResponses as ConfigErrorResponses, ExternalAuthCache as ConfigExternalAuthCache,
+    ExternalAuthFailureMode as ConfigExternalAuthFailureMode,
+    ExternalAuthFilter as ConfigExternalAuthFilter, JwtAuthFilter as ConfigJwtAuthFilter,
+    JwtClaimHeader as ConfigJwtClaimHeader, ProblemDetailErrorResponse, ProxyHeaders,
+    RateLimitFilter as ConfigRateLimitFilter, RateLimitFilterKey as ConfigRateLimitFilterKey,
+    RateLimitMode as ConfigRateLimitMode, RequestBodyMode as ConfigRequestBodyMode,
+    RequestLimitsFilter as ConfigRequestLimitsFilter, StaticResponse, StaticResponseBody,
+    StatusClass,
 };
 use vg_core::config::gateway::types::{GatewayConfiguration, GatewayConfigurationBuilder};
 use vg_core::net::{Hostname, Port};
@@ -57,6 +84,9 @@ struct TemplateValues {
     gateway_name: String,
     #[builder(setter(into))]
     config_yaml: String,
+    /// The version the IPC endpoint serves the configuration under, set once it is inserted
+    #[builder(default, setter(into))]
+    config_version: String,
 }
 
 #[derive(TypedBuilder, CloneGetters, Clone)]
@@ -165,17 +195,20 @@ fn generate_gateway_configmaps(
 
                     let deleted_refs = current_configmap_refs.difference(&desired_configmap_refs);
                     for deleted_ref in deleted_refs {
-                        let _ = params
+                        match params
                             .sync_tx
                             .send(SyncObjectAction::Delete(deleted_ref.clone()))
-                            .inspect(|()| {
+                        {
+                            Ok(()) => {
                                 params
                                     .ipc_services()
-                                    .remove_gateway_configuration(deleted_ref);
-                            })
-                            .inspect_err(|err| {
+                                    .remove_gateway_configuration(deleted_ref)
+                                    .await;
+                            }
+                            Err(err) => {
                                 error!("Failed to send delete action: {}", err);
-                            });
+                            }
+                        }
                     }
 
                     'send_and_insert: for gateway_state in desired_gateway_configurations {
@@ -183,23 +216,37 @@ fn generate_gateway_configmaps(
                             continue 'send_and_insert;
                         };
 
+                        // The ConfigMap carries the version, so gateways restarted without the
+                        // control plane can tell whether it is newer than their cached one
+                        let version = match params
+                            .ipc_services()
+                            .try_insert_gateway_configuration(
+                                gateway_state.gateway_ref.clone(),
+                                config.clone(),
+                            )
+                            .await
+                        {
+                            Ok(version) => version,
+                            Err(err) => {
+                                warn!("Failed to insert gateway configuration: {}", err);
+                                continue 'send_and_insert;
+                            }
+                        };
+
+                        let template_values = TemplateValues {
+                            config_version: version.to_string(),
+                            ..template_values.clone()
+                        };
+
                         if let Err(err) = params.sync_tx.send(SyncObjectAction::Upsert(
                             gateway_state.configmap_ref.clone(),
                             gateway_state.gateway_ref.clone(),
-                            template_values.clone(),
+                            template_values,
                             None,
                         )) {
                             warn!("Failed to send upsert action: {}", err);
                             continue 'send_and_insert;
                         }
-
-                        if let Err(err) = params.ipc_services().try_insert_gateway_configuration(
-                            gateway_state.gateway_ref.clone(),
-                            config.clone(),
-                        ) {
-                            warn!("Failed to insert gateway configuration: {}", err);
-                            continue 'send_and_insert;
-                        }
                     }
                 }
 
@@ -308,6 +355,10 @@ fn generate_gateway_configurations(
                                     &mut gateway_configuration,
                                     gateway_instance,
                                 );
+                                set_access_logs_strategy(
+                                    &mut gateway_configuration,
+                                    gateway_instance,
+                                );
                                 if let Some(extension_filters) = extension_filters {
                                     apply_static_response_filters(
                                         &mut gateway_configuration,
@@ -317,6 +368,39 @@ fn generate_gateway_configurations(
                                         &mut gateway_configuration,
                                         extension_filters,
                                     );
+                                    apply_rate_limit_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_jwt_auth_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_external_auth_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                        backends,
+                                    );
+                                    apply_basic_auth_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_cors_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_compression_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_cache_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
+                                    apply_request_limits_filters(
+                                        &mut gateway_configuration,
+                                        extension_filters,
+                                    );
                                 }
 
                                 add_listeners(&mut gateway_configuration, gateway_instance);
@@ -383,6 +467,369 @@ fn apply_access_control_filters(
     }
 }
 
+fn apply_rate_limit_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.rate_limits().is_empty() {
+        let filters = extension_filters
+            .rate_limits()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_rate_limit_spec(spec) {
+                    warn!("Skipping invalid RateLimitFilter {}", ref_);
+                    return None;
+                }
+
+                let limit_key = match spec.key.source {
+                    RateLimitFilterKeySource::ClientAddress => ConfigRateLimitFilterKey::ClientAddr,
+                    RateLimitFilterKeySource::Header => {
+                        ConfigRateLimitFilterKey::Header(spec.key.header.clone()?)
+                    }
+                    RateLimitFilterKeySource::Route => ConfigRateLimitFilterKey::Route,
+                };
+
+                let mode = match spec.mode {
+                    RateLimitFilterMode::Local => ConfigRateLimitMode::Local,
+                    RateLimitFilterMode::Global => ConfigRateLimitMode::Global,
+                };
+
+                let filter = ConfigRateLimitFilter::builder()
+                    .key(ref_.to_string())
+                    .limit_key(limit_key)
+                    .requests(spec.requests)
+                    .period_seconds(spec.period_seconds)
+                    .mode(mode);
+
+                Some(match spec.burst {
+                    Some(burst) => filter.burst(burst).build(),
+                    None => filter.build(),
+                })
+            })
+            .collect();
+
+        gateway_configuration.with_rate_limit_filters(filters);
+    }
+}
+
+fn apply_jwt_auth_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.jwt_auths().is_empty() {
+        let filters = extension_filters
+            .jwt_auths()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_jwt_auth_spec(spec) {
+                    warn!("Skipping invalid JwtAuthFilter {}", ref_);
+                    return None;
+                }
+
+                let version_key = filter.metadata.resource_version.as_ref()?;
+                let uid = filter.uid()?;
+
+                let claim_headers = spec
+                    .claim_headers
+                    .iter()
+                    .map(|claim_header| {
+                        ConfigJwtClaimHeader::builder()
+                            .claim(&claim_header.claim)
+                            .header(&claim_header.header)
+                            .build()
+                    })
+                    .collect();
+
+                let filter = ConfigJwtAuthFilter::builder()
+                    .key(ref_.to_string())
+                    .version_key(version_key)
+                    .jwks_identifier(uid)
+                    .issuer(&spec.issuer)
+                    .audiences(spec.audiences.clone())
+                    .clock_skew_seconds(spec.clock_skew_seconds)
+                    .claim_headers(claim_headers);
+
+                Some(match &spec.realm {
+                    Some(realm) => filter.realm(realm).build(),
+                    None => filter.build(),
+                })
+            })
+            .collect();
+
+        gateway_configuration.with_jwt_auth_filters(filters);
+    }
+}
+
+fn apply_basic_auth_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.basic_auths().is_empty() {
+        let filters = extension_filters
+            .basic_auths()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_basic_auth_spec(spec) {
+                    warn!("Skipping invalid BasicAuthFilter {}", ref_);
+                    return None;
+                }
+
+                let version_key = filter.metadata.resource_version.as_ref()?;
+                let uid = filter.uid()?;
+
+                Some(
+                    ConfigBasicAuthFilter::builder()
+                        .key(ref_.to_string())
+                        .version_key(version_key)
+                        .htpasswd_identifier(uid)
+                        .strip_authorization(spec.strip_authorization)
+                        .username_header(spec.username_header.clone())
+                        .realm(spec.realm.clone())
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_basic_auth_filters(filters);
+    }
+}
+
+fn apply_cors_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.cors().is_empty() {
+        let filters = extension_filters
+            .cors()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_cors_spec(spec) {
+                    warn!("Skipping invalid CorsFilter {}", ref_);
+                    return None;
+                }
+
+                let allow_origins = spec
+                    .allow_origins
+                    .iter()
+                    .map(|origin| match origin.type_ {
+                        CorsFilterOriginMatchType::Exact => {
+                            ConfigCorsOrigin::Exact(origin.value.clone())
+                        }
+                        CorsFilterOriginMatchType::Wildcard => {
+                            ConfigCorsOrigin::Wildcard(origin.value.clone())
+                        }
+                        CorsFilterOriginMatchType::RegularExpression => {
+                            ConfigCorsOrigin::Regex(origin.value.clone())
+                        }
+                    })
+                    .collect();
+
+                Some(
+                    ConfigCorsFilter::builder()
+                        .key(ref_.to_string())
+                        .allow_origins(allow_origins)
+                        .allow_methods(spec.allow_methods.clone())
+                        .allow_headers(spec.allow_headers.clone())
+                        .expose_headers(spec.expose_headers.clone())
+                        .allow_credentials(spec.allow_credentials)
+                        .max_age_seconds(spec.max_age_seconds)
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_cors_filters(filters);
+    }
+}
+
+fn apply_compression_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.compressions().is_empty() {
+        let filters = extension_filters
+            .compressions()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_compression_spec(spec) {
+                    warn!("Skipping invalid CompressionFilter {}", ref_);
+                    return None;
+                }
+
+                let algorithms = spec
+                    .algorithms
+                    .iter()
+                    .map(|algorithm| match algorithm {
+                        CompressionFilterAlgorithm::Gzip => ConfigCompressionAlgorithm::Gzip,
+                        CompressionFilterAlgorithm::Brotli => ConfigCompressionAlgorithm::Brotli,
+                        CompressionFilterAlgorithm::Zstd => ConfigCompressionAlgorithm::Zstd,
+                    })
+                    .collect();
+
+                Some(
+                    ConfigCompressionFilter::builder()
+                        .key(ref_.to_string())
+                        .algorithms(algorithms)
+                        .content_types(spec.content_types.clone())
+                        .min_size_bytes(spec.min_size_bytes)
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_compression_filters(filters);
+    }
+}
+
+fn apply_cache_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.caches().is_empty() {
+        let filters = extension_filters
+            .caches()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_cache_spec(spec) {
+                    warn!("Skipping invalid CacheFilter {}", ref_);
+                    return None;
+                }
+
+                Some(
+                    ConfigCacheFilter::builder()
+                        .key(ref_.to_string())
+                        .max_object_size_bytes(spec.max_object_size_bytes)
+                        .stale_while_revalidate_seconds(spec.stale_while_revalidate_seconds)
+                        .stale_if_error_seconds(spec.stale_if_error_seconds)
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_cache_filters(filters);
+    }
+}
+
+fn apply_request_limits_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+) {
+    if !extension_filters.request_limits().is_empty() {
+        let filters = extension_filters
+            .request_limits()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_request_limits_spec(spec) {
+                    warn!("Skipping invalid RequestLimitsFilter {}", ref_);
+                    return None;
+                }
+
+                let body_mode = match spec.body_mode {
+                    RequestLimitsFilterBodyMode::Streaming => ConfigRequestBodyMode::Streaming,
+                    RequestLimitsFilterBodyMode::Buffered => ConfigRequestBodyMode::Buffered,
+                };
+
+                Some(
+                    ConfigRequestLimitsFilter::builder()
+                        .key(ref_.to_string())
+                        .max_request_body_bytes(spec.max_request_body_bytes)
+                        .max_request_headers_bytes(spec.max_request_headers_bytes)
+                        .body_mode(body_mode)
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_request_limits_filters(filters);
+    }
+}
+
+fn apply_external_auth_filters(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    extension_filters: &ExtensionFilters,
+    backends: &HashMap<ObjectRef, Backend>,
+) {
+    if !extension_filters.external_auths().is_empty() {
+        let filters = extension_filters
+            .external_auths()
+            .iter()
+            .filter_map(|(ref_, _, filter)| {
+                let spec = &filter.spec;
+                if !is_valid_external_auth_spec(spec) {
+                    warn!("Skipping invalid ExternalAuthFilter {}", ref_);
+                    return None;
+                }
+
+                let service_ref = ObjectRef::of_kind::<Service>()
+                    .namespace(filter.metadata.namespace.clone())
+                    .name(&spec.backend_ref.name)
+                    .build();
+
+                // Without endpoints the filter is still configured, so requests follow
+                // the failure mode rather than skipping authorization
+                let mut backend = BackendBuilder::default();
+                match backends.get(&service_ref) {
+                    Some(source) => configure_backend(source, &mut backend),
+                    None => {
+                        warn!(
+                            "Backend {} not found for ExternalAuthFilter {}",
+                            service_ref, ref_
+                        );
+                        backend
+                            .named(service_ref.name())
+                            .with_namespace(service_ref.namespace().as_ref());
+                    }
+                }
+                backend.with_port(Some(Port::new(spec.backend_ref.port)));
+                let backend = match backend.build() {
+                    Ok(backend) => backend,
+                    Err(err) => {
+                        warn!(
+                            "Failed to build backend for ExternalAuthFilter {}: {}",
+                            ref_, err
+                        );
+                        return None;
+                    }
+                };
+
+                let failure_mode = match spec.failure_mode {
+                    ExternalAuthFilterFailureMode::Closed => ConfigExternalAuthFailureMode::Closed,
+                    ExternalAuthFilterFailureMode::Open => ConfigExternalAuthFailureMode::Open,
+                };
+
+                let cache = spec.cache.as_ref().map(|cache| {
+                    ConfigExternalAuthCache::builder()
+                        .key_header(&cache.key_header)
+                        .ttl_seconds(cache.ttl_seconds)
+                        .build()
+                });
+
+                Some(
+                    ConfigExternalAuthFilter::builder()
+                        .key(ref_.to_string())
+                        .backend(backend)
+                        .path_prefix(spec.path_prefix.clone())
+                        .request_headers(spec.request_headers.clone())
+                        .upstream_headers(spec.upstream_headers.clone())
+                        .failure_mode(failure_mode)
+                        .timeout_milliseconds(spec.timeout_milliseconds)
+                        .cache(cache)
+                        .build(),
+                )
+            })
+            .collect();
+
+        gateway_configuration.with_external_auth_filters(filters);
+    }
+}
+
 fn apply_static_response_filters(
     builder: &mut GatewayConfigurationBuilder,
     extension_filters: &ExtensionFilters,
@@ -425,28 +872,46 @@ fn format_rule_id(gateway: &Gateway, route: &HTTPRoute, idx: usize) -> Option<St
 }
 
 fn add_backend(backend: &Backend, target: &mut HttpRouteRuleBuilder) {
-    target.add_backend(|target| {
-        let object_ref = backend.object_ref();
-        target
-            .named(object_ref.name())
-            .with_namespace(object_ref.namespace().as_ref())
-            .with_port(backend.port())
-            .with_weight(backend.weight());
-
-        for endpoint in backend.endpoints() {
-            for address in endpoint.addresses().iter().copied() {
-                target.add_endpoint(address, |target| {
-                    let zone_ref = endpoint.location();
-                    if let Some(node) = zone_ref.node() {
-                        target.with_node(node);
-                    }
-                    if let Some(zone) = zone_ref.zone() {
-                        target.with_zone(zone);
-                    }
-                });
+    target.add_backend(|target| configure_backend(backend, target));
+}
+
+fn configure_backend(backend: &Backend, target: &mut BackendBuilder) {
+    let object_ref = backend.object_ref();
+    target
+        .named(object_ref.name())
+        .with_namespace(object_ref.namespace().as_ref())
+        .with_port(backend.port())
+        .with_weight(backend.weight())
+        .with_tls(backend.tls().clone())
+        .with_slow_start(backend.traffic().slow_start())
+        .with_circuit_breaker(backend.traffic().circuit_breaker());
+
+    if let Some(host_header) = backend.host_header() {
+        let mut modifier = RequestHeaderModifierBuilder::new();
+        match modifier.set_header("Host", host_header) {
+            Ok(_) => {
+                target.with_request_header_modifier(modifier.build());
             }
+            Err(err) => warn!("Invalid Host header for backend {}: {}", object_ref, err),
         }
-    });
+    }
+
+    for endpoint in backend.endpoints() {
+        for address in endpoint.addresses().iter().cloned() {
+            target.add_endpoint(address, |target| {
+                let zone_ref = endpoint.location();
+                if let Some(node) = zone_ref.node() {
+                    target.with_node(node);
+                }
+                if let Some(zone) = zone_ref.zone() {
+                    target.with_zone(zone);
+                }
+                for zone_hint in endpoint.zone_hints() {
+                    target.add_zone_hint(zone_hint);
+                }
+            });
+        }
+    }
 }
 
 fn add_query_params_matches(
@@ -503,6 +968,11 @@ fn process_http_routes(
             }
 
             gateway_configuration.add_http_route(|r| {
+                r.with_key(format!(
+                    "{}/{}",
+                    http_route.namespace().unwrap_or_default(),
+                    http_route.name_any()
+                ));
                 add_host_header_matches_for_route(http_route, r);
 
                 // Process rules - handle the Option<Vec<HTTPRouteRules>> properly
@@ -549,6 +1019,14 @@ fn process_http_routes(
                                             url_rewrite: None,
                                             ext_static_response: None,
                                             ext_access_control: None,
+                                            ext_rate_limit: None,
+                                            ext_jwt_auth: None,
+                                            ext_external_auth: None,
+                                            ext_basic_auth: None,
+                                            ext_cors: None,
+                                            ext_compression: None,
+                                            ext_cache: None,
+                                            ext_request_limits: None,
                                         };
 
                                         target.add_filter(vg_filter);
@@ -588,6 +1066,14 @@ fn process_http_routes(
                                             url_rewrite: None,
                                             ext_static_response: None,
                                             ext_access_control: None,
+                                            ext_rate_limit: None,
+                                            ext_jwt_auth: None,
+                                            ext_external_auth: None,
+                                            ext_basic_auth: None,
+                                            ext_cors: None,
+                                            ext_compression: None,
+                                            ext_cache: None,
+                                            ext_request_limits: None,
                                         };
 
                                         target.add_filter(vg_filter);
@@ -606,6 +1092,14 @@ fn process_http_routes(
                                             url_rewrite: None,
                                             ext_static_response: None,
                                             ext_access_control: None,
+                                            ext_rate_limit: None,
+                                            ext_jwt_auth: None,
+                                            ext_external_auth: None,
+                                            ext_basic_auth: None,
+                                            ext_cors: None,
+                                            ext_compression: None,
+                                            ext_cache: None,
+                                            ext_request_limits: None,
                                         };
                                         target.add_filter(vg_filter);
                                     }
@@ -649,6 +1143,14 @@ fn process_http_routes(
                                             url_rewrite: Some(vg_url_rewrite),
                                             ext_static_response: None,
                                             ext_access_control: None,
+                                            ext_rate_limit: None,
+                                            ext_jwt_auth: None,
+                                            ext_external_auth: None,
+                                            ext_basic_auth: None,
+                                            ext_cors: None,
+                                            ext_compression: None,
+                                            ext_cache: None,
+                                            ext_request_limits: None,
                                         };
                                         target.add_filter(vg_filter);
                                     }
@@ -676,6 +1178,14 @@ fn process_http_routes(
                                                         url_rewrite: None,
                                                         ext_static_response: Some(static_response),
                                                         ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
                                                     };
 
                                                     target.add_filter(vg_filter);
@@ -699,6 +1209,262 @@ fn process_http_routes(
                                                         url_rewrite: None,
                                                         ext_static_response: None,
                                                         ext_access_control: Some(access_control),
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::RateLimitFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<RateLimitFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let rate_limit = ExtRateLimitRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtRateLimit,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: Some(rate_limit),
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::JwtAuthFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<JwtAuthFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let jwt_auth = ExtJwtAuthRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtJwtAuth,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: Some(jwt_auth),
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::ExternalAuthFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<ExternalAuthFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let external_auth = ExtExternalAuthRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtExternalAuth,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: Some(external_auth),
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::BasicAuthFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<BasicAuthFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let basic_auth = ExtBasicAuthRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtBasicAuth,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: Some(basic_auth),
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::CorsFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<CorsFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let cors = ExtCorsRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtCors,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: Some(cors),
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::CompressionFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<CompressionFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let compression = ExtCompressionRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtCompression,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: Some(compression),
+                                                        ext_cache: None,
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::CacheFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<CacheFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let cache = ExtCacheRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtCache,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: Some(cache),
+                                                        ext_request_limits: None,
+                                                    };
+
+                                                    target.add_filter(vg_filter);
+                                                }
+                                                Ok(ExtensionFilterKind::RequestLimitsFilter) => {
+                                                    let filter_ref = ObjectRef::of_kind::<RequestLimitsFilter>()
+                                                        .namespace(http_route.metadata.namespace.clone())
+                                                        .name(&extension_ref.name)
+                                                        .build();
+
+                                                    let request_limits = ExtRequestLimitsRef::builder()
+                                                        .key(filter_ref.to_string())
+                                                        .build();
+
+                                                    let vg_filter = HttpRouteFilter {
+                                                        filter_type: HttpRouteFilterType::ExtRequestLimits,
+                                                        request_header_modifier: None,
+                                                        response_header_modifier: None,
+                                                        request_mirror: None,
+                                                        request_redirect: None,
+                                                        url_rewrite: None,
+                                                        ext_static_response: None,
+                                                        ext_access_control: None,
+                                                        ext_rate_limit: None,
+                                                        ext_jwt_auth: None,
+                                                        ext_external_auth: None,
+                                                        ext_basic_auth: None,
+                                                        ext_cors: None,
+                                                        ext_compression: None,
+                                                        ext_cache: None,
+                                                        ext_request_limits: Some(request_limits),
                                                     };
 
                                                     target.add_filter(vg_filter);
@@ -735,16 +1501,12 @@ fn process_http_routes(
                             // Process backend references
                             if let Some(backend_refs) = &rule.backend_refs {
                                 for backend_ref in backend_refs {
-                                    let source_ref = ObjectRef::of_kind::<Service>()
-                                        .namespace(
-                                            backend_ref.namespace.clone().or_else(|| {
-                                                http_route.metadata.namespace.clone()
-                                            }),
-                                        )
-                                        .name(&backend_ref.name)
-                                        .build();
-
-                                    match backends.get(&source_ref) {
+                                    let source_ref = backend_ref_object_ref(
+                                        backend_ref,
+                                        http_route.metadata.namespace.as_deref(),
+                                    );
+
+                                    match source_ref.and_then(|r| backends.get(&r)) {
                                         Some(source) => {
                                             add_backend(source, target);
                                         }
@@ -841,6 +1603,9 @@ fn set_ipc(
 ) {
     gateway_configuration.with_ipc(|cp| {
         cp.with_endpoint(primary_instance_ip_addr, ipc_services.port());
+        if let Some(ca_certificate) = ipc_services.ca_certificate() {
+            cp.with_ca_certificate(ca_certificate);
+        }
     });
 }
 
@@ -914,6 +1679,81 @@ fn set_error_responses_strategy(
     gateway_configuration.with_error_responses(error_responses);
 }
 
+fn set_access_logs_strategy(
+    gateway_configuration: &mut GatewayConfigurationBuilder,
+    instance: &GatewayInstanceConfiguration,
+) {
+    let Some(access_logs) = instance.configuration().access_logs.as_ref() else {
+        return;
+    };
+    let gateway_namespace = instance.gateway().namespace().unwrap_or_default();
+
+    let format = match (access_logs.format, &access_logs.template) {
+        (AccessLogFormat::Json, _) => ConfigAccessLogFormat::Json,
+        (AccessLogFormat::Common, _) => ConfigAccessLogFormat::Common,
+        (AccessLogFormat::Combined, _) => ConfigAccessLogFormat::Combined,
+        (AccessLogFormat::Template, Some(template)) => {
+            ConfigAccessLogFormat::Template(template.clone())
+        }
+        (AccessLogFormat::Template, None) => {
+            warn!("AccessLogFormat::Template requires a template to be set, logging JSON");
+            ConfigAccessLogFormat::Json
+        }
+    };
+
+    let sink = match (access_logs.sink, &access_logs.file) {
+        (AccessLogSink::Stdout, _) => ConfigAccessLogSink::Stdout,
+        (AccessLogSink::File, Some(file)) => ConfigAccessLogSink::File(
+            ConfigAccessLogFile::builder()
+                .path(&file.path)
+                .max_size_bytes(file.max_size_bytes)
+                .max_files(file.max_files)
+                .build(),
+        ),
+        (AccessLogSink::File, None) => {
+            warn!("AccessLogSink::File requires a file to be set, logging to stdout");
+            ConfigAccessLogSink::Stdout
+        }
+        (AccessLogSink::OpenTelemetry, _) => ConfigAccessLogSink::OpenTelemetry,
+    };
+
+    let routes = access_logs
+        .routes
+        .iter()
+        .map(|route| {
+            let namespace = route.namespace.as_deref().unwrap_or(&gateway_namespace);
+            ConfigAccessLogRoute::builder()
+                .key(format!("{}/{}", namespace, route.name))
+                .sample_ratio(route.sample_ratio.map(|ratio| ratio.clamp(0.0, 1.0)))
+                .status_classes(map_access_log_status_classes(&route.status_classes))
+                .build()
+        })
+        .collect();
+
+    gateway_configuration.with_access_logs(
+        ConfigAccessLogs::builder()
+            .format(format)
+            .sink(sink)
+            .sample_ratio(access_logs.sample_ratio.unwrap_or(1.0).clamp(0.0, 1.0))
+            .status_classes(map_access_log_status_classes(&access_logs.status_classes))
+            .routes(routes)
+            .build(),
+    );
+}
+
+fn map_access_log_status_classes(status_classes: &[AccessLogStatusClass]) -> Vec<StatusClass> {
+    status_classes
+        .iter()
+        .map(|status_class| match status_class {
+            AccessLogStatusClass::Informational => StatusClass::Informational,
+            AccessLogStatusClass::Successful => StatusClass::Successful,
+            AccessLogStatusClass::Redirection => StatusClass::Redirection,
+            AccessLogStatusClass::ClientError => StatusClass::ClientError,
+            AccessLogStatusClass::ServerError => StatusClass::ServerError,
+        })
+        .collect()
+}
+
 fn set_client_addrs_strategy(
     gateway_configuration: &mut GatewayConfigurationBuilder,
     instance: &GatewayInstanceConfiguration,
@@ -1,4 +1,5 @@
 use crate::controllers::instances::InstanceRole;
+use crate::controllers::sync::gateway_service_account_name;
 use crate::controllers::transformers::GatewayInstanceConfiguration;
 use crate::kubernetes::objects::{ObjectRef, SyncObjectAction};
 use crate::kubernetes::KubeClientCell;
@@ -18,6 +19,8 @@ use vg_api::v1alpha1::{
     GatewayInstrumentationOpenTelemetrySamplingType,
 };
 use vg_core::continue_after;
+use vg_core::ipc::configuration::{ConfigurationRollout, CONFIGURATION_ROLLOUT_ANNOTATION};
+use vg_core::ipc::IPC_TOKEN_AUDIENCE;
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
 use vg_core::{await_ready, ReadyState};
@@ -45,6 +48,8 @@ struct TemplateValues {
     #[builder(setter(into))]
     configmap_name: String,
     #[builder(setter(into))]
+    service_account_name: String,
+    #[builder(setter(into))]
     image_pull_policy: String,
     #[builder(setter(into))]
     image_repository: String,
@@ -52,6 +57,14 @@ struct TemplateValues {
     image_tag: String,
     replicas: i32,
     open_telemetry: OpenTelemetryTemplateValues,
+    prometheus_enabled: bool,
+    #[builder(setter(into))]
+    ipc_token_audience: String,
+    #[builder(setter(into))]
+    configuration_rollout_annotation: String,
+    /// The [`ConfigurationRollout`] of the gateway as JSON, for `vgctl` to show drift
+    #[builder(setter(into))]
+    configuration_rollout: String,
 }
 
 pub fn sync_gateway_deployments(
@@ -60,6 +73,7 @@ pub fn sync_gateway_deployments(
     kube_client_rx: &Receiver<KubeClientCell>,
     instance_role_rx: &Receiver<InstanceRole>,
     gateway_instances_rx: &Receiver<HashMap<ObjectRef, GatewayInstanceConfiguration>>,
+    configuration_rollouts_rx: &Receiver<HashMap<ObjectRef, ConfigurationRollout>>,
 ) {
     let (tx, current_service_refs_rx): (
         tokio::sync::mpsc::UnboundedSender<
@@ -86,6 +100,7 @@ pub fn sync_gateway_deployments(
         tx,
         current_service_refs_rx,
         gateway_instances_rx,
+        configuration_rollouts_rx,
     );
 }
 
@@ -155,8 +170,10 @@ fn generate_gateway_deployments(
     tx: UnboundedSender<SyncObjectAction<TemplateValues, Deployment>>,
     current_service_refs_rx: Receiver<HashSet<ObjectRef>>,
     gateway_instances_rx: &Receiver<HashMap<ObjectRef, GatewayInstanceConfiguration>>,
+    configuration_rollouts_rx: &Receiver<HashMap<ObjectRef, ConfigurationRollout>>,
 ) {
     let gateway_instances_rx = gateway_instances_rx.clone();
+    let configuration_rollouts_rx = configuration_rollouts_rx.clone();
 
     task_builder
         .new_task(stringify!(generate_gateway_deployments))
@@ -165,6 +182,7 @@ fn generate_gateway_deployments(
                 if let ReadyState::Ready((gateway_instances, current_service_refs)) =
                     await_ready!(gateway_instances_rx, current_service_refs_rx)
                 {
+                    let configuration_rollouts = configuration_rollouts_rx.get().await;
                     let desired_deployments: Vec<_> = gateway_instances
                         .iter()
                         .map(|(gateway_ref, instance)| {
@@ -196,6 +214,9 @@ fn generate_gateway_deployments(
                                 .gateway_name(gateway_ref.name())
                                 .cluster_name("TBD")
                                 .configmap_name(format!("{}-config", gateway_ref.name()))
+                                .service_account_name(gateway_service_account_name(
+                                    gateway_ref.name(),
+                                ))
                                 .image_pull_policy(Into::<&'static str>::into(
                                     instance.image_pull_policy(),
                                 ))
@@ -203,6 +224,22 @@ fn generate_gateway_deployments(
                                 .image_tag(instance.image_tag().to_string())
                                 .replicas(replicas)
                                 .open_telemetry(open_telemetry)
+                                .prometheus_enabled(
+                                    instance
+                                        .merged_prometheus()
+                                        .as_ref()
+                                        .and_then(|prometheus| prometheus.enabled)
+                                        .unwrap_or_default(),
+                                )
+                                .ipc_token_audience(IPC_TOKEN_AUDIENCE)
+                                .configuration_rollout_annotation(CONFIGURATION_ROLLOUT_ANNOTATION)
+                                .configuration_rollout(
+                                    configuration_rollouts
+                                        .as_ref()
+                                        .and_then(|rollouts| rollouts.get(gateway_ref))
+                                        .and_then(|rollout| serde_json::to_string(rollout).ok())
+                                        .unwrap_or_default(),
+                                )
                                 .build();
 
                             (
@@ -253,7 +290,8 @@ fn generate_gateway_deployments(
                 continue_after!(
                     options.auto_cycle_duration(),
                     gateway_instances_rx.changed(),
-                    current_service_refs_rx.changed()
+                    current_service_refs_rx.changed(),
+                    configuration_rollouts_rx.changed()
                 );
             }
         });
@@ -0,0 +1,156 @@
+use crate::controllers::instances::InstanceRole;
+use crate::controllers::transformers::GatewayInstanceConfiguration;
+use crate::kubernetes::objects::{ObjectRef, SyncObjectAction};
+use crate::kubernetes::KubeClientCell;
+use crate::options::Options;
+use crate::{sync_objects, watch_objects};
+use gtmpl_derive::Gtmpl;
+use k8s_openapi::api::core::v1::ServiceAccount;
+use kube::Resource;
+use kube::api::ObjectMeta;
+use kube::runtime::watcher::Config;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use typed_builder::TypedBuilder;
+use vg_core::continue_after;
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, ReadyState};
+
+const TEMPLATE: &str = include_str!("./templates/gateway_service_account.kubernetes-helm-yaml");
+
+#[derive(Clone, TypedBuilder, Debug, Gtmpl)]
+struct TemplateValues {
+    #[builder(setter(into))]
+    gateway_name: String,
+}
+
+/// The ServiceAccount the pods of a gateway run as, which is the identity the IPC endpoint
+/// authorizes them with. It is suffixed so it never takes over an existing ServiceAccount such
+/// as `default`
+pub fn gateway_service_account_name(gateway_name: &str) -> String {
+    format!("{gateway_name}-vale-gateway")
+}
+
+/// Each gateway runs as its own ServiceAccount, controlled by its Gateway
+pub fn sync_gateway_service_accounts(
+    options: Arc<Options>,
+    task_builder: &TaskBuilder,
+    kube_client_rx: Receiver<KubeClientCell>,
+    instance_role_rx: Receiver<InstanceRole>,
+    gateway_instances_rx: &Receiver<HashMap<ObjectRef, GatewayInstanceConfiguration>>,
+) {
+    let (tx, current_refs_rx) = sync_objects!(
+        options,
+        task_builder,
+        ServiceAccount,
+        kube_client_rx,
+        instance_role_rx,
+        TemplateValues,
+        TEMPLATE
+    );
+    generate_gateway_service_accounts(
+        options,
+        task_builder,
+        tx,
+        current_refs_rx,
+        gateway_instances_rx,
+    );
+}
+
+fn generate_gateway_service_accounts(
+    options: Arc<Options>,
+    task_builder: &TaskBuilder,
+    tx: UnboundedSender<SyncObjectAction<TemplateValues, ServiceAccount>>,
+    service_account_refs_rx: Receiver<HashSet<ObjectRef>>,
+    gateway_instances_rx: &Receiver<HashMap<ObjectRef, GatewayInstanceConfiguration>>,
+) {
+    let gateway_instances_rx = gateway_instances_rx.clone();
+
+    task_builder
+        .new_task(stringify!(generate_gateway_service_accounts))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((gateway_instances, service_account_refs)) =
+                    await_ready!(gateway_instances_rx, service_account_refs_rx)
+                {
+                    let desired_service_accounts: Vec<_> = gateway_instances
+                        .iter()
+                        .filter_map(|(gateway_ref, gateway_instance)| {
+                            let Some(owner_reference) =
+                                gateway_instance.gateway().controller_owner_ref(&())
+                            else {
+                                warn!(
+                                    "Skipping ServiceAccount of gateway {} without a UID",
+                                    gateway_ref
+                                );
+                                return None;
+                            };
+
+                            let service_account_ref = ObjectRef::of_kind::<ServiceAccount>()
+                                .namespace(gateway_ref.namespace().clone())
+                                .name(gateway_service_account_name(gateway_ref.name()))
+                                .build();
+
+                            let template_values = TemplateValues::builder()
+                                .gateway_name(gateway_ref.name())
+                                .build();
+
+                            // The sync only patches ServiceAccounts this Gateway controls
+                            let service_account_overrides = ServiceAccount {
+                                metadata: ObjectMeta {
+                                    owner_references: Some(vec![owner_reference]),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            };
+
+                            Some((
+                                service_account_ref,
+                                gateway_ref,
+                                template_values,
+                                service_account_overrides,
+                            ))
+                        })
+                        .collect();
+
+                    let desired_service_account_refs: HashSet<_> = desired_service_accounts
+                        .iter()
+                        .map(|(ref_, _, _, _)| ref_.clone())
+                        .collect();
+
+                    let deleted_refs =
+                        service_account_refs.difference(&desired_service_account_refs);
+                    for deleted_ref in deleted_refs {
+                        let _ = tx
+                            .send(SyncObjectAction::Delete(deleted_ref.clone()))
+                            .inspect_err(|err| warn!("Failed to send delete action: {}", err));
+                    }
+
+                    for (
+                        service_account_ref,
+                        gateway_ref,
+                        template_values,
+                        service_account_overrides,
+                    ) in desired_service_accounts
+                    {
+                        let _ = tx
+                            .send(SyncObjectAction::Upsert(
+                                service_account_ref,
+                                gateway_ref.clone(),
+                                template_values,
+                                Some(service_account_overrides),
+                            ))
+                            .inspect_err(|err| warn!("Failed to send upsert action: {}", err));
+                    }
+                }
+                continue_after!(
+                    options.auto_cycle_duration(),
+                    gateway_instances_rx.changed(),
+                    service_account_refs_rx.changed()
+                );
+            }
+        });
+}
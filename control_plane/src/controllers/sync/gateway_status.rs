@@ -6,9 +6,12 @@ use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
 use k8s_openapi::chrono;
 use kube::api::PostParams;
 use kube::Api;
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, info, info_span, instrument, warn, Instrument};
+use vg_core::ipc::configuration::{ConfigurationDrift, ConfigurationRollout};
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
 use vg_core::{continue_after, await_ready, ReadyState};
@@ -18,10 +21,12 @@ pub fn sync_gateway_status(
     kube_client_rx: &Receiver<KubeClientCell>,
     instance_role_rx: &Receiver<InstanceRole>,
     gateways_rx: &Receiver<Objects<Gateway>>,
+    configuration_rollouts_rx: &Receiver<HashMap<ObjectRef, ConfigurationRollout>>,
 ) {
     let kube_client_rx = kube_client_rx.clone();
     let instance_role_rx = instance_role_rx.clone();
     let gateways_rx = gateways_rx.clone();
+    let configuration_rollouts_rx = configuration_rollouts_rx.clone();
 
     task_builder
         .new_task(stringify!(sync_gateway_status))
@@ -33,8 +38,14 @@ pub fn sync_gateway_status(
                         continue;
                     }
 
+                    // Rollouts are only known once a configuration was generated
+                    let configuration_rollouts = configuration_rollouts_rx.get().await.clone();
                     for (gateway_ref, _, gateway) in gateways.iter() {
-                        sync_single_gateway_status(kube_client, gateway_ref, &gateway).await;
+                        let rollout = configuration_rollouts
+                            .as_ref()
+                            .and_then(|rollouts| rollouts.get(&gateway_ref));
+                        sync_single_gateway_status(kube_client, gateway_ref, &gateway, rollout)
+                            .await;
                     }
                 }
 
@@ -42,21 +53,23 @@ pub fn sync_gateway_status(
                     Duration::from_secs(30),
                     kube_client_rx.changed(),
                     instance_role_rx.changed(),
-                    gateways_rx.changed()
+                    gateways_rx.changed(),
+                    configuration_rollouts_rx.changed()
                 );
             }
         });
 }
 
-#[instrument(skip(kube_client, gateway))]
+#[instrument(skip(kube_client, gateway, rollout))]
 async fn sync_single_gateway_status(
     kube_client: &KubeClientCell,
     gateway_ref: ObjectRef,
     gateway: &Arc<Gateway>,
+    rollout: Option<&ConfigurationRollout>,
 ) {
     info!("Syncing status for Gateway: {:?}", gateway_ref);
 
-    let status = build_gateway_status(gateway);
+    let status = build_gateway_status(gateway, rollout);
     debug!("Gateway status to be updated: {:?}", status);
 
     let gateway_api = Api::<Gateway>::namespaced(
@@ -102,8 +115,12 @@ async fn sync_single_gateway_status(
     }
 }
 
-fn build_gateway_status(gateway: &Gateway) -> GatewayStatus {
+fn build_gateway_status(
+    gateway: &Gateway,
+    rollout: Option<&ConfigurationRollout>,
+) -> GatewayStatus {
     let now = chrono::Utc::now();
+    let (programmed_status, programmed_reason, programmed_message) = programmed_condition(rollout);
     let spec = &gateway.spec;
 
     // Build listener statuses - simplified for now since the correct struct names need to be found
@@ -147,9 +164,9 @@ fn build_gateway_status(gateway: &Gateway) -> GatewayStatus {
             "lastTransitionTime": now.to_rfc3339()
         }, {
             "type": "Programmed",
-            "status": "True",
-            "reason": "Programmed",
-            "message": "Gateway is programmed",
+            "status": programmed_status,
+            "reason": programmed_reason,
+            "message": programmed_message,
             "lastTransitionTime": now.to_rfc3339()
         }],
         "listeners": listener_statuses
@@ -169,3 +186,147 @@ fn build_gateway_status(gateway: &Gateway) -> GatewayStatus {
         listeners: None,
     })
 }
+
+/// The status, reason and message of the Programmed condition, from how far the gateway pods
+/// got in applying the latest configuration version
+fn programmed_condition(
+    rollout: Option<&ConfigurationRollout>,
+) -> (&'static str, &'static str, String) {
+    let Some(rollout) = rollout else {
+        return (
+            "False",
+            "Pending",
+            "Waiting for the gateway configuration to be generated".to_string(),
+        );
+    };
+
+    if rollout.is_complete() {
+        return (
+            "True",
+            "Programmed",
+            format!(
+                "All {} pod(s) applied configuration version {}",
+                rollout.pods().len(),
+                rollout.version()
+            ),
+        );
+    }
+
+    let applied = rollout.pods().len() - rollout.drifted_pods().count();
+    let mut message = format!(
+        "{applied} of {} pod(s) applied configuration version {}",
+        rollout.pods().len(),
+        rollout.version()
+    );
+    let rejections: Vec<_> = rollout
+        .drifted_pods()
+        .filter(|(_, drift)| *drift == ConfigurationDrift::Rejected)
+        .filter_map(|(pod_name, _)| {
+            let error = rollout.pods().get(pod_name)?.error().as_ref()?;
+            Some((pod_name, error))
+        })
+        .collect();
+
+    if let Some((pod_name, error)) = rejections.first() {
+        let _ = write!(message, "; rejected by pod {pod_name}: {error}");
+        ("False", "Invalid", message)
+    } else {
+        ("False", "Pending", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use vg_core::ipc::configuration::{
+        ConfigurationStatus, ConfigurationStatusReport, PodConfigurationState,
+    };
+
+    fn pod_state(version: u64, status: ConfigurationStatus) -> PodConfigurationState {
+        let mut state = PodConfigurationState::default();
+        state.apply(
+            &ConfigurationStatusReport::builder()
+                .pod_name("gateway")
+                .version(version)
+                .status(status)
+                .build(),
+        );
+        state
+    }
+
+    #[test]
+    fn test_programmed_condition() {
+        assert_eq!(programmed_condition(None).1, "Pending");
+
+        let rollout = ConfigurationRollout::builder().version(2).build();
+        assert_eq!(
+            programmed_condition(Some(&rollout)),
+            (
+                "False",
+                "Pending",
+                "0 of 0 pod(s) applied configuration version 2".to_string()
+            )
+        );
+
+        let rollout = ConfigurationRollout::builder()
+            .version(2)
+            .pods(BTreeMap::from([
+                (
+                    "gateway-a".to_string(),
+                    pod_state(2, ConfigurationStatus::Applied),
+                ),
+                (
+                    "gateway-b".to_string(),
+                    pod_state(1, ConfigurationStatus::Applied),
+                ),
+            ]))
+            .build();
+        assert_eq!(
+            programmed_condition(Some(&rollout)),
+            (
+                "False",
+                "Pending",
+                "1 of 2 pod(s) applied configuration version 2".to_string()
+            )
+        );
+
+        let rejected = ConfigurationStatus::Rejected {
+            error: "invalid listener".to_string(),
+        };
+        let rollout = ConfigurationRollout::builder()
+            .version(2)
+            .pods(BTreeMap::from([
+                (
+                    "gateway-a".to_string(),
+                    pod_state(2, ConfigurationStatus::Applied),
+                ),
+                ("gateway-b".to_string(), pod_state(2, rejected)),
+            ]))
+            .build();
+        assert_eq!(
+            programmed_condition(Some(&rollout)),
+            (
+                "False",
+                "Invalid",
+                "1 of 2 pod(s) applied configuration version 2; rejected by pod gateway-b: invalid listener".to_string()
+            )
+        );
+
+        let rollout = ConfigurationRollout::builder()
+            .version(2)
+            .pods(BTreeMap::from([(
+                "gateway-a".to_string(),
+                pod_state(2, ConfigurationStatus::Applied),
+            )]))
+            .build();
+        assert_eq!(
+            programmed_condition(Some(&rollout)),
+            (
+                "True",
+                "Programmed",
+                "All 1 pod(s) applied configuration version 2".to_string()
+            )
+        );
+    }
+}
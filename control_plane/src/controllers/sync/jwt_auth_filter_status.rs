@@ -0,0 +1,369 @@
+use super::rate_limit_filter_status::is_valid_header_name;
+use crate::kubernetes::objects::Objects;
+use crate::kubernetes::KubeClientCell;
+use anyhow::{Context, Result};
+use gateway_api::apis::standard::httproutes::HTTPRoute;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::chrono::Utc;
+use kube::api::PostParams;
+use kube::{Api, Client};
+use std::ops::Deref;
+use tracing::{debug, info, info_span, warn, Instrument};
+use vg_api::v1alpha1::{
+    JwtAuthFilter, JwtAuthFilterConditionReason, JwtAuthFilterConditionType, JwtAuthFilterStatus,
+};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, ReadyState};
+
+/// Controller for managing `JwtAuthFilter` status updates
+pub fn sync_jwt_auth_filter_status(
+    task_builder: &TaskBuilder,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    jwt_auth_filters_rx: &Receiver<Objects<JwtAuthFilter>>,
+    http_routes_rx: &Receiver<Objects<HTTPRoute>>,
+) {
+    let kube_client_rx = kube_client_rx.clone();
+    let jwt_auth_filters_rx = jwt_auth_filters_rx.clone();
+    let http_routes_rx = http_routes_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_jwt_auth_filter_status))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((kube_client, jwt_auth_filters, http_routes)) =
+                    await_ready!(kube_client_rx, jwt_auth_filters_rx, http_routes_rx)
+                {
+                    info!("Syncing status for JwtAuthFilters");
+
+                    // Iterate through all JWT auth filters
+                    for (filter_ref, _, filter) in jwt_auth_filters.iter() {
+                        debug!("Processing JwtAuthFilter: {}", filter_ref);
+
+                        let attached_routes = count_attached_routes(&filter, http_routes);
+                        let status = create_filter_status(&filter.spec, attached_routes);
+
+                        if let Err(e) =
+                            update_filter_status(kube_client.deref().clone(), &filter, status).await
+                        {
+                            warn!(
+                                "Failed to update status for JwtAuthFilter {}: {}",
+                                filter_ref, e
+                            );
+                        }
+                    }
+                }
+
+                vg_core::continue_on!(
+                    jwt_auth_filters_rx.changed(),
+                    http_routes_rx.changed(),
+                    kube_client_rx.changed()
+                );
+            }
+        });
+}
+
+/// Count how many routes are using this JWT auth filter
+fn count_attached_routes(filter: &JwtAuthFilter, http_routes: &Objects<HTTPRoute>) -> i32 {
+    let default_name = String::new();
+    let default_namespace = String::new();
+    let filter_name = filter.metadata.name.as_ref().unwrap_or(&default_name);
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .unwrap_or(&default_namespace);
+
+    let mut count = 0;
+    for (_, _, route) in http_routes.iter() {
+        if is_filter_attached_to_route(filter_name, filter_namespace, &route) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Check if a JWT auth filter is attached to a specific HTTP route
+fn is_filter_attached_to_route(
+    filter_name: &str,
+    filter_namespace: &str,
+    route: &HTTPRoute,
+) -> bool {
+    if let Some(rules) = &route.spec.rules {
+        for rule in rules {
+            if let Some(filters) = &rule.filters {
+                for filter in filters {
+                    if let Some(extension_ref) = &filter.extension_ref {
+                        // Check if this is a reference to our JwtAuthFilter
+                        if extension_ref.group == "vale-gateway.whitefamily.in"
+                            && extension_ref.kind == "JwtAuthFilter"
+                            && extension_ref.name == filter_name
+                        {
+                            // For extension refs, we assume same namespace as the route since
+                            // the HTTPRoute extension ref doesn't have a namespace field
+                            let route_namespace = route.metadata.namespace.as_deref();
+                            if route_namespace == Some(filter_namespace) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Create status for a `JwtAuthFilter` based on its spec and attachment info
+fn create_filter_status(
+    spec: &vg_api::v1alpha1::JwtAuthFilterSpec,
+    attached_routes: i32,
+) -> JwtAuthFilterStatus {
+    let now = Time(Utc::now());
+    let mut conditions = Vec::new();
+
+    // Accepted condition - validate the filter configuration
+    let accepted_condition = if is_valid_spec(spec) {
+        Condition {
+            type_: JwtAuthFilterConditionType::Accepted.as_str().to_string(),
+            status: "True".to_string(),
+            reason: JwtAuthFilterConditionReason::Accepted
+                .as_str()
+                .to_string(),
+            message: "JwtAuthFilter configuration is valid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: JwtAuthFilterConditionType::Accepted.as_str().to_string(),
+            status: "False".to_string(),
+            reason: JwtAuthFilterConditionReason::InvalidConfiguration
+                .as_str()
+                .to_string(),
+            message: "JwtAuthFilter configuration is invalid".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(accepted_condition);
+
+    // Ready condition - filter is ready if it's accepted
+    let ready_condition = if conditions[0].status == "True" {
+        Condition {
+            type_: JwtAuthFilterConditionType::Ready.as_str().to_string(),
+            status: "True".to_string(),
+            reason: JwtAuthFilterConditionReason::Ready.as_str().to_string(),
+            message: "JwtAuthFilter is ready to authenticate requests".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: JwtAuthFilterConditionType::Ready.as_str().to_string(),
+            status: "False".to_string(),
+            reason: JwtAuthFilterConditionReason::NotReady
+                .as_str()
+                .to_string(),
+            message: "JwtAuthFilter is not ready due to invalid configuration".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(ready_condition);
+
+    // Attached condition - whether the filter is attached to any routes
+    let attached_condition = if attached_routes > 0 {
+        Condition {
+            type_: JwtAuthFilterConditionType::Attached.as_str().to_string(),
+            status: "True".to_string(),
+            reason: JwtAuthFilterConditionReason::AttachedToRoute
+                .as_str()
+                .to_string(),
+            message: format!("JwtAuthFilter is attached to {attached_routes} route(s)"),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    } else {
+        Condition {
+            type_: JwtAuthFilterConditionType::Attached.as_str().to_string(),
+            status: "False".to_string(),
+            reason: JwtAuthFilterConditionReason::NotAttached
+                .as_str()
+                .to_string(),
+            message: "JwtAuthFilter is not attached to any routes".to_string(),
+            last_transition_time: now.clone(),
+            observed_generation: None,
+        }
+    };
+    conditions.push(attached_condition);
+
+    JwtAuthFilterStatus {
+        conditions: Some(conditions),
+        attached_routes,
+        last_updated: Some(now),
+    }
+}
+
+/// Validate that tokens can be verified and that claim headers name a header
+pub(super) fn is_valid_spec(spec: &vg_api::v1alpha1::JwtAuthFilterSpec) -> bool {
+    !spec.issuer.is_empty()
+        && !spec.jwks.name.is_empty()
+        && !spec.jwks.key.is_empty()
+        && spec.claim_headers.iter().all(|claim_header| {
+            !claim_header.claim.is_empty() && is_valid_header_name(&claim_header.header)
+        })
+}
+
+/// Update the status of a `JwtAuthFilter`
+async fn update_filter_status(
+    client: Client,
+    filter: &JwtAuthFilter,
+    status: JwtAuthFilterStatus,
+) -> Result<()> {
+    let filter_name = filter
+        .metadata
+        .name
+        .as_ref()
+        .context("Filter name not found")?;
+    let filter_namespace = filter
+        .metadata
+        .namespace
+        .as_ref()
+        .context("Filter namespace not found")?;
+
+    let api: Api<JwtAuthFilter> = Api::namespaced(client, filter_namespace);
+
+    debug!(
+        "Updating status for JwtAuthFilter {}/{}",
+        filter_namespace, filter_name
+    );
+
+    // Retry mechanism to handle conflicts (optimistic concurrency control)
+    let max_retries = 5;
+    let mut attempt = 0;
+
+    while attempt < max_retries {
+        attempt += 1;
+
+        // Get the latest version of the filter
+        let current_filter = api
+            .get_status(filter_name)
+            .instrument(info_span!("get_jwt_auth_filter_status"))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get current status of JwtAuthFilter {filter_namespace}/{filter_name}"
+                )
+            })?;
+
+        // Check if the status actually needs to be updated
+        if let Some(existing_status) = &current_filter.status
+            && existing_status == &status
+        {
+            debug!(
+                "Status for JwtAuthFilter {}/{} is already up to date",
+                filter_namespace, filter_name
+            );
+            return Ok(());
+        }
+
+        // Create a new version with updated status
+        let mut updated_filter = current_filter.clone();
+        updated_filter.status = Some(status.clone());
+
+        // Attempt to update the status
+        match api
+            .replace_status(
+                filter_name,
+                &PostParams::default(),
+                serde_json::to_vec(&updated_filter)?,
+            )
+            .instrument(info_span!("replace_jwt_auth_filter_status"))
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    "Successfully updated status for JwtAuthFilter {}/{} on attempt {}",
+                    filter_namespace, filter_name, attempt
+                );
+                return Ok(());
+            }
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                // Conflict error - resource was modified, retry
+                warn!(
+                    "Conflict updating JwtAuthFilter {}/{} status on attempt {}, retrying...",
+                    filter_namespace, filter_name, attempt
+                );
+                if attempt >= max_retries {
+                    return Err(anyhow::anyhow!(
+                        "Failed to update status after {} attempts due to conflicts: {}",
+                        max_retries,
+                        api_error
+                    ));
+                }
+                // Brief delay before retry to avoid tight retry loops
+                tokio::time::sleep(tokio::time::Duration::from_millis(100 * attempt as u64)).await;
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to update status of JwtAuthFilter {}/{}: {}",
+                    filter_namespace,
+                    filter_name,
+                    e
+                ));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Exhausted all {} retry attempts for JwtAuthFilter {}/{}",
+        max_retries,
+        filter_namespace,
+        filter_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_api::v1alpha1::{
+        JwtAuthFilterClaimHeader, JwtAuthFilterJwks, JwtAuthFilterJwksSource, JwtAuthFilterSpec,
+    };
+
+    fn spec() -> JwtAuthFilterSpec {
+        JwtAuthFilterSpec {
+            issuer: "https://issuer.example.com".to_string(),
+            audiences: vec!["api".to_string()],
+            jwks: JwtAuthFilterJwks {
+                source: JwtAuthFilterJwksSource::Secret,
+                name: "jwks".to_string(),
+                key: "jwks.json".to_string(),
+            },
+            clock_skew_seconds: 60,
+            claim_headers: vec![JwtAuthFilterClaimHeader {
+                claim: "sub".to_string(),
+                header: "x-user-id".to_string(),
+            }],
+            realm: None,
+        }
+    }
+
+    #[test]
+    fn test_is_valid_spec() {
+        assert!(is_valid_spec(&spec()));
+
+        let mut missing_issuer = spec();
+        missing_issuer.issuer = String::new();
+        assert!(!is_valid_spec(&missing_issuer));
+
+        let mut missing_jwks = spec();
+        missing_jwks.jwks.name = String::new();
+        assert!(!is_valid_spec(&missing_jwks));
+
+        let mut invalid_header = spec();
+        invalid_header.claim_headers[0].header = "not a header".to_string();
+        assert!(!is_valid_spec(&invalid_header));
+    }
+}
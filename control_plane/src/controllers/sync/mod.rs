@@ -1,18 +1,38 @@
+mod gateway_cache_purges;
 mod gateway_configmaps;
 mod gateway_deployments;
+mod gateway_service_accounts;
 mod gateway_services;
 
 mod access_control_filter_status;
+mod basic_auth_filter_status;
+mod cache_filter_status;
+mod compression_filter_status;
+mod cors_filter_status;
+mod external_auth_filter_status;
 mod gateway_class_status;
 mod gateway_status;
 mod http_route_status;
+mod jwt_auth_filter_status;
+mod rate_limit_filter_status;
+mod request_limits_filter_status;
 mod static_response_filter_status;
 
 pub use access_control_filter_status::sync_access_control_filter_status;
+pub use basic_auth_filter_status::sync_basic_auth_filter_status;
+pub use cache_filter_status::sync_cache_filter_status;
+pub use compression_filter_status::sync_compression_filter_status;
+pub use cors_filter_status::sync_cors_filter_status;
+pub use external_auth_filter_status::sync_external_auth_filter_status;
+pub use gateway_cache_purges::sync_gateway_cache_purges;
 pub use gateway_class_status::sync_gateway_class_status;
 pub use gateway_configmaps::{SyncGatewayConfigmapsParams, sync_gateway_configmaps};
 pub use gateway_deployments::sync_gateway_deployments;
+pub use gateway_service_accounts::{gateway_service_account_name, sync_gateway_service_accounts};
 pub use gateway_services::sync_gateway_services;
 pub use gateway_status::sync_gateway_status;
 pub use http_route_status::{RouteAttachmentState, sync_http_route_status};
+pub use jwt_auth_filter_status::sync_jwt_auth_filter_status;
+pub use rate_limit_filter_status::sync_rate_limit_filter_status;
+pub use request_limits_filter_status::sync_request_limits_filter_status;
 pub use static_response_filter_status::sync_static_response_filter_status;
@@ -0,0 +1,246 @@
+use crate::controllers::transformers::http_routes::backend_object_ref;
+use crate::kubernetes::objects::{ObjectRef, Objects};
+use gateway_api::apis::experimental::backendtlspolicies::BackendTLSPolicy;
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::ResourceExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use vg_api::constants::BACKEND_TLS_CLIENT_CERTIFICATE_OPTION;
+use vg_core::config::gateway::types::net::{BackendTls, ClientCertificate};
+use vg_core::net::EndpointAddress;
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// Key of the CA certificates in the `ConfigMap`s referenced by `BackendTLSPolicy`s
+const CA_CERTIFICATES_KEY: &str = "ca.crt";
+
+/// Collects the TLS settings of the `Service`s and `Backend`s targeted by `BackendTLSPolicy`s,
+/// with their CA certificates read from `ConfigMap`s. The oldest policy wins when several
+/// target the same backend
+pub fn collect_backend_tls(
+    task_builder: &TaskBuilder,
+    backend_tls_policies_rx: &Receiver<Objects<BackendTLSPolicy>>,
+    config_maps_rx: &Receiver<Objects<ConfigMap>>,
+) -> Receiver<HashMap<ObjectRef, BackendTls>> {
+    let (tx, rx) = signal("collected_backend_tls");
+    let backend_tls_policies_rx = backend_tls_policies_rx.clone();
+    let config_maps_rx = config_maps_rx.clone();
+
+    task_builder
+        .new_task(stringify!(collect_backend_tls))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((policies, config_maps)) =
+                    await_ready!(backend_tls_policies_rx, config_maps_rx)
+                {
+                    let mut policies: Vec<_> = policies.iter().map(|(_, _, policy)| policy).collect();
+                    policies.sort_by_key(|policy| {
+                        (
+                            policy.metadata.creation_timestamp.clone(),
+                            policy.namespace(),
+                            policy.name_any(),
+                        )
+                    });
+
+                    let mut backend_tls = HashMap::new();
+                    for policy in policies {
+                        info!(
+                            "Collecting BackendTLSPolicy: object.ref={}/{}",
+                            policy.namespace().unwrap_or_default(),
+                            policy.name_any()
+                        );
+                        let Some(tls) = extract_backend_tls(&policy, &config_maps) else {
+                            continue;
+                        };
+
+                        for target_ref in &policy.spec.target_refs {
+                            let Some(object_ref) = backend_object_ref(
+                                &target_ref.group,
+                                &target_ref.kind,
+                                policy.namespace(),
+                                &target_ref.name,
+                            ) else {
+                                warn!(
+                                    "Skipping target {} of unsupported kind {} of BackendTLSPolicy {}",
+                                    target_ref.name,
+                                    target_ref.kind,
+                                    policy.name_any()
+                                );
+                                continue;
+                            };
+                            backend_tls.entry(object_ref).or_insert_with(|| tls.clone());
+                        }
+                    }
+
+                    tx.set(backend_tls).await;
+                }
+
+                continue_on!(backend_tls_policies_rx.changed(), config_maps_rx.changed());
+            }
+        });
+
+    rx
+}
+
+fn extract_backend_tls(
+    policy: &Arc<BackendTLSPolicy>,
+    config_maps: &Objects<ConfigMap>,
+) -> Option<BackendTls> {
+    let validation = &policy.spec.validation;
+    let sni = match validation.hostname.parse::<EndpointAddress>() {
+        Ok(EndpointAddress::Hostname(hostname)) => hostname,
+        _ => {
+            warn!(
+                "Skipping BackendTLSPolicy {}: invalid hostname {:?}",
+                policy.name_any(),
+                validation.hostname
+            );
+            return None;
+        }
+    };
+
+    // Certificates of ConfigMaps that cannot be read are left out rather than falling back to
+    // the CAs of the system, so connections fail instead of trusting more than intended
+    let ca_certificates = validation
+        .ca_certificate_refs
+        .as_ref()
+        .filter(|ca_certificate_refs| !ca_certificate_refs.is_empty())
+        .map(|ca_certificate_refs| {
+            ca_certificate_refs
+                .iter()
+                .filter_map(|ca_certificate_ref| {
+                    if !ca_certificate_ref.group.is_empty()
+                        || ca_certificate_ref.kind != "ConfigMap"
+                    {
+                        warn!(
+                            "Unsupported CA certificates {} of kind {} in BackendTLSPolicy {}",
+                            ca_certificate_ref.name,
+                            ca_certificate_ref.kind,
+                            policy.name_any()
+                        );
+                        return None;
+                    }
+
+                    let config_map_ref = ObjectRef::of_kind::<ConfigMap>()
+                        .namespace(policy.namespace())
+                        .name(&ca_certificate_ref.name)
+                        .build();
+                    let ca_certificates = config_maps
+                        .get_by_ref(&config_map_ref)
+                        .and_then(|config_map| config_map.data.clone())
+                        .and_then(|mut data| data.remove(CA_CERTIFICATES_KEY));
+                    if ca_certificates.is_none() {
+                        warn!(
+                            "CA certificates {} of BackendTLSPolicy {} not found",
+                            config_map_ref,
+                            policy.name_any()
+                        );
+                    }
+                    ca_certificates
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+
+    // The Secret itself is read when a gateway fetches the certificate
+    let client_certificate = policy
+        .spec
+        .options
+        .as_ref()
+        .filter(|options| options.contains_key(BACKEND_TLS_CLIENT_CERTIFICATE_OPTION))
+        .and_then(|_| {
+            Some(
+                ClientCertificate::builder()
+                    .version_key(policy.metadata.resource_version.as_ref()?)
+                    .identifier(policy.uid()?)
+                    .build(),
+            )
+        });
+
+    Some(
+        BackendTls::builder()
+            .sni(sni)
+            .ca_certificates(ca_certificates)
+            .client_certificate(client_certificate)
+            .build(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vg_core::net::Hostname;
+
+    fn policy(ca_certificate_refs: serde_json::Value) -> Arc<BackendTLSPolicy> {
+        Arc::new(
+            serde_json::from_value(json!({
+                "apiVersion": "gateway.networking.k8s.io/v1alpha3",
+                "kind": "BackendTLSPolicy",
+                "metadata": {
+                    "name": "api",
+                    "namespace": "default",
+                    "uid": "policy-uid",
+                    "resourceVersion": "7",
+                },
+                "spec": {
+                    "targetRefs": [{ "group": "", "kind": "Service", "name": "api" }],
+                    "validation": {
+                        "hostname": "API.example.com",
+                        "caCertificateRefs": ca_certificate_refs,
+                    },
+                    "options": {
+                        BACKEND_TLS_CLIENT_CERTIFICATE_OPTION: "api-client",
+                    },
+                },
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn config_maps() -> Objects<ConfigMap> {
+        let mut config_maps = Objects::default();
+        let config_map = serde_json::from_value(json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "api-ca", "namespace": "default", "uid": "config-map-uid" },
+            "data": { CA_CERTIFICATES_KEY: "-----BEGIN CERTIFICATE-----" },
+        }))
+        .unwrap();
+        config_maps.insert(Arc::new(config_map)).unwrap();
+        config_maps
+    }
+
+    #[test]
+    fn test_extract_backend_tls() {
+        let policy = policy(json!([{ "group": "", "kind": "ConfigMap", "name": "api-ca" }]));
+
+        let tls = extract_backend_tls(&policy, &config_maps()).unwrap();
+
+        assert_eq!(tls.sni(), &Hostname::new("api.example.com"));
+        assert_eq!(
+            tls.ca_certificates().as_deref(),
+            Some("-----BEGIN CERTIFICATE-----")
+        );
+        assert_eq!(
+            tls.client_certificate(),
+            &Some(
+                ClientCertificate::builder()
+                    .version_key("7")
+                    .identifier("policy-uid")
+                    .build()
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_ca_certificates_trust_nothing() {
+        let policy = policy(json!([{ "group": "", "kind": "ConfigMap", "name": "missing" }]));
+
+        let tls = extract_backend_tls(&policy, &config_maps()).unwrap();
+
+        assert_eq!(tls.ca_certificates().as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::controllers::transformers::http_routes::backend_object_ref;
+use crate::kubernetes::objects::{ObjectRef, Objects};
+use getset::CopyGetters;
+use kube::ResourceExt;
+use std::collections::HashMap;
+use tracing::{info, warn};
+use typed_builder::TypedBuilder;
+use vg_api::v1alpha1::BackendTrafficPolicy;
+use vg_core::config::gateway::types::net::{CircuitBreaker, SlowStart};
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// How the gateways send traffic to a backend, from the `BackendTrafficPolicy` targeting it
+#[derive(Debug, Default, TypedBuilder, CopyGetters, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BackendTraffic {
+    #[getset(get_copy = "pub")]
+    #[builder(default)]
+    slow_start: Option<SlowStart>,
+
+    #[getset(get_copy = "pub")]
+    #[builder(default)]
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+/// Collects the traffic settings of the `Service`s and `Backend`s targeted by
+/// `BackendTrafficPolicy`s. The oldest policy wins when several target the same backend
+pub fn collect_backend_traffic(
+    task_builder: &TaskBuilder,
+    backend_traffic_policies_rx: &Receiver<Objects<BackendTrafficPolicy>>,
+) -> Receiver<HashMap<ObjectRef, BackendTraffic>> {
+    let (tx, rx) = signal("collected_backend_traffic");
+    let backend_traffic_policies_rx = backend_traffic_policies_rx.clone();
+
+    task_builder
+        .new_task(stringify!(collect_backend_traffic))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(policies) = await_ready!(backend_traffic_policies_rx) {
+                    let mut policies: Vec<_> = policies.iter().map(|(_, _, policy)| policy).collect();
+                    policies.sort_by_key(|policy| {
+                        (
+                            policy.metadata.creation_timestamp.clone(),
+                            policy.namespace(),
+                            policy.name_any(),
+                        )
+                    });
+
+                    let mut backend_traffic = HashMap::new();
+                    for policy in policies {
+                        info!(
+                            "Collecting BackendTrafficPolicy: object.ref={}/{}",
+                            policy.namespace().unwrap_or_default(),
+                            policy.name_any()
+                        );
+                        let traffic = extract_backend_traffic(&policy);
+
+                        for target_ref in &policy.spec.target_refs {
+                            let Some(object_ref) = backend_object_ref(
+                                &target_ref.group,
+                                &target_ref.kind,
+                                policy.namespace(),
+                                &target_ref.name,
+                            ) else {
+                                warn!(
+                                    "Skipping target {} of unsupported kind {} of BackendTrafficPolicy {}",
+                                    target_ref.name,
+                                    target_ref.kind,
+                                    policy.name_any()
+                                );
+                                continue;
+                            };
+                            backend_traffic.entry(object_ref).or_insert(traffic);
+                        }
+                    }
+
+                    tx.set(backend_traffic).await;
+                }
+
+                continue_on!(backend_traffic_policies_rx.changed());
+            }
+        });
+
+    rx
+}
+
+fn extract_backend_traffic(policy: &BackendTrafficPolicy) -> BackendTraffic {
+    let spec = &policy.spec;
+
+    BackendTraffic::builder()
+        .slow_start(spec.slow_start.as_ref().map(|slow_start| {
+            SlowStart::builder()
+                .window_seconds(slow_start.window_seconds)
+                .min_weight_percent(slow_start.min_weight_percent)
+                .build()
+        }))
+        .circuit_breaker(spec.circuit_breaker.as_ref().map(|circuit_breaker| {
+            CircuitBreaker::builder()
+                .max_requests(circuit_breaker.max_requests)
+                .max_pending_connections(circuit_breaker.max_pending_connections)
+                .build()
+        }))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_backend_traffic() {
+        let policy: BackendTrafficPolicy = serde_json::from_value(json!({
+            "apiVersion": "vale-gateway.whitefamily.in/v1alpha1",
+            "kind": "BackendTrafficPolicy",
+            "metadata": { "name": "api", "namespace": "default" },
+            "spec": {
+                "targetRefs": [{ "group": "", "kind": "Service", "name": "api" }],
+                "slowStart": { "windowSeconds": 30 },
+                "circuitBreaker": { "maxRequests": 100 },
+            },
+        }))
+        .unwrap();
+
+        let traffic = extract_backend_traffic(&policy);
+
+        assert_eq!(
+            traffic.slow_start(),
+            Some(
+                SlowStart::builder()
+                    .window_seconds(30)
+                    .min_weight_percent(10)
+                    .build()
+            )
+        );
+        assert_eq!(
+            traffic.circuit_breaker(),
+            Some(CircuitBreaker::builder().max_requests(Some(100)).build())
+        );
+    }
+}
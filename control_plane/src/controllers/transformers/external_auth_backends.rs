@@ -0,0 +1,60 @@
+use crate::controllers::transformers::http_routes::HttpRouteBackend;
+use crate::kubernetes::objects::{ObjectRef, Objects};
+use k8s_openapi::api::core::v1::Service;
+use std::collections::HashMap;
+use tracing::info;
+use vg_api::v1alpha1::ExternalAuthFilter;
+use vg_core::net::Port;
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// Adds the authorization `Service`s of `ExternalAuthFilter`s to the backends referenced
+/// by `HTTPRoute`s, so their endpoints are resolved from `EndpointSlice`s the same way
+pub fn collect_external_auth_backends(
+    task_builder: &TaskBuilder,
+    http_route_backends_rx: &Receiver<HashMap<ObjectRef, HttpRouteBackend>>,
+    external_auth_filters_rx: &Receiver<Objects<ExternalAuthFilter>>,
+) -> Receiver<HashMap<ObjectRef, HttpRouteBackend>> {
+    let (tx, rx) = signal("collected_external_auth_backends");
+    let http_route_backends_rx = http_route_backends_rx.clone();
+    let external_auth_filters_rx = external_auth_filters_rx.clone();
+
+    task_builder
+        .new_task(stringify!(collect_external_auth_backends))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((http_route_backends, external_auth_filters)) =
+                    await_ready!(http_route_backends_rx, external_auth_filters_rx)
+                {
+                    let mut backends = http_route_backends.clone();
+
+                    for (filter_ref, _, filter) in external_auth_filters.iter() {
+                        info!(
+                            "Collecting backend for ExternalAuthFilter: object.ref={}",
+                            filter_ref
+                        );
+                        let service_ref = ObjectRef::of_kind::<Service>()
+                            .namespace(filter.metadata.namespace.clone())
+                            .name(&filter.spec.backend_ref.name)
+                            .build();
+                        let backend = HttpRouteBackend::builder()
+                            .object_ref(service_ref.clone())
+                            .port(Some(Port::new(filter.spec.backend_ref.port)))
+                            .weight(None)
+                            .build();
+                        backends.entry(service_ref).or_insert(backend);
+                    }
+
+                    tx.set(backends).await;
+                }
+
+                continue_on!(
+                    http_route_backends_rx.changed(),
+                    external_auth_filters_rx.changed()
+                );
+            }
+        });
+
+    rx
+}
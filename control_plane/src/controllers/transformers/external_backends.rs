@@ -0,0 +1,119 @@
+use crate::controllers::transformers::backend_traffic_policies::BackendTraffic;
+use crate::controllers::transformers::http_routes::HttpRouteBackend;
+use crate::controllers::transformers::services::{Backend, Endpoints};
+use crate::kubernetes::objects::{ObjectRef, Objects};
+use std::collections::HashMap;
+use tracing::{info, warn};
+use vg_api::v1alpha1::{self, BackendHostHeader};
+use vg_core::config::gateway::types::net::BackendTls;
+use vg_core::net::{EndpointAddress, Port};
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// Adds the hosts of Vale `Backend`s referenced by `HTTPRoute`s to the backends of `Service`s.
+/// Their single endpoint is resolved by the gateways when it is a hostname
+pub fn collect_external_backends(
+    task_builder: &TaskBuilder,
+    http_route_backends_rx: &Receiver<HashMap<ObjectRef, HttpRouteBackend>>,
+    external_backends_rx: &Receiver<Objects<v1alpha1::Backend>>,
+    backend_tls_rx: &Receiver<HashMap<ObjectRef, BackendTls>>,
+    backend_traffic_rx: &Receiver<HashMap<ObjectRef, BackendTraffic>>,
+    backends_rx: &Receiver<HashMap<ObjectRef, Backend>>,
+) -> Receiver<HashMap<ObjectRef, Backend>> {
+    let (tx, rx) = signal("collected_external_backends");
+    let http_route_backends_rx = http_route_backends_rx.clone();
+    let external_backends_rx = external_backends_rx.clone();
+    let backend_tls_rx = backend_tls_rx.clone();
+    let backend_traffic_rx = backend_traffic_rx.clone();
+    let backends_rx = backends_rx.clone();
+
+    task_builder
+        .new_task(stringify!(collect_external_backends))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready((
+                    http_route_backends,
+                    external_backends,
+                    backend_tls,
+                    backend_traffic,
+                    backends,
+                )) = await_ready!(
+                    http_route_backends_rx,
+                    external_backends_rx,
+                    backend_tls_rx,
+                    backend_traffic_rx,
+                    backends_rx
+                ) {
+                    let mut backends = backends.clone();
+
+                    for (object_ref, http_route_backend) in &http_route_backends {
+                        let Some(external_backend) = external_backends.get_by_ref(object_ref)
+                        else {
+                            continue;
+                        };
+                        info!("Collecting external backend: object.ref={}", object_ref);
+                        if let Some(backend) = extract_backend(
+                            object_ref,
+                            http_route_backend,
+                            &external_backend,
+                            backend_tls.get(object_ref).cloned(),
+                            backend_traffic.get(object_ref).copied().unwrap_or_default(),
+                        ) {
+                            backends.insert(object_ref.clone(), backend);
+                        }
+                    }
+
+                    tx.set(backends).await;
+                }
+
+                continue_on!(
+                    http_route_backends_rx.changed(),
+                    external_backends_rx.changed(),
+                    backend_tls_rx.changed(),
+                    backend_traffic_rx.changed(),
+                    backends_rx.changed()
+                );
+            }
+        });
+
+    rx
+}
+
+fn extract_backend(
+    object_ref: &ObjectRef,
+    http_route_backend: &HttpRouteBackend,
+    external_backend: &v1alpha1::Backend,
+    tls: Option<BackendTls>,
+    traffic: BackendTraffic,
+) -> Option<Backend> {
+    let spec = &external_backend.spec;
+    let address = match spec.host.parse::<EndpointAddress>() {
+        Ok(address) => address,
+        Err(err) => {
+            warn!("Skipping host of Backend {}: {}", object_ref, err);
+            return None;
+        }
+    };
+
+    let host_header = match spec.host_header {
+        BackendHostHeader::Rewrite => address.hostname().cloned(),
+        BackendHostHeader::Preserve => None,
+    };
+
+    Some(
+        Backend::builder()
+            .object_ref(object_ref.clone())
+            .host_header(host_header)
+            .endpoints(vec![Endpoints::external(address)])
+            .port(
+                http_route_backend
+                    .port()
+                    .unwrap_or_else(|| Port::new(spec.port)),
+            )
+            .weight(http_route_backend.weight())
+            .tls(tls)
+            .traffic(traffic)
+            .build(),
+    )
+}
@@ -5,7 +5,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use strum::{EnumString, IntoStaticStr};
 use tracing::{debug, info};
-use vg_api::v1alpha1::{AccessControlFilter, StaticResponseFilter};
+use vg_api::v1alpha1::{
+    AccessControlFilter, BasicAuthFilter, CacheFilter, CompressionFilter, CorsFilter,
+    ExternalAuthFilter, JwtAuthFilter, RateLimitFilter, RequestLimitsFilter, StaticResponseFilter,
+};
 use vg_core::continue_on;
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
@@ -15,6 +18,14 @@ use vg_core::{await_ready, ReadyState};
 pub enum ExtensionFilterKind {
     StaticResponseFilter,
     AccessControlFilter,
+    RateLimitFilter,
+    JwtAuthFilter,
+    ExternalAuthFilter,
+    BasicAuthFilter,
+    CorsFilter,
+    CompressionFilter,
+    CacheFilter,
+    RequestLimitsFilter,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Getters)]
@@ -23,6 +34,22 @@ pub struct ExtensionFilters {
     static_responses: Objects<StaticResponseFilter>,
     #[getset(get = "pub")]
     access_controls: Objects<AccessControlFilter>,
+    #[getset(get = "pub")]
+    rate_limits: Objects<RateLimitFilter>,
+    #[getset(get = "pub")]
+    jwt_auths: Objects<JwtAuthFilter>,
+    #[getset(get = "pub")]
+    external_auths: Objects<ExternalAuthFilter>,
+    #[getset(get = "pub")]
+    basic_auths: Objects<BasicAuthFilter>,
+    #[getset(get = "pub")]
+    cors: Objects<CorsFilter>,
+    #[getset(get = "pub")]
+    compressions: Objects<CompressionFilter>,
+    #[getset(get = "pub")]
+    caches: Objects<CacheFilter>,
+    #[getset(get = "pub")]
+    request_limits: Objects<RequestLimitsFilter>,
 }
 
 pub fn collect_extension_filters_by_gateway(
@@ -30,12 +57,28 @@ pub fn collect_extension_filters_by_gateway(
     http_routes_by_gateway_rx: &Receiver<HashMap<ObjectRef, Vec<Arc<HTTPRoute>>>>,
     static_response_filters_rx: &Receiver<Objects<StaticResponseFilter>>,
     access_control_filters_rx: &Receiver<Objects<AccessControlFilter>>,
+    rate_limit_filters_rx: &Receiver<Objects<RateLimitFilter>>,
+    jwt_auth_filters_rx: &Receiver<Objects<JwtAuthFilter>>,
+    external_auth_filters_rx: &Receiver<Objects<ExternalAuthFilter>>,
+    basic_auth_filters_rx: &Receiver<Objects<BasicAuthFilter>>,
+    cors_filters_rx: &Receiver<Objects<CorsFilter>>,
+    compression_filters_rx: &Receiver<Objects<CompressionFilter>>,
+    cache_filters_rx: &Receiver<Objects<CacheFilter>>,
+    request_limits_filters_rx: &Receiver<Objects<RequestLimitsFilter>>,
 ) -> Receiver<HashMap<ObjectRef, ExtensionFilters>> {
     let (tx, rx) = signal("collected_extension_filters_by_gateway");
 
     let http_routes_by_gateway_rx = http_routes_by_gateway_rx.clone();
     let static_response_filters_rx = static_response_filters_rx.clone();
     let access_control_filters_rx = access_control_filters_rx.clone();
+    let rate_limit_filters_rx = rate_limit_filters_rx.clone();
+    let jwt_auth_filters_rx = jwt_auth_filters_rx.clone();
+    let external_auth_filters_rx = external_auth_filters_rx.clone();
+    let basic_auth_filters_rx = basic_auth_filters_rx.clone();
+    let cors_filters_rx = cors_filters_rx.clone();
+    let compression_filters_rx = compression_filters_rx.clone();
+    let cache_filters_rx = cache_filters_rx.clone();
+    let request_limits_filters_rx = request_limits_filters_rx.clone();
 
     task_builder
         .new_task(stringify!(pub fn collect_extension_filters_by_gateway))
@@ -45,10 +88,26 @@ pub fn collect_extension_filters_by_gateway(
                     http_routes_by_gateway,
                     static_response_filters,
                     access_control_filters,
+                    rate_limit_filters,
+                    jwt_auth_filters,
+                    external_auth_filters,
+                    basic_auth_filters,
+                    cors_filters,
+                    compression_filters,
+                    cache_filters,
+                    request_limits_filters,
                 )) = await_ready!(
                     http_routes_by_gateway_rx,
                     static_response_filters_rx,
-                    access_control_filters_rx
+                    access_control_filters_rx,
+                    rate_limit_filters_rx,
+                    jwt_auth_filters_rx,
+                    external_auth_filters_rx,
+                    basic_auth_filters_rx,
+                    cors_filters_rx,
+                    compression_filters_rx,
+                    cache_filters_rx,
+                    request_limits_filters_rx
                 ) {
                     let mut filters: HashMap<ObjectRef, ExtensionFilters> = HashMap::new();
 
@@ -100,6 +159,95 @@ pub fn collect_extension_filters_by_gateway(
                                         .access_controls
                                         .insert(access_control_filter);
                                 }
+                            } else if Ok(ExtensionFilterKind::RateLimitFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<RateLimitFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(rate_limit_filter) =
+                                    rate_limit_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ = extension_filters.rate_limits.insert(rate_limit_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::JwtAuthFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<JwtAuthFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(jwt_auth_filter) =
+                                    jwt_auth_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ = extension_filters.jwt_auths.insert(jwt_auth_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::ExternalAuthFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<ExternalAuthFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(external_auth_filter) =
+                                    external_auth_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ = extension_filters
+                                        .external_auths
+                                        .insert(external_auth_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::BasicAuthFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<BasicAuthFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(basic_auth_filter) =
+                                    basic_auth_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ = extension_filters.basic_auths.insert(basic_auth_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::CorsFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<CorsFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(cors_filter) = cors_filters.get_by_ref(&filter_ref) {
+                                    let _ = extension_filters.cors.insert(cors_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::CompressionFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<CompressionFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(compression_filter) =
+                                    compression_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ =
+                                        extension_filters.compressions.insert(compression_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::CacheFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<CacheFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(cache_filter) = cache_filters.get_by_ref(&filter_ref) {
+                                    let _ = extension_filters.caches.insert(cache_filter);
+                                }
+                            } else if Ok(ExtensionFilterKind::RequestLimitsFilter) == kind {
+                                let filter_ref = ObjectRef::of_kind::<RequestLimitsFilter>()
+                                    .namespace(gateway_ref.namespace().clone())
+                                    .name(&filter.name)
+                                    .build();
+
+                                if let Some(request_limits_filter) =
+                                    request_limits_filters.get_by_ref(&filter_ref)
+                                {
+                                    let _ = extension_filters
+                                        .request_limits
+                                        .insert(request_limits_filter);
+                                }
                             }
                         }
                     }
@@ -110,7 +258,15 @@ pub fn collect_extension_filters_by_gateway(
                 continue_on!(
                     http_routes_by_gateway_rx.changed(),
                     static_response_filters_rx.changed(),
-                    access_control_filters_rx.changed()
+                    access_control_filters_rx.changed(),
+                    rate_limit_filters_rx.changed(),
+                    jwt_auth_filters_rx.changed(),
+                    external_auth_filters_rx.changed(),
+                    basic_auth_filters_rx.changed(),
+                    cors_filters_rx.changed(),
+                    compression_filters_rx.changed(),
+                    cache_filters_rx.changed(),
+                    request_limits_filters_rx.changed()
                 );
             }
         });
@@ -13,7 +13,7 @@ use strum::IntoStaticStr;
 use tracing::{info, warn};
 use vg_api::v1alpha1::{
     GatewayClassParameters, GatewayConfiguration, GatewayInstrumentationOpenTelemetry,
-    GatewayParameters, ImagePullPolicy as ApiImagePullPolicy,
+    GatewayInstrumentationPrometheus, GatewayParameters, ImagePullPolicy as ApiImagePullPolicy,
 };
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
@@ -64,6 +64,9 @@ pub struct GatewayInstanceConfiguration {
 
     #[getset(get = "pub")]
     merged_open_telemetry: Option<GatewayInstrumentationOpenTelemetry>,
+
+    #[getset(get = "pub")]
+    merged_prometheus: Option<GatewayInstrumentationPrometheus>,
 }
 
 pub fn collect_gateway_instances(
@@ -130,6 +133,17 @@ pub fn collect_gateway_instances(
                                         .and_then(|g| g.instrumentation.as_ref())
                                         .and_then(|inst| inst.open_telemetry.clone())
                                 });
+                            let merged_prometheus = gateway_parameters
+                                .and_then(|p| p.spec.common.as_ref())
+                                .and_then(|c| c.gateway.as_ref())
+                                .and_then(|g| g.instrumentation.as_ref())
+                                .and_then(|inst| inst.prometheus.clone())
+                                .or_else(|| {
+                                    gateway_class_parameters
+                                        .and_then(|p| p.spec.common.gateway.as_ref())
+                                        .and_then(|g| g.instrumentation.as_ref())
+                                        .and_then(|inst| inst.prometheus.clone())
+                                });
                             (
                                 gateway_ref,
                                 GatewayInstanceConfiguration {
@@ -141,6 +155,7 @@ pub fn collect_gateway_instances(
                                     image_tag,
                                     configuration,
                                     merged_open_telemetry,
+                                    merged_prometheus,
                                 },
                             )
                         })
@@ -324,5 +339,12 @@ fn merge_gateway_configuration(
         .cloned()
         .or_else(|| class_instrumentation.cloned());
 
+    // Merge access logs: gateway > class > none
+    let gateway_access_logs = gateway.and_then(|g| g.access_logs.as_ref());
+    let class_access_logs = gateway_class.and_then(|g| g.access_logs.as_ref());
+    config.access_logs = gateway_access_logs
+        .cloned()
+        .or_else(|| class_access_logs.cloned());
+
     config
 }
@@ -1,7 +1,7 @@
 use crate::controllers::sync::RouteAttachmentState;
 use crate::kubernetes::objects::{ObjectRef, Objects};
 use gateway_api::apis::standard::gateways::Gateway;
-use gateway_api::apis::standard::httproutes::HTTPRoute;
+use gateway_api::apis::standard::httproutes::{HTTPRoute, HTTPRouteRulesBackendRefs};
 use getset::{CopyGetters, Getters};
 use k8s_openapi::api::core::v1::Service;
 use std::collections::hash_map::Entry;
@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info};
 use typed_builder::TypedBuilder;
+use vg_api::constants::GROUP;
+use vg_api::v1alpha1::Backend;
 use vg_core::net::Port;
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
@@ -49,23 +51,22 @@ pub fn collect_http_route_backends(
                             for (backend_idx, backend_ref) in
                                 rule.backend_refs.iter().flatten().enumerate()
                             {
-                                #[allow(clippy::single_match_else)]
-                                // We'll likely add more kinds later
-                                if let Some("Service") = backend_ref.kind.as_deref() {
-                                    let service_ref =
-                                        ObjectRef::of_kind::<Service>()
-                                            .namespace(backend_ref.namespace.clone().or_else(
-                                                || http_route.metadata.namespace.clone(),
-                                            ))
-                                            .name(&backend_ref.name)
-                                            .build();
-                                    let backend = HttpRouteBackend::builder()
-                                        .object_ref(service_ref.clone())
-                                        .port(backend_ref.port.map(|p| Port::new(p as u16)))
-                                        .weight(backend_ref.weight)
-                                        .build();
-                                    http_route_backends.insert(service_ref, backend);
-                                }
+                                let Some(object_ref) = backend_ref_object_ref(
+                                    backend_ref,
+                                    http_route.metadata.namespace.as_deref(),
+                                ) else {
+                                    debug!(
+                                        "Skipping backendRef {} of unsupported kind {:?}",
+                                        backend_ref.name, backend_ref.kind
+                                    );
+                                    continue;
+                                };
+                                let backend = HttpRouteBackend::builder()
+                                    .object_ref(object_ref.clone())
+                                    .port(backend_ref.port.map(|p| Port::new(p as u16)))
+                                    .weight(backend_ref.weight)
+                                    .build();
+                                http_route_backends.insert(object_ref, backend);
                             }
                         }
                     }
@@ -79,6 +80,49 @@ pub fn collect_http_route_backends(
     rx
 }
 
+/// The object a `backendRef` refers to, a `Service` unless it names another kind
+pub fn backend_ref_object_ref(
+    backend_ref: &HTTPRouteRulesBackendRefs,
+    route_namespace: Option<&str>,
+) -> Option<ObjectRef> {
+    let namespace = backend_ref
+        .namespace
+        .clone()
+        .or_else(|| route_namespace.map(str::to_string));
+
+    backend_object_ref(
+        backend_ref.group.as_deref().unwrap_or_default(),
+        backend_ref.kind.as_deref().unwrap_or("Service"),
+        namespace,
+        &backend_ref.name,
+    )
+}
+
+/// The object of a kind routes can send requests to, `Service`s and Vale `Backend`s for hosts
+/// outside of the cluster
+pub fn backend_object_ref(
+    group: &str,
+    kind: &str,
+    namespace: Option<String>,
+    name: &str,
+) -> Option<ObjectRef> {
+    match (group, kind) {
+        ("", "Service") => Some(
+            ObjectRef::of_kind::<Service>()
+                .namespace(namespace)
+                .name(name)
+                .build(),
+        ),
+        (GROUP, "Backend") => Some(
+            ObjectRef::of_kind::<Backend>()
+                .namespace(namespace)
+                .name(name)
+                .build(),
+        ),
+        _ => None,
+    }
+}
+
 pub fn collect_http_routes_by_gateway(
     task_builder: &TaskBuilder,
     http_routes_rx: &Receiver<Objects<HTTPRoute>>,
@@ -1,11 +1,21 @@
+mod backend_tls_policies;
+mod backend_traffic_policies;
+mod external_auth_backends;
+mod external_backends;
 mod gateway_extension_filters;
 mod gateway_instances;
 mod http_routes;
+mod secrets_cache;
 mod services;
 mod static_responses_cache;
 
+pub use backend_tls_policies::*;
+pub use backend_traffic_policies::*;
+pub use external_auth_backends::*;
+pub use external_backends::*;
 pub use gateway_extension_filters::*;
 pub use gateway_instances::*;
 pub use http_routes::*;
+pub use secrets_cache::*;
 pub use services::*;
 pub use static_responses_cache::*;
@@ -0,0 +1,276 @@
+use crate::kubernetes::KubeClientCell;
+use crate::kubernetes::objects::{ObjectUniqueId, Objects};
+use async_trait::async_trait;
+use bytes::Bytes;
+use dashmap::DashMap;
+use gateway_api::apis::experimental::backendtlspolicies::BackendTLSPolicy;
+use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+use kube::{Api, Client, Resource, ResourceExt};
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use vg_api::constants::BACKEND_TLS_CLIENT_CERTIFICATE_OPTION;
+use vg_api::v1alpha1::{BasicAuthFilter, JwtAuthFilter, JwtAuthFilterJwksSource};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{ReadyState, await_ready, continue_on};
+
+/// How long secret material read from the Kubernetes API is served before it is read
+/// again, so rotations are picked up without every gateway pod hitting the API
+const SECRET_TTL: Duration = Duration::from_secs(30);
+
+/// An object referencing secret material gateways fetch over IPC, such as the JWKS of a
+/// `JwtAuthFilter`
+#[async_trait]
+pub trait SecretSource:
+    Resource<DynamicType = ()> + ResourceExt + Clone + PartialEq + Debug + Send + Sync + 'static
+{
+    /// Reads the secret material from the object's namespace
+    async fn read(&self, client: Client, namespace: &str) -> Option<Bytes>;
+}
+
+#[async_trait]
+impl SecretSource for JwtAuthFilter {
+    async fn read(&self, client: Client, namespace: &str) -> Option<Bytes> {
+        let jwks = &self.spec.jwks;
+        debug!(
+            "Reading JWKS from {:?} {}/{} key {}",
+            jwks.source, namespace, jwks.name, jwks.key
+        );
+
+        let bytes = match jwks.source {
+            JwtAuthFilterJwksSource::Secret => {
+                let api: Api<Secret> = Api::namespaced(client, namespace);
+                match api.get(&jwks.name).await {
+                    Ok(secret) => secret
+                        .data
+                        .and_then(|mut data| data.remove(&jwks.key))
+                        .map(|value| Bytes::from(value.0)),
+                    Err(err) => {
+                        warn!(
+                            "Failed to read JWKS Secret {}/{}: {}",
+                            namespace, jwks.name, err
+                        );
+                        return None;
+                    }
+                }
+            }
+            JwtAuthFilterJwksSource::ConfigMap => {
+                let api: Api<ConfigMap> = Api::namespaced(client, namespace);
+                match api.get(&jwks.name).await {
+                    Ok(config_map) => config_map
+                        .data
+                        .and_then(|mut data| data.remove(&jwks.key))
+                        .map(|value| Bytes::from(value.into_bytes()))
+                        .or_else(|| {
+                            config_map
+                                .binary_data
+                                .and_then(|mut data| data.remove(&jwks.key))
+                                .map(|value| Bytes::from(value.0))
+                        }),
+                    Err(err) => {
+                        warn!(
+                            "Failed to read JWKS ConfigMap {}/{}: {}",
+                            namespace, jwks.name, err
+                        );
+                        return None;
+                    }
+                }
+            }
+        };
+
+        if bytes.is_none() {
+            warn!(
+                "JWKS key {} not found in {:?} {}/{}",
+                jwks.key, jwks.source, namespace, jwks.name
+            );
+        }
+        bytes
+    }
+}
+
+#[async_trait]
+impl SecretSource for BasicAuthFilter {
+    async fn read(&self, client: Client, namespace: &str) -> Option<Bytes> {
+        let secret_ref = &self.spec.secret_ref;
+        debug!(
+            "Reading htpasswd from Secret {}/{} key {}",
+            namespace, secret_ref.name, secret_ref.key
+        );
+
+        let api: Api<Secret> = Api::namespaced(client, namespace);
+        let bytes = match api.get(&secret_ref.name).await {
+            Ok(secret) => secret
+                .data
+                .and_then(|mut data| data.remove(&secret_ref.key))
+                .map(|value| Bytes::from(value.0)),
+            Err(err) => {
+                warn!(
+                    "Failed to read htpasswd Secret {}/{}: {}",
+                    namespace, secret_ref.name, err
+                );
+                return None;
+            }
+        };
+
+        if bytes.is_none() {
+            warn!(
+                "htpasswd key {} not found in Secret {}/{}",
+                secret_ref.key, namespace, secret_ref.name
+            );
+        }
+        bytes
+    }
+}
+
+/// Client certificates are served as one PEM document, the certificate chain then the key,
+/// read from the `kubernetes.io/tls` Secret named by the policy
+#[async_trait]
+impl SecretSource for BackendTLSPolicy {
+    async fn read(&self, client: Client, namespace: &str) -> Option<Bytes> {
+        let secret_name = self
+            .spec
+            .options
+            .as_ref()?
+            .get(BACKEND_TLS_CLIENT_CERTIFICATE_OPTION)?;
+        debug!(
+            "Reading client certificate from Secret {}/{}",
+            namespace, secret_name
+        );
+
+        let api: Api<Secret> = Api::namespaced(client, namespace);
+        let bytes = match api.get(secret_name).await {
+            Ok(secret) => secret.data.and_then(|mut data| {
+                let certificate = data.remove("tls.crt")?;
+                let key = data.remove("tls.key")?;
+                Some(Bytes::from([certificate.0, b"\n".to_vec(), key.0].concat()))
+            }),
+            Err(err) => {
+                warn!(
+                    "Failed to read client certificate Secret {}/{}: {}",
+                    namespace, secret_name, err
+                );
+                return None;
+            }
+        };
+
+        if bytes.is_none() {
+            warn!(
+                "tls.crt or tls.key not found in Secret {}/{}",
+                namespace, secret_name
+            );
+        }
+        bytes
+    }
+}
+
+/// The JWKS documents referenced by `JwtAuthFilter`s
+pub type JwksCache = SecretsCache<JwtAuthFilter>;
+
+/// The htpasswd files referenced by `BasicAuthFilter`s
+pub type HtpasswdCache = SecretsCache<BasicAuthFilter>;
+
+/// The client certificates referenced by `BackendTLSPolicy`s
+pub type ClientCertificatesCache = SecretsCache<BackendTLSPolicy>;
+
+struct SecretsCacheState<K: SecretSource> {
+    cache: DashMap<ObjectUniqueId, (Instant, Bytes)>,
+    objects_rx: Option<Receiver<Objects<K>>>,
+    kube_client_rx: Option<Receiver<KubeClientCell>>,
+}
+
+/// Serves the secret material referenced by objects of a kind, by the unique ID of the
+/// referencing object.
+pub struct SecretsCache<K: SecretSource> {
+    data: Arc<RwLock<SecretsCacheState<K>>>,
+}
+
+impl<K: SecretSource> Default for SecretsCache<K> {
+    fn default() -> Self {
+        Self {
+            data: Arc::new(RwLock::new(SecretsCacheState {
+                cache: DashMap::new(),
+                objects_rx: None,
+                kube_client_rx: None,
+            })),
+        }
+    }
+}
+
+impl<K: SecretSource> Clone for SecretsCache<K> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<K: SecretSource> SecretsCache<K> {
+    pub async fn get(&self, key: ObjectUniqueId) -> Option<Bytes> {
+        let data = self.data.read().await;
+        if let Some(entry) = data.cache.get(&key)
+            && entry.0.elapsed() < SECRET_TTL
+        {
+            return Some(entry.1.clone());
+        }
+
+        let object = data
+            .objects_rx
+            .as_ref()?
+            .get()
+            .await
+            .as_ref()?
+            .get_by_unique_id(&key)?;
+        let client: Client = data
+            .kube_client_rx
+            .as_ref()?
+            .get()
+            .await
+            .as_ref()?
+            .deref()
+            .clone();
+
+        let namespace = object.namespace()?;
+        let bytes = object.read(client, &namespace).await?;
+
+        data.cache.insert(key, (Instant::now(), bytes.clone()));
+        Some(bytes)
+    }
+
+    pub async fn reset(
+        &mut self,
+        objects_rx: Receiver<Objects<K>>,
+        kube_client_rx: Receiver<KubeClientCell>,
+    ) {
+        let mut data = self.data.write().await;
+        data.cache.clear();
+        data.objects_rx = Some(objects_rx);
+        data.kube_client_rx = Some(kube_client_rx);
+    }
+}
+
+pub fn bind_secrets_cache<K: SecretSource>(
+    task_builder: &TaskBuilder,
+    objects_rx: &Receiver<Objects<K>>,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    secrets_cache: SecretsCache<K>,
+) {
+    let objects_rx = objects_rx.clone();
+    let kube_client_rx = kube_client_rx.clone();
+    let mut secrets_cache = secrets_cache.clone();
+
+    task_builder
+        .new_task(stringify!(secrets_cache))
+        .spawn(async move {
+            loop {
+                let rx = objects_rx.clone();
+                if let ReadyState::Ready(_) = await_ready!(objects_rx) {
+                    secrets_cache.reset(rx, kube_client_rx.clone()).await;
+                }
+                continue_on!(objects_rx.changed())
+            }
+        });
+}
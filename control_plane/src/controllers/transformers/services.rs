@@ -1,13 +1,16 @@
+use crate::controllers::transformers::backend_traffic_policies::BackendTraffic;
 use crate::controllers::transformers::http_routes::HttpRouteBackend;
 use crate::kubernetes::objects::{ObjectRef, Objects, TopologyLocation};
 use getset::{CopyGetters, Getters};
-use k8s_openapi::api::core::v1::Service;
+use k8s_openapi::api::core::v1::{Service, ServiceSpec};
 use k8s_openapi::api::discovery::v1::EndpointSlice;
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-use tracing::debug;
+use std::sync::Arc;
+use tracing::{debug, warn};
 use typed_builder::TypedBuilder;
-use vg_core::net::Port;
+use vg_core::config::gateway::types::net::BackendTls;
+use vg_core::net::{EndpointAddress, Hostname, Port};
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
 use vg_core::ReadyState;
@@ -20,7 +23,11 @@ pub struct Endpoints {
 
     #[getset(get = "pub")]
     #[builder(setter(into))]
-    addresses: Vec<IpAddr>,
+    addresses: Vec<EndpointAddress>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    zone_hints: Vec<String>,
 }
 
 #[derive(Debug, TypedBuilder, Getters, CopyGetters, Clone, Hash, PartialEq, Eq)]
@@ -38,54 +45,120 @@ pub struct Backend {
 
     #[getset(get = "pub")]
     endpoints: Vec<Endpoints>,
+
+    /// Host header of requests to the backend, instead of the one of the client
+    #[getset(get = "pub")]
+    #[builder(default)]
+    host_header: Option<Hostname>,
+
+    /// TLS to the endpoints, from the `BackendTLSPolicy` targeting the backend
+    #[getset(get = "pub")]
+    #[builder(default)]
+    tls: Option<BackendTls>,
+
+    #[getset(get_copy = "pub")]
+    #[builder(default)]
+    traffic: BackendTraffic,
+}
+
+impl Endpoints {
+    /// An endpoint outside of the cluster, which has no place in its topology
+    pub fn external(address: EndpointAddress) -> Self {
+        Endpoints::builder()
+            .location(TopologyLocation::builder().build())
+            .addresses(vec![address])
+            .build()
+    }
 }
 
 pub fn collect_service_backends(
     task_builder: &TaskBuilder,
     http_route_backends_rx: &Receiver<HashMap<ObjectRef, HttpRouteBackend>>,
+    services_rx: &Receiver<Objects<Service>>,
     endpoint_slices_rx: &Receiver<Objects<EndpointSlice>>,
+    backend_tls_rx: &Receiver<HashMap<ObjectRef, BackendTls>>,
+    backend_traffic_rx: &Receiver<HashMap<ObjectRef, BackendTraffic>>,
 ) -> Receiver<HashMap<ObjectRef, Backend>> {
     let (tx, rx) = signal("collected_service_backends");
     let http_route_backends_rx = http_route_backends_rx.clone();
+    let services_rx = services_rx.clone();
     let endpoint_slices_rx = endpoint_slices_rx.clone();
+    let backend_tls_rx = backend_tls_rx.clone();
+    let backend_traffic_rx = backend_traffic_rx.clone();
 
     task_builder
         .new_task(stringify!(collect_service_backends))
         .spawn(async move {
             loop {
-                if let ReadyState::Ready((http_route_backends, endpoint_slices)) =
-                    await_ready!(http_route_backends_rx, endpoint_slices_rx)
-                {
-                    let endpoint_slices_by_service: HashMap<_, _> = endpoint_slices
+                if let ReadyState::Ready((
+                    http_route_backends,
+                    services,
+                    endpoint_slices,
+                    backend_tls,
+                    backend_traffic,
+                )) = await_ready!(
+                    http_route_backends_rx,
+                    services_rx,
+                    endpoint_slices_rx,
+                    backend_tls_rx,
+                    backend_traffic_rx
+                ) {
+                    // A Service may have several EndpointSlices, such as one per address family
+                    let mut endpoint_slices_by_service: HashMap<_, Vec<_>> = HashMap::new();
+                    for (_, _, endpoint_slice) in endpoint_slices.iter() {
+                        let Some(service_name) = endpoint_slice
+                            .metadata
+                            .labels
+                            .as_ref()
+                            .and_then(|labels| labels.get("kubernetes.io/service-name"))
+                        else {
+                            continue;
+                        };
+                        let service_ref = ObjectRef::of_kind::<Service>()
+                            .namespace(endpoint_slice.metadata.namespace.clone())
+                            .name(service_name)
+                            .build();
+                        endpoint_slices_by_service
+                            .entry(service_ref)
+                            .or_default()
+                            .push(endpoint_slice);
+                    }
+
+                    let backends = http_route_backends
                         .iter()
-                        .filter_map(|(_, _, endpoint_slice)| {
-                            let metadata = &endpoint_slice.metadata;
-                            let labels = metadata.labels.as_ref()?;
-                            labels
-                                .get("kubernetes.io/service-name")
-                                .map(|service_name| {
-                                    ObjectRef::of_kind::<Service>()
-                                        .namespace(endpoint_slice.metadata.namespace.clone())
-                                        .name(service_name)
-                                        .build()
-                                })
-                                .map(|service_ref| (service_ref, endpoint_slice))
-                        })
-                        .filter_map(|(service_ref, endpoint_slice)| {
-                            http_route_backends.get(&service_ref).map(|h| {
-                                (
-                                    service_ref.clone(),
-                                    extract_backend(&service_ref, h, endpoint_slice.as_ref()),
-                                )
-                            })
+                        .filter(|(object_ref, _)| object_ref.kind() == "Service")
+                        .filter_map(|(service_ref, http_route_backend)| {
+                            let service = services.get_by_ref(service_ref);
+                            let endpoint_slices = endpoint_slices_by_service
+                                .get(service_ref)
+                                .map(Vec::as_slice);
+                            if service.is_none() && endpoint_slices.is_none() {
+                                return None;
+                            }
+
+                            let backend = extract_backend(
+                                service_ref,
+                                http_route_backend,
+                                service.as_deref(),
+                                endpoint_slices.unwrap_or_default(),
+                                backend_tls.get(service_ref).cloned(),
+                                backend_traffic
+                                    .get(service_ref)
+                                    .copied()
+                                    .unwrap_or_default(),
+                            );
+                            Some((service_ref.clone(), backend))
                         })
                         .collect();
-                    tx.set(endpoint_slices_by_service).await;
+                    tx.set(backends).await;
                 }
 
                 continue_on!(
                     http_route_backends_rx.changed(),
-                    endpoint_slices_rx.changed()
+                    services_rx.changed(),
+                    endpoint_slices_rx.changed(),
+                    backend_tls_rx.changed(),
+                    backend_traffic_rx.changed()
                 );
             }
         });
@@ -96,9 +169,97 @@ pub fn collect_service_backends(
 fn extract_backend(
     object_ref: &ObjectRef,
     http_route_backend: &HttpRouteBackend,
-    endpoint_slice: &EndpointSlice,
+    service: Option<&Service>,
+    endpoint_slices: &[Arc<EndpointSlice>],
+    tls: Option<BackendTls>,
+    traffic: BackendTraffic,
+) -> Backend {
+    let spec = service.and_then(|service| service.spec.as_ref());
+    if let Some(spec) = spec
+        && spec.type_.as_deref() == Some("ExternalName")
+    {
+        return extract_external_name_backend(object_ref, http_route_backend, spec, tls, traffic);
+    }
+
+    let endpoints = endpoint_slices
+        .iter()
+        .flat_map(|endpoint_slice| extract_endpoints(endpoint_slice))
+        .collect();
+
+    Backend::builder()
+        .object_ref(object_ref.clone())
+        .endpoints(endpoints)
+        .port(
+            target_port(http_route_backend.port(), spec, endpoint_slices)
+                .or(http_route_backend.port()),
+        )
+        .weight(http_route_backend.weight())
+        .tls(tls)
+        .traffic(traffic)
+        .build()
+}
+
+/// An `ExternalName` Service has no endpoints, requests go to its DNS name instead, with a
+/// matching Host header for virtually hosted services
+fn extract_external_name_backend(
+    object_ref: &ObjectRef,
+    http_route_backend: &HttpRouteBackend,
+    spec: &ServiceSpec,
+    tls: Option<BackendTls>,
+    traffic: BackendTraffic,
 ) -> Backend {
-    let endpoints = endpoint_slice
+    let address = spec.external_name.as_deref().and_then(|external_name| {
+        match external_name.parse::<EndpointAddress>() {
+            Ok(address) => Some(address),
+            Err(err) => {
+                warn!("Skipping externalName of Service {}: {}", object_ref, err);
+                None
+            }
+        }
+    });
+
+    Backend::builder()
+        .object_ref(object_ref.clone())
+        .host_header(
+            address
+                .as_ref()
+                .and_then(EndpointAddress::hostname)
+                .cloned(),
+        )
+        .endpoints(address.map(Endpoints::external).into_iter().collect())
+        .port(http_route_backend.port())
+        .weight(http_route_backend.weight())
+        .tls(tls)
+        .traffic(traffic)
+        .build()
+}
+
+/// The port the endpoints listen on for a port of the Service, which may map it to a different
+/// `targetPort`. Matched by name through the ports of the EndpointSlices
+fn target_port(
+    port: Option<Port>,
+    spec: Option<&ServiceSpec>,
+    endpoint_slices: &[Arc<EndpointSlice>],
+) -> Option<Port> {
+    let port = i32::from(*port?.get());
+    let service_port = spec?
+        .ports
+        .iter()
+        .flatten()
+        .find(|service_port| service_port.port == port)?;
+    let name = service_port.name.as_deref().unwrap_or_default();
+
+    endpoint_slices
+        .iter()
+        .flat_map(|endpoint_slice| endpoint_slice.ports.iter().flatten())
+        .find(|endpoint_port| endpoint_port.name.as_deref().unwrap_or_default() == name)
+        .and_then(|endpoint_port| endpoint_port.port)
+        .and_then(|port| u16::try_from(port).ok())
+        .map(Port::new)
+}
+
+fn extract_endpoints(endpoint_slice: &EndpointSlice) -> impl Iterator<Item = Endpoints> + '_ {
+    endpoint_slice
         .endpoints
         .iter()
         .filter(|endpoint| {
@@ -122,23 +283,179 @@ fn extract_backend(
                 .addresses
                 .iter()
                 .filter_map(|a| match endpoint_slice.address_type.as_str() {
-                    "IPv4" => a.parse::<Ipv4Addr>().ok().map(IpAddr::from),
-                    "IPv6" => a.parse::<Ipv6Addr>().ok().map(IpAddr::from),
+                    "IPv4" => a
+                        .parse::<Ipv4Addr>()
+                        .ok()
+                        .map(IpAddr::from)
+                        .map(EndpointAddress::from),
+                    "IPv6" => a
+                        .parse::<Ipv6Addr>()
+                        .ok()
+                        .map(IpAddr::from)
+                        .map(EndpointAddress::from),
+                    "FQDN" => a.parse::<EndpointAddress>().ok(),
                     _ => None,
                 })
                 .collect();
 
+            let zone_hints: Vec<_> = endpoint
+                .hints
+                .iter()
+                .flat_map(|hints| hints.for_zones.iter().flatten())
+                .map(|for_zone| for_zone.name.clone())
+                .collect();
+
             Endpoints::builder()
                 .location(location)
                 .addresses(addresses)
+                .zone_hints(zone_hints)
                 .build()
         })
-        .collect();
+}
 
-    Backend::builder()
-        .object_ref(object_ref.clone())
-        .endpoints(endpoints)
-        .port(http_route_backend.port())
-        .weight(http_route_backend.weight())
-        .build()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::ServicePort;
+    use k8s_openapi::api::discovery::v1::{Endpoint, EndpointConditions, EndpointPort};
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+    use vg_core::config::gateway::types::net::{CircuitBreaker, SlowStart};
+
+    fn http_route_backend(port: u16) -> HttpRouteBackend {
+        HttpRouteBackend::builder()
+            .object_ref(service_ref())
+            .port(Some(Port::new(port)))
+            .weight(None)
+            .build()
+    }
+
+    fn service_ref() -> ObjectRef {
+        ObjectRef::of_kind::<Service>()
+            .namespace(Some("default".to_string()))
+            .name("api")
+            .build()
+    }
+
+    fn service(spec: ServiceSpec) -> Service {
+        Service {
+            spec: Some(spec),
+            ..Default::default()
+        }
+    }
+
+    fn endpoint_slice(address_type: &str, addresses: &[&str]) -> Arc<EndpointSlice> {
+        Arc::new(EndpointSlice {
+            address_type: address_type.to_string(),
+            endpoints: addresses
+                .iter()
+                .map(|address| Endpoint {
+                    addresses: vec![(*address).to_string()],
+                    conditions: Some(EndpointConditions {
+                        ready: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .collect(),
+            ports: Some(vec![EndpointPort {
+                name: Some("http".to_string()),
+                port: Some(8080),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        })
+    }
+
+    fn addresses(backend: &Backend) -> Vec<EndpointAddress> {
+        backend
+            .endpoints()
+            .iter()
+            .flat_map(|endpoints| endpoints.addresses().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_headless_service_backend() {
+        let service = service(ServiceSpec {
+            cluster_ip: Some("None".to_string()),
+            ports: Some(vec![ServicePort {
+                name: Some("http".to_string()),
+                port: 80,
+                target_port: Some(IntOrString::String("http".to_string())),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        let endpoint_slices = [
+            endpoint_slice("IPv4", &["10.0.0.1", "10.0.0.2"]),
+            endpoint_slice("IPv6", &["fd00::1"]),
+        ];
+
+        let backend = extract_backend(
+            &service_ref(),
+            &http_route_backend(80),
+            Some(&service),
+            &endpoint_slices,
+            None,
+            BackendTraffic::default(),
+        );
+
+        // Every EndpointSlice of the Service contributes, on the port the endpoints listen on
+        assert_eq!(
+            addresses(&backend),
+            ["10.0.0.1", "10.0.0.2", "fd00::1"]
+                .map(|address| address.parse::<EndpointAddress>().unwrap())
+        );
+        assert_eq!(backend.port(), Some(Port::new(8080)));
+        assert_eq!(backend.host_header(), &None);
+    }
+
+    #[test]
+    fn test_external_name_service_backend() {
+        let service = service(ServiceSpec {
+            type_: Some("ExternalName".to_string()),
+            external_name: Some("API.example.com".to_string()),
+            ..Default::default()
+        });
+
+        let backend = extract_backend(
+            &service_ref(),
+            &http_route_backend(443),
+            Some(&service),
+            &[],
+            None,
+            BackendTraffic::default(),
+        );
+
+        assert_eq!(
+            addresses(&backend),
+            [EndpointAddress::Hostname(Hostname::new("api.example.com"))]
+        );
+        assert_eq!(backend.port(), Some(Port::new(443)));
+        assert_eq!(
+            backend.host_header(),
+            &Some(Hostname::new("api.example.com"))
+        );
+    }
+
+    #[test]
+    fn test_backend_traffic() {
+        let traffic = BackendTraffic::builder()
+            .slow_start(Some(SlowStart::builder().window_seconds(30).build()))
+            .circuit_breaker(Some(
+                CircuitBreaker::builder().max_requests(Some(100)).build(),
+            ))
+            .build();
+
+        let backend = extract_backend(
+            &service_ref(),
+            &http_route_backend(80),
+            Some(&service(ServiceSpec::default())),
+            &[endpoint_slice("IPv4", &["10.0.0.1"])],
+            None,
+            traffic,
+        );
+
+        assert_eq!(backend.traffic(), traffic);
+    }
 }
@@ -0,0 +1,486 @@
+use crate::controllers::gateway_service_account_name;
+use crate::kubernetes::KubeClientCell;
+use async_trait::async_trait;
+use axum::extract::{Path, Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use clap::ValueEnum;
+use getset::Getters;
+use k8s_openapi::api::authentication::v1::{TokenReview, TokenReviewSpec};
+use kube::Api;
+use kube::api::PostParams;
+use problemdetails::Problem;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+use vg_core::instrumentation::trace_id;
+use vg_core::ipc::IPC_TOKEN_AUDIENCE;
+use vg_core::sync::signal::Receiver;
+
+/// The extra field of a token review holding the name of the pod the token was issued to
+const POD_NAME_EXTRA: &str = "authentication.kubernetes.io/pod-name";
+
+/// How gateways authenticate to the IPC endpoint. Keeping what they fetch from being read on the
+/// network is up to [`IpcTls`](crate::ipc::tls::IpcTls)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IpcAuthentication {
+    /// Projected ServiceAccount tokens, verified through the TokenReview API
+    ServiceAccountToken,
+    /// No authentication, for development outside of a cluster
+    Disabled,
+}
+
+/// The ServiceAccount a gateway pod proved it runs as
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct GatewayIdentity {
+    #[getset(get = "pub")]
+    namespace: String,
+
+    #[getset(get = "pub")]
+    service_account: String,
+
+    #[getset(get = "pub")]
+    pod_name: Option<String>,
+}
+
+impl GatewayIdentity {
+    /// Parses the username Kubernetes gives to ServiceAccounts,
+    /// `system:serviceaccount:<namespace>:<name>`
+    fn from_username(username: &str, pod_name: Option<String>) -> Option<Self> {
+        let (namespace, service_account) = username
+            .strip_prefix("system:serviceaccount:")?
+            .split_once(':')?;
+
+        Some(Self {
+            namespace: namespace.to_string(),
+            service_account: service_account.to_string(),
+            pod_name,
+        })
+    }
+
+    /// Gateway pods run as the dedicated ServiceAccount of their Gateway, and present tokens
+    /// bound to a pod of its Deployment rather than ones issued to the ServiceAccount alone
+    fn is_gateway(&self, gateway_namespace: &str, gateway_name: &str) -> bool {
+        self.namespace == gateway_namespace
+            && self.service_account == gateway_service_account_name(gateway_name)
+            && self
+                .pod_name
+                .as_deref()
+                .and_then(|pod_name| pod_name.strip_prefix(gateway_name))
+                .is_some_and(|suffix| suffix.starts_with('-'))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TokenReviewError {
+    #[error("Kubernetes client is not available")]
+    MissingClient,
+    #[error("Error reviewing token: {0}")]
+    Request(#[source] kube::Error),
+    #[error("Token is not authenticated: {0}")]
+    Unauthenticated(String),
+    #[error("Token does not belong to a ServiceAccount: {0}")]
+    NotServiceAccount(String),
+}
+
+/// Verifies the tokens gateways present, stubbed in tests
+#[async_trait]
+pub trait TokenReviewer: Send + Sync {
+    async fn review(&self, token: &str) -> Result<GatewayIdentity, TokenReviewError>;
+}
+
+/// Verifies projected ServiceAccount tokens through the TokenReview API
+pub struct KubernetesTokenReviewer(Receiver<KubeClientCell>);
+
+impl KubernetesTokenReviewer {
+    pub fn new(kube_client_rx: &Receiver<KubeClientCell>) -> Self {
+        Self(kube_client_rx.clone())
+    }
+}
+
+#[async_trait]
+impl TokenReviewer for KubernetesTokenReviewer {
+    #[instrument(skip_all, name = "ipc::review_token")]
+    async fn review(&self, token: &str) -> Result<GatewayIdentity, TokenReviewError> {
+        let kube_client = self
+            .0
+            .get()
+            .await
+            .as_deref()
+            .cloned()
+            .ok_or(TokenReviewError::MissingClient)?;
+
+        let review = TokenReview {
+            spec: TokenReviewSpec {
+                token: Some(token.to_string()),
+                audiences: Some(vec![IPC_TOKEN_AUDIENCE.to_string()]),
+            },
+            ..Default::default()
+        };
+
+        let review = Api::<TokenReview>::all(kube_client)
+            .create(&PostParams::default(), &review)
+            .await
+            .map_err(TokenReviewError::Request)?;
+
+        let status = review.status.unwrap_or_default();
+        if status.authenticated != Some(true) {
+            return Err(TokenReviewError::Unauthenticated(
+                status.error.unwrap_or_default(),
+            ));
+        }
+
+        let user = status.user.unwrap_or_default();
+        let username = user.username.unwrap_or_default();
+        let pod_name = user
+            .extra
+            .and_then(|mut extra| extra.remove(POD_NAME_EXTRA))
+            .and_then(|values| values.into_iter().next());
+
+        GatewayIdentity::from_username(&username, pod_name)
+            .ok_or(TokenReviewError::NotServiceAccount(username))
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IpcAuthError {
+    #[error("Missing bearer token")]
+    MissingToken,
+    #[error("Invalid bearer token")]
+    InvalidToken,
+    #[error("Token review is unavailable")]
+    Unavailable,
+    #[error("ServiceAccount {0}/{1} is not allowed to access gateway {2}/{3}")]
+    Forbidden(String, String, String, String),
+}
+
+impl IntoResponse for IpcAuthError {
+    fn into_response(self) -> Response {
+        let (status, title) = match self {
+            IpcAuthError::MissingToken | IpcAuthError::InvalidToken => {
+                (StatusCode::UNAUTHORIZED, "Unauthorized")
+            }
+            IpcAuthError::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "Unavailable"),
+            IpcAuthError::Forbidden(..) => (StatusCode::FORBIDDEN, "Forbidden"),
+        };
+
+        let mut problem = Problem::from(status)
+            .with_value("status", status.as_u16())
+            .with_title(title)
+            .with_detail(self.to_string());
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        problem.into_response()
+    }
+}
+
+type ReviewCache = HashMap<String, (Instant, GatewayIdentity)>;
+
+/// Authorizes each gateway to read only the resources of its own Gateway. Reviews
+/// are cached, as gateways call the control plane every second to sync rate limits.
+#[derive(Clone)]
+pub struct IpcAuthenticator {
+    reviewer: Option<Arc<dyn TokenReviewer>>,
+    cache_duration: Duration,
+    cache: Arc<Mutex<ReviewCache>>,
+}
+
+impl IpcAuthenticator {
+    pub fn new(reviewer: Arc<dyn TokenReviewer>, cache_duration: Duration) -> Self {
+        Self {
+            reviewer: Some(reviewer),
+            cache_duration,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Lets every request through, for development outside of a cluster
+    pub fn disabled() -> Self {
+        Self {
+            reviewer: None,
+            cache_duration: Duration::ZERO,
+            cache: Arc::default(),
+        }
+    }
+
+    pub async fn authorize(
+        &self,
+        headers: &HeaderMap,
+        gateway_namespace: &str,
+        gateway_name: &str,
+    ) -> Result<Option<GatewayIdentity>, IpcAuthError> {
+        let Some(reviewer) = &self.reviewer else {
+            return Ok(None);
+        };
+
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or(IpcAuthError::MissingToken)?;
+
+        let identity = match self.cached(token) {
+            Some(identity) => identity,
+            None => {
+                let identity = reviewer.review(token).await.map_err(|e| {
+                    warn!("Failed to review IPC token: {}", e);
+                    match e {
+                        TokenReviewError::MissingClient | TokenReviewError::Request(_) => {
+                            IpcAuthError::Unavailable
+                        }
+                        TokenReviewError::Unauthenticated(_)
+                        | TokenReviewError::NotServiceAccount(_) => IpcAuthError::InvalidToken,
+                    }
+                })?;
+                self.cache(token, &identity);
+                identity
+            }
+        };
+
+        if identity.is_gateway(gateway_namespace, gateway_name) {
+            Ok(Some(identity))
+        } else {
+            Err(IpcAuthError::Forbidden(
+                identity.namespace,
+                identity.service_account,
+                gateway_namespace.to_string(),
+                gateway_name.to_string(),
+            ))
+        }
+    }
+
+    fn cached(&self, token: &str) -> Option<GatewayIdentity> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(token)
+            .filter(|(reviewed_at, _)| reviewed_at.elapsed() < self.cache_duration)
+            .map(|(_, identity)| identity.clone())
+    }
+
+    fn cache(&self, token: &str, identity: &GatewayIdentity) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.retain(|_, (reviewed_at, _)| reviewed_at.elapsed() < self.cache_duration);
+        cache.insert(token.to_string(), (Instant::now(), identity.clone()));
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GatewayPathParams {
+    gateway_namespace: String,
+    gateway_name: String,
+}
+
+/// Rejects requests for the resources of a Gateway made by anything but its own pods,
+/// and passes the [`GatewayIdentity`] of authorized pods on as a request extension
+pub async fn authorize_gateway(
+    State(authenticator): State<IpcAuthenticator>,
+    Path(path_params): Path<GatewayPathParams>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match authenticator
+        .authorize(
+            request.headers(),
+            &path_params.gateway_namespace,
+            &path_params.gateway_name,
+        )
+        .await
+    {
+        Ok(identity) => {
+            if let Some(identity) = identity {
+                debug!(
+                    "Authorized pod {:?} of gateway {}/{}",
+                    identity.pod_name(),
+                    path_params.gateway_namespace,
+                    path_params.gateway_name
+                );
+                request.extensions_mut().insert(identity);
+            }
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct StubTokenReviewer {
+        identities: HashMap<&'static str, (&'static str, Option<&'static str>)>,
+        reviews: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TokenReviewer for StubTokenReviewer {
+        async fn review(&self, token: &str) -> Result<GatewayIdentity, TokenReviewError> {
+            self.reviews.fetch_add(1, Ordering::Relaxed);
+            let (username, pod_name) = self
+                .identities
+                .get(token)
+                .ok_or_else(|| TokenReviewError::Unauthenticated("unknown token".to_string()))?;
+            GatewayIdentity::from_username(username, pod_name.map(str::to_string))
+                .ok_or_else(|| TokenReviewError::NotServiceAccount(username.to_string()))
+        }
+    }
+
+    fn authenticator() -> (IpcAuthenticator, Arc<StubTokenReviewer>) {
+        let reviewer = Arc::new(StubTokenReviewer {
+            identities: HashMap::from([
+                (
+                    "gateway-a",
+                    (
+                        "system:serviceaccount:default:gateway-a-vale-gateway",
+                        Some("gateway-a-5d4f8b7c9-x2x7q"),
+                    ),
+                ),
+                (
+                    "unbound",
+                    ("system:serviceaccount:default:gateway-a-vale-gateway", None),
+                ),
+                (
+                    "other-pod",
+                    (
+                        "system:serviceaccount:default:gateway-a-vale-gateway",
+                        Some("gateway-b-5d4f8b7c9-x2x7q"),
+                    ),
+                ),
+                (
+                    "same-name",
+                    (
+                        "system:serviceaccount:default:gateway-a",
+                        Some("gateway-a-5d4f8b7c9-x2x7q"),
+                    ),
+                ),
+                ("user", ("alice", None)),
+            ]),
+            ..Default::default()
+        });
+        let authenticator = IpcAuthenticator::new(reviewer.clone(), Duration::from_secs(60));
+        (authenticator, reviewer)
+    }
+
+    fn headers(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).expect("header value"),
+        );
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_authorize_own_gateway() {
+        let (authenticator, reviewer) = authenticator();
+
+        for _ in 0..2 {
+            let identity = authenticator
+                .authorize(&headers("gateway-a"), "default", "gateway-a")
+                .await
+                .expect("authorized")
+                .expect("identity");
+            assert_eq!(identity.service_account(), "gateway-a-vale-gateway");
+            assert_eq!(
+                identity.pod_name().as_deref(),
+                Some("gateway-a-5d4f8b7c9-x2x7q")
+            );
+        }
+
+        // The second request is answered from the cache
+        assert_eq!(reviewer.reviews.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_authorize_rejections() {
+        let (authenticator, _) = authenticator();
+
+        assert_eq!(
+            authenticator
+                .authorize(&HeaderMap::new(), "default", "gateway-a")
+                .await,
+            Err(IpcAuthError::MissingToken)
+        );
+        assert_eq!(
+            authenticator
+                .authorize(&headers("unknown"), "default", "gateway-a")
+                .await,
+            Err(IpcAuthError::InvalidToken)
+        );
+        assert_eq!(
+            authenticator
+                .authorize(&headers("user"), "default", "gateway-a")
+                .await,
+            Err(IpcAuthError::InvalidToken)
+        );
+        assert_eq!(
+            authenticator
+                .authorize(&headers("gateway-a"), "default", "gateway-b")
+                .await,
+            Err(IpcAuthError::Forbidden(
+                "default".to_string(),
+                "gateway-a-vale-gateway".to_string(),
+                "default".to_string(),
+                "gateway-b".to_string(),
+            ))
+        );
+        assert_eq!(
+            authenticator
+                .authorize(&headers("gateway-a"), "other", "gateway-a")
+                .await,
+            Err(IpcAuthError::Forbidden(
+                "default".to_string(),
+                "gateway-a-vale-gateway".to_string(),
+                "other".to_string(),
+                "gateway-a".to_string(),
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorize_requires_dedicated_service_account_and_pod() {
+        let (authenticator, _) = authenticator();
+
+        for (token, service_account) in [
+            ("unbound", "gateway-a-vale-gateway"),
+            ("other-pod", "gateway-a-vale-gateway"),
+            // A ServiceAccount that merely shares the name of the Gateway
+            ("same-name", "gateway-a"),
+        ] {
+            assert_eq!(
+                authenticator
+                    .authorize(&headers(token), "default", "gateway-a")
+                    .await,
+                Err(IpcAuthError::Forbidden(
+                    "default".to_string(),
+                    service_account.to_string(),
+                    "default".to_string(),
+                    "gateway-a".to_string(),
+                )),
+                "{token}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled() {
+        assert_eq!(
+            IpcAuthenticator::disabled()
+                .authorize(&HeaderMap::new(), "default", "gateway-a")
+                .await,
+            Ok(None)
+        );
+    }
+}
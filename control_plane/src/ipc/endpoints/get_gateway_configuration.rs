@@ -1,14 +1,16 @@
 use crate::ipc::endpoints::IpcEndpointState;
 use crate::kubernetes::objects::ObjectRef;
 use axum::extract::{Path, Query};
-use axum::http::StatusCode;
-use axum::http::header::CONTENT_TYPE;
+use axum::http::header::{ACCEPT, CONTENT_TYPE, HeaderName, VARY};
+use axum::http::{HeaderMap, StatusCode};
 use axum::{extract::State, response::IntoResponse};
 use gateway_api::apis::standard::gateways::Gateway;
 use problemdetails::Problem;
 use serde::Deserialize;
 use tracing::{debug, instrument};
+use vg_core::config::gateway::serde::ConfigurationEncoding;
 use vg_core::instrumentation::trace_id;
+use vg_core::ipc::configuration::CONFIGURATION_VERSION_HEADER;
 
 #[derive(Deserialize, Debug)]
 pub struct PathParams {
@@ -21,11 +23,12 @@ pub struct QueryParams {
     pod_name: String,
 }
 
-#[instrument(skip(state), name = "ipc::get_gateway_configuration")]
+#[instrument(skip(state, headers), name = "ipc::get_gateway_configuration")]
 pub async fn get_gateway_configuration(
     State(state): State<IpcEndpointState>,
     Path(path_params): Path<PathParams>,
     Query(query_params): Query<QueryParams>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     if path_params.gateway_namespace.is_empty() {
         let mut problem = Problem::from(StatusCode::BAD_REQUEST)
@@ -62,12 +65,31 @@ pub async fn get_gateway_configuration(
         query_params.pod_name, gateway_ref
     );
 
-    if let Some(config) = state.gateways.get_configuration_yaml(&gateway_ref) {
-        debug!("Returning configuration for {}", gateway_ref);
+    // Gateways that do not ask for an encoding get YAML
+    let encoding = headers
+        .get(ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .map(ConfigurationEncoding::negotiate)
+        .unwrap_or_default();
+
+    if let Some(config) = state.gateways.get_configuration(&gateway_ref) {
+        debug!(
+            "Returning configuration version {} for {} as {}",
+            config.version(),
+            gateway_ref,
+            encoding
+        );
         (
             StatusCode::OK,
-            [(CONTENT_TYPE, "application/yaml")],
-            config.value().clone(),
+            [
+                (CONTENT_TYPE, encoding.media_type().to_string()),
+                (VARY, ACCEPT.to_string()),
+                (
+                    HeaderName::from_static(CONFIGURATION_VERSION_HEADER),
+                    config.version().to_string(),
+                ),
+            ],
+            config.encoded(encoding),
         )
             .into_response()
     } else {
@@ -13,6 +13,7 @@ use problemdetails::Problem;
 use serde::Deserialize;
 use tracing::{debug, instrument, warn};
 use vg_core::instrumentation::trace_id;
+use vg_core::ipc::GatewayEvent;
 
 #[derive(Deserialize, Debug)]
 pub struct PathParams {
@@ -83,13 +84,16 @@ pub async fn get_gateway_events(
         .events
         .named_gateway_events(gateway_ref)
         .map_ok(|event| {
-            Event::default()
-                .event(&event)
-                .json_data(event.gateway_ref())
-                .unwrap_or_else(|err| {
-                    warn!("Failed to serialize event for SSE: {err}");
-                    Event::default().comment("keep-alive")
-                })
+            let sse_event = Event::default().event(&event);
+            let sse_event = match &event {
+                GatewayEvent::ConfigurationDelta(delta) => sse_event.json_data(delta),
+                GatewayEvent::CachePurge(purge) => sse_event.json_data(purge),
+                _ => sse_event.json_data(event.gateway_ref()),
+            };
+            sse_event.unwrap_or_else(|err| {
+                warn!("Failed to serialize event for SSE: {err}");
+                Event::default().comment("keep-alive")
+            })
         });
 
     Sse::new(stream)
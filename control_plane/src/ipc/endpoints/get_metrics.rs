@@ -0,0 +1,13 @@
+use crate::ipc::endpoints::not_found;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{IntoResponse, Response};
+use tracing::instrument;
+use vg_core::instrumentation::{PROMETHEUS_CONTENT_TYPE, prometheus_metrics};
+
+#[instrument(name = "ipc::get_metrics")]
+pub async fn get_metrics() -> Response {
+    match prometheus_metrics() {
+        Some(metrics) => ([(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], metrics).into_response(),
+        None => not_found().await.into_response(),
+    }
+}
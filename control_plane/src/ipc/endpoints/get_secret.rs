@@ -0,0 +1,233 @@
+use crate::controllers::{
+    ClientCertificatesCache, HtpasswdCache, JwksCache, SecretSource, SecretsCache,
+};
+use crate::ipc::endpoints::IpcEndpointState;
+use crate::kubernetes::objects::{ObjectRef, ObjectUniqueId};
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::{extract::State, response::IntoResponse};
+use gateway_api::apis::experimental::backendtlspolicies::BackendTLSPolicy;
+use gateway_api::apis::standard::gateways::Gateway;
+use problemdetails::Problem;
+use serde::Deserialize;
+use tracing::{debug, instrument};
+use vg_api::v1alpha1::{BasicAuthFilter, JwtAuthFilter};
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::instrumentation::trace_id;
+
+/// Secret material served to gateways, by the unique ID of the object referencing it
+pub trait IpcSecretSource: SecretSource {
+    /// What the secret material is called in logs and problems
+    const NAME: &'static str;
+
+    const CONTENT_TYPE: &'static str;
+
+    fn cache(state: &IpcEndpointState) -> SecretsCache<Self>;
+
+    /// Whether the configuration of a gateway references the object, so gateways can only
+    /// read the secret material of their own routes
+    fn is_referenced(configuration: &GatewayConfiguration, secret_id: &str) -> bool;
+}
+
+impl IpcSecretSource for JwtAuthFilter {
+    const NAME: &'static str = "JWKS";
+
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn cache(state: &IpcEndpointState) -> JwksCache {
+        state.jwks_cache()
+    }
+
+    fn is_referenced(configuration: &GatewayConfiguration, secret_id: &str) -> bool {
+        configuration
+            .jwt_auth_filters()
+            .iter()
+            .any(|filter| filter.jwks_identifier() == secret_id)
+    }
+}
+
+impl IpcSecretSource for BasicAuthFilter {
+    const NAME: &'static str = "htpasswd";
+
+    const CONTENT_TYPE: &'static str = "text/plain; charset=utf-8";
+
+    fn cache(state: &IpcEndpointState) -> HtpasswdCache {
+        state.htpasswd_cache()
+    }
+
+    fn is_referenced(configuration: &GatewayConfiguration, secret_id: &str) -> bool {
+        configuration
+            .basic_auth_filters()
+            .iter()
+            .any(|filter| filter.htpasswd_identifier() == secret_id)
+    }
+}
+
+impl IpcSecretSource for BackendTLSPolicy {
+    const NAME: &'static str = "client certificate";
+
+    const CONTENT_TYPE: &'static str = "application/x-pem-file";
+
+    fn cache(state: &IpcEndpointState) -> ClientCertificatesCache {
+        state.client_certificates_cache()
+    }
+
+    fn is_referenced(configuration: &GatewayConfiguration, secret_id: &str) -> bool {
+        configuration
+            .http_routes()
+            .iter()
+            .flat_map(|route| route.rules())
+            .flat_map(|rule| rule.backends())
+            .filter_map(|backend| backend.tls().as_ref()?.client_certificate().as_ref())
+            .any(|client_certificate| client_certificate.identifier() == secret_id)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PathParams {
+    gateway_namespace: String,
+    gateway_name: String,
+    secret_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    pod_name: String,
+}
+
+#[instrument(skip(state), name = "ipc::get_secret", fields(secret = K::NAME))]
+pub async fn get_secret<K: IpcSecretSource>(
+    State(state): State<IpcEndpointState>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> impl IntoResponse {
+    if path_params.gateway_namespace.is_empty() {
+        let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+            .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+            .with_title("Invalid Namespace")
+            .with_detail("Gateway namespace cannot be empty");
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+    if path_params.gateway_name.is_empty() {
+        let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+            .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+            .with_title("Invalid Name")
+            .with_detail("Gateway name cannot be empty");
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+    if path_params.secret_id.is_empty() {
+        let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+            .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+            .with_title("Invalid ID")
+            .with_detail(format!("{} ID cannot be empty", K::kind(&())));
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+
+    let gateway_ref = ObjectRef::of_kind::<Gateway>()
+        .name(path_params.gateway_name)
+        .namespace(Some(path_params.gateway_namespace))
+        .build();
+
+    debug!(
+        "Pod {} requesting {} for {:?} with {} ID {}",
+        query_params.pod_name,
+        K::NAME,
+        gateway_ref,
+        K::kind(&()),
+        path_params.secret_id
+    );
+
+    let Some(is_referenced) = state
+        .gateways
+        .get_configuration(&gateway_ref)
+        .map(|configuration| {
+            K::is_referenced(configuration.configuration(), &path_params.secret_id)
+        })
+    else {
+        debug!("Gateway for {} not found", gateway_ref);
+        let mut problem = Problem::from(StatusCode::NOT_FOUND)
+            .with_value("status", StatusCode::NOT_FOUND.as_u16())
+            .with_title("Gateway Not Found")
+            .with_detail(format!("Gateway {gateway_ref} not found"));
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    };
+
+    // Not telling referenced objects apart from missing ones, the IDs of other gateways'
+    // objects are not revealed either
+    if is_referenced
+        && let Some(body) = K::cache(&state)
+            .get(ObjectUniqueId::new(&path_params.secret_id))
+            .await
+    {
+        return (StatusCode::OK, [(CONTENT_TYPE, K::CONTENT_TYPE)], body).into_response();
+    }
+
+    debug!(
+        "{} for {} {} not found",
+        K::NAME,
+        K::kind(&()),
+        path_params.secret_id
+    );
+    let mut problem = Problem::from(StatusCode::NOT_FOUND)
+        .with_value("status", StatusCode::NOT_FOUND.as_u16())
+        .with_title("Secret Not Found")
+        .with_detail(format!(
+            "{} for {} {} not found",
+            K::NAME,
+            K::kind(&()),
+            path_params.secret_id
+        ));
+
+    if let Some(trace_id) = trace_id() {
+        problem = problem.with_instance(trace_id);
+    }
+
+    problem.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_core::config::gateway::types::GatewayConfigurationBuilder;
+    use vg_core::config::gateway::types::net::BasicAuthFilter as ConfigBasicAuthFilter;
+
+    #[test]
+    fn test_only_referenced_secrets_are_served() {
+        let configuration = GatewayConfigurationBuilder::default()
+            .with_basic_auth_filters(vec![
+                ConfigBasicAuthFilter::builder()
+                    .key("default/basic")
+                    .version_key("1")
+                    .htpasswd_identifier("uid")
+                    .build(),
+            ])
+            .build()
+            .expect("Failed to build configuration");
+
+        assert!(BasicAuthFilter::is_referenced(&configuration, "uid"));
+        assert!(!BasicAuthFilter::is_referenced(&configuration, "other-uid"));
+        assert!(!JwtAuthFilter::is_referenced(&configuration, "uid"));
+        assert!(!BackendTLSPolicy::is_referenced(&configuration, "uid"));
+    }
+}
@@ -1,35 +1,52 @@
 mod get_gateway_configuration;
 mod get_gateway_events;
+mod get_metrics;
+mod get_secret;
 mod get_static_response;
 mod liveness_check;
+mod report_configuration_status;
+mod sync_rate_limits;
 
 use self::get_gateway_configuration::get_gateway_configuration;
 use self::get_gateway_events::get_gateway_events;
-use crate::controllers::StaticResponsesCache;
+use crate::controllers::{ClientCertificatesCache, HtpasswdCache, JwksCache, StaticResponsesCache};
 use crate::health::KubernetesApiHealthIndicator;
+use crate::ipc::auth::{
+    IpcAuthentication, IpcAuthenticator, KubernetesTokenReviewer, authorize_gateway,
+};
+use crate::ipc::endpoints::get_metrics::get_metrics;
+use crate::ipc::endpoints::get_secret::get_secret;
 use crate::ipc::endpoints::get_static_response::get_static_response;
 use crate::ipc::endpoints::liveness_check::liveness_check;
-use crate::ipc::events::EventStreamFactory;
-use crate::ipc::gateways::GatewayConfigurationReader;
+use crate::ipc::endpoints::report_configuration_status::report_configuration_status;
+use crate::ipc::endpoints::sync_rate_limits::sync_rate_limits;
+use crate::ipc::events::{EventSender, EventStreamFactory};
+use crate::ipc::gateways::{GatewayConfigurationReader, GatewayConfigurationRollouts};
 use crate::kubernetes::KubeClientCell;
 use crate::options::Options;
 use axum::Router;
 use axum::http::StatusCode;
+use axum::middleware::from_fn_with_state;
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum_health::Health;
 use axum_otel_metrics::HttpMetricsLayerBuilder;
+use axum_server::tls_rustls::RustlsConfig;
 use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
+use gateway_api::apis::experimental::backendtlspolicies::BackendTLSPolicy;
 use getset::{CloneGetters, CopyGetters, Getters};
 use problemdetails::Problem;
+use rustls::ServerConfig;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::net::TcpListener;
 use tokio::select;
-use tracing::info;
+use tracing::{info, warn};
 use typed_builder::TypedBuilder;
+use vg_api::v1alpha1::{BasicAuthFilter, JwtAuthFilter};
 use vg_core::instrumentation::trace_id;
+use vg_core::ipc::rate_limits::GlobalRateLimits;
 use vg_core::net::Port;
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
@@ -42,11 +59,30 @@ pub struct IpcEndpointState {
     #[getset(get = "pub")]
     events: EventStreamFactory,
 
+    #[getset(get = "pub")]
+    event_sender: EventSender,
+
     #[getset(get = "pub")]
     gateways: GatewayConfigurationReader,
 
+    #[getset(get = "pub")]
+    configuration_rollouts: GatewayConfigurationRollouts,
+
     #[getset(get_clone = "pub")]
     static_responses_cache: StaticResponsesCache,
+
+    #[getset(get_clone = "pub")]
+    jwks_cache: JwksCache,
+
+    #[getset(get_clone = "pub")]
+    htpasswd_cache: HtpasswdCache,
+
+    #[getset(get_clone = "pub")]
+    client_certificates_cache: ClientCertificatesCache,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    rate_limits: Arc<GlobalRateLimits>,
 }
 
 #[derive(TypedBuilder, CloneGetters, CopyGetters)]
@@ -57,17 +93,38 @@ pub struct SpawnIpcEndpointParameters {
     #[getset(get_copy = "")]
     port: Port,
 
+    #[getset(get_copy = "")]
+    authentication: IpcAuthentication,
+
+    #[getset(get_clone = "")]
+    tls_config: Option<Arc<ServerConfig>>,
+
     #[getset(get_clone = "")]
     events: EventStreamFactory,
 
+    #[getset(get_clone = "")]
+    event_sender: EventSender,
+
     #[getset(get_clone = "")]
     gateways: GatewayConfigurationReader,
 
+    #[getset(get_clone = "")]
+    configuration_rollouts: GatewayConfigurationRollouts,
+
     #[getset(get_clone = "")]
     kube_client_rx: Receiver<KubeClientCell>,
 
     #[getset(get_clone = "")]
     static_responses_cache: StaticResponsesCache,
+
+    #[getset(get_clone = "")]
+    jwks_cache: JwksCache,
+
+    #[getset(get_clone = "")]
+    htpasswd_cache: HtpasswdCache,
+
+    #[getset(get_clone = "")]
+    client_certificates_cache: ClientCertificatesCache,
 }
 
 impl SpawnIpcEndpointParameters {
@@ -92,21 +149,38 @@ pub async fn spawn_ipc_endpoint(
     let initial_state = IpcEndpointState::builder()
         .options(params.options())
         .gateways(params.gateways())
+        .configuration_rollouts(params.configuration_rollouts())
         .events(params.events())
+        .event_sender(params.event_sender())
         .static_responses_cache(params.static_responses_cache())
+        .jwks_cache(params.jwks_cache())
+        .htpasswd_cache(params.htpasswd_cache())
+        .client_certificates_cache(params.client_certificates_cache())
         .build();
 
+    let authenticator = match params.authentication() {
+        IpcAuthentication::ServiceAccountToken => IpcAuthenticator::new(
+            Arc::new(KubernetesTokenReviewer::new(&params.kube_client_rx)),
+            params.options.ipc_token_review_cache_duration(),
+        ),
+        IpcAuthentication::Disabled => {
+            warn!("IPC authentication is disabled, any client can read gateway configurations");
+            IpcAuthenticator::disabled()
+        }
+    };
+
     let kube_health = KubernetesApiHealthIndicator::new(&params.kube_client_rx);
     let health = Health::builder().with_indicator(kube_health).build();
 
     let endpoint = params.endpoint();
     let tcp_listener = TcpListener::bind(endpoint).await?;
+    let tls_config = params.tls_config();
 
     task_builder
         .new_task("ipc_endpoint")
         .spawn(async move {
             select! {
-                _ = axum::serve(tcp_listener, router(initial_state, health)) => info!("IPC service stopped"),
+                _ = serve(tcp_listener, tls_config, router(initial_state, authenticator, health)) => info!("IPC service stopped"),
                 _ = tokio::signal::ctrl_c() => info!("Received shutdown signal, stopping IPC service")
             }
         });
@@ -114,14 +188,35 @@ pub async fn spawn_ipc_endpoint(
     Ok(())
 }
 
-fn router(state: IpcEndpointState, health: Health) -> Router {
-    Router::new()
-        .route("/healthz/liveness", get(liveness_check))
-        .route("/healthz/readiness", get(axum_health::health))
+async fn serve(
+    tcp_listener: TcpListener,
+    tls_config: Option<Arc<ServerConfig>>,
+    router: Router,
+) -> std::io::Result<()> {
+    match tls_config {
+        Some(tls_config) => {
+            axum_server::from_tcp_rustls(
+                tcp_listener.into_std()?,
+                RustlsConfig::from_config(tls_config),
+            )
+            .serve(router.into_make_service())
+            .await
+        }
+        None => axum::serve(tcp_listener, router).await,
+    }
+}
+
+fn router(state: IpcEndpointState, authenticator: IpcAuthenticator, health: Health) -> Router {
+    // Every gateway is only allowed to read the resources of its own Gateway
+    let gateway_routes = Router::new()
         .route(
             "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/configuration",
             get(get_gateway_configuration),
         )
+        .route(
+            "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/configuration/status",
+            post(report_configuration_status),
+        )
         .route(
             "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/events",
             get(get_gateway_events),
@@ -130,6 +225,29 @@ fn router(state: IpcEndpointState, health: Health) -> Router {
             "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/static_responses/{static_response_filter_id}",
             get(get_static_response),
         )
+        .route(
+            "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/jwks/{secret_id}",
+            get(get_secret::<JwtAuthFilter>),
+        )
+        .route(
+            "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/htpasswd/{secret_id}",
+            get(get_secret::<BasicAuthFilter>),
+        )
+        .route(
+            "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/client_certificates/{secret_id}",
+            get(get_secret::<BackendTLSPolicy>),
+        )
+        .route(
+            "/ipc/namespaces/{gateway_namespace}/gateways/{gateway_name}/rate_limits",
+            post(sync_rate_limits),
+        )
+        .route_layer(from_fn_with_state(authenticator, authorize_gateway));
+
+    Router::new()
+        .route("/healthz/liveness", get(liveness_check))
+        .route("/healthz/readiness", get(axum_health::health))
+        .route("/metrics", get(get_metrics))
+        .merge(gateway_routes)
         .fallback(not_found)
         .method_not_allowed_fallback(method_not_allowed)
         .with_state(state)
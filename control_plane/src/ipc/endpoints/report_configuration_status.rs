@@ -0,0 +1,107 @@
+use crate::ipc::auth::GatewayIdentity;
+use crate::ipc::endpoints::IpcEndpointState;
+use crate::ipc::gateways::ReportConfigurationStatusError;
+use crate::kubernetes::objects::ObjectRef;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use gateway_api::apis::standard::gateways::Gateway;
+use problemdetails::Problem;
+use serde::Deserialize;
+use std::time::Instant;
+use tracing::{debug, instrument, warn};
+use vg_core::instrumentation::trace_id;
+use vg_core::ipc::configuration::{ConfigurationStatus, ConfigurationStatusReport};
+
+#[derive(Deserialize, Debug)]
+pub struct PathParams {
+    gateway_namespace: String,
+    gateway_name: String,
+}
+
+#[instrument(
+    skip(state, identity, report),
+    name = "ipc::report_configuration_status"
+)]
+pub async fn report_configuration_status(
+    State(state): State<IpcEndpointState>,
+    Path(path_params): Path<PathParams>,
+    identity: Option<Extension<GatewayIdentity>>,
+    Json(report): Json<ConfigurationStatusReport>,
+) -> impl IntoResponse {
+    // A pod may only report for itself, so its token has to name it
+    if let Some(Extension(identity)) = identity
+        && identity.pod_name().as_deref() != Some(report.pod_name().as_str())
+    {
+        let detail = match identity.pod_name() {
+            Some(pod_name) => format!(
+                "Pod {pod_name} cannot report the configuration status of pod {}",
+                report.pod_name()
+            ),
+            None => format!(
+                "Token is not bound to a pod, cannot report the configuration status of pod {}",
+                report.pod_name()
+            ),
+        };
+        let mut problem = Problem::from(StatusCode::FORBIDDEN)
+            .with_value("status", StatusCode::FORBIDDEN.as_u16())
+            .with_title("Forbidden")
+            .with_detail(detail);
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+
+    let gateway_ref = ObjectRef::of_kind::<Gateway>()
+        .name(path_params.gateway_name)
+        .namespace(Some(path_params.gateway_namespace))
+        .build();
+
+    match report.status() {
+        ConfigurationStatus::Applied => debug!(
+            "Pod {} applied configuration version {} of {}",
+            report.pod_name(),
+            report.version(),
+            gateway_ref
+        ),
+        ConfigurationStatus::Rejected { error } => warn!(
+            "Pod {} rejected configuration version {} of {}: {}",
+            report.pod_name(),
+            report.version(),
+            gateway_ref,
+            error
+        ),
+    }
+
+    match state
+        .configuration_rollouts()
+        .report(&gateway_ref, &report, Instant::now())
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => {
+            let (status, title) = match err {
+                ReportConfigurationStatusError::UnknownGateway(_) => {
+                    (StatusCode::NOT_FOUND, "Gateway Configuration Not Found")
+                }
+                ReportConfigurationStatusError::UnknownVersion(_, _) => {
+                    (StatusCode::CONFLICT, "Unknown Configuration Version")
+                }
+            };
+            let mut problem = Problem::from(status)
+                .with_value("status", status.as_u16())
+                .with_title(title)
+                .with_detail(err.to_string());
+
+            if let Some(trace_id) = trace_id() {
+                problem = problem.with_instance(trace_id);
+            }
+
+            problem.into_response()
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::ipc::endpoints::IpcEndpointState;
+use crate::kubernetes::objects::ObjectRef;
+use axum::Json;
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::{extract::State, response::IntoResponse};
+use gateway_api::apis::standard::gateways::Gateway;
+use problemdetails::Problem;
+use serde::Deserialize;
+use std::time::Instant;
+use tracing::{debug, instrument};
+use vg_core::instrumentation::trace_id;
+use vg_core::ipc::Ref as IpcRef;
+use vg_core::ipc::rate_limits::RateLimitSyncRequest;
+
+#[derive(Deserialize, Debug)]
+pub struct PathParams {
+    gateway_namespace: String,
+    gateway_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    pod_name: String,
+}
+
+#[instrument(skip(state, request), name = "ipc::sync_rate_limits")]
+pub async fn sync_rate_limits(
+    State(state): State<IpcEndpointState>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+    Json(request): Json<RateLimitSyncRequest>,
+) -> impl IntoResponse {
+    if path_params.gateway_namespace.is_empty() {
+        let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+            .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+            .with_title("Invalid Namespace")
+            .with_detail("Gateway namespace cannot be empty");
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+    if path_params.gateway_name.is_empty() {
+        let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+            .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+            .with_title("Invalid Name")
+            .with_detail("Gateway name cannot be empty");
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+
+    let gateway_ref = ObjectRef::of_kind::<Gateway>()
+        .name(&path_params.gateway_name)
+        .namespace(Some(path_params.gateway_namespace.clone()))
+        .build();
+
+    if !state.gateways.exists(&gateway_ref) {
+        debug!("Gateway for {} not found", gateway_ref);
+        let mut problem = Problem::from(StatusCode::NOT_FOUND)
+            .with_value("status", StatusCode::NOT_FOUND.as_u16())
+            .with_title("Gateway Not Found")
+            .with_detail(format!("Gateway {gateway_ref} not found"));
+
+        if let Some(trace_id) = trace_id() {
+            problem = problem.with_instance(trace_id);
+        }
+
+        return problem.into_response();
+    }
+
+    debug!(
+        "Pod {} syncing {} rate limit usage(s) for {}",
+        query_params.pod_name,
+        request.usages().len(),
+        gateway_ref
+    );
+
+    let ipc_ref = IpcRef::builder()
+        .namespace(path_params.gateway_namespace)
+        .name(path_params.gateway_name)
+        .build();
+
+    let response =
+        state
+            .rate_limits()
+            .sync(&ipc_ref, &query_params.pod_name, &request, Instant::now());
+
+    (StatusCode::OK, Json(response)).into_response()
+}
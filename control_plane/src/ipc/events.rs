@@ -62,7 +62,12 @@ impl EventStreamFactory {
         gateway_ref: ObjectRef,
     ) -> impl Stream<Item = Result<GatewayEvent, RecvError>> + Send + use<> {
         self.gateway_events().filter(move |event| match event {
-            Ok(GatewayEvent::ConfigurationUpdate(ref_)) => {
+            Ok(
+                event @ (GatewayEvent::ConfigurationUpdate(_)
+                | GatewayEvent::ConfigurationDelta(_)
+                | GatewayEvent::CachePurge(_)),
+            ) => {
+                let ref_ = event.gateway_ref();
                 ref_.name() == gateway_ref.name()
                     && Some(ref_.namespace()) == gateway_ref.namespace().as_ref()
             }
@@ -1,27 +1,89 @@
 use crate::kubernetes::objects::ObjectRef;
+use bytes::Bytes;
 use dashmap::DashMap;
 use dashmap::mapref::one::Ref;
+use getset::{CopyGetters, Getters};
+use std::collections::{BTreeMap, HashMap};
 use std::io::{BufWriter, IntoInnerError};
 use std::string::FromUtf8Error;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
-use vg_core::config::gateway::serde::{WriteError, write_configuration};
+use vg_core::config::gateway::serde::{
+    ConfigurationEncoding, WriteError, write_configuration, write_encoded_configuration,
+};
 use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::ipc::configuration::{
+    ConfigurationRollout, ConfigurationStatusReport, PodConfigurationState,
+};
+use vg_core::ipc::delta::{ConfigurationChange, diff};
+use vg_core::sync::signal::{Receiver, Sender, signal};
 
-pub fn create_gateway_configuration_services()
--> (GatewayConfigurationReader, GatewayConfigurationManager) {
+pub fn create_gateway_configuration_services(
+    report_ttl: Duration,
+) -> (
+    GatewayConfigurationReader,
+    GatewayConfigurationManager,
+    GatewayConfigurationRollouts,
+    Receiver<HashMap<ObjectRef, ConfigurationRollout>>,
+) {
     let configurations = Arc::new(DashMap::new());
+    let (rollouts_tx, rollouts_rx) = signal("configuration_rollouts");
+    let rollouts = GatewayConfigurationRollouts {
+        configurations: configurations.clone(),
+        reports: Arc::new(DashMap::new()),
+        report_ttl,
+        rollouts_tx,
+    };
     (
         GatewayConfigurationReader {
             configurations: configurations.clone(),
         },
-        GatewayConfigurationManager { configurations },
+        GatewayConfigurationManager {
+            configurations,
+            next_version: Arc::new(AtomicU64::new(initial_version())),
+            rollouts: rollouts.clone(),
+        },
+        rollouts,
+        rollouts_rx,
     )
 }
 
+/// Seeds versions with the current time, so they keep growing across control plane restarts
+fn initial_version() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// A gateway configuration serialized for delivery, with the version gateways acknowledge
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters)]
+pub struct VersionedConfiguration {
+    #[getset(get_copy = "pub")]
+    version: u64,
+
+    yaml: String,
+
+    cbor: Bytes,
+
+    #[getset(get = "pub")]
+    configuration: GatewayConfiguration,
+}
+
+impl VersionedConfiguration {
+    pub fn encoded(&self, encoding: ConfigurationEncoding) -> Bytes {
+        match encoding {
+            ConfigurationEncoding::Yaml => Bytes::from(self.yaml.clone()),
+            ConfigurationEncoding::Cbor => self.cbor.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GatewayConfigurationReader {
-    configurations: Arc<DashMap<ObjectRef, String>>,
+    configurations: Arc<DashMap<ObjectRef, VersionedConfiguration>>,
 }
 
 impl GatewayConfigurationReader {
@@ -29,17 +91,44 @@ impl GatewayConfigurationReader {
         self.configurations.contains_key(gateway_ref)
     }
 
-    pub fn get_configuration_yaml(
+    pub fn get_configuration(
         &'_ self,
         gateway_ref: &ObjectRef,
-    ) -> Option<Ref<'_, ObjectRef, String>> {
+    ) -> Option<Ref<'_, ObjectRef, VersionedConfiguration>> {
         self.configurations.get(gateway_ref)
     }
 }
 
+/// What inserting a gateway configuration changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertedConfiguration {
+    /// The configuration matches the latest version
+    Unchanged { version: u64 },
+    /// A new version, reached by applying the changes to the previous one
+    Delta {
+        base_version: u64,
+        version: u64,
+        changes: Vec<ConfigurationChange>,
+    },
+    /// A new version that gateways have to fetch in full
+    Snapshot { version: u64 },
+}
+
+impl InsertedConfiguration {
+    pub fn version(&self) -> u64 {
+        match self {
+            InsertedConfiguration::Unchanged { version }
+            | InsertedConfiguration::Delta { version, .. }
+            | InsertedConfiguration::Snapshot { version } => *version,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GatewayConfigurationManager {
-    configurations: Arc<DashMap<ObjectRef, String>>,
+    configurations: Arc<DashMap<ObjectRef, VersionedConfiguration>>,
+    next_version: Arc<AtomicU64>,
+    rollouts: GatewayConfigurationRollouts,
 }
 
 #[derive(Debug, Error)]
@@ -53,20 +142,345 @@ pub enum GatewayConfigurationManagerInsertError {
 }
 
 impl GatewayConfigurationManager {
-    pub fn try_insert(
+    /// Stores the configuration of a gateway, under a new version if it changed
+    pub async fn try_insert(
         &self,
         gateway_ref: ObjectRef,
         configuration: GatewayConfiguration,
-    ) -> Result<(), GatewayConfigurationManagerInsertError> {
+    ) -> Result<InsertedConfiguration, GatewayConfigurationManagerInsertError> {
         let mut buf = BufWriter::new(Vec::new());
         write_configuration(&configuration, &mut buf)?;
         let buf = buf.into_inner()?;
         let yaml = String::from_utf8(buf)?;
-        self.configurations.insert(gateway_ref, yaml);
+
+        let inserted = match self.configurations.get(&gateway_ref) {
+            Some(current) if current.yaml == yaml => {
+                return Ok(InsertedConfiguration::Unchanged {
+                    version: current.version,
+                });
+            }
+            Some(current) => {
+                let version = self.next_version.fetch_add(1, Ordering::Relaxed);
+                match diff(&current.configuration, &configuration) {
+                    Some(changes) => InsertedConfiguration::Delta {
+                        base_version: current.version,
+                        version,
+                        changes,
+                    },
+                    None => InsertedConfiguration::Snapshot { version },
+                }
+            }
+            None => InsertedConfiguration::Snapshot {
+                version: self.next_version.fetch_add(1, Ordering::Relaxed),
+            },
+        };
+
+        let mut cbor = Vec::new();
+        write_encoded_configuration(ConfigurationEncoding::Cbor, &configuration, &mut cbor)?;
+
+        self.configurations.insert(
+            gateway_ref,
+            VersionedConfiguration {
+                version: inserted.version(),
+                yaml,
+                cbor: Bytes::from(cbor),
+                configuration,
+            },
+        );
+        self.rollouts.publish(Instant::now()).await;
+
+        Ok(inserted)
+    }
+
+    pub async fn remove(&self, gateway_ref: &ObjectRef) -> bool {
+        let removed = self.configurations.remove(gateway_ref).is_some();
+        if removed {
+            self.rollouts.reports.remove(gateway_ref);
+            self.rollouts.publish(Instant::now()).await;
+        }
+        removed
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PodReport {
+    state: PodConfigurationState,
+    reported_at: Instant,
+}
+
+/// Tracks the configuration versions gateway pods report, and publishes how far each
+/// gateway got in rolling out its latest version
+#[derive(Debug, Clone)]
+pub struct GatewayConfigurationRollouts {
+    configurations: Arc<DashMap<ObjectRef, VersionedConfiguration>>,
+    reports: Arc<DashMap<ObjectRef, HashMap<String, PodReport>>>,
+    report_ttl: Duration,
+    rollouts_tx: Sender<HashMap<ObjectRef, ConfigurationRollout>>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReportConfigurationStatusError {
+    #[error("No configuration for gateway {0}")]
+    UnknownGateway(ObjectRef),
+    #[error("Version {0} was never issued, the latest is {1}")]
+    UnknownVersion(u64, u64),
+}
+
+impl GatewayConfigurationRollouts {
+    pub async fn report(
+        &self,
+        gateway_ref: &ObjectRef,
+        report: &ConfigurationStatusReport,
+        now: Instant,
+    ) -> Result<(), ReportConfigurationStatusError> {
+        let latest_version = self
+            .configurations
+            .get(gateway_ref)
+            .map(|configuration| configuration.version)
+            .ok_or_else(|| ReportConfigurationStatusError::UnknownGateway(gateway_ref.clone()))?;
+        if report.version() > latest_version {
+            return Err(ReportConfigurationStatusError::UnknownVersion(
+                report.version(),
+                latest_version,
+            ));
+        }
+
+        {
+            let mut pods = self.reports.entry(gateway_ref.clone()).or_default();
+            let pod = pods
+                .entry(report.pod_name().clone())
+                .or_insert_with(|| PodReport {
+                    state: PodConfigurationState::default(),
+                    reported_at: now,
+                });
+            pod.state.apply(report);
+            pod.reported_at = now;
+        }
+
+        self.publish(now).await;
         Ok(())
     }
 
-    pub fn remove(&self, gateway_ref: &ObjectRef) -> bool {
-        self.configurations.remove(gateway_ref).is_some()
+    /// Publishes the rollout of every gateway, forgetting pods that stopped reporting
+    async fn publish(&self, now: Instant) {
+        self.reports.retain(|_, pods| {
+            pods.retain(|_, pod| now.duration_since(pod.reported_at) < self.report_ttl);
+            !pods.is_empty()
+        });
+
+        let rollouts = self
+            .configurations
+            .iter()
+            .map(|configuration| {
+                let pods: BTreeMap<_, _> = self
+                    .reports
+                    .get(configuration.key())
+                    .map(|pods| {
+                        pods.iter()
+                            .map(|(pod_name, pod)| (pod_name.clone(), pod.state.clone()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let rollout = ConfigurationRollout::builder()
+                    .version(configuration.version)
+                    .pods(pods)
+                    .build();
+                (configuration.key().clone(), rollout)
+            })
+            .collect();
+
+        self.rollouts_tx.set(rollouts).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_api::apis::standard::gateways::Gateway;
+    use std::net::{IpAddr, Ipv4Addr};
+    use vg_core::config::gateway::types::GatewayConfigurationBuilder;
+    use vg_core::ipc::configuration::{ConfigurationDrift, ConfigurationStatus};
+    use vg_core::net::Port;
+
+    fn gateway_ref() -> ObjectRef {
+        ObjectRef::of_kind::<Gateway>()
+            .namespace(Some("default".to_string()))
+            .name("gateway")
+            .build()
+    }
+
+    fn configuration(ipc_port: u16) -> GatewayConfiguration {
+        GatewayConfigurationBuilder::default()
+            .with_ipc(|ipc| {
+                ipc.with_endpoint(IpAddr::V4(Ipv4Addr::LOCALHOST), Port::new(ipc_port));
+            })
+            .build()
+            .expect("Failed to build configuration")
+    }
+
+    fn report(
+        pod_name: &str,
+        version: u64,
+        status: ConfigurationStatus,
+    ) -> ConfigurationStatusReport {
+        ConfigurationStatusReport::builder()
+            .pod_name(pod_name)
+            .version(version)
+            .status(status)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_versions_only_change_with_configuration() {
+        let (reader, manager, _, _) =
+            create_gateway_configuration_services(Duration::from_secs(60));
+
+        let first = manager
+            .try_insert(gateway_ref(), configuration(8081))
+            .await
+            .expect("insert");
+        assert!(matches!(first, InsertedConfiguration::Snapshot { .. }));
+        let second = manager
+            .try_insert(gateway_ref(), configuration(8081))
+            .await
+            .expect("insert");
+        assert_eq!(
+            second,
+            InsertedConfiguration::Unchanged {
+                version: first.version()
+            }
+        );
+
+        // Changes outside of routes cannot be sent as a delta
+        let third = manager
+            .try_insert(gateway_ref(), configuration(8082))
+            .await
+            .expect("insert");
+        assert!(matches!(
+            third,
+            InsertedConfiguration::Snapshot { version } if version > first.version()
+        ));
+        assert_eq!(
+            reader
+                .get_configuration(&gateway_ref())
+                .map(|c| c.version()),
+            Some(third.version())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_changes_inserted_as_delta() {
+        let (_, manager, _, _) = create_gateway_configuration_services(Duration::from_secs(60));
+        let with_route = |address: Ipv4Addr| {
+            let mut builder = GatewayConfigurationBuilder::default();
+            builder.add_http_route(|route| {
+                route.with_key("default/echo").add_rule("echo-0", |rule| {
+                    rule.add_backend(|backend| {
+                        backend
+                            .named("echo")
+                            .add_endpoint(IpAddr::V4(address), |_| {});
+                    });
+                });
+            });
+            builder.build().expect("Failed to build configuration")
+        };
+
+        let first = manager
+            .try_insert(gateway_ref(), with_route(Ipv4Addr::new(10, 0, 0, 1)))
+            .await
+            .expect("insert");
+        let second = manager
+            .try_insert(gateway_ref(), with_route(Ipv4Addr::new(10, 0, 0, 2)))
+            .await
+            .expect("insert");
+
+        match second {
+            InsertedConfiguration::Delta {
+                base_version,
+                changes,
+                ..
+            } => {
+                assert_eq!(base_version, first.version());
+                assert!(matches!(
+                    changes.as_slice(),
+                    [ConfigurationChange::SetBackendEndpoints { .. }]
+                ));
+            }
+            other => panic!("Expected a delta, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_track_pod_reports() {
+        let (_, manager, rollouts, rollouts_rx) =
+            create_gateway_configuration_services(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert_eq!(
+            rollouts
+                .report(
+                    &gateway_ref(),
+                    &report("gateway-a", 1, ConfigurationStatus::Applied),
+                    now
+                )
+                .await,
+            Err(ReportConfigurationStatusError::UnknownGateway(gateway_ref()))
+        );
+
+        let version = manager
+            .try_insert(gateway_ref(), configuration(8081))
+            .await
+            .expect("insert")
+            .version();
+        assert_eq!(
+            rollouts
+                .report(
+                    &gateway_ref(),
+                    &report("gateway-a", version + 1, ConfigurationStatus::Applied),
+                    now
+                )
+                .await,
+            Err(ReportConfigurationStatusError::UnknownVersion(
+                version + 1,
+                version
+            ))
+        );
+
+        rollouts
+            .report(
+                &gateway_ref(),
+                &report("gateway-a", version, ConfigurationStatus::Applied),
+                now,
+            )
+            .await
+            .expect("report");
+        let rejected = ConfigurationStatus::Rejected {
+            error: "invalid listener".to_string(),
+        };
+        rollouts
+            .report(&gateway_ref(), &report("gateway-b", version, rejected), now)
+            .await
+            .expect("report");
+
+        let rollout = rollouts_rx
+            .get()
+            .await
+            .as_ref()
+            .and_then(|rollouts| rollouts.get(&gateway_ref()).cloned())
+            .expect("rollout");
+        assert_eq!(rollout.version(), version);
+        assert_eq!(
+            rollout.drifted_pods().collect::<Vec<_>>(),
+            vec![(&"gateway-b".to_string(), ConfigurationDrift::Rejected)]
+        );
+
+        rollouts.publish(now + Duration::from_secs(61)).await;
+        let rollout = rollouts_rx
+            .get()
+            .await
+            .as_ref()
+            .and_then(|rollouts| rollouts.get(&gateway_ref()).cloned())
+            .expect("rollout");
+        assert!(rollout.pods().is_empty());
     }
 }
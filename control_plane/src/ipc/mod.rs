@@ -1,24 +1,37 @@
+pub mod auth;
 pub mod endpoints;
 pub mod events;
 mod gateways;
+pub mod tls;
 
-use crate::controllers::StaticResponsesCache;
+use crate::controllers::{
+    ClientCertificatesCache, HtpasswdCache, JwksCache, StaticResponsesCache,
+};
+use crate::ipc::auth::IpcAuthentication;
 use crate::ipc::endpoints::{
     SpawnIpcEndpointError, SpawnIpcEndpointParameters, spawn_ipc_endpoint,
 };
 use crate::ipc::events::EventSender;
 use crate::ipc::gateways::{
-    GatewayConfigurationManager, GatewayConfigurationManagerInsertError,
+    GatewayConfigurationManager, GatewayConfigurationManagerInsertError, InsertedConfiguration,
     create_gateway_configuration_services,
 };
+use crate::ipc::tls::{IpcCertificateAuthority, IpcTls, IpcTlsError};
 use crate::kubernetes::KubeClientCell;
 use crate::kubernetes::objects::ObjectRef;
 use crate::options::Options;
 use getset::{CopyGetters, Getters};
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::Arc;
 use thiserror::Error;
+use tracing::warn;
 use typed_builder::TypedBuilder;
 use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::continue_after;
+use vg_core::ipc::cache::CachePurge;
+use vg_core::ipc::configuration::ConfigurationRollout;
+use vg_core::ipc::delta::ConfigurationDelta;
 use vg_core::ipc::{Event, GatewayEvent, Ref as IpcRef};
 use vg_core::net::Port;
 use vg_core::sync::signal::Receiver;
@@ -28,8 +41,13 @@ use vg_core::task::Builder as TaskBuilder;
 pub struct IpcServices {
     events: EventSender,
     gateway_configuration_manager: GatewayConfigurationManager,
+    #[getset(get = "pub")]
+    configuration_rollouts_rx: Receiver<HashMap<ObjectRef, ConfigurationRollout>>,
     #[getset(get_copy = "pub")]
     port: Port,
+    /// The CA gateways pin to verify the IPC endpoint, when it serves TLS
+    #[getset(get = "pub")]
+    ca_certificate: Option<String>,
 }
 
 #[derive(Debug, Error)]
@@ -74,41 +92,95 @@ pub enum IpcInsertGatewayConfigurationError {
 }
 
 impl IpcServices {
-    pub fn try_insert_gateway_configuration(
+    /// Stores the configuration of a gateway and notifies gateways, returning its version
+    pub async fn try_insert_gateway_configuration(
         &self,
         gateway_ref: ObjectRef,
         configuration: GatewayConfiguration,
-    ) -> Result<(), IpcInsertGatewayConfigurationError> {
-        self.gateway_configuration_manager
-            .try_insert(gateway_ref.clone(), configuration)?;
+    ) -> Result<u64, IpcInsertGatewayConfigurationError> {
+        let inserted = self
+            .gateway_configuration_manager
+            .try_insert(gateway_ref.clone(), configuration)
+            .await?;
+        let version = inserted.version();
 
         let gateway_ref: IpcRef = gateway_ref.try_into()?;
 
-        self.events
-            .send(Event::Gateway(GatewayEvent::ConfigurationUpdate(
-                gateway_ref,
-            )));
+        // Gateways already at the latest version only acknowledge an empty delta again,
+        // while those behind it fetch the full configuration
+        let event = match inserted {
+            InsertedConfiguration::Unchanged { version } => GatewayEvent::ConfigurationDelta(
+                ConfigurationDelta::builder()
+                    .gateway_ref(gateway_ref)
+                    .base_version(version)
+                    .version(version)
+                    .build(),
+            ),
+            InsertedConfiguration::Delta {
+                base_version,
+                version,
+                changes,
+            } => GatewayEvent::ConfigurationDelta(
+                ConfigurationDelta::builder()
+                    .gateway_ref(gateway_ref)
+                    .base_version(base_version)
+                    .version(version)
+                    .changes(changes)
+                    .build(),
+            ),
+            InsertedConfiguration::Snapshot { .. } => {
+                GatewayEvent::ConfigurationUpdate(gateway_ref)
+            }
+        };
+        self.events.send(Event::Gateway(event));
 
-        Ok(())
+        Ok(version)
     }
 
-    pub fn remove_gateway_configuration(&self, gateway_ref: &ObjectRef) {
-        if self.gateway_configuration_manager.remove(gateway_ref)
+    pub async fn remove_gateway_configuration(&self, gateway_ref: &ObjectRef) {
+        if self.gateway_configuration_manager.remove(gateway_ref).await
             && let Ok(gateway_ref) = gateway_ref.try_into()
         {
             self.events
                 .send(Event::Gateway(GatewayEvent::Deleted(gateway_ref)));
         }
     }
+
+    /// Asks the pods of a gateway watching its events to drop their cached responses, either for
+    /// one hostname or all of them
+    pub fn purge_gateway_cache(
+        &self,
+        gateway_ref: &ObjectRef,
+        hostname: Option<String>,
+    ) -> Result<(), TryFromObjectRefError> {
+        let purge = CachePurge::builder()
+            .gateway_ref(gateway_ref.try_into()?)
+            .hostname(hostname)
+            .build();
+        self.events
+            .send(Event::Gateway(GatewayEvent::CachePurge(purge)));
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, TypedBuilder)]
 pub struct SpawnIpcParameters {
     #[builder(setter(into))]
     port: Port,
+    authentication: IpcAuthentication,
+    tls: IpcTls,
+    #[builder(setter(into))]
+    pod_namespace: String,
+    pod_ip: Option<IpAddr>,
+    #[builder(setter(into))]
+    instance_name: String,
     kube_client_rx: Receiver<KubeClientCell>,
     options: Arc<Options>,
     static_responses_cache: StaticResponsesCache,
+    jwks_cache: JwksCache,
+    htpasswd_cache: HtpasswdCache,
+    client_certificates_cache: ClientCertificatesCache,
 }
 
 #[derive(Debug, Error)]
@@ -119,6 +191,12 @@ pub enum SpawnIpcError {
     Services,
     #[error("Failed to spawn IPC endpoint")]
     SpawnEndpoint(#[from] SpawnIpcEndpointError),
+    #[error("The pod IP is required to issue the IPC certificate")]
+    MissingPodIp,
+    #[error("Failed to load the IPC CA")]
+    MissingCertificateAuthority,
+    #[error("Failed to configure IPC TLS: {0}")]
+    Tls(#[from] IpcTlsError),
 }
 
 pub async fn spawn_ipc(
@@ -126,15 +204,47 @@ pub async fn spawn_ipc(
     params: SpawnIpcParameters,
 ) -> Result<IpcServices, SpawnIpcError> {
     let (event_sender, events_factory) = events::events_channel();
-    let (reader, gateway_manager) = create_gateway_configuration_services();
+    let (reader, gateway_manager, rollouts, rollouts_rx) =
+        create_gateway_configuration_services(params.options.ipc_configuration_report_ttl());
+
+    let (tls_config, ca_certificate) = match params.tls {
+        IpcTls::ControlPlaneCa => {
+            let pod_ip = params.pod_ip.ok_or(SpawnIpcError::MissingPodIp)?;
+            let authority = load_certificate_authority(
+                &params.options,
+                &params.kube_client_rx,
+                &params.pod_namespace,
+                &format!("{}-ipc-ca", params.instance_name),
+            )
+            .await
+            .ok_or(SpawnIpcError::MissingCertificateAuthority)?;
+            let tls_config = authority.server_config(pod_ip)?;
+
+            (
+                Some(Arc::new(tls_config)),
+                Some(authority.certificate_pem().clone()),
+            )
+        }
+        IpcTls::Disabled => {
+            warn!("IPC TLS is disabled, gateway configurations and secrets are sent in plain text");
+            (None, None)
+        }
+    };
 
     let ipc_endpoint_params = SpawnIpcEndpointParameters::builder()
         .options(params.options)
         .port(params.port)
+        .authentication(params.authentication)
+        .tls_config(tls_config)
         .events(events_factory)
+        .event_sender(event_sender.clone())
         .gateways(reader)
+        .configuration_rollouts(rollouts)
         .kube_client_rx(params.kube_client_rx)
         .static_responses_cache(params.static_responses_cache)
+        .jwks_cache(params.jwks_cache)
+        .htpasswd_cache(params.htpasswd_cache)
+        .client_certificates_cache(params.client_certificates_cache)
         .build();
 
     spawn_ipc_endpoint(task_builder, ipc_endpoint_params).await?;
@@ -142,8 +252,37 @@ pub async fn spawn_ipc(
     let ipc_services = IpcServices::builder()
         .events(event_sender)
         .gateway_configuration_manager(gateway_manager)
+        .configuration_rollouts_rx(rollouts_rx)
         .port(params.port)
+        .ca_certificate(ca_certificate)
         .build();
 
     Ok(ipc_services)
 }
+
+/// Waits for the Kubernetes API to read or create the CA, as the IPC endpoint cannot be served
+/// without it
+async fn load_certificate_authority(
+    options: &Options,
+    kube_client_rx: &Receiver<KubeClientCell>,
+    namespace: &str,
+    name: &str,
+) -> Option<IpcCertificateAuthority> {
+    loop {
+        let kube_client = kube_client_rx.get().await.as_deref().cloned();
+        if let Some(kube_client) = kube_client {
+            match IpcCertificateAuthority::load_or_create(kube_client.into(), namespace, name).await
+            {
+                Ok(authority) => return Some(authority),
+                Err(e) => warn!("Failed to load IPC CA {}/{}: {}", namespace, name, e),
+            }
+        }
+
+        continue_after!(
+            options.controller_error_requeue_duration(),
+            kube_client_rx.changed()
+        );
+    }
+
+    None
+}
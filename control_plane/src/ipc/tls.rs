@@ -0,0 +1,222 @@
+use clap::ValueEnum;
+use getset::Getters;
+use k8s_openapi::ByteString;
+use k8s_openapi::api::core::v1::Secret;
+use kube::api::{ObjectMeta, PostParams};
+use kube::{Api, Client};
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+    KeyUsagePurpose, SanType,
+};
+use rustls::ServerConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use thiserror::Error;
+use tracing::info;
+
+/// The keys of the Secret holding the IPC certificate authority
+const CA_CERTIFICATE_KEY: &str = "ca.crt";
+const CA_KEY_KEY: &str = "ca.key";
+
+/// How the IPC endpoint protects the configuration and secret material it serves on the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IpcTls {
+    /// TLS with certificates issued by a CA the control plane keeps in a Secret, which gateways
+    /// receive in their configuration and pin
+    ControlPlaneCa,
+    /// Plain HTTP, for development outside of a cluster
+    Disabled,
+}
+
+#[derive(Debug, Error)]
+pub enum IpcTlsError {
+    #[error("Error reading or creating IPC CA secret: {0}")]
+    Secret(#[from] kube::Error),
+    #[error("IPC CA secret {0} is missing its certificate or key")]
+    IncompleteSecret(String),
+    #[error("Error issuing IPC certificate: {0}")]
+    Certificate(#[from] rcgen::Error),
+    #[error("Error building IPC TLS configuration: {0}")]
+    Configuration(#[from] rustls::Error),
+}
+
+/// The CA the control plane issues its IPC serving certificates from. All instances share it
+/// through a Secret, so gateways keep trusting the endpoint when another instance becomes primary
+#[derive(Debug, Clone, Getters)]
+pub struct IpcCertificateAuthority {
+    #[getset(get = "pub")]
+    certificate_pem: String,
+    key_pem: String,
+}
+
+impl IpcCertificateAuthority {
+    pub fn generate() -> Result<Self, IpcTlsError> {
+        let key = KeyPair::generate()?;
+        let mut params = CertificateParams::new(Vec::<String>::new())?;
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Vale Gateway IPC CA");
+        params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let certificate = params.self_signed(&key)?;
+
+        Ok(Self {
+            certificate_pem: certificate.pem(),
+            key_pem: key.serialize_pem(),
+        })
+    }
+
+    /// Reads the CA from its Secret, creating it when no instance has yet
+    pub async fn load_or_create(
+        kube_client: Client,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Self, IpcTlsError> {
+        let api = Api::<Secret>::namespaced(kube_client, namespace);
+
+        if let Some(secret) = api.get_opt(name).await? {
+            return Self::from_secret(name, &secret);
+        }
+
+        let authority = Self::generate()?;
+        match api
+            .create(&PostParams::default(), &authority.to_secret(name))
+            .await
+        {
+            Ok(_) => {
+                info!("Created IPC CA secret {}/{}", namespace, name);
+                Ok(authority)
+            }
+            // Another instance created it first
+            Err(kube::Error::Api(api_error)) if api_error.code == 409 => {
+                Self::from_secret(name, &api.get(name).await?)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn from_secret(name: &str, secret: &Secret) -> Result<Self, IpcTlsError> {
+        let value = |key| {
+            secret
+                .data
+                .as_ref()
+                .and_then(|data| data.get(key))
+                .and_then(|value| String::from_utf8(value.0.clone()).ok())
+                .ok_or_else(|| IpcTlsError::IncompleteSecret(name.to_string()))
+        };
+
+        Ok(Self {
+            certificate_pem: value(CA_CERTIFICATE_KEY)?,
+            key_pem: value(CA_KEY_KEY)?,
+        })
+    }
+
+    fn to_secret(&self, name: &str) -> Secret {
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            data: Some(BTreeMap::from([
+                (
+                    CA_CERTIFICATE_KEY.to_string(),
+                    ByteString(self.certificate_pem.clone().into_bytes()),
+                ),
+                (
+                    CA_KEY_KEY.to_string(),
+                    ByteString(self.key_pem.clone().into_bytes()),
+                ),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    /// Builds the TLS configuration of the IPC endpoint, with a serving certificate for the
+    /// address gateways reach this instance on
+    pub fn server_config(&self, ip_addr: IpAddr) -> Result<ServerConfig, IpcTlsError> {
+        let (certificate, key) = self.issue_server_certificate(ip_addr)?;
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![certificate], key)?;
+
+        Ok(config)
+    }
+
+    fn issue_server_certificate(
+        &self,
+        ip_addr: IpAddr,
+    ) -> Result<(CertificateDer<'static>, PrivateKeyDer<'static>), IpcTlsError> {
+        let ca_key = KeyPair::from_pem(&self.key_pem)?;
+        let ca_certificate =
+            CertificateParams::from_ca_cert_pem(&self.certificate_pem)?.self_signed(&ca_key)?;
+
+        let key = KeyPair::generate()?;
+        let mut params = CertificateParams::new(Vec::<String>::new())?;
+        params
+            .distinguished_name
+            .push(DnType::CommonName, ip_addr.to_string());
+        params.subject_alt_names = vec![SanType::IpAddress(ip_addr)];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let certificate = params.signed_by(&key, &ca_certificate, &ca_key)?;
+
+        Ok((
+            certificate.der().clone(),
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der())),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustls::RootCertStore;
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::client::danger::ServerCertVerifier;
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{ServerName, UnixTime};
+
+    #[test]
+    fn test_server_certificate_is_issued_by_ca() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let ip_addr = IpAddr::from([10, 0, 0, 1]);
+        // Instances other than the one generating the CA read it back from the Secret
+        let authority = IpcCertificateAuthority::generate().expect("Failed to generate CA");
+        let authority =
+            IpcCertificateAuthority::from_secret("ipc-ca", &authority.to_secret("ipc-ca"))
+                .expect("Failed to read CA");
+
+        let (certificate, _) = authority
+            .issue_server_certificate(ip_addr)
+            .expect("Failed to issue certificate");
+
+        let mut roots = RootCertStore::empty();
+        let ca = CertificateDer::from_pem_slice(authority.certificate_pem().as_bytes())
+            .expect("Failed to parse CA");
+        roots.add(ca).expect("Failed to add CA");
+        let verifier = WebPkiServerVerifier::builder(roots.into())
+            .build()
+            .expect("Failed to build verifier");
+        let verify = |ip_addr: IpAddr| {
+            verifier.verify_server_cert(
+                &certificate,
+                &[],
+                &ServerName::IpAddress(ip_addr.into()),
+                &[],
+                UnixTime::now(),
+            )
+        };
+
+        assert!(verify(ip_addr).is_ok());
+        assert!(verify(IpAddr::from([10, 0, 0, 2])).is_err());
+        assert!(authority.server_config(ip_addr).is_ok());
+    }
+
+    #[test]
+    fn test_incomplete_secret() {
+        assert!(matches!(
+            IpcCertificateAuthority::from_secret("ipc-ca", &Secret::default()),
+            Err(IpcTlsError::IncompleteSecret(_))
+        ));
+    }
+}
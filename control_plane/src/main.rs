@@ -24,7 +24,10 @@ pub mod ipc;
 pub mod kubernetes;
 mod options;
 
-use crate::controllers::{SpawnControllersParams, StaticResponsesCache, spawn_controllers};
+use crate::controllers::{
+    ClientCertificatesCache, HtpasswdCache, JwksCache, SpawnControllersParams,
+    StaticResponsesCache, spawn_controllers,
+};
 use crate::ipc::{SpawnIpcError, SpawnIpcParameters, spawn_ipc};
 use crate::kubernetes::start_kubernetes_client;
 use crate::options::Options;
@@ -55,18 +58,29 @@ async fn main() -> Result<(), MainError> {
     let options = Arc::new(Options::default());
 
     init_crypto();
-    init_instrumentation(&task_builder, "vg-control-plane");
+    init_instrumentation(&task_builder, "vg-control-plane", args.prometheus_enabled());
 
     let kube_client_rx = start_kubernetes_client(&task_builder);
     let static_responses_cache = StaticResponsesCache::default();
+    let jwks_cache = JwksCache::default();
+    let htpasswd_cache = HtpasswdCache::default();
+    let client_certificates_cache = ClientCertificatesCache::default();
 
     // IPC is half 1 - it is what the gateway use to ensure that they have the latest configuration
     let ipc_services = {
         let params = SpawnIpcParameters::builder()
             .options(options.clone())
             .port(args.port())
+            .authentication(args.ipc_authentication())
+            .tls(args.ipc_tls())
+            .pod_namespace(args.pod_namespace())
+            .pod_ip(args.pod_ip())
+            .instance_name(args.instance_name())
             .kube_client_rx(kube_client_rx.clone())
             .static_responses_cache(static_responses_cache.clone())
+            .jwks_cache(jwks_cache.clone())
+            .htpasswd_cache(htpasswd_cache.clone())
+            .client_certificates_cache(client_certificates_cache.clone())
             .build();
 
         spawn_ipc(&task_builder, params)
@@ -85,6 +99,9 @@ async fn main() -> Result<(), MainError> {
             .pod_name(args.pod_name())
             .instance_name(args.instance_name())
             .static_responses_cache(static_responses_cache)
+            .jwks_cache(jwks_cache)
+            .htpasswd_cache(htpasswd_cache)
+            .client_certificates_cache(client_certificates_cache)
             .build();
 
         spawn_controllers(&task_builder, params);
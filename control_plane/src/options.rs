@@ -20,6 +20,12 @@ pub struct Options {
 
     #[getset(get_copy = "pub")]
     ipc_sse_keep_alive_interval: Duration,
+
+    #[getset(get_copy = "pub")]
+    ipc_token_review_cache_duration: Duration,
+
+    #[getset(get_copy = "pub")]
+    ipc_configuration_report_ttl: Duration,
 }
 
 impl Default for Options {
@@ -31,6 +37,8 @@ impl Default for Options {
             controller_requeue_duration: Duration::from_secs(60),
             controller_error_requeue_duration: Duration::from_secs(5),
             ipc_sse_keep_alive_interval: Duration::from_secs(15),
+            ipc_token_review_cache_duration: Duration::from_secs(60),
+            ipc_configuration_report_ttl: Duration::from_secs(60),
         }
     }
 }
@@ -0,0 +1,94 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use std::net::{IpAddr, Ipv4Addr};
+use vg_core::config::gateway::serde::{
+    ConfigurationEncoding, read_encoded_configuration, write_encoded_configuration,
+};
+use vg_core::config::gateway::types::{GatewayConfiguration, GatewayConfigurationBuilder};
+use vg_core::net::Port;
+
+const ENCODINGS: [ConfigurationEncoding; 2] =
+    [ConfigurationEncoding::Yaml, ConfigurationEncoding::Cbor];
+
+/// A gateway with as many routes as a configuration allows, each with large endpoint lists
+fn large_configuration() -> GatewayConfiguration {
+    let mut builder = GatewayConfigurationBuilder::default();
+    for route in 0..64u8 {
+        builder.add_http_route(|target| {
+            target
+                .with_key(format!("default/route-{route}"))
+                .add_exact_host_header(format!("route-{route}.example.com"));
+            for rule in 0..4u8 {
+                target.add_rule(format!("route-{route}:{rule}"), |target| {
+                    target.add_match(|target| {
+                        target.with_path_prefix(format!("/rule-{rule}"));
+                    });
+                    for backend in 0..2u8 {
+                        target.add_backend(|target| {
+                            target
+                                .named(format!("backend-{backend}"))
+                                .with_namespace(Some("default"))
+                                .with_port(Some(Port::new(8080)))
+                                .with_weight(Some(1));
+                            for endpoint in 0..50u8 {
+                                let address = Ipv4Addr::new(10, route, backend, endpoint);
+                                target.add_endpoint(IpAddr::V4(address), |target| {
+                                    target.with_zone(format!("zone-{}", endpoint % 3));
+                                });
+                            }
+                        });
+                    }
+                });
+            }
+        });
+    }
+    builder.build().expect("Failed to build configuration")
+}
+
+fn encode(encoding: ConfigurationEncoding, configuration: &GatewayConfiguration) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    write_encoded_configuration(encoding, configuration, &mut buffer)
+        .expect("Failed to write configuration");
+    buffer
+}
+
+fn benchmark_read_configuration(c: &mut Criterion) {
+    let configuration = large_configuration();
+    let mut group = c.benchmark_group("read_configuration");
+
+    for encoding in ENCODINGS {
+        let buffer = encode(encoding, &configuration);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(encoding),
+            &buffer,
+            |b, buffer| {
+                b.iter(|| read_encoded_configuration(encoding, black_box(buffer.as_slice())));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn benchmark_write_configuration(c: &mut Criterion) {
+    let configuration = large_configuration();
+    let mut group = c.benchmark_group("write_configuration");
+
+    for encoding in ENCODINGS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(encoding),
+            &configuration,
+            |b, configuration| {
+                b.iter(|| encode(encoding, black_box(configuration)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_read_configuration,
+    benchmark_write_configuration
+);
+criterion_main!(benches);
@@ -1,15 +1,80 @@
 use crate::config::gateway::types::GatewayConfiguration;
-use serde_valid::Validate;
 use serde_valid::validation::{Error, Errors};
+use serde_valid::Validate;
 use std::fmt::Debug;
 use std::io::{Read, Write};
+use strum::{Display, EnumString};
 use thiserror::Error;
 use tracing::{debug, instrument, warn};
 
+/// How a configuration is encoded, YAML for ConfigMaps, files and debugging, CBOR where parsing
+/// large configurations has to be cheap
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum ConfigurationEncoding {
+    #[default]
+    Yaml,
+    Cbor,
+}
+
+impl ConfigurationEncoding {
+    pub fn media_type(self) -> &'static str {
+        match self {
+            ConfigurationEncoding::Yaml => "application/yaml",
+            ConfigurationEncoding::Cbor => "application/cbor",
+        }
+    }
+
+    /// The encoding of a `Content-Type`, ignoring its parameters
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        [ConfigurationEncoding::Yaml, ConfigurationEncoding::Cbor]
+            .into_iter()
+            .find(|encoding| essence.eq_ignore_ascii_case(encoding.media_type()))
+    }
+
+    /// The `Accept` header requesting this encoding, with YAML as the fallback
+    pub fn accept(self) -> String {
+        match self {
+            ConfigurationEncoding::Yaml => self.media_type().to_string(),
+            ConfigurationEncoding::Cbor => format!(
+                "{}, {};q=0.5",
+                self.media_type(),
+                ConfigurationEncoding::Yaml.media_type()
+            ),
+        }
+    }
+
+    /// The encoding preferred by an `Accept` header, YAML when it accepts none of them
+    pub fn negotiate(accept: &str) -> Self {
+        accept
+            .split(',')
+            .filter_map(|media_range| {
+                let mut parts = media_range.split(';');
+                let encoding = Self::from_media_type(parts.next().unwrap_or_default())?;
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((encoding, quality))
+            })
+            // The first of the most preferred encodings
+            .reduce(|best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .map(|(encoding, _)| encoding)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum ReadError {
-    #[error("Failed to read configuration")]
-    Error,
+    #[error("Failed to read configuration: {0}")]
+    Error(String),
 
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(#[from] Errors<Error>),
@@ -17,9 +82,24 @@ pub enum ReadError {
 
 #[instrument(skip(reader), level = "debug")]
 pub fn read_configuration(reader: impl Read) -> Result<GatewayConfiguration, ReadError> {
-    let configuration = serde_yaml::from_reader::<_, GatewayConfiguration>(reader)
-        .inspect_err(|e| warn!("Failed to parse configuration: {}", e))
-        .map_err(|_| ReadError::Error)?;
+    read_encoded_configuration(ConfigurationEncoding::Yaml, reader)
+}
+
+#[instrument(skip(reader), level = "debug")]
+pub fn read_encoded_configuration(
+    encoding: ConfigurationEncoding,
+    reader: impl Read,
+) -> Result<GatewayConfiguration, ReadError> {
+    let configuration = match encoding {
+        ConfigurationEncoding::Yaml => {
+            serde_yaml::from_reader::<_, GatewayConfiguration>(reader).map_err(|e| e.to_string())
+        }
+        ConfigurationEncoding::Cbor => {
+            ciborium::from_reader::<GatewayConfiguration, _>(reader).map_err(|e| e.to_string())
+        }
+    }
+    .inspect_err(|e| warn!("Failed to parse configuration: {}", e))
+    .map_err(ReadError::Error)?;
 
     configuration
         .validate()
@@ -42,17 +122,37 @@ pub enum WriteError {
 pub fn write_configuration<W: Write>(
     config: &GatewayConfiguration,
     writer: &mut W,
+) -> Result<(), WriteError> {
+    write_encoded_configuration(ConfigurationEncoding::Yaml, config, writer)
+}
+
+#[instrument(skip(config, writer))]
+pub fn write_encoded_configuration<W: Write>(
+    encoding: ConfigurationEncoding,
+    config: &GatewayConfiguration,
+    writer: &mut W,
 ) -> Result<(), WriteError> {
     config
         .validate()
         .map_err(WriteError::InvalidConfiguration)?;
 
-    serde_yaml::to_writer(writer, &config).map_err(|_| WriteError::Error)
+    match encoding {
+        ConfigurationEncoding::Yaml => {
+            serde_yaml::to_writer(writer, &config).map_err(|_| WriteError::Error)
+        }
+        ConfigurationEncoding::Cbor => {
+            ciborium::into_writer(&config, writer).map_err(|_| WriteError::Error)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::gateway::types::net::{
+        AccessLogFormat, AccessLogSink, CompressionAlgorithm, CorsOrigin, ExternalAuthFailureMode,
+        RateLimitFilterKey, RateLimitMode, RequestBodyMode, StatusClass,
+    };
     use crate::config::gateway::types::GatewayConfigurationVersion;
     use assertables::{assert_ok, assert_ok_eq};
 
@@ -92,6 +192,220 @@ mod tests {
         assert_eq!(round_trip_config, config);
     }
 
+    #[test]
+    fn round_trip_access_logs() {
+        let yaml = include_str!("tests/access_logs.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        assert_eq!(
+            config.http_routes()[0].key().as_deref(),
+            Some("default/echo-route")
+        );
+        let access_logs = config.access_logs().as_ref().expect("access logs");
+        assert_eq!(
+            *access_logs.format(),
+            AccessLogFormat::Template("${method} ${path} ${status}".to_string())
+        );
+        let AccessLogSink::File(file) = access_logs.sink() else {
+            panic!("access logs should be written to a file");
+        };
+        assert_eq!(*file.max_files(), 5);
+        assert_eq!(
+            access_logs.status_classes(),
+            &vec![StatusClass::ClientError, StatusClass::ServerError]
+        );
+        assert_eq!(*access_logs.routes()[0].sample_ratio(), Some(1.0));
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_rate_limit() {
+        let yaml = include_str!("tests/rate_limit.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.rate_limit_filters()[0];
+        assert_eq!(
+            filter.limit_key(),
+            &RateLimitFilterKey::Header("x-api-key".to_string())
+        );
+        assert_eq!(*filter.period_seconds(), 60);
+        assert_eq!(*filter.burst(), Some(20));
+        assert_eq!(*filter.mode(), RateLimitMode::Global);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_external_auth() {
+        let yaml = include_str!("tests/external_auth.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.external_auth_filters()[0];
+        assert_eq!(filter.backend().endpoints().len(), 1);
+        assert_eq!(*filter.failure_mode(), ExternalAuthFailureMode::Open);
+        assert_eq!(*filter.timeout_milliseconds(), 1000);
+        let cache = filter.cache().as_ref().expect("cache");
+        assert_eq!(cache.key_header(), "authorization");
+        assert_eq!(*cache.ttl_seconds(), 60);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_basic_auth() {
+        let yaml = include_str!("tests/basic_auth.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.basic_auth_filters()[0];
+        assert_eq!(filter.version_key(), "48213");
+        assert!(*filter.strip_authorization());
+        assert_eq!(filter.username_header().as_deref(), Some("x-remote-user"));
+        assert_eq!(*filter.realm(), None);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_cors() {
+        let yaml = include_str!("tests/cors.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.cors_filters()[0];
+        assert_eq!(
+            filter.allow_origins(),
+            &vec![
+                CorsOrigin::Exact("https://app.example.com".to_string()),
+                CorsOrigin::Wildcard("https://*.example.com".to_string()),
+                CorsOrigin::Regex("https://preview-[0-9]+\\.example\\.dev".to_string()),
+            ]
+        );
+        assert!(*filter.allow_credentials());
+        assert_eq!(*filter.max_age_seconds(), Some(600));
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_compression() {
+        let yaml = include_str!("tests/compression.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.compression_filters()[0];
+        assert_eq!(
+            filter.algorithms(),
+            &vec![CompressionAlgorithm::Brotli, CompressionAlgorithm::Gzip]
+        );
+        assert_eq!(*filter.min_size_bytes(), 1024);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_cache() {
+        let yaml = include_str!("tests/cache.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.cache_filters()[0];
+        assert_eq!(*filter.max_object_size_bytes(), 1048576);
+        assert_eq!(*filter.stale_while_revalidate_seconds(), 30);
+        assert_eq!(*filter.stale_if_error_seconds(), 0);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_request_limits() {
+        let yaml = include_str!("tests/request_limits.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let filter = &config.request_limits_filters()[0];
+        assert_eq!(*filter.max_request_body_bytes(), Some(1048576));
+        assert_eq!(*filter.max_request_headers_bytes(), None);
+        assert_eq!(*filter.body_mode(), RequestBodyMode::Buffered);
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_configuration(&config, &mut buffer));
+
+        let round_trip_config = assert_ok!(read_configuration(buffer.as_slice()));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn round_trip_cbor() {
+        let yaml = include_str!("tests/config1.yaml").as_bytes();
+        let config = assert_ok!(read_configuration(yaml));
+
+        let mut buffer = Vec::new();
+        assert_ok!(write_encoded_configuration(
+            ConfigurationEncoding::Cbor,
+            &config,
+            &mut buffer
+        ));
+
+        let round_trip_config = assert_ok!(read_encoded_configuration(
+            ConfigurationEncoding::Cbor,
+            buffer.as_slice()
+        ));
+        assert_eq!(round_trip_config, config);
+    }
+
+    #[test]
+    fn test_negotiate_encoding() {
+        assert_eq!(
+            ConfigurationEncoding::negotiate("application/cbor, application/yaml;q=0.5"),
+            ConfigurationEncoding::Cbor
+        );
+        assert_eq!(
+            ConfigurationEncoding::negotiate("application/yaml, application/cbor"),
+            ConfigurationEncoding::Yaml
+        );
+        assert_eq!(
+            ConfigurationEncoding::negotiate("application/yaml;q=0.1, application/cbor;q=0.9"),
+            ConfigurationEncoding::Cbor
+        );
+        assert_eq!(
+            ConfigurationEncoding::negotiate("application/cbor;q=0, */*"),
+            ConfigurationEncoding::Yaml
+        );
+        assert_eq!(
+            ConfigurationEncoding::negotiate(&ConfigurationEncoding::Cbor.accept()),
+            ConfigurationEncoding::Cbor
+        );
+        assert_eq!(
+            ConfigurationEncoding::from_media_type("application/CBOR; charset=binary"),
+            Some(ConfigurationEncoding::Cbor)
+        );
+    }
+
     // #[test]
     // fn test_write_configuration() {
     //     let config = GatewayConfiguration {
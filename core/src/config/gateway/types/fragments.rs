@@ -0,0 +1,292 @@
+use crate::config::gateway::types::http::router::HttpRoute;
+use crate::config::gateway::types::net::{
+    AccessControlFilter, AccessLogs, BasicAuthFilter, CacheFilter, ClientAddrs, CompressionFilter,
+    CorsFilter, ErrorResponses, ExternalAuthFilter, JwtAuthFilter, Listener, RateLimitFilter,
+    RequestLimitsFilter, StaticResponse, StaticResponses,
+};
+use crate::config::gateway::types::{
+    GatewayConfiguration, GatewayConfigurationVersion, IpcConfiguration,
+};
+use serde::Deserialize;
+use serde_valid::Validate;
+use serde_valid::validation::{Error, Errors};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io::Read;
+use thiserror::Error;
+
+/// A part of a gateway configuration, such as the listeners or a few routes, kept in one file of
+/// a configuration directory. A complete configuration is a valid fragment too
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct GatewayConfigurationFragment {
+    #[serde(default)]
+    version: Option<GatewayConfigurationVersion>,
+    #[serde(default)]
+    ipc: Option<IpcConfiguration>,
+    #[serde(default)]
+    listeners: Vec<Listener>,
+    #[serde(default)]
+    http_routes: Vec<HttpRoute>,
+    #[serde(default)]
+    client_addrs: Option<ClientAddrs>,
+    #[serde(default)]
+    error_responses: Option<ErrorResponses>,
+    #[serde(default)]
+    access_logs: Option<AccessLogs>,
+    #[serde(default)]
+    static_responses: Option<StaticResponses>,
+    #[serde(default)]
+    access_control_filters: Vec<AccessControlFilter>,
+    #[serde(default)]
+    rate_limit_filters: Vec<RateLimitFilter>,
+    #[serde(default)]
+    jwt_auth_filters: Vec<JwtAuthFilter>,
+    #[serde(default)]
+    external_auth_filters: Vec<ExternalAuthFilter>,
+    #[serde(default)]
+    basic_auth_filters: Vec<BasicAuthFilter>,
+    #[serde(default)]
+    cors_filters: Vec<CorsFilter>,
+    #[serde(default)]
+    compression_filters: Vec<CompressionFilter>,
+    #[serde(default)]
+    cache_filters: Vec<CacheFilter>,
+    #[serde(default)]
+    request_limits_filters: Vec<RequestLimitsFilter>,
+}
+
+/// Reads a YAML fragment, JSON being read as the YAML it is a subset of
+pub fn read_fragment(reader: impl Read) -> Result<GatewayConfigurationFragment, serde_yaml::Error> {
+    serde_yaml::from_reader(reader)
+}
+
+#[derive(Debug, Error)]
+pub enum MergeError {
+    #[error("{0} {1:?} is defined in both {2} and {3}")]
+    Conflict(&'static str, String, String, String),
+
+    #[error("Invalid merged configuration: {0}")]
+    InvalidConfiguration(#[from] Errors<Error>),
+}
+
+/// Merges fragments, named after where they come from, into one configuration. Anything named
+/// (listeners, routes, rules, static responses and filters) may only be defined once, and so may
+/// the settings that are not lists
+pub fn merge_fragments<S: AsRef<str>>(
+    fragments: impl IntoIterator<Item = (S, GatewayConfigurationFragment)>,
+) -> Result<GatewayConfiguration, MergeError> {
+    let mut merged = GatewayConfigurationFragment::default();
+    let mut static_responses: Vec<StaticResponse> = Vec::new();
+    let mut definitions = Definitions::default();
+
+    for (source, fragment) in fragments {
+        let source = source.as_ref();
+
+        if let Some(version) = fragment.version {
+            definitions.define("setting", "version", source)?;
+            merged.version = Some(version);
+        }
+        if let Some(ipc) = fragment.ipc {
+            definitions.define("setting", "ipc", source)?;
+            merged.ipc = Some(ipc);
+        }
+        if let Some(client_addrs) = fragment.client_addrs {
+            definitions.define("setting", "client_addrs", source)?;
+            merged.client_addrs = Some(client_addrs);
+        }
+        if let Some(error_responses) = fragment.error_responses {
+            definitions.define("setting", "error_responses", source)?;
+            merged.error_responses = Some(error_responses);
+        }
+        if let Some(access_logs) = fragment.access_logs {
+            definitions.define("setting", "access_logs", source)?;
+            merged.access_logs = Some(access_logs);
+        }
+
+        for listener in &fragment.listeners {
+            definitions.define("listener", listener.name(), source)?;
+        }
+        merged.listeners.extend(fragment.listeners);
+
+        for route in &fragment.http_routes {
+            if let Some(key) = route.key() {
+                definitions.define("HTTP route", key, source)?;
+            }
+            for rule in route.rules() {
+                definitions.define("HTTP route rule", rule.unique_id().get(), source)?;
+            }
+        }
+        merged.http_routes.extend(fragment.http_routes);
+
+        for response in fragment
+            .static_responses
+            .iter()
+            .flat_map(StaticResponses::responses)
+        {
+            definitions.define("static response", response.key(), source)?;
+            static_responses.push(response.clone());
+        }
+
+        for filter in &fragment.access_control_filters {
+            definitions.define("access control filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .access_control_filters
+            .extend(fragment.access_control_filters);
+
+        for filter in &fragment.rate_limit_filters {
+            definitions.define("rate limit filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .rate_limit_filters
+            .extend(fragment.rate_limit_filters);
+
+        for filter in &fragment.jwt_auth_filters {
+            definitions.define("JWT auth filter", filter.key().as_ref(), source)?;
+        }
+        merged.jwt_auth_filters.extend(fragment.jwt_auth_filters);
+
+        for filter in &fragment.external_auth_filters {
+            definitions.define("external auth filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .external_auth_filters
+            .extend(fragment.external_auth_filters);
+
+        for filter in &fragment.basic_auth_filters {
+            definitions.define("basic auth filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .basic_auth_filters
+            .extend(fragment.basic_auth_filters);
+
+        for filter in &fragment.cors_filters {
+            definitions.define("CORS filter", filter.key().as_ref(), source)?;
+        }
+        merged.cors_filters.extend(fragment.cors_filters);
+
+        for filter in &fragment.compression_filters {
+            definitions.define("compression filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .compression_filters
+            .extend(fragment.compression_filters);
+
+        for filter in &fragment.cache_filters {
+            definitions.define("cache filter", filter.key().as_ref(), source)?;
+        }
+        merged.cache_filters.extend(fragment.cache_filters);
+
+        for filter in &fragment.request_limits_filters {
+            definitions.define("request limits filter", filter.key().as_ref(), source)?;
+        }
+        merged
+            .request_limits_filters
+            .extend(fragment.request_limits_filters);
+    }
+
+    let config = GatewayConfiguration {
+        version: merged.version.unwrap_or_default(),
+        ipc: merged.ipc,
+        listeners: merged.listeners,
+        http_routes: merged.http_routes,
+        client_addrs: merged.client_addrs,
+        error_responses: merged.error_responses,
+        access_logs: merged.access_logs,
+        static_responses: (!static_responses.is_empty()).then(|| {
+            StaticResponses::builder()
+                .responses(static_responses)
+                .build()
+        }),
+        access_control_filters: merged.access_control_filters,
+        rate_limit_filters: merged.rate_limit_filters,
+        jwt_auth_filters: merged.jwt_auth_filters,
+        external_auth_filters: merged.external_auth_filters,
+        basic_auth_filters: merged.basic_auth_filters,
+        cors_filters: merged.cors_filters,
+        compression_filters: merged.compression_filters,
+        cache_filters: merged.cache_filters,
+        request_limits_filters: merged.request_limits_filters,
+    };
+
+    config.validate()?;
+    Ok(config)
+}
+
+/// Where each named part of the merged configuration was defined
+#[derive(Default)]
+struct Definitions(HashMap<(&'static str, String), String>);
+
+impl Definitions {
+    fn define(&mut self, kind: &'static str, name: &str, source: &str) -> Result<(), MergeError> {
+        match self.0.entry((kind, name.to_string())) {
+            Entry::Occupied(entry) => Err(MergeError::Conflict(
+                kind,
+                name.to_string(),
+                entry.get().clone(),
+                source.to_string(),
+            )),
+            Entry::Vacant(entry) => {
+                entry.insert(source.to_string());
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_ok;
+
+    const LISTENERS: &str = r#"
+version: v1alpha1
+listeners:
+  - name: http
+    port: 80
+    protocol: HTTP
+"#;
+
+    const ROUTES: &str = r#"
+{
+  "http_routes": [
+    {
+      "key": "default/echo",
+      "rules": [
+        {
+          "unique_id": "echo:0",
+          "matches": [{ "path": { "value": "/" } }],
+          "backends": [{ "name": "echo", "namespace": "default", "port": 80, "endpoints": [] }]
+        }
+      ]
+    }
+  ]
+}
+"#;
+
+    #[test]
+    fn test_merge_fragments() {
+        let listeners = assert_ok!(read_fragment(LISTENERS.as_bytes()));
+        let routes = assert_ok!(read_fragment(ROUTES.as_bytes()));
+
+        let merged = assert_ok!(merge_fragments([
+            ("listeners.yaml", listeners.clone()),
+            ("routes.json", routes.clone()),
+        ]));
+        assert_eq!(merged.listeners().len(), 1);
+        assert_eq!(
+            merged.http_routes()[0].key().as_deref(),
+            Some("default/echo")
+        );
+
+        let conflict = merge_fragments([
+            ("listeners.yaml", listeners),
+            ("routes.json", routes.clone()),
+            ("more-routes.json", routes),
+        ]);
+        assert_eq!(
+            conflict.map_err(|err| err.to_string()).err().as_deref(),
+            Some("HTTP route \"default/echo\" is defined in both routes.json and more-routes.json")
+        );
+    }
+}
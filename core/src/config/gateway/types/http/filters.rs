@@ -1,4 +1,12 @@
 use crate::types::filters::access_control::Key;
+use crate::types::filters::basic_auth::Key as BasicAuthKey;
+use crate::types::filters::cache::Key as CacheKey;
+use crate::types::filters::compression::Key as CompressionKey;
+use crate::types::filters::cors::Key as CorsKey;
+use crate::types::filters::external_auth::Key as ExternalAuthKey;
+use crate::types::filters::jwt_auth::Key as JwtAuthKey;
+use crate::types::filters::rate_limit::Key as RateLimitKey;
+use crate::types::filters::request_limits::Key as RequestLimitsKey;
 use getset::Getters;
 use http::HeaderName;
 use schemars::JsonSchema;
@@ -41,6 +49,30 @@ pub struct HttpRouteFilter {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ext_access_control: Option<ExtAccessControlRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_rate_limit: Option<ExtRateLimitRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_jwt_auth: Option<ExtJwtAuthRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_external_auth: Option<ExtExternalAuthRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_basic_auth: Option<ExtBasicAuthRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_cors: Option<ExtCorsRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_compression: Option<ExtCompressionRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_cache: Option<ExtCacheRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_request_limits: Option<ExtRequestLimitsRef>,
 }
 
 /// HTTP Route Filter Types - matches Gateway API filter types
@@ -60,6 +92,22 @@ pub enum HttpRouteFilterType {
     ExtStaticResponse,
     #[serde(rename = "AccessControl")]
     ExtAccessControl,
+    #[serde(rename = "RateLimit")]
+    ExtRateLimit,
+    #[serde(rename = "JwtAuth")]
+    ExtJwtAuth,
+    #[serde(rename = "ExternalAuth")]
+    ExtExternalAuth,
+    #[serde(rename = "BasicAuth")]
+    ExtBasicAuth,
+    #[serde(rename = "Cors")]
+    ExtCors,
+    #[serde(rename = "Compression")]
+    ExtCompression,
+    #[serde(rename = "Cache")]
+    ExtCache,
+    #[serde(rename = "RequestLimits")]
+    ExtRequestLimits,
 }
 
 /// Request header modification filter - matches Gateway API `RequestHeaderModifier` structure
@@ -204,6 +252,78 @@ pub struct ExtAccessControlRef {
     key: Key,
 }
 
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtRateLimitRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: RateLimitKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtJwtAuthRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: JwtAuthKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtExternalAuthRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: ExternalAuthKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtBasicAuthRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: BasicAuthKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtCorsRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CorsKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtCompressionRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CompressionKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtCacheRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CacheKey,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TypedBuilder, Getters,
+)]
+pub struct ExtRequestLimitsRef {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: RequestLimitsKey,
+}
+
 #[derive(Debug, Error)]
 pub enum HTTPRouteFilterBuilderError {
     #[error("Header name cannot be empty")]
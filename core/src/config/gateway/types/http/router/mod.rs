@@ -2,7 +2,7 @@ mod matches;
 
 use crate::config::gateway::types::http::filters::HttpRouteFilter;
 use crate::config::gateway::types::net::{Backend, BackendBuilder, BackendBuilderError};
-use getset::Getters;
+use getset::{Getters, MutGetters};
 use itertools::{Either, Itertools};
 pub use matches::*;
 use schemars::JsonSchema;
@@ -21,7 +21,9 @@ impl HttpRouteRuleUniqueId {
     }
 }
 
-#[derive(Validate, Getters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(
+    Validate, Getters, MutGetters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
 pub struct HttpRouteRule {
     #[getset(get = "pub")]
     unique_id: HttpRouteRuleUniqueId,
@@ -30,7 +32,7 @@ pub struct HttpRouteRule {
     #[validate(max_items = 16)]
     matches: Vec<HttpRouteRuleMatches>,
 
-    #[getset(get = "pub")]
+    #[getset(get = "pub", get_mut = "pub(crate)")]
     #[validate(max_items = 16)]
     backends: Vec<Backend>,
 
@@ -119,8 +121,15 @@ impl HttpRouteRuleBuilder {
     }
 }
 
-#[derive(Validate, Getters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(
+    Validate, Getters, MutGetters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
 pub struct HttpRoute {
+    /// Namespaced name of the `HTTPRoute` the route was generated from
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+
     #[getset(get = "pub")]
     #[validate(max_items = 16)]
     #[serde(
@@ -130,7 +139,7 @@ pub struct HttpRoute {
     )]
     host_header_matches: Vec<HostHeaderMatch>,
 
-    #[getset(get = "pub")]
+    #[getset(get = "pub", get_mut = "pub(crate)")]
     #[validate(max_items = 16)]
     rules: Vec<HttpRouteRule>,
 }
@@ -143,6 +152,7 @@ pub enum HttpRouteBuilderError {
 
 #[derive(Debug, Default)]
 pub struct HttpRouteBuilder {
+    key: Option<String>,
     host_header_matches: Vec<HostHeaderMatch>,
     rule_builders: Vec<HttpRouteRuleBuilder>,
 }
@@ -164,11 +174,17 @@ impl HttpRouteBuilder {
         }
 
         Ok(HttpRoute {
+            key: self.key,
             host_header_matches: self.host_header_matches,
             rules,
         })
     }
 
+    pub fn with_key<S: AsRef<str>>(&mut self, key: S) -> &mut Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
     pub fn add_exact_host_header<S: AsRef<str>>(&mut self, host: S) -> &mut Self {
         let host_header_match = HostHeaderMatch::exactly(host);
         self.host_header_matches.push(host_header_match);
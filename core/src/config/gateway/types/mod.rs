@@ -1,3 +1,4 @@
+pub mod fragments;
 pub mod http;
 pub mod net;
 
@@ -5,11 +6,13 @@ use crate::config::gateway::types::http::router::{
     HttpRoute, HttpRouteBuilder, HttpRouteBuilderError,
 };
 use crate::config::gateway::types::net::{
-    AccessControlFilter, ClientAddrs, ClientAddrsBuilder, ErrorResponses, Listener,
-    ListenerBuilder, ListenerBuilderError, StaticResponse, StaticResponses,
+    AccessControlFilter, AccessLogs, BasicAuthFilter, CacheFilter, ClientAddrs, ClientAddrsBuilder,
+    CompressionFilter, CorsFilter, ErrorResponses, ExternalAuthFilter, JwtAuthFilter, Listener,
+    ListenerBuilder, ListenerBuilderError, RateLimitFilter, RequestLimitsFilter, StaticResponse,
+    StaticResponses,
 };
 use crate::net::Port;
-use getset::{CloneGetters, CopyGetters, Getters};
+use getset::{CloneGetters, CopyGetters, Getters, MutGetters};
 use itertools::{Either, Itertools};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -44,6 +47,7 @@ pub enum GatewayConfigurationVersion {
     Getters,
     CloneGetters,
     CopyGetters,
+    MutGetters,
     Debug,
     Clone,
     PartialEq,
@@ -63,7 +67,7 @@ pub struct GatewayConfiguration {
     #[validate(max_items = 64)]
     listeners: Vec<Listener>,
 
-    #[getset(get = "pub")]
+    #[getset(get = "pub", get_mut = "pub(crate)")]
     #[validate(max_items = 64)]
     http_routes: Vec<HttpRoute>,
 
@@ -75,6 +79,10 @@ pub struct GatewayConfiguration {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     error_responses: Option<ErrorResponses>,
 
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    access_logs: Option<AccessLogs>,
+
     #[getset(get = "pub")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     static_responses: Option<StaticResponses>,
@@ -82,6 +90,80 @@ pub struct GatewayConfiguration {
     #[getset(get = "pub")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     access_control_filters: Vec<AccessControlFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rate_limit_filters: Vec<RateLimitFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    jwt_auth_filters: Vec<JwtAuthFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    external_auth_filters: Vec<ExternalAuthFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    basic_auth_filters: Vec<BasicAuthFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cors_filters: Vec<CorsFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    compression_filters: Vec<CompressionFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cache_filters: Vec<CacheFilter>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    request_limits_filters: Vec<RequestLimitsFilter>,
+}
+
+impl GatewayConfiguration {
+    /// Whether both configurations are equal, ignoring their HTTP routes
+    pub(crate) fn eq_except_http_routes(&self, other: &Self) -> bool {
+        let Self {
+            version,
+            ipc,
+            listeners,
+            http_routes: _,
+            client_addrs,
+            error_responses,
+            access_logs,
+            static_responses,
+            access_control_filters,
+            rate_limit_filters,
+            jwt_auth_filters,
+            external_auth_filters,
+            basic_auth_filters,
+            cors_filters,
+            compression_filters,
+            cache_filters,
+            request_limits_filters,
+        } = self;
+
+        *version == other.version
+            && *ipc == other.ipc
+            && *listeners == other.listeners
+            && *client_addrs == other.client_addrs
+            && *error_responses == other.error_responses
+            && *access_logs == other.access_logs
+            && *static_responses == other.static_responses
+            && *access_control_filters == other.access_control_filters
+            && *rate_limit_filters == other.rate_limit_filters
+            && *jwt_auth_filters == other.jwt_auth_filters
+            && *external_auth_filters == other.external_auth_filters
+            && *basic_auth_filters == other.basic_auth_filters
+            && *cors_filters == other.cors_filters
+            && *compression_filters == other.compression_filters
+            && *cache_filters == other.cache_filters
+            && *request_limits_filters == other.request_limits_filters
+    }
 }
 
 #[derive(Debug, Default)]
@@ -92,8 +174,17 @@ pub struct GatewayConfigurationBuilder {
     http_route_builders: Vec<HttpRouteBuilder>,
     client_addrs_builder: Option<ClientAddrsBuilder>,
     error_responses: Option<ErrorResponses>,
+    access_logs: Option<AccessLogs>,
     static_responses: Option<StaticResponses>,
     access_control_filters: Vec<AccessControlFilter>,
+    rate_limit_filters: Vec<RateLimitFilter>,
+    jwt_auth_filters: Vec<JwtAuthFilter>,
+    external_auth_filters: Vec<ExternalAuthFilter>,
+    basic_auth_filters: Vec<BasicAuthFilter>,
+    cors_filters: Vec<CorsFilter>,
+    compression_filters: Vec<CompressionFilter>,
+    cache_filters: Vec<CacheFilter>,
+    request_limits_filters: Vec<RequestLimitsFilter>,
 }
 
 #[derive(Debug, Error)]
@@ -147,8 +238,17 @@ impl GatewayConfigurationBuilder {
             http_routes,
             client_addrs: self.client_addrs_builder.map(ClientAddrsBuilder::build),
             error_responses: self.error_responses,
+            access_logs: self.access_logs,
             static_responses: self.static_responses,
             access_control_filters: self.access_control_filters,
+            rate_limit_filters: self.rate_limit_filters,
+            jwt_auth_filters: self.jwt_auth_filters,
+            external_auth_filters: self.external_auth_filters,
+            basic_auth_filters: self.basic_auth_filters,
+            cors_filters: self.cors_filters,
+            compression_filters: self.compression_filters,
+            cache_filters: self.cache_filters,
+            request_limits_filters: self.request_limits_filters,
         })
     }
 
@@ -202,6 +302,11 @@ impl GatewayConfigurationBuilder {
         self
     }
 
+    pub fn with_access_logs(&mut self, access_logs: AccessLogs) -> &mut Self {
+        self.access_logs = Some(access_logs);
+        self
+    }
+
     pub fn with_static_responses(&mut self, static_responses: Vec<StaticResponse>) -> &mut Self {
         self.static_responses = if static_responses.is_empty() {
             None
@@ -218,6 +323,46 @@ impl GatewayConfigurationBuilder {
         self.access_control_filters = filters;
         self
     }
+
+    pub fn with_rate_limit_filters(&mut self, filters: Vec<RateLimitFilter>) -> &mut Self {
+        self.rate_limit_filters = filters;
+        self
+    }
+
+    pub fn with_jwt_auth_filters(&mut self, filters: Vec<JwtAuthFilter>) -> &mut Self {
+        self.jwt_auth_filters = filters;
+        self
+    }
+
+    pub fn with_external_auth_filters(&mut self, filters: Vec<ExternalAuthFilter>) -> &mut Self {
+        self.external_auth_filters = filters;
+        self
+    }
+
+    pub fn with_basic_auth_filters(&mut self, filters: Vec<BasicAuthFilter>) -> &mut Self {
+        self.basic_auth_filters = filters;
+        self
+    }
+
+    pub fn with_cors_filters(&mut self, filters: Vec<CorsFilter>) -> &mut Self {
+        self.cors_filters = filters;
+        self
+    }
+
+    pub fn with_compression_filters(&mut self, filters: Vec<CompressionFilter>) -> &mut Self {
+        self.compression_filters = filters;
+        self
+    }
+
+    pub fn with_cache_filters(&mut self, filters: Vec<CacheFilter>) -> &mut Self {
+        self.cache_filters = filters;
+        self
+    }
+
+    pub fn with_request_limits_filters(&mut self, filters: Vec<RequestLimitsFilter>) -> &mut Self {
+        self.request_limits_filters = filters;
+        self
+    }
 }
 
 #[derive(Validate, Getters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -225,11 +370,18 @@ pub struct IpcConfiguration {
     #[getset(get = "pub")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     endpoint: Option<SocketAddr>,
+
+    /// The PEM encoded CA the endpoint's certificate is issued by. The endpoint is reached
+    /// over plain HTTP without one
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ca_certificate: Option<String>,
 }
 
 #[derive(Debug, Default)]
 pub struct IpcConfigurationBuilder {
     endpoint: Option<SocketAddr>,
+    ca_certificate: Option<String>,
 }
 
 impl IpcConfigurationBuilder {
@@ -243,9 +395,15 @@ impl IpcConfigurationBuilder {
         self
     }
 
+    pub fn with_ca_certificate(&mut self, ca_certificate: impl Into<String>) -> &mut Self {
+        self.ca_certificate = Some(ca_certificate.into());
+        self
+    }
+
     pub fn build(self) -> IpcConfiguration {
         IpcConfiguration {
             endpoint: self.endpoint,
+            ca_certificate: self.ca_certificate,
         }
     }
 }
@@ -1,7 +1,15 @@
 use crate::config::gateway::types::http::filters::RequestHeaderModifier;
-use crate::net::{Hostname, Port};
+use crate::net::{EndpointAddress, Hostname, Port};
 use crate::types::filters::access_control::Key;
-use getset::Getters;
+use crate::types::filters::basic_auth::Key as BasicAuthKey;
+use crate::types::filters::cache::Key as CacheKey;
+use crate::types::filters::compression::Key as CompressionKey;
+use crate::types::filters::cors::Key as CorsKey;
+use crate::types::filters::external_auth::Key as ExternalAuthKey;
+use crate::types::filters::jwt_auth::Key as JwtAuthKey;
+use crate::types::filters::rate_limit::Key as RateLimitKey;
+use crate::types::filters::request_limits::Key as RequestLimitsKey;
+use getset::{Getters, MutGetters};
 use ipnet::IpNet;
 use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
@@ -10,7 +18,9 @@ use std::net::IpAddr;
 use thiserror::Error;
 use typed_builder::TypedBuilder;
 
-#[derive(Validate, Getters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(
+    Validate, Getters, MutGetters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
 pub struct Backend {
     #[getset(get = "pub")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -27,13 +37,54 @@ pub struct Backend {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     namespace: Option<String>,
 
-    #[getset(get = "pub")]
+    #[getset(get = "pub", get_mut = "pub(crate)")]
     endpoints: Vec<Endpoint>,
 
     /// Request header modifier for this backend
     #[getset(get = "pub")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     request_header_modifier: Option<RequestHeaderModifier>,
+
+    /// Slow start ramp-up applied to endpoints that become ready after the gateway started
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slow_start: Option<SlowStart>,
+
+    /// Limits on concurrent requests and pending connections to this backend
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    circuit_breaker: Option<CircuitBreaker>,
+
+    /// TLS to the endpoints of this backend, which are sent plain HTTP without it
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tls: Option<BackendTls>,
+}
+
+impl Backend {
+    /// Whether both backends are equal, ignoring their endpoints
+    pub(crate) fn eq_except_endpoints(&self, other: &Self) -> bool {
+        let Self {
+            weight,
+            port,
+            name,
+            namespace,
+            endpoints: _,
+            request_header_modifier,
+            slow_start,
+            circuit_breaker,
+            tls,
+        } = self;
+
+        *weight == other.weight
+            && *port == other.port
+            && *name == other.name
+            && *namespace == other.namespace
+            && *request_header_modifier == other.request_header_modifier
+            && *slow_start == other.slow_start
+            && *circuit_breaker == other.circuit_breaker
+            && *tls == other.tls
+    }
 }
 
 #[derive(Default, Debug)]
@@ -44,6 +95,9 @@ pub struct BackendBuilder {
     namespace: Option<String>,
     endpoint_builders: Vec<EndpointBuilder>,
     request_header_modifier: Option<RequestHeaderModifier>,
+    slow_start: Option<SlowStart>,
+    circuit_breaker: Option<CircuitBreaker>,
+    tls: Option<BackendTls>,
 }
 
 #[derive(Debug, Error)]
@@ -73,11 +127,12 @@ impl BackendBuilder {
         self
     }
 
-    pub fn add_endpoint<F>(&mut self, address: IpAddr, factory: F) -> &mut Self
+    /// Adds an endpoint by IP address, or by a hostname the gateway resolves
+    pub fn add_endpoint<F>(&mut self, address: impl Into<EndpointAddress>, factory: F) -> &mut Self
     where
         F: FnOnce(&mut EndpointBuilder),
     {
-        let mut endpoint_builder = EndpointBuilder::new(address);
+        let mut endpoint_builder = EndpointBuilder::new(address.into());
         factory(&mut endpoint_builder);
         self.endpoint_builders.push(endpoint_builder);
         self
@@ -89,6 +144,21 @@ impl BackendBuilder {
         self
     }
 
+    pub fn with_slow_start(&mut self, slow_start: Option<SlowStart>) -> &mut Self {
+        self.slow_start = slow_start;
+        self
+    }
+
+    pub fn with_circuit_breaker(&mut self, circuit_breaker: Option<CircuitBreaker>) -> &mut Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    pub fn with_tls(&mut self, tls: Option<BackendTls>) -> &mut Self {
+        self.tls = tls;
+        self
+    }
+
     pub fn build(self) -> Result<Backend, BackendBuilderError> {
         let name = self.name.ok_or(BackendBuilderError::MissingName)?;
         Ok(Backend {
@@ -102,10 +172,134 @@ impl BackendBuilder {
                 .map(EndpointBuilder::build)
                 .collect(),
             request_header_modifier: self.request_header_modifier,
+            slow_start: self.slow_start,
+            circuit_breaker: self.circuit_breaker,
+            tls: self.tls,
         })
     }
 }
 
+/// Linearly ramps the selection weight of a newly ready endpoint from
+/// `min_weight_percent` up to its full weight over `window_seconds`.
+#[derive(
+    Validate,
+    Getters,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    TypedBuilder,
+)]
+pub struct SlowStart {
+    #[getset(get = "pub")]
+    window_seconds: u32,
+
+    #[getset(get = "pub")]
+    #[serde(default = "default_slow_start_min_weight_percent")]
+    #[builder(default = default_slow_start_min_weight_percent())]
+    min_weight_percent: u8,
+}
+
+fn default_slow_start_min_weight_percent() -> u8 {
+    10
+}
+
+/// Thresholds that reject requests to a backend once it has too much work in flight.
+/// Unset thresholds are unlimited.
+#[derive(
+    Validate,
+    Getters,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    TypedBuilder,
+)]
+pub struct CircuitBreaker {
+    /// Maximum number of in-flight requests to the backend
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    max_requests: Option<u32>,
+
+    /// Maximum number of requests waiting for a connection to the backend
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    max_pending_connections: Option<u32>,
+}
+
+/// TLS to the endpoints of a backend, whose certificate is verified for the server name
+#[derive(
+    Validate,
+    Getters,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    TypedBuilder,
+)]
+pub struct BackendTls {
+    /// Server name sent with SNI, which the certificate of the backend must be valid for
+    #[getset(get = "pub")]
+    sni: Hostname,
+
+    /// PEM encoded certificates of the CAs trusted to sign the certificate of the backend,
+    /// instead of the ones of the system. None are trusted when it is empty
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    ca_certificates: Option<String>,
+
+    /// Certificate the gateway presents to the backend
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    client_certificate: Option<ClientCertificate>,
+}
+
+/// A client certificate with its private key, which gateways fetch from the control plane
+/// rather than receive with the configuration
+#[derive(
+    Validate,
+    Getters,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    TypedBuilder,
+)]
+pub struct ClientCertificate {
+    /// Changes whenever the source of the certificate is updated, so gateways know to refetch it
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    version_key: String,
+
+    /// Identifier the certificate is fetched from the control plane with
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    identifier: String,
+}
+
 #[derive(
     Validate, Getters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, JsonSchema,
 )]
@@ -119,22 +313,29 @@ pub struct Endpoint {
     zone: Option<String>,
 
     #[getset(get = "pub")]
-    address: IpAddr,
+    address: EndpointAddress,
+
+    /// Zones this endpoint should serve, taken from the `EndpointSlice` `hints.forZones`
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    zone_hints: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct EndpointBuilder {
     node: Option<String>,
     zone: Option<String>,
-    address: IpAddr,
+    address: EndpointAddress,
+    zone_hints: Vec<String>,
 }
 
 impl EndpointBuilder {
-    fn new(address: IpAddr) -> Self {
+    fn new(address: EndpointAddress) -> Self {
         Self {
             node: None,
             zone: None,
             address,
+            zone_hints: Vec::new(),
         }
     }
 
@@ -143,6 +344,7 @@ impl EndpointBuilder {
             node: self.node,
             zone: self.zone,
             address: self.address,
+            zone_hints: self.zone_hints,
         }
     }
 
@@ -155,6 +357,11 @@ impl EndpointBuilder {
         self.zone = Some(zone.as_ref().to_string());
         self
     }
+
+    pub fn add_zone_hint<S: AsRef<str>>(&mut self, zone: S) -> &mut Self {
+        self.zone_hints.push(zone.as_ref().to_string());
+        self
+    }
 }
 
 #[derive(
@@ -485,6 +692,107 @@ pub struct ProblemDetailErrorResponse {
     authority: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct AccessLogs {
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    format: AccessLogFormat,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    sink: AccessLogSink,
+
+    /// Fraction of the requests to log, from 0 to 1
+    #[getset(get = "pub")]
+    #[serde(default = "default_access_logs_sample_ratio")]
+    #[builder(default = default_access_logs_sample_ratio())]
+    sample_ratio: f64,
+
+    /// Classes of the response statuses to log, all of them when empty
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    status_classes: Vec<StatusClass>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    routes: Vec<AccessLogRoute>,
+}
+
+fn default_access_logs_sample_ratio() -> f64 {
+    1.0
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum AccessLogFormat {
+    #[default]
+    Json,
+    Common,
+    Combined,
+    /// Lines rendered from a template with `${field}` placeholders
+    Template(String),
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum AccessLogSink {
+    #[default]
+    Stdout,
+    File(AccessLogFile),
+    OpenTelemetry,
+}
+
+#[derive(
+    Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq, Getters, TypedBuilder,
+)]
+pub struct AccessLogFile {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    path: String,
+
+    /// The file is rotated once it grows larger
+    #[getset(get = "pub")]
+    max_size_bytes: u64,
+
+    /// Number of rotated files kept besides the current one
+    #[getset(get = "pub")]
+    max_files: u32,
+}
+
+/// The class of a response status, from its first digit
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusClass {
+    Informational,
+    Successful,
+    Redirection,
+    ClientError,
+    ServerError,
+}
+
+/// Overrides of the access logs for the requests matching a route
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct AccessLogRoute {
+    /// Namespaced name of the `HTTPRoute`
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: String,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    sample_ratio: Option<f64>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    status_classes: Vec<StatusClass>,
+}
+
 #[derive(
     Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, Default, TypedBuilder,
 )]
@@ -554,3 +862,355 @@ pub struct AccessControlFilterClientMatches {
     #[schemars(schema_with = "cidr_array_schema")]
     ip_ranges: Vec<IpNet>,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct RateLimitFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: RateLimitKey,
+
+    #[getset(get = "pub")]
+    limit_key: RateLimitFilterKey,
+
+    /// Number of tokens replenished every period
+    #[getset(get = "pub")]
+    requests: u32,
+
+    #[getset(get = "pub")]
+    #[serde(default = "default_rate_limit_period_seconds")]
+    #[builder(default = default_rate_limit_period_seconds())]
+    period_seconds: u32,
+
+    /// Capacity of the token bucket, defaults to `requests`
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    burst: Option<u32>,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    mode: RateLimitMode,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitMode {
+    #[default]
+    Local,
+    /// Consumption is shared with the other replicas through the control plane
+    Global,
+}
+
+fn default_rate_limit_period_seconds() -> u32 {
+    1
+}
+
+/// Where the token bucket a request draws from is keyed from
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case", tag = "source", content = "name")]
+pub enum RateLimitFilterKey {
+    ClientAddr,
+    Header(String),
+    Route,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct JwtAuthFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: JwtAuthKey,
+
+    /// Changes whenever the filter is updated, so gateways know to refetch the JWKS
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    version_key: String,
+
+    /// Identifier the JWKS document is fetched from the control plane with
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    jwks_identifier: String,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    issuer: String,
+
+    /// Accepted audiences, the audience is not checked when empty
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    audiences: Vec<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default = "default_jwt_auth_clock_skew_seconds")]
+    #[builder(default = default_jwt_auth_clock_skew_seconds())]
+    clock_skew_seconds: u32,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    claim_headers: Vec<JwtClaimHeader>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    realm: Option<String>,
+}
+
+fn default_jwt_auth_clock_skew_seconds() -> u32 {
+    60
+}
+
+/// Copies a claim of a verified token into an upstream request header
+#[derive(
+    Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq, Getters, TypedBuilder,
+)]
+pub struct JwtClaimHeader {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    claim: String,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    header: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct ExternalAuthFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: ExternalAuthKey,
+
+    /// The authorization service, with the endpoints of its `Service`
+    #[getset(get = "pub")]
+    backend: Backend,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    path_prefix: Option<String>,
+
+    /// Request headers forwarded to the authorization service
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    request_headers: Vec<String>,
+
+    /// Headers of an allowing response copied into the upstream request
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    upstream_headers: Vec<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    failure_mode: ExternalAuthFailureMode,
+
+    #[getset(get = "pub")]
+    #[serde(default = "default_external_auth_timeout_milliseconds")]
+    #[builder(default = default_external_auth_timeout_milliseconds())]
+    timeout_milliseconds: u32,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    cache: Option<ExternalAuthCache>,
+}
+
+fn default_external_auth_timeout_milliseconds() -> u32 {
+    1000
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalAuthFailureMode {
+    #[default]
+    Closed,
+    Open,
+}
+
+/// Caches decisions of the authorization service by the value of a request header
+#[derive(
+    Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq, Getters, TypedBuilder,
+)]
+pub struct ExternalAuthCache {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key_header: String,
+
+    #[getset(get = "pub")]
+    #[serde(default = "default_external_auth_cache_ttl_seconds")]
+    #[builder(default = default_external_auth_cache_ttl_seconds())]
+    ttl_seconds: u32,
+}
+
+fn default_external_auth_cache_ttl_seconds() -> u32 {
+    60
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct BasicAuthFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: BasicAuthKey,
+
+    /// Changes whenever the filter is updated, so gateways know to refetch the credentials
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    version_key: String,
+
+    /// Identifier the htpasswd file is fetched from the control plane with
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    htpasswd_identifier: String,
+
+    /// Removes the `Authorization` header before the request is proxied
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    strip_authorization: bool,
+
+    /// Upstream request header set to the authenticated username
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    username_header: Option<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    realm: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct CorsFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CorsKey,
+
+    #[getset(get = "pub")]
+    allow_origins: Vec<CorsOrigin>,
+
+    /// Methods allowed in preflight requests, `*` allows any method
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    allow_methods: Vec<String>,
+
+    /// Request headers allowed in preflight requests, `*` allows any header
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    allow_headers: Vec<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    expose_headers: Vec<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    allow_credentials: bool,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    max_age_seconds: Option<u32>,
+}
+
+/// How the `Origin` of a cross-origin request is matched
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum CorsOrigin {
+    Exact(String),
+    /// `*` matches any characters other than `/`, and on its own matches any origin
+    Wildcard(String),
+    Regex(String),
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct CompressionFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CompressionKey,
+
+    /// Algorithms in order of preference
+    #[getset(get = "pub")]
+    algorithms: Vec<CompressionAlgorithm>,
+
+    /// Media types of the responses to compress, `type/*` matches any subtype
+    #[getset(get = "pub")]
+    content_types: Vec<String>,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    min_size_bytes: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct CacheFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: CacheKey,
+
+    /// Responses with a larger body are not cached
+    #[getset(get = "pub")]
+    max_object_size_bytes: u32,
+
+    /// Used when the upstream does not set `stale-while-revalidate`
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    stale_while_revalidate_seconds: u32,
+
+    /// Used when the upstream does not set `stale-if-error`
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    stale_if_error_seconds: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema, PartialEq, Getters, TypedBuilder)]
+pub struct RequestLimitsFilter {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: RequestLimitsKey,
+
+    /// Requests with a larger body are rejected with `413`
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    max_request_body_bytes: Option<u64>,
+
+    /// Requests with larger header fields are rejected with `431`
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    max_request_headers_bytes: Option<u32>,
+
+    #[getset(get = "pub")]
+    #[serde(default)]
+    #[builder(default)]
+    body_mode: RequestBodyMode,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestBodyMode {
+    /// The body is forwarded to the upstream as it arrives
+    #[default]
+    Streaming,
+    /// The body is read in full before it is forwarded, so it can be replayed
+    Buffered,
+}
@@ -1,18 +1,25 @@
+mod prometheus;
+
+pub use prometheus::PROMETHEUS_CONTENT_TYPE;
+
+use crate::instrumentation::prometheus::PrometheusExporter;
 use crate::task::Builder as TaskBuilder;
 use opentelemetry::global::{
     meter, set_meter_provider, set_text_map_propagator, set_tracer_provider,
 };
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider, Severity};
 use opentelemetry::metrics::Meter;
 use opentelemetry::trace::{TraceContextExt, TracerProvider};
 use opentelemetry::{Context, Key, KeyValue, Value};
 use opentelemetry_appender_log::OpenTelemetryLogBridge;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::{LogExporterBuilder, MetricExporterBuilder, SpanExporterBuilder};
-use opentelemetry_sdk::logs::LoggerProviderBuilder;
+use opentelemetry_sdk::logs::{LoggerProviderBuilder, SdkLogger};
 use opentelemetry_sdk::metrics::MeterProviderBuilder;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::TracerProviderBuilder;
-use std::sync::{LazyLock, Once};
+use std::sync::{LazyLock, Once, OnceLock};
+use std::time::SystemTime;
 use tracing::info;
 use tracing::log::set_boxed_logger;
 use tracing::subscriber::set_global_default;
@@ -23,7 +30,11 @@ use tracing_subscriber::{EnvFilter, Registry};
 
 #[allow(clippy::expect_used)]
 #[track_caller]
-pub fn init_instrumentation(task_builder: &TaskBuilder, name: &'static str) {
+pub fn init_instrumentation(
+    task_builder: &TaskBuilder,
+    name: &'static str,
+    prometheus_enabled: bool,
+) {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
         #[cfg(debug_assertions)]
@@ -36,9 +47,15 @@ pub fn init_instrumentation(task_builder: &TaskBuilder, name: &'static str) {
             .build()
             .expect("Failed to create OTLP exporter");
 
-        let meter_provider = MeterProviderBuilder::default()
-            .with_periodic_exporter(otlp_metrics_exporter)
-            .build();
+        let mut meter_provider_builder =
+            MeterProviderBuilder::default().with_periodic_exporter(otlp_metrics_exporter);
+        if prometheus_enabled {
+            let prometheus_exporter = PrometheusExporter::default();
+            meter_provider_builder =
+                meter_provider_builder.with_reader(prometheus_exporter.reader());
+            let _ = PROMETHEUS.set(prometheus_exporter);
+        }
+        let meter_provider = meter_provider_builder.build();
         set_meter_provider(meter_provider.clone());
 
         let otlp_logs_exporter = LogExporterBuilder::new()
@@ -50,6 +67,8 @@ pub fn init_instrumentation(task_builder: &TaskBuilder, name: &'static str) {
             .with_batch_exporter(otlp_logs_exporter)
             .build();
 
+        let _ = LOGGER.set(logs_provider.logger(name));
+
         let logger = OpenTelemetryLogBridge::new(&logs_provider);
         set_boxed_logger(Box::new(logger)).expect("Failed to set logger");
 
@@ -99,6 +118,36 @@ pub fn init_instrumentation(task_builder: &TaskBuilder, name: &'static str) {
 
 pub(crate) static METER: LazyLock<Meter> = LazyLock::new(|| meter("vg-core"));
 
+static LOGGER: OnceLock<SdkLogger> = OnceLock::new();
+
+static PROMETHEUS: OnceLock<PrometheusExporter> = OnceLock::new();
+
+/// Renders every metric of the process in the Prometheus text exposition format, or
+/// nothing when the Prometheus exporter is not enabled
+pub fn prometheus_metrics() -> Option<String> {
+    PROMETHEUS.get().map(PrometheusExporter::render)
+}
+
+/// Emits a record straight to the OpenTelemetry logs exporter, regardless of the level
+/// filter of the tracing subscriber. Nothing is emitted before instrumentation is set up.
+pub fn emit_log_record<I, K, V>(event_name: &'static str, body: String, attributes: I)
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<Key>,
+    V: Into<AnyValue>,
+{
+    if let Some(logger) = LOGGER.get() {
+        let mut record = logger.create_log_record();
+        record.set_event_name(event_name);
+        record.set_timestamp(SystemTime::now());
+        record.set_severity_number(Severity::Info);
+        record.set_severity_text("INFO");
+        record.set_body(body.into());
+        record.add_attributes(attributes);
+        logger.emit(record);
+    }
+}
+
 pub fn trace_id() -> Option<String> {
     let context = Context::current();
     if context.has_active_span() {
@@ -0,0 +1,373 @@
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{
+    AggregatedMetrics, Gauge, Histogram, Metric, MetricData, ResourceMetrics, Sum,
+};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{InstrumentKind, ManualReader, Pipeline, Temporality};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tracing::warn;
+
+/// The content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Collects the metrics of the meter provider on demand, when Prometheus scrapes them
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusExporter {
+    reader: Arc<ManualReader>,
+}
+
+impl PrometheusExporter {
+    /// The reader to register with the meter provider
+    pub fn reader(&self) -> PrometheusReader {
+        PrometheusReader(self.reader.clone())
+    }
+
+    /// Renders the current value of every metric in the text exposition format
+    pub fn render(&self) -> String {
+        let mut resource_metrics = ResourceMetrics::default();
+        if let Err(e) = self.reader.collect(&mut resource_metrics) {
+            warn!("Failed to collect metrics for Prometheus: {}", e);
+        }
+        render(&resource_metrics)
+    }
+}
+
+#[derive(Debug)]
+pub struct PrometheusReader(Arc<ManualReader>);
+
+impl MetricReader for PrometheusReader {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.0.register_pipeline(pipeline);
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.0.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, _kind: InstrumentKind) -> Temporality {
+        // Prometheus expects counters and histograms to only ever grow
+        Temporality::Cumulative
+    }
+}
+
+/// The samples of a metric, merged across instrumentation scopes
+struct Family {
+    kind: &'static str,
+    help: String,
+    samples: Vec<String>,
+}
+
+fn render(resource_metrics: &ResourceMetrics) -> String {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+
+    for metric in resource_metrics
+        .scope_metrics()
+        .flat_map(|scope_metrics| scope_metrics.metrics())
+    {
+        match metric.data() {
+            AggregatedMetrics::F64(data) => add_metric_data(&mut families, metric, data),
+            AggregatedMetrics::U64(data) => add_metric_data(&mut families, metric, data),
+            AggregatedMetrics::I64(data) => add_metric_data(&mut families, metric, data),
+        }
+    }
+
+    let mut text = String::new();
+    for (name, family) in families {
+        if !family.help.is_empty() {
+            let _ = writeln!(text, "# HELP {name} {}", escape_help(&family.help));
+        }
+        let _ = writeln!(text, "# TYPE {name} {}", family.kind);
+        for sample in family.samples {
+            text.push_str(&sample);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+fn add_metric_data<T: SampleValue>(
+    families: &mut BTreeMap<String, Family>,
+    metric: &Metric,
+    data: &MetricData<T>,
+) {
+    match data {
+        MetricData::Gauge(gauge) => add_gauge(families, metric, gauge),
+        MetricData::Sum(sum) => add_sum(families, metric, sum),
+        MetricData::Histogram(histogram) => add_histogram(families, metric, histogram),
+        MetricData::ExponentialHistogram(_) => {
+            warn!(
+                "Exponential histogram {} cannot be exported to Prometheus",
+                metric.name()
+            );
+        }
+    }
+}
+
+fn family<'a>(
+    families: &'a mut BTreeMap<String, Family>,
+    name: &str,
+    kind: &'static str,
+    metric: &Metric,
+) -> &'a mut Family {
+    families.entry(name.to_string()).or_insert_with(|| Family {
+        kind,
+        help: metric.description().to_string(),
+        samples: vec![],
+    })
+}
+
+fn add_gauge<T: SampleValue>(
+    families: &mut BTreeMap<String, Family>,
+    metric: &Metric,
+    gauge: &Gauge<T>,
+) {
+    let name = metric_name(metric, None);
+    let family = family(families, &name, "gauge", metric);
+    for data_point in gauge.data_points() {
+        family.samples.push(sample(
+            &name,
+            data_point.attributes(),
+            None,
+            data_point.value(),
+        ));
+    }
+}
+
+fn add_sum<T: SampleValue>(families: &mut BTreeMap<String, Family>, metric: &Metric, sum: &Sum<T>) {
+    let (name, kind) = if sum.is_monotonic() {
+        (metric_name(metric, Some("total")), "counter")
+    } else {
+        (metric_name(metric, None), "gauge")
+    };
+    let family = family(families, &name, kind, metric);
+    for data_point in sum.data_points() {
+        family.samples.push(sample(
+            &name,
+            data_point.attributes(),
+            None,
+            data_point.value(),
+        ));
+    }
+}
+
+fn add_histogram<T: SampleValue>(
+    families: &mut BTreeMap<String, Family>,
+    metric: &Metric,
+    histogram: &Histogram<T>,
+) {
+    let name = metric_name(metric, None);
+    let family = family(families, &name, "histogram", metric);
+    let bucket_name = format!("{name}_bucket");
+    for data_point in histogram.data_points() {
+        let mut cumulative_count = 0;
+        let upper_bounds = data_point.bounds().chain([f64::INFINITY]);
+        for (upper_bound, count) in upper_bounds.zip(data_point.bucket_counts()) {
+            cumulative_count += count;
+            family.samples.push(sample(
+                &bucket_name,
+                data_point.attributes(),
+                Some(("le", format_f64(upper_bound))),
+                cumulative_count,
+            ));
+        }
+        family.samples.push(sample(
+            &format!("{name}_sum"),
+            data_point.attributes(),
+            None,
+            data_point.sum(),
+        ));
+        family.samples.push(sample(
+            &format!("{name}_count"),
+            data_point.attributes(),
+            None,
+            data_point.count(),
+        ));
+    }
+}
+
+/// A value as written in a sample line
+trait SampleValue: Copy {
+    fn format(self) -> String;
+}
+
+impl SampleValue for f64 {
+    fn format(self) -> String {
+        format_f64(self)
+    }
+}
+
+impl SampleValue for u64 {
+    fn format(self) -> String {
+        self.to_string()
+    }
+}
+
+impl SampleValue for i64 {
+    fn format(self) -> String {
+        self.to_string()
+    }
+}
+
+fn format_f64(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn sample<'a>(
+    name: &str,
+    attributes: impl Iterator<Item = &'a KeyValue>,
+    extra_label: Option<(&str, String)>,
+    value: impl SampleValue,
+) -> String {
+    let mut labels: Vec<(String, String)> = attributes
+        .map(|kv| (sanitize_name(kv.key.as_str()), kv.value.to_string()))
+        .collect();
+    if let Some((name, value)) = extra_label {
+        labels.push((name.to_string(), value));
+    }
+
+    if labels.is_empty() {
+        format!("{name} {}", value.format())
+    } else {
+        let labels = labels
+            .iter()
+            .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{name}{{{labels}}} {}", value.format())
+    }
+}
+
+/// The Prometheus name of a metric, with its unit and type suffixes
+fn metric_name(metric: &Metric, type_suffix: Option<&str>) -> String {
+    let mut name = sanitize_name(metric.name());
+    let unit_suffix = match metric.unit() {
+        "s" => Some("seconds"),
+        "ms" => Some("milliseconds"),
+        "By" => Some("bytes"),
+        _ => None,
+    };
+    for suffix in unit_suffix.into_iter().chain(type_suffix) {
+        if !name.ends_with(&format!("_{suffix}")) {
+            name = format!("{name}_{suffix}");
+        }
+    }
+    name
+}
+
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_help(help: impl Display) -> String {
+    help.to_string().replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+
+    #[test]
+    fn test_render() {
+        let exporter = PrometheusExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(exporter.reader())
+            .build();
+        let meter = provider.meter("test");
+
+        let counter = meter
+            .u64_counter("signal_set_applied")
+            .with_description("Number of times a signal value was set")
+            .build();
+        counter.add(2, &[KeyValue::new("signal", "http_router")]);
+        counter.add(1, &[KeyValue::new("signal", "http_router")]);
+
+        let active_requests = meter
+            .i64_up_down_counter("http.server.active_requests")
+            .build();
+        active_requests.add(3, &[]);
+        active_requests.add(-1, &[]);
+
+        let duration = meter
+            .f64_histogram("http.server.request.duration")
+            .with_unit("s")
+            .with_boundaries(vec![0.1, 1.0])
+            .build();
+        duration.record(0.0625, &[KeyValue::new("http.request.method", "GET")]);
+        duration.record(0.5, &[KeyValue::new("http.request.method", "GET")]);
+        duration.record(2.0, &[KeyValue::new("http.request.method", "GET")]);
+
+        let text = exporter.render();
+
+        assert!(text.contains(
+            "# HELP signal_set_applied_total Number of times a signal value was set\n\
+             # TYPE signal_set_applied_total counter\n\
+             signal_set_applied_total{signal=\"http_router\"} 3\n"
+        ));
+        assert!(text
+            .contains("# TYPE http_server_active_requests gauge\nhttp_server_active_requests 2\n"));
+        assert!(text.contains("# TYPE http_server_request_duration_seconds histogram\n"));
+        for line in [
+            "http_server_request_duration_seconds_bucket{http_request_method=\"GET\",le=\"0.1\"} 1",
+            "http_server_request_duration_seconds_bucket{http_request_method=\"GET\",le=\"1\"} 2",
+            "http_server_request_duration_seconds_bucket{http_request_method=\"GET\",le=\"+Inf\"} 3",
+            "http_server_request_duration_seconds_sum{http_request_method=\"GET\"} 2.5625",
+            "http_server_request_duration_seconds_count{http_request_method=\"GET\"} 3",
+        ] {
+            assert!(text.contains(line), "missing {line} in\n{text}");
+        }
+    }
+
+    #[test]
+    fn test_names_and_labels() {
+        assert_eq!(
+            sanitize_name("vale_gateway.access_logs.dropped-lines"),
+            "vale_gateway_access_logs_dropped_lines"
+        );
+        assert_eq!(sanitize_name("1st"), "_1st");
+        assert_eq!(escape_label_value("a \"b\"\\\n"), "a \\\"b\\\"\\\\\\n");
+        assert_eq!(format_f64(f64::INFINITY), "+Inf");
+        assert_eq!(format_f64(0.25), "0.25");
+    }
+}
@@ -26,7 +26,7 @@ impl EventHandler for SignalEventHandler {
     fn handle_event(&mut self, event: notify::Result<Event>) {
         if let Ok(event) = event {
             debug!("File watcher event: {:?}", event);
-            if event.kind.is_modify() || event.kind.is_create() {
+            if event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove() {
                 let tx = self.tx.clone();
                 let generation = self.increment_generation();
                 tokio::spawn(async move {
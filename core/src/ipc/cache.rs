@@ -0,0 +1,67 @@
+use crate::ipc::Ref;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// The annotation of a Gateway holding the latest [`CachePurgeRequest`] for it as JSON. Purging
+/// is up to whoever may update the Gateway, and every new value purges its gateways once.
+pub const CACHE_PURGE_ANNOTATION: &str = "vale-gateway.whitefamily.in/cache-purge";
+
+/// Drops the cached responses of a gateway, either for one hostname or all of them
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TypedBuilder, Getters)]
+pub struct CachePurge {
+    #[getset(get = "pub")]
+    gateway_ref: Ref,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TypedBuilder, Getters)]
+#[serde(rename_all = "camelCase")]
+pub struct CachePurgeRequest {
+    /// Tells successive requests for the same hostname apart
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    requested_at: String,
+
+    /// Only purge responses cached for this hostname
+    #[getset(get = "pub")]
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::GatewayEvent;
+
+    #[test]
+    fn test_parse_cache_purge_event() {
+        let purge = CachePurge::builder()
+            .gateway_ref(Ref::builder().namespace("default").name("gateway").build())
+            .hostname(Some("echo.example.com".to_string()))
+            .build();
+        let event = GatewayEvent::CachePurge(purge.clone());
+
+        let data = serde_json::to_string(&purge).expect("serialize");
+        let parsed = GatewayEvent::try_parse(event.as_ref(), data).expect("parse");
+
+        assert_eq!(parsed, event);
+        assert_eq!(parsed.gateway_ref(), purge.gateway_ref());
+    }
+
+    #[test]
+    fn test_parse_cache_purge_request() {
+        let request: CachePurgeRequest = serde_json::from_str(
+            r#"{"requestedAt":"2026-10-18T16:25:20Z","hostname":"echo.example.com"}"#,
+        )
+        .expect("parse");
+
+        assert_eq!(request.requested_at(), "2026-10-18T16:25:20Z");
+        assert_eq!(request.hostname().as_deref(), Some("echo.example.com"));
+    }
+}
@@ -0,0 +1,188 @@
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use typed_builder::TypedBuilder;
+
+/// The response header carrying the version of a gateway configuration
+pub const CONFIGURATION_VERSION_HEADER: &str = "x-vale-gateway-configuration-version";
+
+/// The annotation of a gateway Deployment holding its [`ConfigurationRollout`] as JSON
+pub const CONFIGURATION_ROLLOUT_ANNOTATION: &str =
+    "vale-gateway.whitefamily.in/configuration-rollout";
+
+/// Whether a gateway pod applied a configuration version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum ConfigurationStatus {
+    Applied,
+    Rejected { error: String },
+}
+
+/// Acknowledges (or rejects) one version of a gateway configuration on behalf of a pod
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters,
+)]
+pub struct ConfigurationStatusReport {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    pod_name: String,
+
+    #[getset(get_copy = "pub")]
+    version: u64,
+
+    #[getset(get = "pub")]
+    status: ConfigurationStatus,
+}
+
+/// Where a gateway pod stands relative to the latest configuration version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigurationDrift {
+    InSync,
+    Pending,
+    Rejected,
+}
+
+/// The configuration versions a gateway pod reported
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct PodConfigurationState {
+    #[getset(get_copy = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    applied_version: Option<u64>,
+
+    #[getset(get_copy = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rejected_version: Option<u64>,
+
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl PodConfigurationState {
+    pub fn apply(&mut self, report: &ConfigurationStatusReport) {
+        match report.status() {
+            ConfigurationStatus::Applied => {
+                self.applied_version = Some(report.version());
+                self.rejected_version = None;
+                self.error = None;
+            }
+            ConfigurationStatus::Rejected { error } => {
+                self.rejected_version = Some(report.version());
+                self.error = Some(error.clone());
+            }
+        }
+    }
+
+    pub fn drift(&self, version: u64) -> ConfigurationDrift {
+        if self.applied_version == Some(version) {
+            ConfigurationDrift::InSync
+        } else if self.rejected_version == Some(version) {
+            ConfigurationDrift::Rejected
+        } else {
+            ConfigurationDrift::Pending
+        }
+    }
+}
+
+/// How far the pods of a gateway got in applying its latest configuration version
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters,
+)]
+pub struct ConfigurationRollout {
+    #[getset(get_copy = "pub")]
+    version: u64,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    #[serde(default)]
+    pods: BTreeMap<String, PodConfigurationState>,
+}
+
+impl ConfigurationRollout {
+    /// The pods that are not running the latest version, with their drift
+    pub fn drifted_pods(&self) -> impl Iterator<Item = (&String, ConfigurationDrift)> {
+        self.pods
+            .iter()
+            .map(|(pod_name, state)| (pod_name, state.drift(self.version)))
+            .filter(|(_, drift)| *drift != ConfigurationDrift::InSync)
+    }
+
+    /// Whether at least one pod reported, and all of them applied the latest version
+    pub fn is_complete(&self) -> bool {
+        !self.pods.is_empty() && self.drifted_pods().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_configuration_status_report() {
+        let report = ConfigurationStatusReport::builder()
+            .pod_name("gateway-7d9f8-abcde")
+            .version(42)
+            .status(ConfigurationStatus::Rejected {
+                error: "invalid listener".to_string(),
+            })
+            .build();
+
+        let json = serde_json::to_value(&report).expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pod_name": "gateway-7d9f8-abcde",
+                "version": 42,
+                "status": { "result": "rejected", "error": "invalid listener" },
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<ConfigurationStatusReport>(json).expect("deserialize"),
+            report
+        );
+    }
+
+    #[test]
+    fn test_configuration_drift() {
+        let report = |version, status| {
+            ConfigurationStatusReport::builder()
+                .pod_name("gateway-a")
+                .version(version)
+                .status(status)
+                .build()
+        };
+        let rejected = ConfigurationStatus::Rejected {
+            error: "invalid listener".to_string(),
+        };
+
+        let mut state = PodConfigurationState::default();
+        assert_eq!(state.drift(1), ConfigurationDrift::Pending);
+
+        state.apply(&report(1, ConfigurationStatus::Applied));
+        assert_eq!(state.drift(1), ConfigurationDrift::InSync);
+
+        state.apply(&report(2, rejected));
+        assert_eq!(state.drift(2), ConfigurationDrift::Rejected);
+        assert_eq!(state.applied_version(), Some(1));
+        assert_eq!(state.error().as_deref(), Some("invalid listener"));
+
+        state.apply(&report(3, ConfigurationStatus::Applied));
+        assert_eq!(state.drift(3), ConfigurationDrift::InSync);
+        assert_eq!(state.error(), &None);
+
+        let rollout = ConfigurationRollout::builder()
+            .version(3)
+            .pods(BTreeMap::from([
+                ("gateway-a".to_string(), state),
+                ("gateway-b".to_string(), PodConfigurationState::default()),
+            ]))
+            .build();
+        assert!(!rollout.is_complete());
+        assert_eq!(
+            rollout.drifted_pods().collect::<Vec<_>>(),
+            vec![(&"gateway-b".to_string(), ConfigurationDrift::Pending)]
+        );
+        assert!(!ConfigurationRollout::default().is_complete());
+    }
+}
@@ -0,0 +1,526 @@
+use crate::config::gateway::types::GatewayConfiguration;
+use crate::config::gateway::types::http::router::{
+    HttpRoute, HttpRouteRule, HttpRouteRuleUniqueId,
+};
+use crate::config::gateway::types::net::{Backend, Endpoint};
+use crate::ipc::Ref;
+use crate::net::Port;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use thiserror::Error;
+use typed_builder::TypedBuilder;
+
+/// Identifies a backend within a rule
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters,
+)]
+pub struct BackendRef {
+    #[getset(get = "pub")]
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    name: String,
+
+    #[getset(get_copy = "pub")]
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    port: Option<Port>,
+}
+
+impl From<&Backend> for BackendRef {
+    fn from(backend: &Backend) -> Self {
+        Self {
+            namespace: backend.namespace().clone(),
+            name: backend.name().clone(),
+            port: *backend.port(),
+        }
+    }
+}
+
+impl BackendRef {
+    fn matches(&self, backend: &Backend) -> bool {
+        self.name == *backend.name()
+            && self.namespace == *backend.namespace()
+            && self.port == *backend.port()
+    }
+}
+
+impl Display for BackendRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{namespace}/")?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port.get())?;
+        }
+        Ok(())
+    }
+}
+
+/// One change to the HTTP routes of a gateway configuration, routes being addressed by key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ConfigurationChange {
+    /// Adds a route, or replaces the route with the same key in place
+    UpsertRoute {
+        route: HttpRoute,
+    },
+    RemoveRoute {
+        route_key: String,
+    },
+    /// Adds a rule to a route, or replaces the rule with the same unique id in place
+    UpsertRule {
+        route_key: String,
+        rule: HttpRouteRule,
+    },
+    RemoveRule {
+        route_key: String,
+        rule_id: HttpRouteRuleUniqueId,
+    },
+    /// Replaces the endpoints of a backend, leaving the rest of its rule untouched
+    SetBackendEndpoints {
+        route_key: String,
+        rule_id: HttpRouteRuleUniqueId,
+        backend: BackendRef,
+        endpoints: Vec<Endpoint>,
+    },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ApplyChangeError {
+    #[error("Route has no key")]
+    MissingRouteKey,
+    #[error("Unknown route {0}")]
+    UnknownRoute(String),
+    #[error("Unknown rule {1} in route {0}")]
+    UnknownRule(String, String),
+    #[error("Unknown backend {1} in rule {0}")]
+    UnknownBackend(String, String),
+}
+
+impl ConfigurationChange {
+    /// Applies the change, failing when it addresses a route, rule or backend that does not exist
+    pub fn apply(&self, configuration: &mut GatewayConfiguration) -> Result<(), ApplyChangeError> {
+        match self {
+            ConfigurationChange::UpsertRoute { route } => {
+                let route_key = route
+                    .key()
+                    .as_ref()
+                    .ok_or(ApplyChangeError::MissingRouteKey)?;
+                let routes = configuration.http_routes_mut();
+                match routes
+                    .iter_mut()
+                    .find(|r| r.key().as_ref() == Some(route_key))
+                {
+                    Some(existing) => *existing = route.clone(),
+                    None => routes.push(route.clone()),
+                }
+            }
+            ConfigurationChange::RemoveRoute { route_key } => {
+                let routes = configuration.http_routes_mut();
+                let index = routes
+                    .iter()
+                    .position(|r| r.key().as_ref() == Some(route_key))
+                    .ok_or_else(|| ApplyChangeError::UnknownRoute(route_key.clone()))?;
+                routes.remove(index);
+            }
+            ConfigurationChange::UpsertRule { route_key, rule } => {
+                let rules = route_mut(configuration, route_key)?.rules_mut();
+                match rules.iter_mut().find(|r| r.unique_id() == rule.unique_id()) {
+                    Some(existing) => *existing = rule.clone(),
+                    None => rules.push(rule.clone()),
+                }
+            }
+            ConfigurationChange::RemoveRule { route_key, rule_id } => {
+                let rules = route_mut(configuration, route_key)?.rules_mut();
+                let index = rules
+                    .iter()
+                    .position(|r| r.unique_id() == rule_id)
+                    .ok_or_else(|| {
+                        ApplyChangeError::UnknownRule(route_key.clone(), rule_id.get().clone())
+                    })?;
+                rules.remove(index);
+            }
+            ConfigurationChange::SetBackendEndpoints {
+                route_key,
+                rule_id,
+                backend,
+                endpoints,
+            } => {
+                let rule = route_mut(configuration, route_key)?
+                    .rules_mut()
+                    .iter_mut()
+                    .find(|r| r.unique_id() == rule_id)
+                    .ok_or_else(|| {
+                        ApplyChangeError::UnknownRule(route_key.clone(), rule_id.get().clone())
+                    })?;
+                let target = rule
+                    .backends_mut()
+                    .iter_mut()
+                    .find(|b| backend.matches(b))
+                    .ok_or_else(|| {
+                        ApplyChangeError::UnknownBackend(rule_id.get().clone(), backend.to_string())
+                    })?;
+                *target.endpoints_mut() = endpoints.clone();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn route_mut<'a>(
+    configuration: &'a mut GatewayConfiguration,
+    route_key: &str,
+) -> Result<&'a mut HttpRoute, ApplyChangeError> {
+    configuration
+        .http_routes_mut()
+        .iter_mut()
+        .find(|r| r.key().as_deref() == Some(route_key))
+        .ok_or_else(|| ApplyChangeError::UnknownRoute(route_key.to_string()))
+}
+
+/// Moves a gateway from one configuration version to the next
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters,
+)]
+pub struct ConfigurationDelta {
+    #[getset(get = "pub")]
+    gateway_ref: Ref,
+
+    /// The version the changes apply to
+    #[getset(get_copy = "pub")]
+    base_version: u64,
+
+    /// The version the changes lead to
+    #[getset(get_copy = "pub")]
+    version: u64,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    changes: Vec<ConfigurationChange>,
+}
+
+impl ConfigurationDelta {
+    /// Applies the changes to a copy of the configuration at `base_version`
+    pub fn apply(
+        &self,
+        configuration: &GatewayConfiguration,
+    ) -> Result<GatewayConfiguration, ApplyChangeError> {
+        apply(configuration, &self.changes)
+    }
+}
+
+/// Applies changes in order to a copy of the configuration
+pub fn apply(
+    configuration: &GatewayConfiguration,
+    changes: &[ConfigurationChange],
+) -> Result<GatewayConfiguration, ApplyChangeError> {
+    let mut configuration = configuration.clone();
+    for change in changes {
+        change.apply(&mut configuration)?;
+    }
+    Ok(configuration)
+}
+
+/// The changes turning `previous` into `next`, or `None` when a full snapshot is needed:
+/// anything besides HTTP routes changed, routes cannot be told apart by key, or routes
+/// and rules were reordered.
+pub fn diff(
+    previous: &GatewayConfiguration,
+    next: &GatewayConfiguration,
+) -> Option<Vec<ConfigurationChange>> {
+    if !previous.eq_except_http_routes(next) {
+        return None;
+    }
+
+    let previous_routes = routes_by_key(previous.http_routes())?;
+    let next_routes = routes_by_key(next.http_routes())?;
+
+    let mut changes = Vec::new();
+    for route in previous.http_routes() {
+        let route_key = route.key().as_deref().unwrap_or_default();
+        if !next_routes.contains_key(route_key) {
+            changes.push(ConfigurationChange::RemoveRoute {
+                route_key: route_key.to_string(),
+            });
+        }
+    }
+    for route in next.http_routes() {
+        let route_key = route.key().as_deref().unwrap_or_default();
+        match previous_routes.get(route_key) {
+            Some(previous_route) if *previous_route == route => {}
+            Some(previous_route)
+                if previous_route.host_header_matches() == route.host_header_matches() =>
+            {
+                diff_rules(route_key, previous_route, route, &mut changes);
+            }
+            _ => changes.push(ConfigurationChange::UpsertRoute {
+                route: route.clone(),
+            }),
+        }
+    }
+
+    // Changes cannot reorder routes or rules, so they must reproduce the next configuration
+    match apply(previous, &changes) {
+        Ok(applied) if applied == *next => Some(changes),
+        _ => None,
+    }
+}
+
+fn routes_by_key(routes: &[HttpRoute]) -> Option<HashMap<&str, &HttpRoute>> {
+    let mut by_key = HashMap::with_capacity(routes.len());
+    for route in routes {
+        if by_key.insert(route.key().as_deref()?, route).is_some() {
+            return None;
+        }
+    }
+    Some(by_key)
+}
+
+fn diff_rules(
+    route_key: &str,
+    previous: &HttpRoute,
+    next: &HttpRoute,
+    changes: &mut Vec<ConfigurationChange>,
+) {
+    let previous_rules: HashMap<_, _> = previous
+        .rules()
+        .iter()
+        .map(|rule| (rule.unique_id(), rule))
+        .collect();
+    let next_rule_ids: HashSet<_> = next.rules().iter().map(|rule| rule.unique_id()).collect();
+    if previous_rules.len() != previous.rules().len() || next_rule_ids.len() != next.rules().len() {
+        changes.push(ConfigurationChange::UpsertRoute {
+            route: next.clone(),
+        });
+        return;
+    }
+
+    for rule in previous.rules() {
+        if !next_rule_ids.contains(rule.unique_id()) {
+            changes.push(ConfigurationChange::RemoveRule {
+                route_key: route_key.to_string(),
+                rule_id: rule.unique_id().clone(),
+            });
+        }
+    }
+    for rule in next.rules() {
+        match previous_rules.get(rule.unique_id()) {
+            Some(previous_rule) if *previous_rule == rule => {}
+            Some(previous_rule) if only_endpoints_changed(previous_rule, rule) => {
+                for (previous_backend, backend) in
+                    previous_rule.backends().iter().zip(rule.backends())
+                {
+                    if previous_backend.endpoints() != backend.endpoints() {
+                        changes.push(ConfigurationChange::SetBackendEndpoints {
+                            route_key: route_key.to_string(),
+                            rule_id: rule.unique_id().clone(),
+                            backend: backend.into(),
+                            endpoints: backend.endpoints().clone(),
+                        });
+                    }
+                }
+            }
+            _ => changes.push(ConfigurationChange::UpsertRule {
+                route_key: route_key.to_string(),
+                rule: rule.clone(),
+            }),
+        }
+    }
+}
+
+/// Whether the rules only differ in the endpoints of backends that can be told apart
+fn only_endpoints_changed(previous: &HttpRouteRule, next: &HttpRouteRule) -> bool {
+    let backend_refs: HashSet<BackendRef> = next.backends().iter().map(Into::into).collect();
+
+    previous.matches() == next.matches()
+        && previous.filters() == next.filters()
+        && previous.backends().len() == next.backends().len()
+        && backend_refs.len() == next.backends().len()
+        && previous
+            .backends()
+            .iter()
+            .zip(next.backends())
+            .all(|(previous, next)| previous.eq_except_endpoints(next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gateway::types::GatewayConfigurationBuilder;
+    use crate::ipc::GatewayEvent;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn configuration(routes: &[(&str, &[(&str, &[u8])])], ipc_port: u16) -> GatewayConfiguration {
+        let mut builder = GatewayConfigurationBuilder::default();
+        builder.with_ipc(|ipc| {
+            ipc.with_endpoint(IpAddr::V4(Ipv4Addr::LOCALHOST), Port::new(ipc_port));
+        });
+        for (route_key, rules) in routes {
+            builder.add_http_route(|route| {
+                route.with_key(route_key);
+                for (rule_id, endpoints) in *rules {
+                    route.add_rule(rule_id, |rule| {
+                        rule.add_match(|matches| {
+                            matches.with_path_prefix("/");
+                        });
+                        rule.add_backend(|backend| {
+                            backend
+                                .named("echo")
+                                .with_namespace(Some("default"))
+                                .with_port(Some(Port::new(8080)));
+                            for octet in *endpoints {
+                                backend.add_endpoint(
+                                    IpAddr::V4(Ipv4Addr::new(10, 0, 0, *octet)),
+                                    |_| {},
+                                );
+                            }
+                        });
+                    });
+                }
+            });
+        }
+        builder.build().expect("Failed to build configuration")
+    }
+
+    #[test]
+    fn test_diff_endpoints() {
+        let previous = configuration(
+            &[
+                ("default/a", &[("a-0", &[1, 2])]),
+                ("default/b", &[("b-0", &[3])]),
+            ],
+            8081,
+        );
+        let next = configuration(
+            &[
+                ("default/a", &[("a-0", &[1, 4])]),
+                ("default/b", &[("b-0", &[3])]),
+            ],
+            8081,
+        );
+
+        let changes = diff(&previous, &next).expect("delta");
+        assert_eq!(
+            changes,
+            vec![ConfigurationChange::SetBackendEndpoints {
+                route_key: "default/a".to_string(),
+                rule_id: HttpRouteRuleUniqueId::new("a-0"),
+                backend: BackendRef::builder()
+                    .namespace(Some("default".to_string()))
+                    .name("echo")
+                    .port(Some(Port::new(8080)))
+                    .build(),
+                endpoints: next.http_routes()[0].rules()[0].backends()[0]
+                    .endpoints()
+                    .clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_routes_and_rules() {
+        let previous = configuration(
+            &[
+                ("default/a", &[("a-0", &[1]), ("a-1", &[2])]),
+                ("default/b", &[("b-0", &[3])]),
+            ],
+            8081,
+        );
+        let next = configuration(
+            &[
+                ("default/a", &[("a-0", &[1])]),
+                ("default/c", &[("c-0", &[5])]),
+            ],
+            8081,
+        );
+
+        let changes = diff(&previous, &next).expect("delta");
+        assert_eq!(changes.len(), 3);
+        assert!(changes.contains(&ConfigurationChange::RemoveRoute {
+            route_key: "default/b".to_string()
+        }));
+        assert!(changes.contains(&ConfigurationChange::RemoveRule {
+            route_key: "default/a".to_string(),
+            rule_id: HttpRouteRuleUniqueId::new("a-1"),
+        }));
+        assert!(changes.contains(&ConfigurationChange::UpsertRoute {
+            route: next.http_routes()[1].clone()
+        }));
+    }
+
+    #[test]
+    fn test_diff_requires_snapshot() {
+        let previous = configuration(&[("default/a", &[("a-0", &[1])])], 8081);
+
+        // Changes outside of routes
+        let next = configuration(&[("default/a", &[("a-0", &[1])])], 8082);
+        assert_eq!(diff(&previous, &next), None);
+
+        // Reordered routes
+        let previous = configuration(
+            &[
+                ("default/a", &[("a-0", &[1])]),
+                ("default/b", &[("b-0", &[2])]),
+            ],
+            8081,
+        );
+        let next = configuration(
+            &[
+                ("default/b", &[("b-0", &[2])]),
+                ("default/a", &[("a-0", &[1])]),
+            ],
+            8081,
+        );
+        assert_eq!(diff(&previous, &next), None);
+    }
+
+    #[test]
+    fn test_apply_unknown_rule() {
+        let configuration = configuration(&[("default/a", &[("a-0", &[1])])], 8081);
+        let delta = ConfigurationDelta::builder()
+            .gateway_ref(Ref::builder().namespace("default").name("gateway").build())
+            .base_version(1)
+            .version(2)
+            .changes(vec![ConfigurationChange::RemoveRule {
+                route_key: "default/a".to_string(),
+                rule_id: HttpRouteRuleUniqueId::new("a-1"),
+            }])
+            .build();
+
+        assert_eq!(
+            delta.apply(&configuration),
+            Err(ApplyChangeError::UnknownRule(
+                "default/a".to_string(),
+                "a-1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_configuration_delta_event() {
+        let previous = configuration(&[("default/a", &[("a-0", &[1])])], 8081);
+        let next = configuration(&[("default/a", &[("a-0", &[2])])], 8081);
+        let delta = ConfigurationDelta::builder()
+            .gateway_ref(Ref::builder().namespace("default").name("gateway").build())
+            .base_version(1)
+            .version(2)
+            .changes(diff(&previous, &next).expect("delta"))
+            .build();
+        let event = GatewayEvent::ConfigurationDelta(delta.clone());
+
+        let data = serde_json::to_string(&delta).expect("serialize");
+        let parsed = GatewayEvent::try_parse(event.as_ref(), data).expect("parse");
+
+        assert_eq!(parsed, event);
+        assert_eq!(delta.apply(&previous), Ok(next));
+    }
+}
@@ -1,4 +1,11 @@
+pub mod cache;
+pub mod configuration;
+pub mod delta;
+pub mod rate_limits;
+
 use crate::instrumentation::{KeyValueCollector, KeyValues};
+use crate::ipc::cache::CachePurge;
+use crate::ipc::delta::ConfigurationDelta;
 use getset::Getters;
 use opentelemetry::{StringValue, Value};
 use schemars::_private::serde_json;
@@ -6,7 +13,11 @@ use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, IntoStaticStr};
 use typed_builder::TypedBuilder;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The audience of the projected ServiceAccount tokens gateways authenticate to the
+/// control plane with
+pub const IPC_TOKEN_AUDIENCE: &str = "vale-gateway-ipc";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Event {
     Gateway(GatewayEvent),
@@ -42,12 +53,14 @@ impl From<&Ref> for Value {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, IntoStaticStr, AsRefStr)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, IntoStaticStr, AsRefStr)]
 #[non_exhaustive]
 #[strum(serialize_all = "snake_case")]
 pub enum GatewayEvent {
     ConfigurationUpdate(Ref),
+    ConfigurationDelta(ConfigurationDelta),
     Deleted(Ref),
+    CachePurge(CachePurge),
 }
 
 impl KeyValues for GatewayEvent {
@@ -57,10 +70,18 @@ impl KeyValues for GatewayEvent {
                 collector.add("event_type", "Gateway::ConfigurationUpdate");
                 collector.add("gateway_ref", ref_);
             }
+            GatewayEvent::ConfigurationDelta(delta) => {
+                collector.add("event_type", "Gateway::ConfigurationDelta");
+                collector.add("gateway_ref", delta.gateway_ref());
+            }
             GatewayEvent::Deleted(ref_) => {
                 collector.add("event_type", "Gateway::Deleted");
                 collector.add("gateway_ref", ref_);
             }
+            GatewayEvent::CachePurge(purge) => {
+                collector.add("event_type", "Gateway::CachePurge");
+                collector.add("gateway_ref", purge.gateway_ref());
+            }
         }
     }
 }
@@ -71,6 +92,8 @@ impl GatewayEvent {
             GatewayEvent::ConfigurationUpdate(gateway_ref) | GatewayEvent::Deleted(gateway_ref) => {
                 gateway_ref
             }
+            GatewayEvent::ConfigurationDelta(delta) => delta.gateway_ref(),
+            GatewayEvent::CachePurge(purge) => purge.gateway_ref(),
         }
     }
 
@@ -82,11 +105,22 @@ impl GatewayEvent {
                 })?;
                 Ok(GatewayEvent::ConfigurationUpdate(ref_))
             }
+            "configuration_delta" => {
+                let delta = serde_json::from_str(data.as_ref()).map_err(|e| {
+                    format!("Failed to parse GatewayEvent::ConfigurationDelta: {e}")
+                })?;
+                Ok(GatewayEvent::ConfigurationDelta(delta))
+            }
             "deleted" => {
                 let ref_ = serde_json::from_str(data.as_ref())
                     .map_err(|e| format!("Failed to parse GatewayEvent::Deleted: {e}"))?;
                 Ok(GatewayEvent::Deleted(ref_))
             }
+            "cache_purge" => {
+                let purge = serde_json::from_str(data.as_ref())
+                    .map_err(|e| format!("Failed to parse GatewayEvent::CachePurge: {e}"))?;
+                Ok(GatewayEvent::CachePurge(purge))
+            }
             _ => Err(format!("Unknown event type: {}", event.as_ref())),
         }
     }
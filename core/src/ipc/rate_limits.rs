@@ -0,0 +1,238 @@
+use crate::ipc::Ref;
+use crate::types::filters::rate_limit::Key;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use typed_builder::TypedBuilder;
+
+/// How long a gateway pod counts as a replica after its last sync
+const REPLICA_TTL: Duration = Duration::from_secs(10);
+
+/// The token bucket parameters of a rate limit filter, as configured on the gateway
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TypedBuilder, CopyGetters)]
+pub struct RateLimitBucketLimit {
+    #[getset(get_copy = "pub")]
+    capacity: f64,
+
+    #[getset(get_copy = "pub")]
+    refill_per_second: f64,
+}
+
+/// Tokens consumed by a gateway pod for one key since its previous sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters)]
+pub struct RateLimitUsage {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    filter: Key,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: String,
+
+    #[getset(get_copy = "pub")]
+    limit: RateLimitBucketLimit,
+
+    #[getset(get_copy = "pub")]
+    consumed: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TypedBuilder, Getters)]
+pub struct RateLimitSyncRequest {
+    #[getset(get = "pub")]
+    #[builder(default)]
+    usages: Vec<RateLimitUsage>,
+}
+
+/// The cluster-wide tokens left for one key after all reported consumption
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters)]
+pub struct RateLimitBucketState {
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    filter: Key,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    key: String,
+
+    #[getset(get_copy = "pub")]
+    tokens: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypedBuilder, Getters, CopyGetters)]
+pub struct RateLimitSyncResponse {
+    /// Number of gateway pods currently sharing the limits of the gateway
+    #[getset(get_copy = "pub")]
+    replicas: u32,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    buckets: Vec<RateLimitBucketState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GlobalBucket {
+    limit: RateLimitBucketLimit,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl GlobalBucket {
+    fn refilled(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        (self.tokens + elapsed.as_secs_f64() * self.limit.refill_per_second())
+            .min(self.limit.capacity())
+    }
+}
+
+type BucketKey = (Ref, Key, String);
+
+#[derive(Debug, Default)]
+struct GlobalRateLimitsState {
+    buckets: HashMap<BucketKey, GlobalBucket>,
+    replicas: HashMap<Ref, HashMap<String, Instant>>,
+}
+
+/// Aggregates the token consumption reported by every pod of a gateway into one
+/// bucket per limit key, approximating a cluster-wide rate limit.
+#[derive(Debug, Default)]
+pub struct GlobalRateLimits {
+    state: Mutex<GlobalRateLimitsState>,
+}
+
+impl GlobalRateLimits {
+    pub fn sync(
+        &self,
+        gateway_ref: &Ref,
+        pod_name: &str,
+        request: &RateLimitSyncRequest,
+        now: Instant,
+    ) -> RateLimitSyncResponse {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let pods = state.replicas.entry(gateway_ref.clone()).or_default();
+        pods.insert(pod_name.to_string(), now);
+        pods.retain(|_, synced_at| now.saturating_duration_since(*synced_at) < REPLICA_TTL);
+        let replicas = u32::try_from(pods.len()).unwrap_or(u32::MAX);
+
+        let buckets = request
+            .usages()
+            .iter()
+            .map(|usage| {
+                let limit = usage.limit();
+                let bucket = state
+                    .buckets
+                    .entry((
+                        gateway_ref.clone(),
+                        usage.filter().clone(),
+                        usage.key().clone(),
+                    ))
+                    .or_insert_with(|| GlobalBucket {
+                        limit,
+                        tokens: limit.capacity(),
+                        updated_at: now,
+                    });
+
+                // The latest reported limit wins, in case the filter was reconfigured
+                bucket.limit = limit;
+                bucket.tokens = (bucket.refilled(now) - f64::from(usage.consumed())).max(0.0);
+                bucket.updated_at = now;
+
+                RateLimitBucketState::builder()
+                    .filter(usage.filter().clone())
+                    .key(usage.key().clone())
+                    .tokens(bucket.tokens)
+                    .build()
+            })
+            .collect();
+
+        // Buckets that have refilled completely are indistinguishable from new ones
+        state
+            .buckets
+            .retain(|_, bucket| bucket.refilled(now) < bucket.limit.capacity());
+
+        RateLimitSyncResponse::builder()
+            .replicas(replicas)
+            .buckets(buckets)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gateway_ref() -> Ref {
+        Ref::builder().namespace("default").name("gateway").build()
+    }
+
+    fn usage(consumed: u32) -> RateLimitUsage {
+        RateLimitUsage::builder()
+            .filter("default/limit")
+            .key("client")
+            .limit(
+                RateLimitBucketLimit::builder()
+                    .capacity(10.0)
+                    .refill_per_second(1.0)
+                    .build(),
+            )
+            .consumed(consumed)
+            .build()
+    }
+
+    fn assert_tokens(response: &RateLimitSyncResponse, expected: f64) {
+        let tokens = response.buckets()[0].tokens();
+        assert!((tokens - expected).abs() < 1e-9, "{tokens} != {expected}");
+    }
+
+    #[test]
+    fn test_sync_aggregates_consumption_across_pods() {
+        let global = GlobalRateLimits::default();
+        let now = Instant::now();
+
+        let request = RateLimitSyncRequest::builder()
+            .usages(vec![usage(3)])
+            .build();
+        let response = global.sync(&gateway_ref(), "pod-a", &request, now);
+        assert_eq!(response.replicas(), 1);
+        assert_tokens(&response, 7.0);
+
+        let request = RateLimitSyncRequest::builder()
+            .usages(vec![usage(4)])
+            .build();
+        let response = global.sync(&gateway_ref(), "pod-b", &request, now);
+        assert_eq!(response.replicas(), 2);
+        assert_tokens(&response, 3.0);
+    }
+
+    #[test]
+    fn test_sync_refills_and_expires_replicas() {
+        let global = GlobalRateLimits::default();
+        let now = Instant::now();
+
+        let request = RateLimitSyncRequest::builder()
+            .usages(vec![usage(10)])
+            .build();
+        global.sync(&gateway_ref(), "pod-a", &request, now);
+
+        let later = now + Duration::from_secs(5);
+        let request = RateLimitSyncRequest::builder()
+            .usages(vec![usage(0)])
+            .build();
+        let response = global.sync(&gateway_ref(), "pod-b", &request, later);
+        assert_eq!(response.replicas(), 2);
+        assert_tokens(&response, 5.0);
+
+        let response = global.sync(
+            &gateway_ref(),
+            "pod-b",
+            &RateLimitSyncRequest::default(),
+            now + REPLICA_TTL,
+        );
+        assert_eq!(response.replicas(), 1);
+    }
+}
@@ -348,4 +348,66 @@ macro_rules! await_ready {
             _ => ReadyState::NotReady,
         }
     };
+    // Ten receivers
+    ($r1:ident, $r2:ident, $r3:ident, $r4:ident, $r5:ident, $r6:ident, $r7:ident, $r8:ident, $r9:ident, $r10:ident) => {
+        match (
+            $r1.get().await.as_ref(),
+            $r2.get().await.as_ref(),
+            $r3.get().await.as_ref(),
+            $r4.get().await.as_ref(),
+            $r5.get().await.as_ref(),
+            $r6.get().await.as_ref(),
+            $r7.get().await.as_ref(),
+            $r8.get().await.as_ref(),
+            $r9.get().await.as_ref(),
+            $r10.get().await.as_ref(),
+        ) {
+            (
+                Some(val1),
+                Some(val2),
+                Some(val3),
+                Some(val4),
+                Some(val5),
+                Some(val6),
+                Some(val7),
+                Some(val8),
+                Some(val9),
+                Some(val10),
+            ) => ReadyState::Ready((val1, val2, val3, val4, val5, val6, val7, val8, val9, val10)),
+            _ => ReadyState::NotReady,
+        }
+    };
+    // Eleven receivers
+    ($r1:ident, $r2:ident, $r3:ident, $r4:ident, $r5:ident, $r6:ident, $r7:ident, $r8:ident, $r9:ident, $r10:ident, $r11:ident) => {
+        match (
+            $r1.get().await.as_ref(),
+            $r2.get().await.as_ref(),
+            $r3.get().await.as_ref(),
+            $r4.get().await.as_ref(),
+            $r5.get().await.as_ref(),
+            $r6.get().await.as_ref(),
+            $r7.get().await.as_ref(),
+            $r8.get().await.as_ref(),
+            $r9.get().await.as_ref(),
+            $r10.get().await.as_ref(),
+            $r11.get().await.as_ref(),
+        ) {
+            (
+                Some(val1),
+                Some(val2),
+                Some(val3),
+                Some(val4),
+                Some(val5),
+                Some(val6),
+                Some(val7),
+                Some(val8),
+                Some(val9),
+                Some(val10),
+                Some(val11),
+            ) => ReadyState::Ready((
+                val1, val2, val3, val4, val5, val6, val7, val8, val9, val10, val11,
+            )),
+            _ => ReadyState::NotReady,
+        }
+    };
 }
@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 use std::borrow::Cow;
 use std::fmt::Display;
+use std::net::IpAddr;
 use std::str::FromStr;
+use thiserror::Error;
 
 #[derive(
     Validate, Getters, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Hash,
@@ -110,6 +112,102 @@ impl JsonSchema for Hostname {
     }
 }
 
+/// Where an endpoint is reached, an IP address from an `EndpointSlice` or a hostname the gateway
+/// resolves itself. Both are encoded as strings
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum EndpointAddress {
+    Ip(IpAddr),
+    Hostname(Hostname),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid endpoint address {0:?}, expected an IP address or a hostname")]
+pub struct InvalidEndpointAddress(String);
+
+impl EndpointAddress {
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Self::Ip(ip) => Some(*ip),
+            Self::Hostname(_) => None,
+        }
+    }
+
+    pub fn hostname(&self) -> Option<&Hostname> {
+        match self {
+            Self::Ip(_) => None,
+            Self::Hostname(hostname) => Some(hostname),
+        }
+    }
+}
+
+impl From<IpAddr> for EndpointAddress {
+    fn from(ip: IpAddr) -> Self {
+        Self::Ip(ip)
+    }
+}
+
+impl From<Hostname> for EndpointAddress {
+    fn from(hostname: Hostname) -> Self {
+        Self::Hostname(hostname)
+    }
+}
+
+impl Display for EndpointAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "{ip}"),
+            Self::Hostname(hostname) => write!(f, "{}", hostname.as_ref()),
+        }
+    }
+}
+
+impl FromStr for EndpointAddress {
+    type Err = InvalidEndpointAddress;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ip) = s.parse() {
+            return Ok(Self::Ip(ip));
+        }
+
+        // Suffixes are hostnames too, but nothing to resolve
+        let hostname = Hostname::new(s.to_ascii_lowercase());
+        if s.starts_with('.') || hostname.validate().is_err() {
+            return Err(InvalidEndpointAddress(s.to_string()));
+        }
+        Ok(Self::Hostname(hostname))
+    }
+}
+
+impl TryFrom<String> for EndpointAddress {
+    type Error = InvalidEndpointAddress;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<EndpointAddress> for String {
+    fn from(address: EndpointAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl JsonSchema for EndpointAddress {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::from(stringify!(EndpointAddress))
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "An IP address or a hostname",
+            "minLength": 1,
+            "maxLength": 253
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,6 +421,42 @@ mod tests {
         assert_eq!(map.get(&Hostname::new("Example.Com")), Some(&"value2"));
     }
 
+    #[rstest]
+    #[case("10.0.0.1", Ok(EndpointAddress::Ip("10.0.0.1".parse().unwrap())))]
+    #[case("fd00::1", Ok(EndpointAddress::Ip("fd00::1".parse().unwrap())))]
+    #[case(
+        "API.Example.com",
+        Ok(EndpointAddress::Hostname(Hostname::new("api.example.com")))
+    )]
+    #[case(".example.com", Err(InvalidEndpointAddress(".example.com".to_string())))]
+    #[case("not a host", Err(InvalidEndpointAddress("not a host".to_string())))]
+    fn test_endpoint_address_from_str(
+        #[case] input: &str,
+        #[case] expected: Result<EndpointAddress, InvalidEndpointAddress>,
+    ) {
+        assert_eq!(EndpointAddress::from_str(input), expected);
+    }
+
+    #[test]
+    fn test_endpoint_address_serialization() {
+        let ip = EndpointAddress::Ip("10.0.0.1".parse().unwrap());
+        let hostname = EndpointAddress::Hostname(Hostname::new("api.example.com"));
+
+        assert_eq!(assert_ok!(serde_json::to_string(&ip)), "\"10.0.0.1\"");
+        assert_eq!(
+            assert_ok!(serde_json::to_string(&hostname)),
+            "\"api.example.com\""
+        );
+
+        // The binary encoding of the configuration keeps addresses as strings too
+        for address in [ip, hostname] {
+            let mut bytes = Vec::new();
+            assert_ok!(ciborium::into_writer(&address, &mut bytes));
+            let decoded: EndpointAddress = assert_ok!(ciborium::from_reader(bytes.as_slice()));
+            assert_eq!(decoded, address);
+        }
+    }
+
     proptest! {
         #[test]
         fn test_hostname_properties(
@@ -11,6 +11,13 @@ static SET_APPLIED: LazyLock<Counter<u64>> = LazyLock::new(|| {
         .build()
 });
 
+static SET_SKIPPED: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    METER
+        .u64_counter("signal_set_skipped")
+        .with_description("Number of times a signal value was not set because it was unchanged")
+        .build()
+});
+
 #[track_caller]
 #[inline]
 pub fn record_set_applied(name: &'static str) {
@@ -20,6 +27,7 @@ pub fn record_set_applied(name: &'static str) {
 
 #[track_caller]
 #[inline]
-pub fn record_set_skipped(_name: &'static str) {
+pub fn record_set_skipped(name: &'static str) {
     trace!("Skipped setting value in signal");
+    SET_SKIPPED.add(1, &[KeyValue::new("signal", name)]);
 }
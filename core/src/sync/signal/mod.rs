@@ -1,6 +1,10 @@
 mod instrumentation;
+mod registry;
+
+pub use registry::{signal_statuses, SignalStatus};
 
 use crate::sync::signal::instrumentation::{record_set_applied, record_set_skipped};
+use crate::sync::signal::registry::SignalState;
 use anyhow::Result;
 use atomic_refcell::AtomicRefCell;
 use std::sync::Arc;
@@ -15,18 +19,21 @@ pub struct RecvError;
 
 pub fn signal<T: PartialEq>(name: &'static str) -> (Sender<T>, Receiver<T>) {
     let data = Arc::new(RwLock::new(None));
+    let state = SignalState::register(name);
     let (tx, rx) = channel(10);
     (
         Sender {
             name,
             data: data.clone(),
             tx: tx.clone(),
+            state: state.clone(),
         },
         Receiver {
             name,
             tx,
             rx: AtomicRefCell::new(rx),
             data,
+            _state: state,
         },
     )
 }
@@ -36,6 +43,7 @@ pub struct Sender<T: PartialEq> {
     name: &'static str,
     data: Arc<RwLock<Option<T>>>,
     tx: BroadcastSender<()>,
+    state: Arc<SignalState>,
 }
 
 impl<T: PartialEq> Sender<T> {
@@ -61,6 +69,7 @@ impl<T: PartialEq> Sender<T> {
 
         if let Some(value) = value {
             self.data.write().await.replace(value);
+            self.state.record_change(true);
             let _ = self.tx.send(());
         }
     }
@@ -69,6 +78,7 @@ impl<T: PartialEq> Sender<T> {
         if self.data.read().await.is_some() {
             trace!("Clearing value in signal");
             self.data.write().await.take();
+            self.state.record_change(false);
             let _ = self.tx.send(());
         } else {
             trace!("No value to clear in signal");
@@ -81,10 +91,12 @@ impl<T: PartialEq> Sender<T> {
         } else if let Some(value) = value {
             trace!("Replacing value in signal");
             self.data.write().await.replace(value);
+            self.state.record_change(true);
             let _ = self.tx.send(());
         } else {
             trace!("Clearing value in signal");
             self.data.write().await.take();
+            self.state.record_change(false);
             let _ = self.tx.send(());
         }
     }
@@ -96,6 +108,8 @@ pub struct Receiver<T: PartialEq> {
     tx: BroadcastSender<()>,
     rx: AtomicRefCell<BroadcastReceiver<()>>,
     data: Arc<RwLock<Option<T>>>,
+    /// Keeps the signal registered while only receivers are left
+    _state: Arc<SignalState>,
 }
 
 impl<T: PartialEq> Clone for Receiver<T> {
@@ -106,6 +120,7 @@ impl<T: PartialEq> Clone for Receiver<T> {
             tx: self.tx.clone(),
             rx: AtomicRefCell::new(rx),
             data: self.data.clone(),
+            _state: self._state.clone(),
         }
     }
 }
@@ -292,6 +307,40 @@ mod tests {
         assert!(result.is_err()); // Timeout error
     }
 
+    #[tokio::test]
+    async fn test_signal_statuses() {
+        let status = || {
+            signal_statuses()
+                .into_iter()
+                .find(|status| status.name() == "test_signal_statuses")
+        };
+
+        let (tx, rx) = signal("test_signal_statuses");
+        let status_before = status().expect("registered");
+        assert!(!status_before.is_set());
+        assert_eq!(status_before.changes(), 0);
+        assert_eq!(status_before.last_changed(), None);
+
+        tx.set(1).await;
+        tx.set(1).await; // No change, not counted
+        tx.replace(Some(2)).await;
+        let status_set = status().expect("registered");
+        assert!(status_set.is_set());
+        assert_eq!(status_set.changes(), 2);
+        assert!(status_set.last_changed().is_some());
+
+        tx.clear().await;
+        let status_cleared = status().expect("registered");
+        assert!(!status_cleared.is_set());
+        assert_eq!(status_cleared.changes(), 3);
+
+        // Registered until both ends are dropped
+        drop(tx);
+        assert!(status().is_some());
+        drop(rx);
+        assert!(status().is_none());
+    }
+
     proptest! {
         #[test]
         fn test_signal_properties(values in prop::collection::vec(any::<i32>(), 0..20)) {
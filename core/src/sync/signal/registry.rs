@@ -0,0 +1,79 @@
+use getset::CopyGetters;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+use std::time::SystemTime;
+
+/// The signals alive in the process, dropped entries are pruned as new signals
+/// are registered
+static REGISTRY: LazyLock<Mutex<Vec<Weak<SignalState>>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// What is tracked of a signal, shared by its senders and receivers
+#[derive(Debug)]
+pub(super) struct SignalState {
+    name: &'static str,
+    is_set: AtomicBool,
+    changes: AtomicU64,
+    last_changed: Mutex<Option<SystemTime>>,
+}
+
+impl SignalState {
+    pub(super) fn register(name: &'static str) -> Arc<Self> {
+        let state = Arc::new(Self {
+            name,
+            is_set: AtomicBool::new(false),
+            changes: AtomicU64::new(0),
+            last_changed: Mutex::new(None),
+        });
+
+        let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry.retain(|state| state.strong_count() > 0);
+        registry.push(Arc::downgrade(&state));
+
+        state
+    }
+
+    pub(super) fn record_change(&self, is_set: bool) {
+        self.is_set.store(is_set, Ordering::Relaxed);
+        self.changes.fetch_add(1, Ordering::Relaxed);
+        *self.last_changed.lock().unwrap_or_else(|e| e.into_inner()) = Some(SystemTime::now());
+    }
+
+    fn status(&self) -> SignalStatus {
+        SignalStatus {
+            name: self.name,
+            is_set: self.is_set.load(Ordering::Relaxed),
+            changes: self.changes.load(Ordering::Relaxed),
+            last_changed: *self.last_changed.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+}
+
+/// A snapshot of a signal
+#[derive(Debug, Clone, PartialEq, CopyGetters)]
+pub struct SignalStatus {
+    #[getset(get_copy = "pub")]
+    name: &'static str,
+
+    /// Whether the signal currently holds a value
+    #[getset(get_copy = "pub")]
+    is_set: bool,
+
+    /// Number of times the value was set, replaced or cleared
+    #[getset(get_copy = "pub")]
+    changes: u64,
+
+    #[getset(get_copy = "pub")]
+    last_changed: Option<SystemTime>,
+}
+
+/// Snapshots of the signals alive in the process, sorted by name
+pub fn signal_statuses() -> Vec<SignalStatus> {
+    let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let mut statuses: Vec<_> = registry
+        .iter()
+        .filter_map(Weak::upgrade)
+        .map(|state| state.status())
+        .collect();
+    statuses.sort_by_key(|status| status.name);
+    statuses
+}
@@ -16,3 +16,9 @@ impl From<String> for Key {
         Self(value)
     }
 }
+
+impl AsRef<str> for Key {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -1 +1,9 @@
 pub mod access_control;
+pub mod basic_auth;
+pub mod cache;
+pub mod compression;
+pub mod cors;
+pub mod external_auth;
+pub mod jwt_auth;
+pub mod rate_limit;
+pub mod request_limits;
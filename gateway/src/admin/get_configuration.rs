@@ -0,0 +1,29 @@
+use crate::admin::{not_ready, AdminEndpointState};
+use crate::controllers::config::selector::ConfigurationSource;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Serialize;
+use tracing::instrument;
+use vg_core::config::gateway::types::GatewayConfiguration;
+
+#[derive(Serialize)]
+struct ConfigurationView<'a> {
+    source: Option<ConfigurationSource>,
+    configuration: &'a GatewayConfiguration,
+}
+
+#[instrument(skip(state), name = "admin::get_configuration")]
+pub async fn get_configuration(State(state): State<AdminEndpointState>) -> impl IntoResponse {
+    let source = *state.configuration_source_rx().get().await;
+    let configuration = state.gateway_configuration_rx().get().await;
+
+    match configuration.as_ref() {
+        Some(configuration) => Json(ConfigurationView {
+            source,
+            configuration,
+        })
+        .into_response(),
+        None => not_ready("No configuration was selected yet").into_response(),
+    }
+}
@@ -0,0 +1,102 @@
+use crate::admin::{not_ready, AdminEndpointState};
+use crate::proxy::router::topology::TopologyLocationMatch;
+use crate::proxy::router::{HttpBackend, HttpRouter};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use enumflags2::BitFlags;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::time::Instant;
+use tracing::instrument;
+
+#[derive(Serialize)]
+struct RuleEndpointsView<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route: Option<&'a str>,
+    rule_id: &'a str,
+    backends: Vec<BackendEndpointsView>,
+}
+
+#[derive(Serialize)]
+struct BackendEndpointsView {
+    weight: i32,
+    endpoints: Vec<EndpointView>,
+}
+
+#[derive(Serialize)]
+struct EndpointView {
+    addr: SocketAddr,
+    tier: TopologyTier,
+    /// Selection weight relative to the other endpoints, below 1.0 during slow start
+    weight: f64,
+}
+
+/// The endpoints preferred by the load balancer, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TopologyTier {
+    NodeLocal,
+    ZoneLocal,
+    Fallback,
+}
+
+impl From<BitFlags<TopologyLocationMatch>> for TopologyTier {
+    fn from(location_match: BitFlags<TopologyLocationMatch>) -> Self {
+        if location_match.contains(TopologyLocationMatch::Node) {
+            Self::NodeLocal
+        } else if location_match.contains(TopologyLocationMatch::Zone) {
+            Self::ZoneLocal
+        } else {
+            Self::Fallback
+        }
+    }
+}
+
+impl BackendEndpointsView {
+    fn new(backend: &HttpBackend, now: Instant) -> Self {
+        let mut endpoints: Vec<_> = backend
+            .endpoints()
+            .iter()
+            .flat_map(|(location_match, endpoints)| {
+                endpoints.iter().map(move |endpoint| EndpointView {
+                    addr: endpoint.addr(),
+                    tier: TopologyTier::from(*location_match),
+                    weight: backend.endpoint_weight(endpoint, now),
+                })
+            })
+            .collect();
+        endpoints.sort_by_key(|endpoint| (endpoint.tier, endpoint.addr));
+
+        Self {
+            weight: *backend.weight(),
+            endpoints,
+        }
+    }
+}
+
+fn rule_endpoints(router: &HttpRouter, now: Instant) -> Vec<RuleEndpointsView<'_>> {
+    router
+        .routes()
+        .iter()
+        .flat_map(|route| {
+            route.rules().iter().map(move |rule| RuleEndpointsView {
+                route: route.key().as_deref(),
+                rule_id: rule.unique_id().as_ref(),
+                backends: rule
+                    .backends()
+                    .iter()
+                    .map(|backend| BackendEndpointsView::new(backend, now))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[instrument(skip(state), name = "admin::get_endpoints")]
+pub async fn get_endpoints(State(state): State<AdminEndpointState>) -> impl IntoResponse {
+    match state.router_rx().get().await.as_ref() {
+        Some(router) => Json(rule_endpoints(router, Instant::now())).into_response(),
+        None => not_ready("The router was not synthesized yet").into_response(),
+    }
+}
@@ -0,0 +1,86 @@
+use crate::admin::{not_ready, AdminEndpointState};
+use crate::proxy::router::{HttpRouteRule, HttpRouteRuleMatchesDescription, HttpRouter};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Serialize;
+use tracing::instrument;
+use vg_core::config::gateway::types::http::filters::HttpRouteFilter;
+
+#[derive(Serialize)]
+struct RouterView<'a> {
+    hosts: Vec<String>,
+    routes: Vec<RouteView<'a>>,
+}
+
+#[derive(Serialize)]
+struct RouteView<'a> {
+    index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route: Option<&'a str>,
+    hosts: Vec<String>,
+    rules: Vec<RuleView<'a>>,
+}
+
+#[derive(Serialize)]
+struct RuleView<'a> {
+    rule_id: &'a str,
+    matches: Vec<HttpRouteRuleMatchesDescription>,
+    backends: Vec<BackendView>,
+    filters: &'a [HttpRouteFilter],
+}
+
+#[derive(Serialize)]
+struct BackendView {
+    weight: i32,
+    endpoints: usize,
+}
+
+impl<'a> RouterView<'a> {
+    fn new(router: &'a HttpRouter) -> Self {
+        Self {
+            hosts: router.hosts(),
+            routes: router
+                .routes()
+                .iter()
+                .enumerate()
+                .map(|(index, route)| RouteView {
+                    index,
+                    route: route.key().as_deref(),
+                    hosts: route.host_header_match().describe(),
+                    rules: route
+                        .rules()
+                        .iter()
+                        .map(|rule| RuleView::new(rule))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> RuleView<'a> {
+    fn new(rule: &'a HttpRouteRule) -> Self {
+        Self {
+            rule_id: rule.unique_id().as_ref(),
+            matches: rule.matches().iter().map(|m| m.describe()).collect(),
+            backends: rule
+                .backends()
+                .iter()
+                .map(|backend| BackendView {
+                    weight: *backend.weight(),
+                    endpoints: backend.endpoints().values().map(Vec::len).sum(),
+                })
+                .collect(),
+            filters: rule.filters(),
+        }
+    }
+}
+
+#[instrument(skip(state), name = "admin::get_router")]
+pub async fn get_router(State(state): State<AdminEndpointState>) -> impl IntoResponse {
+    match state.router_rx().get().await.as_ref() {
+        Some(router) => Json(RouterView::new(router)).into_response(),
+        None => not_ready("The router was not synthesized yet").into_response(),
+    }
+}
@@ -0,0 +1,32 @@
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use tracing::instrument;
+use vg_core::sync::signal::signal_statuses;
+
+#[derive(Serialize)]
+struct SignalView {
+    name: &'static str,
+    is_set: bool,
+    changes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_changed: Option<String>,
+}
+
+#[instrument(name = "admin::get_signals")]
+pub async fn get_signals() -> impl IntoResponse {
+    let signals: Vec<_> = signal_statuses()
+        .into_iter()
+        .map(|status| SignalView {
+            name: status.name(),
+            is_set: status.is_set(),
+            changes: status.changes(),
+            last_changed: status.last_changed().map(|last_changed| {
+                DateTime::<Utc>::from(last_changed).to_rfc3339_opts(SecondsFormat::Millis, true)
+            }),
+        })
+        .collect();
+
+    Json(signals)
+}
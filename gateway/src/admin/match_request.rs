@@ -0,0 +1,97 @@
+use crate::admin::{not_ready, AdminEndpointState};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use http::request::Parts;
+use http::Request;
+use problemdetails::Problem;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::instrument;
+use vg_core::instrumentation::trace_id;
+
+/// A synthetic request to route
+#[derive(Deserialize, Debug)]
+pub struct MatchRequest {
+    #[serde(default = "default_method")]
+    method: String,
+
+    /// Path and query of the request
+    path: String,
+
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+impl MatchRequest {
+    fn into_parts(self) -> Result<Parts, http::Error> {
+        let mut builder = Request::builder()
+            .method(self.method.as_str())
+            .uri(self.path);
+        for (name, value) in self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(()).map(|request| request.into_parts().0)
+    }
+}
+
+#[instrument(skip(state), name = "admin::match_request")]
+pub async fn match_request(
+    State(state): State<AdminEndpointState>,
+    Json(request): Json<MatchRequest>,
+) -> impl IntoResponse {
+    let parts = match request.into_parts() {
+        Ok(parts) => parts,
+        Err(e) => {
+            let mut problem = Problem::from(StatusCode::BAD_REQUEST)
+                .with_value("status", StatusCode::BAD_REQUEST.as_u16())
+                .with_title("Invalid Request")
+                .with_detail(format!("The request to match is invalid: {e}"));
+
+            if let Some(trace_id) = trace_id() {
+                problem = problem.with_instance(trace_id);
+            }
+
+            return problem.into_response();
+        }
+    };
+
+    match state.router_rx().get().await.as_ref() {
+        Some(router) => Json(router.explain(&parts)).into_response(),
+        None => not_ready("The router was not synthesized yet").into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_parts() {
+        let request: MatchRequest = serde_json::from_str(
+            r#"{"path": "/api/users?page=2", "headers": {"host": "example.com"}}"#,
+        )
+        .expect("valid request");
+        let parts = request.into_parts().expect("valid parts");
+
+        assert_eq!(parts.method, http::Method::GET);
+        assert_eq!(parts.uri.path(), "/api/users");
+        assert_eq!(parts.uri.query(), Some("page=2"));
+        assert_eq!(parts.headers["host"], "example.com");
+    }
+
+    #[test]
+    fn test_into_parts_with_invalid_header() {
+        let request: MatchRequest = serde_json::from_str(
+            r#"{"method": "POST", "path": "/", "headers": {"bad header": "x"}}"#,
+        )
+        .expect("valid request");
+
+        assert!(request.into_parts().is_err());
+    }
+}
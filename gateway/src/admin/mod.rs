@@ -0,0 +1,166 @@
+mod get_configuration;
+mod get_endpoints;
+mod get_metrics;
+mod get_router;
+mod get_signals;
+mod match_request;
+
+use crate::admin::get_configuration::get_configuration;
+use crate::admin::get_endpoints::get_endpoints;
+use crate::admin::get_metrics::get_metrics;
+use crate::admin::get_router::get_router;
+use crate::admin::get_signals::get_signals;
+use crate::admin::match_request::match_request;
+use crate::controllers::config::selector::ConfigurationSource;
+use crate::proxy::router::HttpRouter;
+use axum::extract::{ConnectInfo, Request};
+use axum::http::StatusCode;
+use axum::middleware::{from_fn, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use getset::{CopyGetters, Getters};
+use problemdetails::Problem;
+use std::net::SocketAddr;
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio::select;
+use tracing::info;
+use typed_builder::TypedBuilder;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::instrumentation::trace_id;
+use vg_core::net::Port;
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+
+#[derive(TypedBuilder, Getters, Clone)]
+pub struct AdminEndpointState {
+    #[getset(get = "pub")]
+    gateway_configuration_rx: Receiver<GatewayConfiguration>,
+
+    #[getset(get = "pub")]
+    configuration_source_rx: Receiver<ConfigurationSource>,
+
+    #[getset(get = "pub")]
+    router_rx: Receiver<HttpRouter>,
+}
+
+#[derive(TypedBuilder, CopyGetters)]
+pub struct SpawnAdminEndpointParameters {
+    #[getset(get_copy = "")]
+    port: Port,
+
+    #[getset(get_copy = "")]
+    prometheus_enabled: bool,
+
+    state: AdminEndpointState,
+}
+
+impl SpawnAdminEndpointParameters {
+    /// The admin endpoint exposes the whole configuration, so it is only bound on the
+    /// loopback interface unless Prometheus has to scrape `/metrics` from outside the pod
+    fn endpoint(&self) -> SocketAddr {
+        if self.prometheus_enabled() {
+            SocketAddr::from(([0, 0, 0, 0], self.port().into()))
+        } else {
+            SocketAddr::from(([127, 0, 0, 1], self.port().into()))
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SpawnAdminEndpointError {
+    #[error("Failed to bind admin endpoint: {0}")]
+    NetworkBind(#[from] std::io::Error),
+}
+
+pub async fn spawn_admin_endpoint(
+    task_builder: &TaskBuilder,
+    params: SpawnAdminEndpointParameters,
+) -> Result<(), SpawnAdminEndpointError> {
+    let endpoint = params.endpoint();
+    let tcp_listener = TcpListener::bind(endpoint).await?;
+    info!("Admin endpoint listening on {}", endpoint);
+
+    task_builder.new_task("admin_endpoint").spawn(async move {
+        select! {
+            _ = axum::serve(
+                tcp_listener,
+                router(params.state).into_make_service_with_connect_info::<SocketAddr>(),
+            ) => info!("Admin service stopped"),
+            _ = tokio::signal::ctrl_c() => info!("Received shutdown signal, stopping admin service")
+        }
+    });
+
+    Ok(())
+}
+
+fn router(state: AdminEndpointState) -> Router {
+    let admin = Router::new()
+        .route("/admin/configuration", get(get_configuration))
+        .route("/admin/router", get(get_router))
+        .route("/admin/endpoints", get(get_endpoints))
+        .route("/admin/signals", get(get_signals))
+        .route("/admin/match", post(match_request))
+        .route_layer(from_fn(loopback_only));
+
+    Router::new()
+        .merge(admin)
+        .route("/metrics", get(get_metrics))
+        .fallback(not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .with_state(state)
+}
+
+/// Hides the admin routes from peers outside the pod, which can only reach `/metrics`
+async fn loopback_only(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if peer.ip().is_loopback() {
+        next.run(request).await
+    } else {
+        not_found().await.into_response()
+    }
+}
+
+/// Answers requests made before the gateway has a configuration or a router
+fn not_ready(detail: &str) -> Problem {
+    let mut problem = Problem::from(StatusCode::SERVICE_UNAVAILABLE)
+        .with_value("status", StatusCode::SERVICE_UNAVAILABLE.as_u16())
+        .with_title("Not Ready")
+        .with_detail(detail);
+
+    if let Some(trace_id) = trace_id() {
+        problem = problem.with_instance(trace_id);
+    }
+
+    problem
+}
+
+async fn not_found() -> impl IntoResponse {
+    let mut problem = Problem::from(StatusCode::NOT_FOUND)
+        .with_value("status", StatusCode::NOT_FOUND.as_u16())
+        .with_title("Not Found")
+        .with_detail("The requested resource could not be found");
+
+    if let Some(trace_id) = trace_id() {
+        problem = problem.with_instance(trace_id);
+    }
+
+    problem
+}
+
+async fn method_not_allowed() -> impl IntoResponse {
+    let mut problem = Problem::from(StatusCode::METHOD_NOT_ALLOWED)
+        .with_value("status", StatusCode::METHOD_NOT_ALLOWED.as_u16())
+        .with_title("Method Not Allowed")
+        .with_detail("The requested method is not allowed for this resource");
+
+    if let Some(trace_id) = trace_id() {
+        problem = problem.with_instance(trace_id);
+    }
+
+    problem
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::Parser;
 use getset::{CloneGetters, CopyGetters, Getters};
 use std::path::PathBuf;
+use vg_core::config::gateway::serde::ConfigurationEncoding;
 use vg_core::net::Port;
 
 #[derive(Parser, Debug, Getters, Clone, CopyGetters, CloneGetters)]
@@ -16,6 +17,25 @@ pub struct Cli {
     )]
     config_file_path: PathBuf,
 
+    /// Directory of YAML or JSON configuration fragments merged into one configuration, watched
+    /// instead of the configuration file when set
+    #[getset(get_clone = "pub")]
+    #[arg(
+        env = "VALE_GATEWAY_CONFIG_DIRECTORY_PATH",
+        long = "config-directory-path"
+    )]
+    config_directory_path: Option<PathBuf>,
+
+    /// Where the last configuration applied from the control plane is kept across restarts, its
+    /// version next to it
+    #[getset(get_clone = "pub")]
+    #[arg(
+        default_value = "/var/cache/vale-gateway/config.yaml",
+        env = "VALE_GATEWAY_CONFIGURATION_CACHE_PATH",
+        long = "configuration-cache-path"
+    )]
+    configuration_cache_path: PathBuf,
+
     #[getset(get_clone = "pub")]
     #[arg(env = "NODE_NAME", long = "node-name")]
     node_name: Option<String>,
@@ -36,9 +56,48 @@ pub struct Cli {
     #[arg(env = "GATEWAY_NAME", long = "gateway-name")]
     gateway_name: String,
 
+    /// Projected ServiceAccount token presented to the control plane
+    #[getset(get_clone = "pub")]
+    #[arg(env = "VALE_GATEWAY_IPC_TOKEN_PATH", long = "ipc-token-path")]
+    ipc_token_path: Option<PathBuf>,
+
+    /// Encoding of the configuration fetched from the control plane, `yaml` or `cbor`
+    #[getset(get_copy = "pub")]
+    #[arg(
+        default_value = "cbor",
+        env = "VALE_GATEWAY_IPC_CONFIGURATION_ENCODING",
+        long = "ipc-configuration-encoding"
+    )]
+    ipc_configuration_encoding: ConfigurationEncoding,
+
     #[getset(get_clone = "pub")]
     #[arg(env = "VALE_GATEWAY_LISTENERS", long = "listeners")]
     vale_gateway_listeners: Option<String>,
+
+    /// Memory budget of the response cache shared by all cache filters
+    #[getset(get_copy = "pub")]
+    #[arg(
+        default_value_t = 64 * 1024 * 1024,
+        env = "VALE_GATEWAY_HTTP_CACHE_SIZE_BYTES",
+        long = "http-cache-size-bytes"
+    )]
+    http_cache_size_bytes: usize,
+
+    /// Port of the admin endpoint, only reachable from the loopback interface except for
+    /// `/metrics` when the Prometheus exporter is enabled
+    #[getset(get_copy = "pub")]
+    #[arg(
+        default_value = "9901",
+        env = "VALE_GATEWAY_ADMIN_PORT",
+        long = "admin-port",
+        value_parser = parse_port
+    )]
+    admin_port: Port,
+
+    /// Serves the metrics of the gateway on `/metrics` of the admin port
+    #[getset(get_copy = "pub")]
+    #[arg(env = "VALE_GATEWAY_PROMETHEUS_ENABLED", long = "prometheus-enabled")]
+    prometheus_enabled: bool,
 }
 
 fn parse_port(arg: &str) -> Result<Port> {
@@ -0,0 +1,153 @@
+use crate::controllers::config::fs::{read_configuration_file, version_file_path};
+use getset::Getters;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs::{create_dir_all, rename, write};
+use tracing::{debug, info, warn};
+use typed_builder::TypedBuilder;
+use vg_core::config::gateway::serde::write_configuration;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::continue_on;
+use vg_core::ipc::configuration::ConfigurationStatus;
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+
+#[derive(Debug, Getters, TypedBuilder)]
+pub struct CacheConfigurationParams {
+    #[builder(setter(into))]
+    file_path: PathBuf,
+    ipc_configuration_source_rx: Receiver<(Option<u64>, GatewayConfiguration)>,
+    configuration_status_rx: Receiver<(u64, ConfigurationStatus)>,
+}
+
+/// Loads the last configuration applied from IPC, then persists every newer one that passed
+/// validation, so a gateway restarted while the control plane is down starts from it rather than
+/// a stale ConfigMap
+pub fn cache_configuration(
+    task_builder: &TaskBuilder,
+    params: CacheConfigurationParams,
+) -> Receiver<(Option<u64>, GatewayConfiguration)> {
+    let (tx, rx) = signal("cached_configuration");
+
+    task_builder
+        .new_task(stringify!(cache_configuration))
+        .spawn(async move {
+            let ipc_configuration_source_rx = params.ipc_configuration_source_rx;
+            let configuration_status_rx = params.configuration_status_rx;
+
+            let mut cached_version = None;
+            if let Some((Some(version), config)) = read_configuration_file(&params.file_path).await
+            {
+                info!(
+                    "Loaded cached configuration version {} from {:?}",
+                    version, params.file_path
+                );
+                cached_version = Some(version);
+                tx.set((cached_version, config)).await;
+            }
+
+            loop {
+                let applied_version = configuration_status_rx
+                    .get()
+                    .await
+                    .as_ref()
+                    .filter(|(_, status)| *status == ConfigurationStatus::Applied)
+                    .map(|(version, _)| *version);
+                let applied = ipc_configuration_source_rx.get().await.as_ref().and_then(
+                    |(version, config)| {
+                        version
+                            .filter(|version| Some(*version) == applied_version)
+                            .filter(|version| Some(*version) > cached_version)
+                            .map(|version| (version, config.clone()))
+                    },
+                );
+
+                if let Some((version, config)) = applied {
+                    match write_configuration_file(&params.file_path, version, &config).await {
+                        Ok(()) => {
+                            debug!("Cached configuration version {}", version);
+                            cached_version = Some(version);
+                        }
+                        Err(err) => {
+                            warn!("Error caching configuration version {}: {}", version, err);
+                        }
+                    }
+                }
+
+                continue_on!(
+                    ipc_configuration_source_rx.changed(),
+                    configuration_status_rx.changed()
+                );
+            }
+        });
+
+    rx
+}
+
+/// Writes a configuration file and its version file, replacing both. The version goes last, so
+/// being interrupted in between can only understate the version of the configuration
+async fn write_configuration_file(
+    file_path: &Path,
+    version: u64,
+    config: &GatewayConfiguration,
+) -> io::Result<()> {
+    let mut contents = Vec::new();
+    write_configuration(config, &mut contents).map_err(io::Error::other)?;
+
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent).await?;
+    }
+
+    replace_file(file_path, &contents).await?;
+    replace_file(
+        &version_file_path(file_path),
+        version.to_string().as_bytes(),
+    )
+    .await
+}
+
+/// Writes the contents aside and renames them into place, readers never see a partial file
+async fn replace_file(file_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_file_path = {
+        let mut temp_file_path = OsString::from(file_path);
+        temp_file_path.push(".tmp");
+        PathBuf::from(temp_file_path)
+    };
+
+    write(&temp_file_path, contents).await?;
+    rename(&temp_file_path, file_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_core::config::gateway::types::GatewayConfigurationBuilder;
+
+    #[tokio::test]
+    async fn test_cached_configuration_round_trip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file_path = dir.path().join("cache").join("config.yaml");
+        let config = GatewayConfigurationBuilder::default()
+            .build()
+            .expect("Failed to build configuration");
+
+        assert_eq!(read_configuration_file(&file_path).await, None);
+
+        write_configuration_file(&file_path, 42, &config)
+            .await
+            .expect("Failed to write configuration");
+        assert_eq!(
+            read_configuration_file(&file_path).await,
+            Some((Some(42), config.clone()))
+        );
+
+        write_configuration_file(&file_path, 43, &config)
+            .await
+            .expect("Failed to write configuration");
+        assert_eq!(
+            read_configuration_file(&file_path).await,
+            Some((Some(43), config))
+        );
+    }
+}
@@ -1,15 +1,17 @@
 use getset::Getters;
 use std::io::Cursor;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
-use tokio::fs::read;
-use tracing::{debug, info};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs::{metadata, read, read_dir, read_to_string};
+use tokio::time::timeout;
+use tracing::{debug, info, warn};
 use typed_builder::TypedBuilder;
 use vg_core::config::gateway::serde::read_configuration;
 use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::config::gateway::types::fragments::{merge_fragments, read_fragment};
 use vg_core::continue_after;
 use vg_core::io::file_watcher::spawn_file_watcher;
-use vg_core::sync::signal::{Receiver, signal};
+use vg_core::sync::signal::{Receiver, RecvError, signal};
 use vg_core::task::Builder as TaskBuilder;
 
 #[derive(Debug, Getters, TypedBuilder)]
@@ -21,7 +23,7 @@ pub struct WatchConfigurationFileParams {
 pub fn watch_configuration_file(
     task_builder: &TaskBuilder,
     params: WatchConfigurationFileParams,
-) -> Receiver<(Instant, GatewayConfiguration)> {
+) -> Receiver<(Option<u64>, GatewayConfiguration)> {
     let (tx, rx) = signal("watched_configuration_file");
 
     task_builder
@@ -36,13 +38,9 @@ pub fn watch_configuration_file(
                 spawn_file_watcher(&params.file_path).expect("Failed to spawn file watcher");
 
             loop {
-                let serial = Instant::now();
-
-                if let Ok(config_reader) = read(&params.file_path).await.map(Cursor::new)
-                    && let Ok(config) = read_configuration(config_reader)
-                {
+                if let Some(versioned_config) = read_configuration_file(&params.file_path).await {
                     debug!("Configuration file read");
-                    tx.set((serial, config)).await;
+                    tx.set(versioned_config).await;
                 }
 
                 continue_after!(
@@ -54,3 +52,177 @@ pub fn watch_configuration_file(
 
     rx
 }
+
+#[derive(Debug, Getters, TypedBuilder)]
+pub struct WatchConfigurationDirectoryParams {
+    #[builder(setter(into))]
+    directory_path: PathBuf,
+
+    /// How long the directory has to stay unchanged before it is read
+    #[builder(default = Duration::from_millis(500))]
+    debounce: Duration,
+}
+
+/// Watches a directory of configuration fragments for a gateway running without a control plane,
+/// merging them into one unversioned configuration. A directory that fails to merge keeps the
+/// previous configuration
+pub fn watch_configuration_directory(
+    task_builder: &TaskBuilder,
+    params: WatchConfigurationDirectoryParams,
+) -> Receiver<(Option<u64>, GatewayConfiguration)> {
+    let (tx, rx) = signal("watched_configuration_directory");
+
+    task_builder
+        .new_task(stringify!(watch_configuration_directory))
+        .spawn(async move {
+            info!(
+                "Spawning file watcher for configuration directory: {:?}",
+                params.directory_path
+            );
+
+            let file_watcher =
+                spawn_file_watcher(&params.directory_path).expect("Failed to spawn file watcher");
+
+            loop {
+                if let Some(config) = read_configuration_directory(&params.directory_path).await {
+                    debug!("Configuration directory read");
+                    tx.set((None, config)).await;
+                }
+
+                continue_after!(
+                    Duration::from_secs(30), // failsafe timeout to force a re-read
+                    debounced(&file_watcher, params.debounce)
+                );
+            }
+        });
+
+    rx
+}
+
+/// Waits for a change followed by a quiet period, so a ConfigMap swapping its `..data` symlink or
+/// an editor writing several files is read once, complete
+async fn debounced(file_watcher: &Receiver<u64>, quiet: Duration) -> Result<(), RecvError> {
+    file_watcher.changed().await?;
+    while let Ok(changed) = timeout(quiet, file_watcher.changed()).await {
+        changed?;
+    }
+    Ok(())
+}
+
+/// Reads and merges the YAML and JSON files of a directory in the order of their names. Hidden
+/// entries are skipped, which includes the timestamped directories of a mounted ConfigMap
+async fn read_configuration_directory(directory_path: &Path) -> Option<GatewayConfiguration> {
+    let mut entries = read_dir(directory_path)
+        .await
+        .inspect_err(|err| warn!("Error reading {:?}: {}", directory_path, err))
+        .ok()?;
+
+    let mut file_paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await.ok()? {
+        let file_path = entry.path();
+        let is_fragment = !entry.file_name().to_string_lossy().starts_with('.')
+            && file_path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| matches!(extension, "yaml" | "yml" | "json"));
+        // Follows symlinks, mounted ConfigMap keys are symlinks to files
+        if is_fragment
+            && metadata(&file_path)
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+        {
+            file_paths.push(file_path);
+        }
+    }
+    file_paths.sort();
+
+    let mut fragments = Vec::with_capacity(file_paths.len());
+    for file_path in file_paths {
+        let contents = read(&file_path)
+            .await
+            .inspect_err(|err| warn!("Error reading {:?}: {}", file_path, err))
+            .ok()?;
+        let fragment = read_fragment(Cursor::new(contents))
+            .inspect_err(|err| warn!("Invalid configuration fragment {:?}: {}", file_path, err))
+            .ok()?;
+        fragments.push((file_path.display().to_string(), fragment));
+    }
+
+    merge_fragments(fragments)
+        .inspect_err(|err| warn!("Error merging {:?}: {}", directory_path, err))
+        .ok()
+}
+
+/// The file holding the version of a configuration file, next to it
+pub fn version_file_path(file_path: &Path) -> PathBuf {
+    file_path.with_extension("version")
+}
+
+/// Reads a configuration file along with its version, when a version file accompanies it
+pub async fn read_configuration_file(
+    file_path: &Path,
+) -> Option<(Option<u64>, GatewayConfiguration)> {
+    let config_reader = read(file_path).await.map(Cursor::new).ok()?;
+    let config = read_configuration(config_reader).ok()?;
+
+    let version = match read_to_string(version_file_path(file_path)).await {
+        Ok(version) => version
+            .trim()
+            .parse()
+            .inspect_err(|err| warn!("Invalid configuration version {:?}: {}", version, err))
+            .ok(),
+        Err(_) => None,
+    };
+
+    Some((version, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir, write};
+
+    const LISTENERS: &str = r#"
+version: v1alpha1
+listeners:
+  - name: http
+    port: 80
+    protocol: HTTP
+"#;
+
+    const ROUTES: &str = r#"
+{
+  "http_routes": [
+    {
+      "rules": [
+        {
+          "unique_id": "echo:0",
+          "matches": [{ "path": { "value": "/" } }],
+          "backends": [{ "name": "echo", "port": 80, "endpoints": [] }]
+        }
+      ]
+    }
+  ]
+}
+"#;
+
+    #[tokio::test]
+    async fn test_read_configuration_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write(dir.path().join("listeners.yaml"), LISTENERS).expect("write");
+        write(dir.path().join("routes.json"), ROUTES).expect("write");
+        write(dir.path().join("README.md"), "Not a fragment").expect("write");
+        create_dir(dir.path().join("..2025_01_01")).expect("create_dir");
+        write(dir.path().join("..2025_01_01").join("stale.yaml"), ROUTES).expect("write");
+
+        let config = read_configuration_directory(dir.path())
+            .await
+            .expect("Failed to read configuration directory");
+        assert_eq!(config.listeners().len(), 1);
+        assert_eq!(config.http_routes().len(), 1);
+
+        // The same rule twice is a conflict, the directory is not read
+        write(dir.path().join("more-routes.yml"), ROUTES).expect("write");
+        assert_eq!(read_configuration_directory(dir.path()).await, None);
+    }
+}
@@ -1,24 +1,37 @@
+use crate::controllers::ipc_tls::IpcCaVerifier;
+use http::header::{ACCEPT, CONTENT_TYPE};
 use http::StatusCode;
+use reqwest::Response;
 use reqwest_middleware::ClientWithMiddleware;
 use reqwest_tracing::{OtelName, OtelPathNames};
 use std::io::BufReader;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::Duration;
 use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 use tracing::{debug, info, warn};
 use typed_builder::TypedBuilder;
 use url::Url;
-use vg_core::config::gateway::serde::read_configuration;
+use vg_core::config::gateway::serde::{read_encoded_configuration, ConfigurationEncoding};
 use vg_core::config::gateway::types::GatewayConfiguration;
-use vg_core::{await_ready, continue_on, ReadyState};
+use vg_core::{await_ready, continue_after, continue_on, ReadyState};
+use vg_core::ipc::configuration::{
+    ConfigurationStatus, ConfigurationStatusReport, CONFIGURATION_VERSION_HEADER,
+};
 use vg_core::ipc::GatewayEvent;
 use vg_core::sync::signal::{Receiver, Sender, signal};
 use vg_core::task::Builder as TaskBuilder;
 
+/// How often the status of the configuration is reported again while it does not change, so
+/// the control plane keeps counting the pod in rollouts, well within its report TTL
+const CONFIGURATION_STATUS_REPORT_INTERVAL: Duration = Duration::from_secs(20);
+
+/// How soon a report the control plane did not accept is sent again
+const CONFIGURATION_STATUS_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Debug, TypedBuilder)]
 pub struct FetchConfigurationParams {
-    ipc_endpoint_rx: Receiver<SocketAddr>,
+    ipc_endpoint_rx: Receiver<IpcEndpoint>,
     gateway_events_rx: BroadcastReceiver<GatewayEvent>,
     #[builder(setter(into))]
     pod_name: String,
@@ -27,12 +40,16 @@ pub struct FetchConfigurationParams {
     #[builder(setter(into))]
     gateway_name: String,
     client: Arc<ClientWithMiddleware>,
+    /// The encoding asked for, the control plane may still answer with YAML
+    encoding: ConfigurationEncoding,
+    /// Outcome of the latest configuration version, acknowledged again when it is unchanged
+    configuration_status_tx: Sender<(u64, ConfigurationStatus)>,
 }
 
 pub fn fetch_configuration(
     task_builder: &TaskBuilder,
     params: FetchConfigurationParams,
-) -> Receiver<(Instant, GatewayConfiguration)> {
+) -> Receiver<(Option<u64>, GatewayConfiguration)> {
     let ipc_endpoint_rx = params.ipc_endpoint_rx.clone();
     let (tx, rx) = signal("fetched_configuration");
 
@@ -40,14 +57,80 @@ pub fn fetch_configuration(
         .new_task(stringify!(fetch_configuration))
         .spawn(async move {
             let mut gateway_events = params.gateway_events_rx;
+            // The version and contents of the last configuration applied, deltas build on it
+            let mut current: Option<(u64, GatewayConfiguration)> = None;
             loop {
-                if let ReadyState::Ready(ipc_endpoint_addr) = await_ready!(ipc_endpoint_rx)
+                if let ReadyState::Ready(ipc_endpoint) = await_ready!(ipc_endpoint_rx)
                     && let Ok(event) = gateway_events.recv().await
-                    && let GatewayEvent::ConfigurationUpdate(_) = event
                 {
+                    let delta = match event {
+                        GatewayEvent::ConfigurationUpdate(_) => None,
+                        GatewayEvent::ConfigurationDelta(delta) => Some(delta),
+                        _ => continue,
+                    };
+
+                    if let Some(delta) = delta
+                        && let Some((version, configuration)) = &current
+                    {
+                        let applied = if *version == delta.version() {
+                            // Nothing changed, the outcome already reported is sent again
+                            let status = params
+                                .configuration_status_tx
+                                .get()
+                                .await
+                                .as_ref()
+                                .filter(|(version, _)| *version == delta.version())
+                                .map(|(_, status)| status.clone());
+                            if let Some(status) = status {
+                                let report = ConfigurationStatusReport::builder()
+                                    .pod_name(&params.pod_name)
+                                    .version(delta.version())
+                                    .status(status)
+                                    .build();
+                                send_configuration_status(
+                                    &params.client,
+                                    ipc_endpoint,
+                                    &params.gateway_namespace,
+                                    &params.gateway_name,
+                                    &report,
+                                )
+                                .await;
+                            }
+                            true
+                        } else if *version == delta.base_version() {
+                            match delta.apply(configuration) {
+                                Ok(configuration) => {
+                                    debug!(
+                                        "Applied {} change(s) of configuration version {}",
+                                        delta.changes().len(),
+                                        delta.version()
+                                    );
+                                    tx.set((Some(delta.version()), configuration.clone())).await;
+                                    current = Some((delta.version(), configuration));
+                                    true
+                                }
+                                Err(err) => {
+                                    warn!("Error applying configuration delta: {}", err);
+                                    false
+                                }
+                            }
+                        } else {
+                            debug!(
+                                "Configuration version {} is not based on version {}",
+                                delta.version(),
+                                version
+                            );
+                            false
+                        };
+
+                        if applied {
+                            continue;
+                        }
+                    }
+
+                    // Without a configuration to build on, fall back to the full configuration
                     let url = {
-                        let mut url = Url::parse(&format!("http://{ipc_endpoint_addr}"))
-                            .expect("Failed to parse URL");
+                        let mut url = ipc_endpoint.url();
                         url.set_path(&format!(
                             "/ipc/namespaces/{}/gateways/{}/configuration",
                             params.gateway_namespace, params.gateway_name
@@ -58,11 +141,10 @@ pub fn fetch_configuration(
 
                     debug!("Fetching configuration from URL: {}", url);
 
-                    let serial = Instant::now(); // capture the time before the request
-
                     let response = params
                         .client
                         .get(url)
+                        .header(ACCEPT, params.encoding.accept())
                         .with_extension(OtelName("fetch_configuration".into()))
                         .with_extension(
                             OtelPathNames::known_paths([
@@ -75,16 +157,30 @@ pub fn fetch_configuration(
 
                     match response {
                         Ok(response) if response.status() == StatusCode::OK => {
+                            let version = configuration_version(&response);
+                            let encoding = configuration_encoding(&response);
                             match response.bytes().await {
                                 Ok(bytes) => {
                                     let buf = BufReader::new(bytes.as_ref());
-                                    match read_configuration(buf) {
+                                    // Whether it applies is reported once it is validated
+                                    match read_encoded_configuration(encoding, buf) {
                                         Ok(configuration) => {
                                             debug!("Configuration fetched successfully");
-                                            tx.set((serial, configuration)).await;
+                                            tx.set((version, configuration.clone())).await;
+                                            current =
+                                                version.map(|version| (version, configuration));
                                         }
                                         Err(err) => {
                                             warn!("Error reading configuration: {}", err);
+                                            if let Some(version) = version {
+                                                let status = ConfigurationStatus::Rejected {
+                                                    error: err.to_string(),
+                                                };
+                                                params
+                                                    .configuration_status_tx
+                                                    .set((version, status))
+                                                    .await;
+                                            }
                                         }
                                     }
                                 }
@@ -107,10 +203,174 @@ pub fn fetch_configuration(
     rx
 }
 
+fn configuration_version(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONFIGURATION_VERSION_HEADER)
+        .and_then(|version| version.to_str().ok())
+        .and_then(|version| version.parse().ok())
+}
+
+fn configuration_encoding(response: &Response) -> ConfigurationEncoding {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(ConfigurationEncoding::from_media_type)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, TypedBuilder)]
+pub struct ReportConfigurationStatusParams {
+    ipc_endpoint_rx: Receiver<IpcEndpoint>,
+    configuration_status_rx: Receiver<(u64, ConfigurationStatus)>,
+    #[builder(setter(into))]
+    pod_name: String,
+    #[builder(setter(into))]
+    gateway_namespace: String,
+    #[builder(setter(into))]
+    gateway_name: String,
+    client: Arc<ClientWithMiddleware>,
+}
+
+/// Reports the outcome of the latest configuration version to the control plane, again and
+/// again while it does not change
+pub fn report_configuration_status(
+    task_builder: &TaskBuilder,
+    params: ReportConfigurationStatusParams,
+) {
+    let ipc_endpoint_rx = params.ipc_endpoint_rx.clone();
+    let configuration_status_rx = params.configuration_status_rx.clone();
+
+    task_builder
+        .new_task(stringify!(report_configuration_status))
+        .spawn(async move {
+            loop {
+                let ready = match await_ready!(ipc_endpoint_rx, configuration_status_rx) {
+                    ReadyState::Ready((ipc_endpoint, (version, status))) => {
+                        Some((*ipc_endpoint, *version, status.clone()))
+                    }
+                    ReadyState::NotReady => None,
+                };
+
+                if let Some((ipc_endpoint, version, status)) = ready {
+                    let report = ConfigurationStatusReport::builder()
+                        .pod_name(&params.pod_name)
+                        .version(version)
+                        .status(status)
+                        .build();
+                    let reported = send_configuration_status(
+                        &params.client,
+                        &ipc_endpoint,
+                        &params.gateway_namespace,
+                        &params.gateway_name,
+                        &report,
+                    )
+                    .await;
+
+                    let report_again_after = if reported {
+                        CONFIGURATION_STATUS_REPORT_INTERVAL
+                    } else {
+                        CONFIGURATION_STATUS_RETRY_INTERVAL
+                    };
+                    continue_after!(
+                        report_again_after,
+                        params.ipc_endpoint_rx.changed(),
+                        params.configuration_status_rx.changed()
+                    );
+                }
+
+                continue_on!(
+                    params.ipc_endpoint_rx.changed(),
+                    params.configuration_status_rx.changed()
+                );
+            }
+        });
+}
+
+/// Acknowledges a configuration version to the control plane, or rejects it with the reason.
+/// Returns whether the control plane accepted the report
+async fn send_configuration_status(
+    client: &ClientWithMiddleware,
+    ipc_endpoint: &IpcEndpoint,
+    gateway_namespace: &str,
+    gateway_name: &str,
+    report: &ConfigurationStatusReport,
+) -> bool {
+    let url = {
+        let mut url = ipc_endpoint.url();
+        url.set_path(&format!(
+            "/ipc/namespaces/{}/gateways/{}/configuration/status",
+            gateway_namespace, gateway_name
+        ));
+        url
+    };
+
+    let body = match serde_json::to_vec(report) {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("Error serializing configuration status: {}", err);
+            return false;
+        }
+    };
+
+    let response = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .with_extension(OtelName("report_configuration_status".into()))
+        .with_extension(
+            OtelPathNames::known_paths([
+                "/ipc/namespaces/{namespace}/gateways/{gateway_name}/configuration/status",
+            ])
+            .expect("Failed to set known paths"),
+        )
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            debug!(
+                "Reported status of configuration version {}",
+                report.version()
+            );
+            true
+        }
+        Ok(response) => {
+            info!(
+                "Unexpected response reporting configuration status: {:?}",
+                response
+            );
+            false
+        }
+        Err(err) => {
+            warn!("Error reporting configuration status: {}", err);
+            false
+        }
+    }
+}
+
+/// Where the IPC endpoint of the primary control plane instance is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpcEndpoint {
+    addr: SocketAddr,
+    tls: bool,
+}
+
+impl IpcEndpoint {
+    /// The base URL of the endpoint, over TLS when the configuration pins a CA for it
+    pub fn url(&self) -> Url {
+        let scheme = if self.tls { "https" } else { "http" };
+        Url::parse(&format!("{scheme}://{}", self.addr)).expect("Failed to parse URL")
+    }
+}
+
+/// Follows the IPC endpoint in the configuration, and pins the CA it is verified against
 pub fn watch_ipc_endpoint(
     task_builder: &TaskBuilder,
     gateway_configuration_rx: &Receiver<GatewayConfiguration>,
-    tx: Sender<SocketAddr>,
+    ca_verifier: Arc<IpcCaVerifier>,
+    tx: Sender<IpcEndpoint>,
 ) {
     let gateway_configuration_rx = gateway_configuration_rx.clone();
 
@@ -119,10 +379,15 @@ pub fn watch_ipc_endpoint(
         .spawn(async move {
             loop {
                 if let ReadyState::Ready(gateway_configuration) = await_ready!(gateway_configuration_rx) {
-                    let primary_endpoint = gateway_configuration
-                        .ipc()
-                        .as_ref()
-                        .and_then(|c| *c.endpoint());
+                    let ipc = gateway_configuration.ipc().as_ref();
+                    let ca_certificate = ipc.and_then(|c| c.ca_certificate().as_deref());
+                    ca_verifier.set_ca_certificate(ca_certificate);
+
+                    let primary_endpoint =
+                        ipc.and_then(|c| *c.endpoint()).map(|addr| IpcEndpoint {
+                            addr,
+                            tls: ca_certificate.is_some(),
+                        });
 
                     tx.replace(primary_endpoint).await;
                 }
@@ -1,3 +1,5 @@
+pub mod cache;
 pub mod fs;
 pub mod ipc;
 pub mod selector;
+pub mod validation;
@@ -1,5 +1,5 @@
 use getset::Getters;
-use std::time::Instant;
+use serde::Serialize;
 use tracing::debug;
 use typed_builder::TypedBuilder;
 use vg_core::config::gateway::types::GatewayConfiguration;
@@ -7,61 +7,173 @@ use vg_core::continue_on;
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
 
+/// Where the selected configuration comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ConfigurationSource {
+    Ipc,
+    File,
+    Cache,
+}
+
+/// A configuration available from a source, with its version
+pub type ConfigurationCandidate = (ConfigurationSource, Option<u64>, GatewayConfiguration);
+
 #[derive(Getters, Debug, Clone, TypedBuilder)]
 pub struct SelectorParams {
-    ipc_configuration_source_rx: Receiver<(Instant, GatewayConfiguration)>,
-    fs_configuration_source_rx: Receiver<(Instant, GatewayConfiguration)>,
+    ipc_configuration_source_rx: Receiver<(Option<u64>, GatewayConfiguration)>,
+    fs_configuration_source_rx: Receiver<(Option<u64>, GatewayConfiguration)>,
+    cache_configuration_source_rx: Receiver<(Option<u64>, GatewayConfiguration)>,
 }
 
+/// Lists the configurations of every source newest first, for validation to activate the
+/// newest valid one
 pub fn select_configuration(
     task_builder: &TaskBuilder,
     params: SelectorParams,
-) -> Receiver<GatewayConfiguration> {
-    let (tx, rx) = signal("selected_configuration");
+) -> Receiver<Vec<ConfigurationCandidate>> {
+    let (tx, rx) = signal("configuration_candidates");
 
     let ipc_config_source_rx = params.ipc_configuration_source_rx.clone();
     let fs_config_source_rx = params.fs_configuration_source_rx.clone();
+    let cache_config_source_rx = params.cache_configuration_source_rx.clone();
 
     task_builder
         .new_task(stringify!(select_configuration))
         .spawn(async move {
             loop {
-                let config = match (
-                    ipc_config_source_rx.get().await.as_ref(),
-                    fs_config_source_rx.get().await.as_ref(),
-                ) {
-                    (Some((_, ipc_config)), None) => {
-                        debug!("Using IPC configuration");
-                        Some(ipc_config.clone())
-                    }
-                    (None, Some((_, fs_config))) => {
-                        debug!("Using file-based configuration");
-                        Some(fs_config.clone())
-                    }
-                    (Some((ipc_serial, ipc_config)), Some((fs_serial, _)))
-                        if fs_serial < ipc_serial =>
-                    {
-                        debug!("Using IPC configuration, newer");
-                        Some(ipc_config.clone())
-                    }
-                    (_, Some((_, fs_config))) => {
-                        debug!("Using file-based configuration, newer");
-                        Some(fs_config.clone())
-                    }
-                    _ => {
-                        debug!("No configuration available from either source");
-                        None
-                    }
-                };
-
-                tx.replace(config).await;
+                let candidates: Vec<_> = by_newest([
+                    (
+                        ConfigurationSource::Ipc,
+                        ipc_config_source_rx.get().await.as_ref(),
+                    ),
+                    (
+                        ConfigurationSource::File,
+                        fs_config_source_rx.get().await.as_ref(),
+                    ),
+                    (
+                        ConfigurationSource::Cache,
+                        cache_config_source_rx.get().await.as_ref(),
+                    ),
+                ])
+                .into_iter()
+                .map(|(source, version, config)| (source, version, config.clone()))
+                .collect();
+
+                match candidates.first() {
+                    Some((source, version, _)) => debug!(
+                        "Newest configuration version {:?} from {:?}",
+                        version, source
+                    ),
+                    None => debug!("No configuration available from any source"),
+                }
+
+                tx.set(candidates).await;
 
                 continue_on!(
                     params.ipc_configuration_source_rx.changed(),
-                    params.fs_configuration_source_rx.changed()
+                    params.fs_configuration_source_rx.changed(),
+                    params.cache_configuration_source_rx.changed()
                 );
             }
         });
 
     rx
 }
+
+/// The available configurations by version, highest first and unversioned ones last. Sources
+/// are listed by preference, the first comes first on a tie
+fn by_newest<'a>(
+    sources: [(
+        ConfigurationSource,
+        Option<&'a (Option<u64>, GatewayConfiguration)>,
+    ); 3],
+) -> Vec<(ConfigurationSource, Option<u64>, &'a GatewayConfiguration)> {
+    let mut candidates: Vec<_> = sources
+        .into_iter()
+        .filter_map(|(source, versioned_config)| {
+            versioned_config.map(|(version, config)| (source, *version, config))
+        })
+        .collect();
+    // The sort is stable, keeping the preference between equal versions
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vg_core::config::gateway::types::GatewayConfigurationBuilder;
+
+    #[test]
+    fn test_configurations_by_newest() {
+        let config = GatewayConfigurationBuilder::default()
+            .build()
+            .expect("Failed to build configuration");
+        let unversioned = (None, config.clone());
+        let older = (Some(1), config.clone());
+        let newer = (Some(2), config);
+
+        let sources = |sources| {
+            by_newest(sources)
+                .into_iter()
+                .map(|(source, version, _)| (source, version))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sources([
+                (ConfigurationSource::Ipc, None),
+                (ConfigurationSource::File, None),
+                (ConfigurationSource::Cache, None),
+            ]),
+            vec![]
+        );
+        assert_eq!(
+            sources([
+                (ConfigurationSource::Ipc, None),
+                (ConfigurationSource::File, Some(&unversioned)),
+                (ConfigurationSource::Cache, None),
+            ]),
+            vec![(ConfigurationSource::File, None)]
+        );
+
+        // A cache ahead of a stale ConfigMap comes first while IPC is not ready
+        assert_eq!(
+            sources([
+                (ConfigurationSource::Ipc, None),
+                (ConfigurationSource::File, Some(&older)),
+                (ConfigurationSource::Cache, Some(&newer)),
+            ]),
+            vec![
+                (ConfigurationSource::Cache, Some(2)),
+                (ConfigurationSource::File, Some(1)),
+            ]
+        );
+        assert_eq!(
+            sources([
+                (ConfigurationSource::Ipc, Some(&newer)),
+                (ConfigurationSource::File, Some(&unversioned)),
+                (ConfigurationSource::Cache, Some(&older)),
+            ]),
+            vec![
+                (ConfigurationSource::Ipc, Some(2)),
+                (ConfigurationSource::Cache, Some(1)),
+                (ConfigurationSource::File, None),
+            ]
+        );
+
+        // The same version from several sources is taken from the preferred one first
+        assert_eq!(
+            sources([
+                (ConfigurationSource::Ipc, Some(&newer)),
+                (ConfigurationSource::File, Some(&newer)),
+                (ConfigurationSource::Cache, Some(&newer)),
+            ]),
+            vec![
+                (ConfigurationSource::Ipc, Some(2)),
+                (ConfigurationSource::File, Some(2)),
+                (ConfigurationSource::Cache, Some(2)),
+            ]
+        );
+    }
+}
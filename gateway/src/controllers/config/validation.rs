@@ -0,0 +1,494 @@
+use crate::controllers::config::selector::{ConfigurationCandidate, ConfigurationSource};
+use http::{HeaderName, HeaderValue, StatusCode};
+use itertools::Itertools;
+use regex::Regex;
+use serde_valid::Validate;
+use std::fmt::Debug;
+use thiserror::Error;
+use tracing::{debug, warn};
+use typed_builder::TypedBuilder;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::config::gateway::types::http::filters::{HTTPHeader, HttpRouteFilter};
+use vg_core::config::gateway::types::http::router::{
+    HttpHeaderMatchType, HttpPathMatchType, HttpQueryParamMatchType, HttpRoute, HttpRouteRule,
+};
+use vg_core::config::gateway::types::net::CorsOrigin;
+use vg_core::continue_on;
+use vg_core::ipc::configuration::ConfigurationStatus;
+use vg_core::sync::signal::{Receiver, Sender, signal};
+use vg_core::task::Builder as TaskBuilder;
+
+/// A problem with a configuration, which would otherwise only surface when requests are proxied
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("Configuration does not match the schema: {0}")]
+    Schema(String),
+
+    #[error("Invalid regular expression {1:?} in {0}: {2}")]
+    InvalidRegex(String, String, String),
+
+    #[error("Invalid header name {1:?} in {0}")]
+    InvalidHeaderName(String, String),
+
+    #[error("Invalid value of header {1:?} in {0}")]
+    InvalidHeaderValue(String, String),
+
+    #[error("Invalid status code {1} in {0}")]
+    InvalidStatusCode(String, u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{}", .0.iter().join("; "))]
+pub struct ValidationErrors(Vec<ValidationError>);
+
+impl ValidationErrors {
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.0
+    }
+}
+
+/// Checks a configuration against its schema, and the regular expressions, header names and status
+/// codes the schema does not cover, reporting every problem rather than the first one. Unknown
+/// filter references are only logged, as the rules referencing them fail closed
+pub fn validate(config: &GatewayConfiguration) -> Result<(), ValidationErrors> {
+    let mut validator = Validator {
+        config,
+        errors: Vec::new(),
+    };
+
+    if let Err(errors) = Validate::validate(config) {
+        validator
+            .errors
+            .push(ValidationError::Schema(errors.to_string()));
+    }
+
+    for route in config.http_routes() {
+        validator.route(route);
+    }
+
+    if let Some(client_addrs) = config.client_addrs()
+        && let Some(header) = client_addrs.header()
+    {
+        validator.header_name("client addresses", header);
+    }
+
+    if let Some(static_responses) = config.static_responses() {
+        for response in static_responses.responses() {
+            let location = format!("static response {}", response.key());
+            if StatusCode::from_u16(*response.status_code()).is_err() {
+                validator.errors.push(ValidationError::InvalidStatusCode(
+                    location,
+                    *response.status_code(),
+                ));
+            }
+        }
+    }
+
+    for filter in config.jwt_auth_filters() {
+        let location = format!("JWT auth filter {:?}", filter.key());
+        for claim_header in filter.claim_headers() {
+            validator.header_name(&location, claim_header.header());
+        }
+    }
+
+    for filter in config.external_auth_filters() {
+        let location = format!("external auth filter {:?}", filter.key());
+        for header in filter
+            .request_headers()
+            .iter()
+            .chain(filter.upstream_headers())
+        {
+            validator.header_name(&location, header);
+        }
+        if let Some(cache) = filter.cache() {
+            validator.header_name(&location, cache.key_header());
+        }
+    }
+
+    for filter in config.basic_auth_filters() {
+        if let Some(username_header) = filter.username_header() {
+            validator.header_name(
+                &format!("basic auth filter {:?}", filter.key()),
+                username_header,
+            );
+        }
+    }
+
+    for filter in config.cors_filters() {
+        let location = format!("CORS filter {:?}", filter.key());
+        for origin in filter.allow_origins() {
+            if let CorsOrigin::Regex(pattern) = origin {
+                validator.regex(&location, pattern);
+            }
+        }
+        for header in filter.allow_headers().iter().chain(filter.expose_headers()) {
+            if header != "*" {
+                validator.header_name(&location, header);
+            }
+        }
+    }
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationErrors(validator.errors))
+    }
+}
+
+struct Validator<'a> {
+    config: &'a GatewayConfiguration,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator<'_> {
+    fn route(&mut self, route: &HttpRoute) {
+        let route_key = route.key().as_deref().unwrap_or("(unnamed)");
+        for rule in route.rules() {
+            let location = format!("rule {} of route {}", rule.unique_id().get(), route_key);
+            self.rule(&location, rule);
+        }
+    }
+
+    fn rule(&mut self, location: &str, rule: &HttpRouteRule) {
+        for matches in rule.matches() {
+            if *matches.path().match_type() == HttpPathMatchType::RegularExpression {
+                self.regex(location, matches.path().value());
+            }
+            for header in matches.headers().iter().flatten() {
+                if *header.match_type() == HttpHeaderMatchType::RegularExpression {
+                    self.regex(location, header.value());
+                }
+            }
+            for query_param in matches.query_params().iter().flatten() {
+                if *query_param.match_type() == HttpQueryParamMatchType::RegularExpression {
+                    self.regex(location, query_param.value());
+                }
+            }
+        }
+
+        for filter in rule.filters() {
+            self.filter(location, filter);
+        }
+
+        for backend in rule.backends() {
+            if let Some(modifier) = backend.request_header_modifier() {
+                let location = format!("backend {} of {}", backend.name(), location);
+                self.header_modifier(&location, &modifier.set, &modifier.add, &modifier.remove);
+            }
+        }
+    }
+
+    fn filter(&mut self, location: &str, filter: &HttpRouteFilter) {
+        if let Some(modifier) = &filter.request_header_modifier {
+            self.header_modifier(location, &modifier.set, &modifier.add, &modifier.remove);
+        }
+        if let Some(modifier) = &filter.response_header_modifier {
+            self.header_modifier(location, &modifier.set, &modifier.add, &modifier.remove);
+        }
+        if let Some(status_code) = filter
+            .request_redirect
+            .as_ref()
+            .and_then(|redirect| redirect.status_code)
+            && !StatusCode::from_u16(status_code).is_ok_and(|status| status.is_redirection())
+        {
+            self.errors.push(ValidationError::InvalidStatusCode(
+                location.to_string(),
+                status_code,
+            ));
+        }
+
+        let config = self.config;
+        if let Some(filter_ref) = &filter.ext_static_response {
+            let known = config.static_responses().as_ref().is_some_and(|responses| {
+                responses
+                    .responses()
+                    .iter()
+                    .any(|response| response.key() == filter_ref.key())
+            });
+            self.filter_ref(location, "static response", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_access_control {
+            let known = config
+                .access_control_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "access control", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_rate_limit {
+            let known = config
+                .rate_limit_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "rate limit", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_jwt_auth {
+            let known = config
+                .jwt_auth_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "JWT auth", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_external_auth {
+            let known = config
+                .external_auth_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "external auth", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_basic_auth {
+            let known = config
+                .basic_auth_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "basic auth", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_cors {
+            let known = config
+                .cors_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "CORS", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_compression {
+            let known = config
+                .compression_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "compression", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_cache {
+            let known = config
+                .cache_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "cache", filter_ref.key(), known);
+        }
+        if let Some(filter_ref) = &filter.ext_request_limits {
+            let known = config
+                .request_limits_filters()
+                .iter()
+                .any(|filter| filter.key() == filter_ref.key());
+            self.filter_ref(location, "request limits", filter_ref.key(), known);
+        }
+    }
+
+    fn header_modifier(
+        &mut self,
+        location: &str,
+        set: &Option<Vec<HTTPHeader>>,
+        add: &Option<Vec<HTTPHeader>>,
+        remove: &Option<Vec<String>>,
+    ) {
+        for header in set.iter().chain(add).flatten() {
+            self.header_name(location, &header.name);
+            if HeaderValue::from_str(&header.value).is_err() {
+                self.errors.push(ValidationError::InvalidHeaderValue(
+                    location.to_string(),
+                    header.name.clone(),
+                ));
+            }
+        }
+        for name in remove.iter().flatten() {
+            self.header_name(location, name);
+        }
+    }
+
+    fn header_name(&mut self, location: &str, name: &str) {
+        if HeaderName::from_bytes(name.as_bytes()).is_err() {
+            self.errors.push(ValidationError::InvalidHeaderName(
+                location.to_string(),
+                name.to_string(),
+            ));
+        }
+    }
+
+    fn regex(&mut self, location: &str, pattern: &str) {
+        if let Err(err) = Regex::new(pattern) {
+            self.errors.push(ValidationError::InvalidRegex(
+                location.to_string(),
+                pattern.to_string(),
+                err.to_string(),
+            ));
+        }
+    }
+
+    /// The control plane still references filters it skipped as invalid, and the rules
+    /// referencing them reject requests on their own, so they do not reject the configuration
+    fn filter_ref<K: Debug>(&self, location: &str, kind: &'static str, key: &K, known: bool) {
+        if !known {
+            warn!(
+                "Unknown {} filter {:?} referenced in {}, its requests will be rejected",
+                kind, key, location
+            );
+        }
+    }
+}
+
+#[derive(Debug, TypedBuilder)]
+pub struct ValidateConfigurationParams {
+    configuration_candidates_rx: Receiver<Vec<ConfigurationCandidate>>,
+    configuration_source_tx: Sender<ConfigurationSource>,
+    configuration_status_tx: Sender<(u64, ConfigurationStatus)>,
+}
+
+/// Activates the newest configuration that passes [`validate`]. When the newest one is invalid,
+/// it is rejected and an older one from another source takes over, or the active one keeps
+/// serving
+pub fn validate_configuration(
+    task_builder: &TaskBuilder,
+    params: ValidateConfigurationParams,
+) -> Receiver<GatewayConfiguration> {
+    let (tx, rx) = signal("validated_configuration");
+
+    task_builder
+        .new_task(stringify!(validate_configuration))
+        .spawn(async move {
+            let configuration_candidates_rx = params.configuration_candidates_rx;
+            loop {
+                let candidates = configuration_candidates_rx.get().await.clone();
+                let (activated, status) = newest_valid(candidates.as_deref().unwrap_or_default());
+
+                if let Some((source, version, config)) = activated {
+                    debug!(
+                        "Activating configuration version {:?} from {:?}",
+                        version, source
+                    );
+                    params.configuration_source_tx.set(*source).await;
+                    tx.set(config.clone()).await;
+                }
+
+                if let Some(status) = status {
+                    params.configuration_status_tx.set(status).await;
+                }
+
+                continue_on!(configuration_candidates_rx.changed());
+            }
+        });
+
+    rx
+}
+
+/// The newest valid configuration, and the outcome of the newest one, which is the version
+/// the control plane waits for
+fn newest_valid(
+    candidates: &[ConfigurationCandidate],
+) -> (
+    Option<&ConfigurationCandidate>,
+    Option<(u64, ConfigurationStatus)>,
+) {
+    let mut status = None;
+    for (index, candidate) in candidates.iter().enumerate() {
+        let (source, version, config) = candidate;
+        let outcome = match validate(config) {
+            Ok(()) => ConfigurationStatus::Applied,
+            Err(errors) => {
+                warn!(
+                    "Rejecting configuration version {:?} from {:?}: {}",
+                    version, source, errors
+                );
+                ConfigurationStatus::Rejected {
+                    error: errors.to_string(),
+                }
+            }
+        };
+        let is_valid = outcome == ConfigurationStatus::Applied;
+
+        if index == 0 {
+            status = version.map(|version| (version, outcome));
+        }
+        if is_valid {
+            return (Some(candidate), status);
+        }
+    }
+
+    (None, status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use vg_core::config::gateway::serde::read_configuration;
+
+    #[test]
+    fn test_validate_configuration() {
+        let config = include_str!("../testcases/simple.yaml");
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        assert_eq!(validate(&config), Ok(()));
+
+        let config = include_str!("../testcases/invalid.yaml");
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        let errors = validate(&config).expect_err("Configuration should be invalid");
+
+        assert!(matches!(
+            errors.errors(),
+            [
+                ValidationError::InvalidRegex(_, pattern, _),
+                ValidationError::InvalidHeaderName(_, name),
+                ValidationError::InvalidStatusCode(_, 200),
+            ] if pattern == "/users/(" && name == "x bad"
+        ));
+    }
+
+    #[test]
+    fn test_validate_configuration_schema() {
+        let listeners: String = (0..65)
+            .map(|index| {
+                format!(
+                    "  - name: http-{index}\n    port: {}\n    protocol: HTTP\n",
+                    8000 + index
+                )
+            })
+            .collect();
+        let config = format!("version: v1alpha1\nlisteners:\n{listeners}");
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        let errors = validate(&config).expect_err("Configuration should be invalid");
+
+        assert!(matches!(errors.errors(), [ValidationError::Schema(_)]));
+    }
+
+    #[test]
+    fn test_newest_valid_configuration() {
+        let valid = include_str!("../testcases/simple.yaml");
+        let valid = read_configuration(Cursor::new(valid)).expect("Failed to read configuration");
+        let invalid = include_str!("../testcases/invalid.yaml");
+        let invalid =
+            read_configuration(Cursor::new(invalid)).expect("Failed to read configuration");
+
+        let activated = |candidates: &[ConfigurationCandidate]| {
+            let (activated, status) = newest_valid(candidates);
+            (
+                activated.map(|(source, version, _)| (*source, *version)),
+                status.map(|(version, status)| {
+                    (version, matches!(status, ConfigurationStatus::Applied))
+                }),
+            )
+        };
+
+        assert_eq!(
+            activated(&[
+                (ConfigurationSource::Ipc, Some(2), valid.clone()),
+                (ConfigurationSource::Cache, Some(1), valid.clone()),
+            ]),
+            (Some((ConfigurationSource::Ipc, Some(2))), Some((2, true)))
+        );
+
+        // An invalid version is rejected, and the cached one keeps serving
+        assert_eq!(
+            activated(&[
+                (ConfigurationSource::Ipc, Some(2), invalid.clone()),
+                (ConfigurationSource::Cache, Some(1), valid),
+            ]),
+            (
+                Some((ConfigurationSource::Cache, Some(1))),
+                Some((2, false))
+            )
+        );
+
+        assert_eq!(
+            activated(&[(ConfigurationSource::File, None, invalid)]),
+            (None, None)
+        );
+        assert_eq!(activated(&[]), (None, None));
+    }
+}
@@ -0,0 +1,330 @@
+use async_trait::async_trait;
+use getset::Getters;
+use hickory_resolver::TokioAsyncResolver;
+use hickory_resolver::error::ResolveError;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, warn};
+use typed_builder::TypedBuilder;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::continue_after;
+use vg_core::net::Hostname;
+use vg_core::sync::signal::{Receiver, signal};
+use vg_core::task::Builder as TaskBuilder;
+
+/// How soon a hostname is resolved again at the earliest, however short its TTL
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the addresses of a hostname are used at most, however long its TTL
+const MAX_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How soon a hostname that failed to resolve is tried again
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The addresses a hostname resolved to, valid until its TTL runs out
+#[derive(Getters, Debug, Clone, PartialEq, Eq, TypedBuilder)]
+pub struct Resolution {
+    #[getset(get = "pub")]
+    addresses: Vec<IpAddr>,
+
+    #[getset(get = "pub")]
+    valid_until: Instant,
+}
+
+#[derive(Debug, Error)]
+pub enum ResolverError {
+    #[error("Lookup failed: {0}")]
+    Lookup(#[source] ResolveError),
+    #[error("No addresses found")]
+    NoAddresses,
+}
+
+/// Resolves the hostnames of endpoints outside of the cluster
+#[async_trait]
+pub trait Resolver: Send + Sync {
+    async fn resolve(&self, hostname: &Hostname) -> Result<Resolution, ResolverError>;
+}
+
+/// Resolves with the DNS servers and search domains of the system, `/etc/resolv.conf` on Linux
+#[derive(Clone)]
+pub struct SystemResolver(TokioAsyncResolver);
+
+impl SystemResolver {
+    pub fn from_system_conf() -> Result<Self, ResolveError> {
+        TokioAsyncResolver::tokio_from_system_conf().map(Self)
+    }
+}
+
+#[async_trait]
+impl Resolver for SystemResolver {
+    async fn resolve(&self, hostname: &Hostname) -> Result<Resolution, ResolverError> {
+        let lookup = self
+            .0
+            .lookup_ip(hostname.as_ref())
+            .await
+            .map_err(ResolverError::Lookup)?;
+
+        Ok(Resolution::builder()
+            .addresses(lookup.iter().collect())
+            .valid_until(lookup.valid_until())
+            .build())
+    }
+}
+
+/// The addresses of the hostname endpoints of a configuration
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ResolvedHostnames(HashMap<Hostname, Vec<IpAddr>>);
+
+impl ResolvedHostnames {
+    /// The addresses of a hostname, none until it first resolves
+    pub fn addresses(&self, hostname: &Hostname) -> &[IpAddr] {
+        self.0.get(hostname).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl FromIterator<(Hostname, Vec<IpAddr>)> for ResolvedHostnames {
+    fn from_iter<T: IntoIterator<Item = (Hostname, Vec<IpAddr>)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Resolves the hostname endpoints of the configuration, again as their TTLs run out. A hostname
+/// that fails to resolve keeps its previous addresses, as they are more likely to work than none
+pub fn resolve_endpoint_hostnames(
+    task_builder: &TaskBuilder,
+    resolver: impl Resolver + 'static,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<ResolvedHostnames> {
+    let (tx, rx) = signal("resolved_hostnames");
+
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(resolve_endpoint_hostnames))
+        .spawn(async move {
+            let mut resolutions = Resolutions::default();
+            loop {
+                let hostnames = gateway_configuration_rx
+                    .get()
+                    .await
+                    .as_ref()
+                    .map(endpoint_hostnames)
+                    .unwrap_or_default();
+
+                let refresh_at = resolutions
+                    .refresh(hostnames, &resolver, Instant::now())
+                    .await;
+                tx.set(resolutions.resolved()).await;
+
+                let refresh_in = refresh_at.map_or(MAX_REFRESH_INTERVAL, |refresh_at| {
+                    refresh_at.saturating_duration_since(Instant::now())
+                });
+                continue_after!(refresh_in, gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
+
+/// The hostnames of the endpoints of every route
+fn endpoint_hostnames(gateway_config: &GatewayConfiguration) -> HashSet<Hostname> {
+    gateway_config
+        .http_routes()
+        .iter()
+        .flat_map(|route| route.rules())
+        .flat_map(|rule| rule.backends())
+        .flat_map(|backend| backend.endpoints())
+        .filter_map(|endpoint| endpoint.address().hostname().cloned())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct Resolutions {
+    entries: HashMap<Hostname, ResolutionEntry>,
+}
+
+#[derive(Debug)]
+struct ResolutionEntry {
+    addresses: Vec<IpAddr>,
+    refresh_at: Instant,
+}
+
+impl Resolutions {
+    /// Resolves the hostnames that are new or due, and forgets the ones no longer used. Returns
+    /// when the next hostname is due
+    async fn refresh<R: Resolver + ?Sized>(
+        &mut self,
+        hostnames: HashSet<Hostname>,
+        resolver: &R,
+        now: Instant,
+    ) -> Option<Instant> {
+        self.entries
+            .retain(|hostname, _| hostnames.contains(hostname));
+
+        for hostname in hostnames {
+            let due = self
+                .entries
+                .get(&hostname)
+                .is_none_or(|entry| entry.refresh_at <= now);
+            if !due {
+                continue;
+            }
+
+            let resolution = resolver.resolve(&hostname).await.and_then(|resolution| {
+                if resolution.addresses.is_empty() {
+                    Err(ResolverError::NoAddresses)
+                } else {
+                    Ok(resolution)
+                }
+            });
+
+            match resolution {
+                Ok(mut resolution) => {
+                    debug!(
+                        "Resolved {:?} to {:?}",
+                        hostname.as_ref(),
+                        resolution.addresses
+                    );
+                    // Round robin DNS reorders the same addresses, which is no change
+                    resolution.addresses.sort_unstable();
+                    let refresh_at = resolution
+                        .valid_until
+                        .clamp(now + MIN_REFRESH_INTERVAL, now + MAX_REFRESH_INTERVAL);
+                    self.entries.insert(
+                        hostname,
+                        ResolutionEntry {
+                            addresses: resolution.addresses,
+                            refresh_at,
+                        },
+                    );
+                }
+                Err(err) => {
+                    warn!("Error resolving {:?}: {}", hostname.as_ref(), err);
+                    let entry = self
+                        .entries
+                        .entry(hostname)
+                        .or_insert_with(|| ResolutionEntry {
+                            addresses: Vec::new(),
+                            refresh_at: now,
+                        });
+                    entry.refresh_at = now + RETRY_INTERVAL;
+                }
+            }
+        }
+
+        self.entries.values().map(|entry| entry.refresh_at).min()
+    }
+
+    fn resolved(&self) -> ResolvedHostnames {
+        self.entries
+            .iter()
+            .map(|(hostname, entry)| (hostname.clone(), entry.addresses.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Answers from a table instead of the network, counting lookups
+    struct StaticResolver {
+        answers: Mutex<HashMap<String, Option<(Vec<IpAddr>, Duration)>>>,
+        lookups: Mutex<usize>,
+        now: Instant,
+    }
+
+    impl StaticResolver {
+        fn new(now: Instant) -> Self {
+            Self {
+                answers: Mutex::default(),
+                lookups: Mutex::default(),
+                now,
+            }
+        }
+
+        fn answer(&self, hostname: &str, answer: Option<(&[&str], u64)>) {
+            let answer = answer.map(|(addresses, ttl)| (ips(addresses), Duration::from_secs(ttl)));
+            self.answers
+                .lock()
+                .unwrap()
+                .insert(hostname.to_string(), answer);
+        }
+
+        fn lookups(&self) -> usize {
+            *self.lookups.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl Resolver for StaticResolver {
+        async fn resolve(&self, hostname: &Hostname) -> Result<Resolution, ResolverError> {
+            *self.lookups.lock().unwrap() += 1;
+            let (addresses, ttl) = self
+                .answers
+                .lock()
+                .unwrap()
+                .get(hostname.as_ref())
+                .cloned()
+                .flatten()
+                .ok_or(ResolverError::NoAddresses)?;
+            Ok(Resolution::builder()
+                .addresses(addresses)
+                .valid_until(self.now + ttl)
+                .build())
+        }
+    }
+
+    fn ips(addresses: &[&str]) -> Vec<IpAddr> {
+        addresses
+            .iter()
+            .map(|address| address.parse().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resolutions_honor_ttls() {
+        let now = Instant::now();
+        let resolver = StaticResolver::new(now);
+        resolver.answer("api.example.com", Some((&["10.0.0.2", "10.0.0.1"], 60)));
+        resolver.answer("short.example.com", Some((&["10.0.1.1"], 1)));
+
+        let api = Hostname::new("api.example.com");
+        let short = Hostname::new("short.example.com");
+        let hostnames = HashSet::from([api.clone(), short.clone()]);
+
+        let mut resolutions = Resolutions::default();
+        let refresh_at = resolutions.refresh(hostnames.clone(), &resolver, now).await;
+        assert_eq!(refresh_at, Some(now + MIN_REFRESH_INTERVAL));
+        assert_eq!(
+            resolutions.resolved().addresses(&api),
+            ips(&["10.0.0.1", "10.0.0.2"])
+        );
+        assert_eq!(resolver.lookups(), 2);
+
+        // Only the hostname with the short TTL is due
+        let later = now + MIN_REFRESH_INTERVAL;
+        let refresh_at = resolutions
+            .refresh(hostnames.clone(), &resolver, later)
+            .await;
+        assert_eq!(refresh_at, Some(later + MIN_REFRESH_INTERVAL));
+        assert_eq!(resolver.lookups(), 3);
+
+        // A failure keeps the previous addresses and is retried soon
+        resolver.answer("short.example.com", None);
+        let later = later + MIN_REFRESH_INTERVAL;
+        let refresh_at = resolutions.refresh(hostnames, &resolver, later).await;
+        assert_eq!(refresh_at, Some(later + RETRY_INTERVAL));
+        assert_eq!(resolutions.resolved().addresses(&short), ips(&["10.0.1.1"]));
+
+        // Hostnames no longer configured are forgotten
+        let refresh_at = resolutions
+            .refresh(HashSet::from([api.clone()]), &resolver, later)
+            .await;
+        assert_eq!(refresh_at, Some(now + Duration::from_secs(60)));
+        assert!(resolutions.resolved().addresses(&short).is_empty());
+    }
+}
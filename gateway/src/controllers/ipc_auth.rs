@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use http::header::AUTHORIZATION;
+use http::{Extensions, HeaderValue};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use std::path::PathBuf;
+use tracing::warn;
+
+/// The projected ServiceAccount token the gateway authenticates to the control plane
+/// with. It is read again for every request, as the kubelet rotates it.
+#[derive(Debug, Clone, Default)]
+pub struct IpcToken(Option<PathBuf>);
+
+impl IpcToken {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self(path)
+    }
+
+    /// The value of the `Authorization` header, if a token is configured and readable
+    pub async fn authorization(&self) -> Option<String> {
+        let path = self.0.as_ref()?;
+        match tokio::fs::read_to_string(path).await {
+            Ok(token) => Some(format!("Bearer {}", token.trim())),
+            Err(e) => {
+                warn!("Failed to read IPC token from {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+/// Adds the IPC token to requests, only used by the client talking to the control plane
+#[derive(Debug, Clone)]
+pub struct IpcAuthMiddleware(IpcToken);
+
+impl IpcAuthMiddleware {
+    pub fn new(token: IpcToken) -> Self {
+        Self(token)
+    }
+}
+
+#[async_trait]
+impl Middleware for IpcAuthMiddleware {
+    async fn handle(
+        &self,
+        mut request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if let Some(authorization) = self.0.authorization().await
+            && let Ok(mut value) = HeaderValue::from_str(&authorization)
+        {
+            value.set_sensitive(true);
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+
+        next.run(request, extensions).await
+    }
+}
@@ -1,23 +1,29 @@
-use eventsource_client::{Client, ClientBuilder, SSE};
-use futures::TryStreamExt;
+use crate::controllers::config::ipc::IpcEndpoint;
+use futures::StreamExt;
 use getset::Getters;
+use http::header::ACCEPT;
+use http::StatusCode;
 use reqwest_middleware::ClientWithMiddleware;
-use std::net::SocketAddr;
+use reqwest_tracing::{OtelName, OtelPathNames};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::select;
 use tokio::signal::ctrl_c;
 use tokio::sync::broadcast::{Sender, channel};
-use tracing::{debug, info};
+use tokio::time::sleep;
+use tracing::{debug, error, info, warn};
 use typed_builder::TypedBuilder;
-use url::Url;
 use vg_core::{await_ready, continue_on, ReadyState};
 use vg_core::ipc::GatewayEvent;
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
 
+/// How long to wait before watching events again after the stream ended or failed
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Getters, TypedBuilder)]
 pub struct PollGatewayEventsParams {
-    ipc_endpoint_rx: Receiver<SocketAddr>,
+    ipc_endpoint_rx: Receiver<IpcEndpoint>,
     #[builder(setter(into))]
     pod_name: String,
     #[builder(setter(into))]
@@ -39,10 +45,9 @@ pub fn poll_gateway_events(
         .new_task(stringify!(poll_gateway_events))
         .spawn(async move {
             'primary: loop {
-                if let ReadyState::Ready(ipc_endpoint_addr) = await_ready!(ipc_endpoint_rx) {
+                if let ReadyState::Ready(ipc_endpoint) = await_ready!(ipc_endpoint_rx) {
                     let url = {
-                        let mut url = Url::parse(&format!("http://{ipc_endpoint_addr}"))
-                            .expect("Failed to parse URL");
+                        let mut url = ipc_endpoint.url();
                         url.set_path(&format!(
                             "/ipc/namespaces/{}/gateways/{}/events",
                             params.gateway_namespace, params.gateway_name
@@ -53,46 +58,80 @@ pub fn poll_gateway_events(
 
                     info!("Watching events at URL: {}", url);
 
-                    let client = ClientBuilder::for_url(url.as_str())
-                        .expect("Failed to create event source client builder")
-                        .build();
-
-                    let mut event_stream = Box::pin(client.stream());
-
-                    'events: loop {
-                        select! {
-                        _ = ctrl_c() => {
-                            break 'primary; // Exit the loop, shutting down the watcher
-                        },
-                        _ = ipc_endpoint_rx.changed() => {
-                            break 'events; // Restart the watcher if the socket address changes
-                        },
-                        event = event_stream.try_next() => {
-                            match event {
-                                Ok(Some(SSE::Event(event))) => {
-                                    let _ = GatewayEvent::try_parse(event.event_type, event.data)
-                                        .map(|gateway_event| {
-                                            tracing::debug!("Received gateway event: {:?}", gateway_event);
-                                            events_tx.send(gateway_event).ok();
-                                        })
-                                        .inspect_err(|e| {
-                                            tracing::error!("Failed to parse gateway event: {}", e);
-                                        });
-                                }
-                                Ok(Some(_)) => {
-                                    tracing::debug!("Received non-event SSE message");
-                                }
-                                Ok(None) => {
-                                    tracing::info!("Event stream ended");
-                                    break 'events; // Exit if the stream ends
-                                }
-                                Err(e) => {
-                                    tracing::error!("Error receiving event: {}", e);
-                                    break 'events; // Exit on error
+                    // The stream goes through the IPC client, so it is authenticated and
+                    // verified against the pinned CA like every other request to the control plane
+                    let response = params
+                        .client
+                        .get(url)
+                        .header(ACCEPT, "text/event-stream")
+                        .with_extension(OtelName("poll_gateway_events".into()))
+                        .with_extension(
+                            OtelPathNames::known_paths([
+                                "/ipc/namespaces/{namespace}/gateways/{gateway_name}/events",
+                            ])
+                            .expect("Failed to set known paths"),
+                        )
+                        .send()
+                        .await;
+
+                    let event_stream = match response {
+                        Ok(response) if response.status() == StatusCode::OK => {
+                            Some(response.bytes_stream())
+                        }
+                        Ok(response) => {
+                            warn!("Unexpected response watching events: {:?}", response);
+                            None
+                        }
+                        Err(e) => {
+                            warn!("Error watching events: {}", e);
+                            None
+                        }
+                    };
+
+                    if let Some(mut event_stream) = event_stream {
+                        let mut decoder = SseDecoder::default();
+
+                        'events: loop {
+                            select! {
+                            _ = ctrl_c() => {
+                                break 'primary; // Exit the loop, shutting down the watcher
+                            },
+                            _ = ipc_endpoint_rx.changed() => {
+                                continue 'primary; // Restart the watcher if the endpoint changes
+                            },
+                            chunk = event_stream.next() => {
+                                match chunk {
+                                    Some(Ok(chunk)) => {
+                                        for (event_type, data) in decoder.decode(&chunk) {
+                                            let _ = GatewayEvent::try_parse(event_type, data)
+                                                .map(|gateway_event| {
+                                                    debug!("Received gateway event: {:?}", gateway_event);
+                                                    events_tx.send(gateway_event).ok();
+                                                })
+                                                .inspect_err(|e| {
+                                                    error!("Failed to parse gateway event: {}", e);
+                                                });
+                                        }
+                                    }
+                                    None => {
+                                        info!("Event stream ended");
+                                        break 'events; // Exit if the stream ends
+                                    }
+                                    Some(Err(e)) => {
+                                        error!("Error receiving event: {}", e);
+                                        break 'events; // Exit on error
+                                    }
                                 }
                             }
+                            }
                         }
                     }
+
+                    // Watch again shortly, unless the endpoint changes first
+                    select! {
+                        _ = ctrl_c() => break 'primary,
+                        _ = ipc_endpoint_rx.changed() => {},
+                        () = sleep(RECONNECT_DELAY) => {},
                     }
                 } else {
                     continue_on!(ipc_endpoint_rx.changed());
@@ -102,3 +141,76 @@ pub fn poll_gateway_events(
 
     tx
 }
+
+/// Splits a server-sent event stream into the type and data of its events, keeping incomplete
+/// lines until the rest of them arrives
+#[derive(Debug, Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+    event_type: Option<String>,
+    data: Option<String>,
+}
+
+impl SseDecoder {
+    fn decode(&mut self, chunk: &[u8]) -> Vec<(String, String)> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = vec![];
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                // A blank line dispatches the event, events without data are ignored
+                let event_type = self.event_type.take();
+                if let Some(data) = self.data.take() {
+                    events.push((event_type.unwrap_or_else(|| "message".to_string()), data));
+                }
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event_type = Some(value.to_string()),
+                "data" => match &mut self.data {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => self.data = Some(value.to_string()),
+                },
+                // Comments, such as keep-alives, ids and retry intervals are not used
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_events() {
+        let mut decoder = SseDecoder::default();
+
+        assert_eq!(
+            decoder.decode(b":\n\nevent: configuration_update\ndata: {\"name\":"),
+            vec![]
+        );
+        assert_eq!(
+            decoder.decode(b"\"a\"}\r\n\r\ndata: 1\ndata: 2\n\n"),
+            vec![
+                (
+                    "configuration_update".to_string(),
+                    "{\"name\":\"a\"}".to_string()
+                ),
+                ("message".to_string(), "1\n2".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use crate::controllers::config::ipc::IpcEndpoint;
+use crate::proxy::filters::basic_auth::Htpasswd;
+use crate::proxy::upstream_tls::{parse_client_certificate, ClientCertKey};
+use dashmap::DashMap;
+use http::StatusCode;
+use jsonwebtoken::jwk::JwkSet;
+use reqwest_middleware::ClientWithMiddleware;
+use reqwest_tracing::{OtelName, OtelPathNames};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+/// How long a secret is used before it is fetched again to pick up rotations
+const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How soon a secret may be fetched again when a request names an entry it does not have
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Secret material gateways fetch from the control plane rather than receive with their
+/// configuration, such as the keys of a JWKS
+pub trait IpcSecret: Sized + Send + Sync + 'static {
+    /// Segment of the IPC path the secret is served under
+    const PATH: &'static str;
+
+    fn parse(bytes: &[u8]) -> Option<Self>;
+
+    /// Whether the secret has an entry a request names, such as a key ID or a user. Secrets
+    /// without one are refetched early, in case the entry was just added
+    fn contains(&self, _name: &str) -> bool {
+        true
+    }
+}
+
+impl IpcSecret for JwkSet {
+    const PATH: &'static str = "jwks";
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes)
+            .inspect_err(|err| warn!("Error parsing JWKS: {}", err))
+            .ok()
+    }
+
+    fn contains(&self, key_id: &str) -> bool {
+        self.find(key_id).is_some()
+    }
+}
+
+impl IpcSecret for Htpasswd {
+    const PATH: &'static str = "htpasswd";
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        Some(Htpasswd::parse(&String::from_utf8_lossy(bytes)))
+    }
+
+    fn contains(&self, username: &str) -> bool {
+        Htpasswd::contains(self, username)
+    }
+}
+
+impl IpcSecret for ClientCertKey {
+    const PATH: &'static str = "client_certificates";
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let cert_key = parse_client_certificate(bytes);
+        if cert_key.is_none() {
+            warn!("Invalid client certificate");
+        }
+        cert_key
+    }
+}
+
+/// The keys tokens of each `JwtAuthFilter` are verified with
+pub type JwksCache = IpcSecretsCache<JwkSet>;
+
+/// The users of each `BasicAuthFilter`
+pub type HtpasswdCache = IpcSecretsCache<Htpasswd>;
+
+/// The client certificates presented to backends requiring one by their `BackendTLSPolicy`
+pub type ClientCertificatesCache = IpcSecretsCache<ClientCertKey>;
+
+struct CachedSecret<T> {
+    version_key: String,
+    fetched_at: Instant,
+    secret: Arc<T>,
+}
+
+impl<T> Clone for CachedSecret<T> {
+    fn clone(&self) -> Self {
+        Self {
+            version_key: self.version_key.clone(),
+            fetched_at: self.fetched_at,
+            secret: self.secret.clone(),
+        }
+    }
+}
+
+struct IpcSecretsCacheState<T> {
+    cache: DashMap<String, CachedSecret<T>>,
+    fetches: DashMap<String, Arc<Mutex<()>>>,
+    client: Arc<ClientWithMiddleware>,
+    ipc_endpoint: IpcEndpoint,
+    pod_name: String,
+    gateway_namespace: String,
+    gateway_name: String,
+}
+
+impl<T: IpcSecret> IpcSecretsCacheState<T> {
+    fn cached(&self, identifier: &str, version_key: &str) -> Option<CachedSecret<T>> {
+        self.cache
+            .get(identifier)
+            .map(|entry| entry.value().clone())
+            .filter(|cached| cached.version_key == version_key)
+    }
+
+    async fn fetch(&self, identifier: &str) -> Option<T> {
+        let url = {
+            let mut url = self.ipc_endpoint.url();
+            url.set_path(&format!(
+                "/ipc/namespaces/{}/gateways/{}/{}/{}",
+                self.gateway_namespace,
+                self.gateway_name,
+                T::PATH,
+                identifier
+            ));
+            url.set_query(Some(&format!("pod_name={}", self.pod_name)));
+            url
+        };
+        debug!("Fetching {} from URL: {}", T::PATH, url);
+
+        let response = self
+            .client
+            .get(url)
+            .with_extension(OtelName(format!("fetch_{}", T::PATH).into()))
+            .with_extension(
+                OtelPathNames::known_paths([format!(
+                    "/ipc/namespaces/{{namespace}}/gateways/{{gateway_name}}/{}/{{secret_id}}",
+                    T::PATH
+                )])
+                .expect("Failed to set known paths"),
+            )
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status() == StatusCode::OK => match response.bytes().await {
+                Ok(bytes) => T::parse(&bytes),
+                Err(err) => {
+                    warn!("Error reading {} response body: {}", T::PATH, err);
+                    None
+                }
+            },
+            Ok(response) => {
+                info!("Unexpected response fetching {}: {:?}", T::PATH, response);
+                None
+            }
+            Err(err) => {
+                warn!("Error fetching {}: {}", T::PATH, err);
+                None
+            }
+        }
+    }
+}
+
+/// Holds secrets fetched from the control plane on demand, by the identifier and version
+/// key they are referenced with in the configuration. A secret is refetched when its version
+/// key changes, when it gets old, and when a request names an entry it does not contain.
+/// Concurrent requests missing the same secret wait for a single fetch.
+pub struct IpcSecretsCache<T> {
+    state: Arc<RwLock<Option<IpcSecretsCacheState<T>>>>,
+}
+
+impl<T> Default for IpcSecretsCache<T> {
+    fn default() -> Self {
+        Self {
+            state: Arc::default(),
+        }
+    }
+}
+
+impl<T> Clone for IpcSecretsCache<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: IpcSecret> IpcSecretsCache<T> {
+    pub async fn get(
+        &self,
+        identifier: &str,
+        version_key: &str,
+        name: Option<&str>,
+    ) -> Option<Arc<T>> {
+        let requested_at = Instant::now();
+        let state = self.state.read().await;
+        let state = state.as_ref()?;
+
+        let is_fresh = |cached: &CachedSecret<T>| {
+            let age = cached.fetched_at.elapsed();
+            let missing = name.is_some_and(|name| !cached.secret.contains(name));
+            age < REFRESH_INTERVAL && !(missing && age >= MIN_REFRESH_INTERVAL)
+        };
+
+        if let Some(cached) = state.cached(identifier, version_key)
+            && is_fresh(&cached)
+        {
+            return Some(cached.secret);
+        }
+
+        let fetch = state
+            .fetches
+            .entry(identifier.to_string())
+            .or_default()
+            .clone();
+        let _fetching = fetch.lock().await;
+
+        // Another request may have fetched the secret while this one was waiting
+        let cached = state.cached(identifier, version_key);
+        if let Some(cached) = &cached
+            && (cached.fetched_at >= requested_at || is_fresh(cached))
+        {
+            return Some(cached.secret.clone());
+        }
+
+        let secret = match state.fetch(identifier).await {
+            Some(secret) => Arc::new(secret),
+            None => {
+                // Keep using the previous secret rather than failing every request while
+                // the control plane is unavailable
+                let cached = cached?;
+                warn!("Using previously fetched {} {}", T::PATH, identifier);
+                cached.secret
+            }
+        };
+
+        state.cache.insert(
+            identifier.to_string(),
+            CachedSecret {
+                version_key: version_key.to_string(),
+                fetched_at: Instant::now(),
+                secret: secret.clone(),
+            },
+        );
+        Some(secret)
+    }
+}
+
+pub fn ipc_secrets_cache<T: IpcSecret>(
+    task_builder: &TaskBuilder,
+    client: Arc<ClientWithMiddleware>,
+    ipc_endpoint_rx: &Receiver<IpcEndpoint>,
+    pod_name: String,
+    gateway_namespace: String,
+    gateway_name: String,
+) -> IpcSecretsCache<T> {
+    let cache = IpcSecretsCache::default();
+    let ipc_endpoint_rx = ipc_endpoint_rx.clone();
+
+    let cache_for_task = cache.clone();
+    task_builder
+        .new_task(stringify!(ipc_secrets_cache))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(ipc_endpoint) = await_ready!(ipc_endpoint_rx) {
+                    let mut state = cache_for_task.state.write().await;
+                    state.replace(IpcSecretsCacheState {
+                        cache: DashMap::new(),
+                        fetches: DashMap::new(),
+                        client: client.clone(),
+                        ipc_endpoint: *ipc_endpoint,
+                        pod_name: pod_name.clone(),
+                        gateway_namespace: gateway_namespace.clone(),
+                        gateway_name: gateway_name.clone(),
+                    });
+                }
+                continue_on!(ipc_endpoint_rx.changed());
+            }
+        });
+
+    cache
+}
@@ -0,0 +1,155 @@
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, Error, RootCertStore, SignatureScheme};
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+/// Verifies the certificate of the IPC endpoint against the CA of the control plane only, never
+/// the CAs of the system. The client talking to the control plane is built at startup while the
+/// CA arrives with the configuration, so the verifier is shared with it and updated in place.
+#[derive(Debug)]
+pub struct IpcCaVerifier {
+    provider: Arc<CryptoProvider>,
+    verifier: RwLock<Option<Arc<WebPkiServerVerifier>>>,
+}
+
+impl Default for IpcCaVerifier {
+    fn default() -> Self {
+        Self {
+            provider: Arc::new(rustls::crypto::ring::default_provider()),
+            verifier: RwLock::default(),
+        }
+    }
+}
+
+impl IpcCaVerifier {
+    /// Pins the PEM encoded CA, the endpoint is trusted by nothing until one is set
+    pub fn set_ca_certificate(&self, pem: Option<&str>) {
+        let verifier = pem.and_then(|pem| {
+            let mut roots = RootCertStore::empty();
+            for certificate in CertificateDer::pem_slice_iter(pem.as_bytes()) {
+                match certificate {
+                    Ok(certificate) => {
+                        if let Err(err) = roots.add(certificate) {
+                            warn!("Skipping invalid IPC CA certificate: {}", err);
+                        }
+                    }
+                    Err(err) => warn!("Skipping invalid IPC CA certificate: {}", err),
+                }
+            }
+
+            WebPkiServerVerifier::builder_with_provider(roots.into(), self.provider.clone())
+                .build()
+                .inspect_err(|err| warn!("Failed to pin IPC CA: {}", err))
+                .ok()
+        });
+
+        *self.verifier.write().unwrap_or_else(|e| e.into_inner()) = verifier;
+    }
+
+    /// The TLS configuration of the client talking to the control plane
+    pub fn client_config(self: &Arc<Self>) -> ClientConfig {
+        ClientConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()
+            .expect("Failed to set TLS protocol versions")
+            .dangerous()
+            .with_custom_certificate_verifier(self.clone())
+            .with_no_client_auth()
+    }
+}
+
+impl ServerCertVerifier for IpcCaVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let verifier = self
+            .verifier
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| Error::General("IPC CA is not configured".to_string()))?;
+
+        verifier.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CA: &str = include_str!("../proxy/testcases/ca.pem");
+    const BACKEND: &[u8] = include_bytes!("../proxy/testcases/backend.pem");
+
+    fn verify(verifier: &IpcCaVerifier, server_name: &str) -> Result<ServerCertVerified, Error> {
+        let certificate = CertificateDer::pem_slice_iter(BACKEND)
+            .next()
+            .expect("Missing certificate")
+            .expect("Failed to parse certificate");
+
+        verifier.verify_server_cert(
+            &certificate,
+            &[],
+            &ServerName::try_from(server_name.to_string()).expect("Invalid server name"),
+            &[],
+            UnixTime::now(),
+        )
+    }
+
+    #[test]
+    fn test_trusts_only_the_pinned_ca() {
+        let verifier = IpcCaVerifier::default();
+        assert!(verify(&verifier, "a.example.com").is_err());
+
+        verifier.set_ca_certificate(Some(CA));
+        assert!(verify(&verifier, "a.example.com").is_ok());
+        assert!(verify(&verifier, "b.example.com").is_err());
+
+        verifier.set_ca_certificate(Some("not a certificate"));
+        assert!(verify(&verifier, "a.example.com").is_err());
+
+        verifier.set_ca_certificate(None);
+        assert!(verify(&verifier, "a.example.com").is_err());
+    }
+}
@@ -1,4 +1,8 @@
 pub mod config;
+pub mod dns;
+pub mod ipc_auth;
 pub mod ipc_events;
+pub mod ipc_secrets_cache;
+pub mod ipc_tls;
 pub mod router;
 pub mod static_response_bodies_cache;
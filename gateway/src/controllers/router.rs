@@ -1,10 +1,16 @@
+use crate::controllers::dns::ResolvedHostnames;
+use crate::proxy::router::circuit_breaker::CircuitBreakerRegistry;
 use crate::proxy::router::topology::TopologyLocation;
-use crate::proxy::router::{HttpRouter, HttpRouterBuilder};
+use crate::proxy::router::{HttpRoute as BuiltHttpRoute, HttpRouter, HttpRouterBuilder};
 use http::HeaderValue;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::debug;
 use vg_core::config::gateway::types::http::router::*;
 use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::net::{EndpointAddress, Hostname};
 use vg_core::sync::signal::{signal, Receiver};
 use vg_core::task::Builder as TaskBuilder;
 use vg_core::{await_ready, continue_on, ReadyState};
@@ -12,33 +18,157 @@ use vg_core::{await_ready, continue_on, ReadyState};
 pub fn synthesize_http_router(
     task_builder: &TaskBuilder,
     gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+    resolved_hostnames_rx: &Receiver<ResolvedHostnames>,
     current_location: TopologyLocation,
 ) -> Receiver<HttpRouter> {
     let (tx, rx) = signal("http_router");
 
     let gateway_configuration_rx = gateway_configuration_rx.clone();
+    let resolved_hostnames_rx = resolved_hostnames_rx.clone();
 
     task_builder
         .new_task(stringify!(synthesize_http_router))
         .spawn(async move {
             let current_location = Arc::new(current_location);
+            let mut state = RouterState::default();
             loop {
                 if let ReadyState::Ready(gateway_configuration) =
                     await_ready!(gateway_configuration_rx)
                 {
-                    let router = build_router(gateway_configuration, current_location.clone());
+                    // Hostnames have no addresses until they first resolve
+                    let resolved_hostnames = resolved_hostnames_rx
+                        .get()
+                        .await
+                        .clone()
+                        .unwrap_or_default();
+                    state.observe(&gateway_configuration, Instant::now());
+                    let router = build_router(
+                        gateway_configuration,
+                        current_location.clone(),
+                        &state,
+                        &resolved_hostnames,
+                    );
+                    state
+                        .routes
+                        .observe(gateway_configuration, &router, resolved_hostnames);
                     tx.set(router).await;
                 }
-                continue_on!(gateway_configuration_rx.changed())
+                continue_on!(
+                    gateway_configuration_rx.changed(),
+                    resolved_hostnames_rx.changed()
+                )
             }
         });
 
     rx
 }
 
+/// State carried across router rebuilds, as each configuration update replaces the router.
+#[derive(Debug, Default)]
+struct RouterState {
+    readiness: EndpointReadiness,
+    circuit_breakers: CircuitBreakerRegistry,
+    routes: BuiltRoutes,
+}
+
+impl RouterState {
+    fn observe(&mut self, gateway_config: &GatewayConfiguration, now: Instant) {
+        self.readiness.observe(gateway_config, now);
+        self.circuit_breakers.observe(gateway_config);
+    }
+}
+
+/// Tracks when each endpoint address first appeared in the configuration, so slow start
+/// can ramp up endpoints that became ready while the gateway was running.
+#[derive(Debug, Default)]
+struct EndpointReadiness {
+    initialized: bool,
+    ready_since: HashMap<IpAddr, Option<Instant>>,
+}
+
+impl EndpointReadiness {
+    fn observe(&mut self, gateway_config: &GatewayConfiguration, now: Instant) {
+        let addresses: HashSet<_> = gateway_config
+            .http_routes()
+            .iter()
+            .flat_map(|route| route.rules())
+            .flat_map(|rule| rule.backends())
+            .flat_map(|backend| backend.endpoints())
+            .filter_map(|endpoint| endpoint.address().ip())
+            .collect();
+
+        self.ready_since
+            .retain(|address, _| addresses.contains(address));
+
+        // Endpoints present in the first configuration are assumed to be warmed up already
+        let ready_since = self.initialized.then_some(now);
+        for address in addresses {
+            self.ready_since.entry(address).or_insert(ready_since);
+        }
+
+        self.initialized = true;
+    }
+
+    fn ready_since(&self, address: &IpAddr) -> Option<Instant> {
+        self.ready_since.get(address).copied().flatten()
+    }
+}
+
+/// The routes of the last router by key, shared with the next router when their configuration
+/// did not change, so a configuration delta only rebuilds the routes it touches. Routes to
+/// hostnames are also rebuilt when the addresses of their hostnames change.
+#[derive(Debug, Default)]
+struct BuiltRoutes {
+    routes: HashMap<String, (HttpRoute, Arc<BuiltHttpRoute>)>,
+    resolved_hostnames: ResolvedHostnames,
+}
+
+impl BuiltRoutes {
+    fn observe(
+        &mut self,
+        gateway_config: &GatewayConfiguration,
+        router: &HttpRouter,
+        resolved_hostnames: ResolvedHostnames,
+    ) {
+        self.resolved_hostnames = resolved_hostnames;
+        self.routes = gateway_config
+            .http_routes()
+            .iter()
+            .zip(router.routes())
+            .filter_map(|(config_route, route)| {
+                let key = config_route.key().clone()?;
+                Some((key, (config_route.clone(), route.clone())))
+            })
+            .collect();
+    }
+
+    fn get(
+        &self,
+        config_route: &HttpRoute,
+        resolved_hostnames: &ResolvedHostnames,
+    ) -> Option<Arc<BuiltHttpRoute>> {
+        let (built_from, route) = self.routes.get(config_route.key().as_ref()?)?;
+        let readdressed = route_hostnames(config_route).any(|hostname| {
+            self.resolved_hostnames.addresses(hostname) != resolved_hostnames.addresses(hostname)
+        });
+        (built_from == config_route && !readdressed).then(|| route.clone())
+    }
+}
+
+fn route_hostnames(config_route: &HttpRoute) -> impl Iterator<Item = &Hostname> {
+    config_route
+        .rules()
+        .iter()
+        .flat_map(|rule| rule.backends())
+        .flat_map(|backend| backend.endpoints())
+        .filter_map(|endpoint| endpoint.address().hostname())
+}
+
 fn build_router(
     gateway_config: &GatewayConfiguration,
     current_location: Arc<TopologyLocation>,
+    state: &RouterState,
+    resolved_hostnames: &ResolvedHostnames,
 ) -> HttpRouter {
     let mut router = HttpRouterBuilder::new(current_location);
 
@@ -54,7 +184,15 @@ fn build_router(
     // }
 
     for config_route in gateway_config.http_routes() {
+        if let Some(route) = state.routes.get(config_route, resolved_hostnames) {
+            router.add_built_route(route);
+            continue;
+        }
+
         router.add_route(|route| {
+            if let Some(key) = config_route.key() {
+                route.with_key(key);
+            }
             for host_header_match in config_route.host_header_matches() {
                 match host_header_match.match_type() {
                     HostHeaderMatchType::Exact => {
@@ -146,12 +284,36 @@ fn build_router(
                                 backend.with_port(*port.get());
                             }
 
+                            backend
+                                .with_slow_start(*config_backend.slow_start())
+                                .with_circuit_breaker(
+                                    state.circuit_breakers.circuit_breaker(config_backend),
+                                )
+                                .with_request_header_modifier(
+                                    config_backend.request_header_modifier().clone(),
+                                )
+                                .with_tls(config_backend.tls().as_ref());
+
                             for config_endpoint in config_backend.endpoints() {
-                                let location = TopologyLocation::builder()
-                                    .zone(config_endpoint.zone().clone())
-                                    .node(config_endpoint.node().clone())
-                                    .build();
-                                backend.add_endpoint(*config_endpoint.address(), location);
+                                match config_endpoint.address() {
+                                    EndpointAddress::Ip(address) => {
+                                        let location = TopologyLocation::builder()
+                                            .zone(config_endpoint.zone().clone())
+                                            .node(config_endpoint.node().clone())
+                                            .build();
+                                        backend.add_endpoint(
+                                            *address,
+                                            location,
+                                            config_endpoint.zone_hints().clone(),
+                                            state.readiness.ready_since(address),
+                                        );
+                                    }
+                                    EndpointAddress::Hostname(hostname) => {
+                                        for address in resolved_hostnames.addresses(hostname) {
+                                            backend.add_external_endpoint(*address);
+                                        }
+                                    }
+                                }
                             }
                         });
                     }
@@ -165,12 +327,20 @@ fn build_router(
 
 #[cfg(test)]
 mod tests {
-    use crate::controllers::router::build_router;
+    use crate::controllers::dns::ResolvedHostnames;
+    use crate::controllers::router::{build_router, EndpointReadiness, RouterState};
     use crate::proxy::router::topology::TopologyLocation;
+    use crate::proxy::router::HttpRouter;
+    use enumflags2::BitFlags;
     use http::request::Builder;
     use std::io::Cursor;
+    use std::net::IpAddr;
     use std::sync::Arc;
+    use std::time::{Duration, Instant};
     use vg_core::config::gateway::serde::read_configuration;
+    use vg_core::config::gateway::types::GatewayConfigurationBuilder;
+    use vg_core::ipc::delta::{apply, ConfigurationChange};
+    use vg_core::net::{Hostname, Port};
 
     #[test]
     fn test_router_simple() {
@@ -183,7 +353,12 @@ mod tests {
         let current_location = current_location.build();
         let current_location = Arc::new(current_location);
 
-        let router = build_router(&config, current_location);
+        let router = build_router(
+            &config,
+            current_location,
+            &RouterState::default(),
+            &ResolvedHostnames::default(),
+        );
 
         // Test with the root path "/" which matches the configuration
         let req = Builder::default().method("GET").uri("/").body(()).unwrap();
@@ -194,4 +369,178 @@ mod tests {
         // or no host at all. The path "/" should match the prefix "/" rule in the config.
         router.match_route(&parts).expect("Failed to match route");
     }
+
+    #[test]
+    fn test_endpoint_readiness_across_updates() {
+        let config = include_str!("./testcases/simple.yaml").to_string();
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        let address: IpAddr = config.http_routes()[0].rules()[0].backends()[0].endpoints()[0]
+            .address()
+            .ip()
+            .expect("Missing IP address");
+
+        let empty = GatewayConfigurationBuilder::default()
+            .build()
+            .expect("Failed to build configuration");
+
+        let mut readiness = EndpointReadiness::default();
+        let started = Instant::now();
+
+        // Endpoints present at startup are treated as warmed up
+        readiness.observe(&config, started);
+        assert_eq!(readiness.ready_since(&address), None);
+
+        // An endpoint that disappears and comes back is ramped from when it returned
+        let returned = started + Duration::from_secs(30);
+        readiness.observe(&empty, returned);
+        readiness.observe(&config, returned);
+        assert_eq!(readiness.ready_since(&address), Some(returned));
+
+        // Later updates keep the original readiness time
+        readiness.observe(&config, returned + Duration::from_secs(30));
+        assert_eq!(readiness.ready_since(&address), Some(returned));
+    }
+
+    #[test]
+    fn test_circuit_breaker_shared_across_rebuilds() {
+        let config = include_str!("./testcases/circuit_breaker.yaml").to_string();
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        let current_location = Arc::new(TopologyLocation::default());
+
+        let mut state = RouterState::default();
+        state.observe(&config, Instant::now());
+
+        let resolved_hostnames = ResolvedHostnames::default();
+        let first = build_router(
+            &config,
+            current_location.clone(),
+            &state,
+            &resolved_hostnames,
+        );
+        let second = build_router(&config, current_location, &state, &resolved_hostnames);
+
+        let circuit_breaker = |router: &HttpRouter| {
+            let req = Builder::default().method("GET").uri("/").body(()).unwrap();
+            let (parts, _) = req.into_parts();
+            let match_result = router.match_route(&parts).expect("Failed to match route");
+            match_result.rule().expect("Missing rule").backends()[0]
+                .circuit_breaker()
+                .clone()
+                .expect("Missing circuit breaker")
+        };
+
+        let _permit = circuit_breaker(&first)
+            .try_acquire()
+            .expect("Failed to acquire permit");
+        assert!(circuit_breaker(&second).try_acquire().is_err());
+    }
+
+    #[test]
+    fn test_unchanged_routes_shared_across_rebuilds() {
+        let config = include_str!("./testcases/routes.yaml").to_string();
+        let config = read_configuration(Cursor::new(config)).expect("Failed to read configuration");
+        let current_location = Arc::new(TopologyLocation::default());
+
+        let mut state = RouterState::default();
+        state.observe(&config, Instant::now());
+        let resolved_hostnames = ResolvedHostnames::default();
+        let first = build_router(
+            &config,
+            current_location.clone(),
+            &state,
+            &resolved_hostnames,
+        );
+        state
+            .routes
+            .observe(&config, &first, resolved_hostnames.clone());
+
+        let echo = &config.http_routes()[0];
+        let rule = &echo.rules()[0];
+        let backend = &rule.backends()[0];
+        let change = ConfigurationChange::SetBackendEndpoints {
+            route_key: "default/echo".to_string(),
+            rule_id: rule.unique_id().clone(),
+            backend: backend.into(),
+            endpoints: config.http_routes()[1].rules()[0].backends()[0]
+                .endpoints()
+                .clone(),
+        };
+        let updated = apply(&config, &[change]).expect("Failed to apply change");
+
+        state.observe(&updated, Instant::now());
+        let second = build_router(&updated, current_location, &state, &resolved_hostnames);
+
+        assert!(!Arc::ptr_eq(&first.routes()[0], &second.routes()[0]));
+        assert!(Arc::ptr_eq(&first.routes()[1], &second.routes()[1]));
+    }
+
+    #[test]
+    fn test_hostname_endpoints_rebuilt_when_readdressed() {
+        let hostname = Hostname::new("api.example.com");
+        let config = {
+            let mut builder = GatewayConfigurationBuilder::default();
+            builder.add_http_route(|route| {
+                route.with_key("default/external");
+                route.add_rule("external:0", |rule| {
+                    rule.add_match(|matches| {
+                        matches.with_path_prefix("/");
+                    });
+                    rule.add_backend(|backend| {
+                        backend
+                            .named("external")
+                            .with_port(Some(Port::new(443)))
+                            .add_endpoint(hostname.clone(), |_| {});
+                    });
+                });
+            });
+            builder.build().expect("Failed to build configuration")
+        };
+        let current_location = Arc::new(TopologyLocation::default());
+        let resolved = |addresses: &[&str]| {
+            ResolvedHostnames::from_iter([(
+                hostname.clone(),
+                addresses
+                    .iter()
+                    .map(|address| address.parse().unwrap())
+                    .collect(),
+            )])
+        };
+        let external_addrs = |router: &HttpRouter| {
+            let req = Builder::default().method("GET").uri("/").body(()).unwrap();
+            let (parts, _) = req.into_parts();
+            let match_result = router.match_route(&parts).expect("Failed to match route");
+            match_result.rule().expect("Missing rule").backends()[0]
+                .endpoints()
+                .get(&BitFlags::empty())
+                .map(|endpoints| {
+                    endpoints
+                        .iter()
+                        .map(|endpoint| endpoint.addr().to_string())
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        let mut state = RouterState::default();
+        state.observe(&config, Instant::now());
+        let first_resolved = resolved(&["192.0.2.1"]);
+        let first = build_router(&config, current_location.clone(), &state, &first_resolved);
+        state
+            .routes
+            .observe(&config, &first, first_resolved.clone());
+        assert_eq!(
+            external_addrs(&first),
+            Some(vec!["192.0.2.1:443".to_string()])
+        );
+
+        let unchanged = build_router(&config, current_location.clone(), &state, &first_resolved);
+        assert!(Arc::ptr_eq(&first.routes()[0], &unchanged.routes()[0]));
+
+        let readdressed =
+            build_router(&config, current_location, &state, &resolved(&["192.0.2.2"]));
+        assert!(!Arc::ptr_eq(&first.routes()[0], &readdressed.routes()[0]));
+        assert_eq!(
+            external_addrs(&readdressed),
+            Some(vec!["192.0.2.2:443".to_string()])
+        );
+    }
 }
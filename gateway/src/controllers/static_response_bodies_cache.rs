@@ -1,3 +1,4 @@
+use crate::controllers::config::ipc::IpcEndpoint;
 use bytes::Bytes;
 use dashmap::DashMap;
 use dashmap::Entry::*;
@@ -5,11 +6,9 @@ use http::StatusCode;
 use reqwest_middleware::ClientWithMiddleware;
 use reqwest_tracing::{OtelName, OtelPathNames};
 use std::collections::HashMap;
-use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
-use url::Url;
 use vg_core::config::gateway::types::net::StaticResponse;
 use vg_core::sync::signal::Receiver;
 use vg_core::task::Builder as TaskBuilder;
@@ -20,7 +19,7 @@ struct StaticResponseBodiesCacheState {
     cache: DashMap<String, (String, Arc<Bytes>)>,
     responses: HashMap<String, StaticResponse>,
     client: Arc<ClientWithMiddleware>,
-    ipc_endpoint: SocketAddr,
+    ipc_endpoint: IpcEndpoint,
     pod_name: String,
     gateway_namespace: String,
     gateway_name: String,
@@ -46,8 +45,7 @@ impl StaticResponseBodiesCache {
             Occupied(entry) => Some(entry.get().clone()),
             Vacant(entry) => {
                 let url = {
-                    let mut url = Url::parse(&format!("http://{}", state.ipc_endpoint))
-                        .expect("Failed to parse URL");
+                    let mut url = state.ipc_endpoint.url();
                     url.set_path(&format!(
                         "/ipc/namespaces/{}/gateways/{}/static_responses/{}",
                         state.gateway_namespace,
@@ -102,7 +100,7 @@ pub fn static_response_bodies_cache(
     task_builder: &TaskBuilder,
     client: Arc<ClientWithMiddleware>,
     static_responses_rx: &Receiver<Arc<HashMap<String, StaticResponse>>>,
-    ipc_endpoint_rx: &Receiver<SocketAddr>,
+    ipc_endpoint_rx: &Receiver<IpcEndpoint>,
     pod_name: String,
     gateway_namespace: String,
     gateway_name: String,
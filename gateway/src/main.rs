@@ -1,19 +1,42 @@
+mod admin;
 mod cli;
 mod controllers;
 mod instrumentation;
 mod proxy;
 mod util;
 
+use crate::admin::{spawn_admin_endpoint, AdminEndpointState, SpawnAdminEndpointParameters};
 use crate::cli::Cli;
-use crate::controllers::config::fs::{watch_configuration_file, WatchConfigurationFileParams};
+use crate::controllers::config::cache::{cache_configuration, CacheConfigurationParams};
+use crate::controllers::config::fs::{
+    watch_configuration_directory, watch_configuration_file, WatchConfigurationDirectoryParams,
+    WatchConfigurationFileParams,
+};
 use crate::controllers::config::ipc::{
-    fetch_configuration, watch_ipc_endpoint, FetchConfigurationParams,
+    fetch_configuration, report_configuration_status, watch_ipc_endpoint,
+    FetchConfigurationParams, ReportConfigurationStatusParams,
 };
 use crate::controllers::config::selector::{select_configuration, SelectorParams};
+use crate::controllers::config::validation::{validate_configuration, ValidateConfigurationParams};
+use crate::controllers::dns::{resolve_endpoint_hostnames, SystemResolver};
 use crate::controllers::ipc_events::{poll_gateway_events, PollGatewayEventsParams};
+use crate::controllers::ipc_auth::{IpcAuthMiddleware, IpcToken};
+use crate::controllers::ipc_secrets_cache::ipc_secrets_cache;
+use crate::controllers::ipc_tls::IpcCaVerifier;
 use crate::controllers::router::synthesize_http_router;
 use crate::controllers::static_response_bodies_cache::static_response_bodies_cache;
+use crate::proxy::access_logs::access_logger;
 use crate::proxy::filters::access_control::access_control_filters_handlers;
+use crate::proxy::filters::basic_auth::basic_auth_filters_handlers;
+use crate::proxy::filters::cache::{cache_filters_handlers, purge_http_cache, HttpCache};
+use crate::proxy::filters::compression::compression_filters_handlers;
+use crate::proxy::filters::cors::cors_filters_handlers;
+use crate::proxy::filters::external_auth::external_auth_filters_handlers;
+use crate::proxy::filters::jwt_auth::jwt_auth_filters_handlers;
+use crate::proxy::filters::rate_limit::{
+    rate_limit_filters_handlers, sync_global_rate_limits, IpcRateLimitSyncClient,
+};
+use crate::proxy::filters::request_limits::request_limits_filters_handlers;
 use crate::proxy::filters::static_responses::static_responses;
 use crate::proxy::responses::error_responses::error_responses;
 use crate::proxy::Proxy;
@@ -34,20 +57,35 @@ use vg_core::task::Builder as TaskBuilder;
 async fn main() {
     let task_builder = TaskBuilder::default();
 
+    let args = Cli::parse();
+
     init_crypto();
-    init_instrumentation(&task_builder, "vg-gateway");
+    init_instrumentation(&task_builder, "vg-gateway", args.prometheus_enabled());
 
-    let client = reqwest::ClientBuilder::new()
+    let http_client = reqwest::ClientBuilder::new()
         .build()
         .expect("Failed to create HTTP client");
 
     let client = Arc::new(
-        ClientBuilder::new(client)
+        ClientBuilder::new(http_client)
             .with(TracingMiddleware::default())
             .build(),
     );
 
-    let args = Cli::parse();
+    // Only requests to the control plane carry the IPC token, and they only trust the CA
+    // of the control plane
+    let ipc_ca_verifier = Arc::new(IpcCaVerifier::default());
+    let ipc_http_client = reqwest::ClientBuilder::new()
+        .use_preconfigured_tls(ipc_ca_verifier.client_config())
+        .build()
+        .expect("Failed to create IPC HTTP client");
+    let ipc_token = IpcToken::new(args.ipc_token_path());
+    let ipc_client = Arc::new(
+        ClientBuilder::new(ipc_http_client)
+            .with(TracingMiddleware::default())
+            .with(IpcAuthMiddleware::new(ipc_token))
+            .build(),
+    );
 
     let current_location = {
         let zone = args.zone_name().filter(|z| !z.is_empty());
@@ -60,7 +98,7 @@ async fn main() {
 
     let gateway_events_tx = {
         let params = PollGatewayEventsParams::builder()
-            .client(client.clone())
+            .client(ipc_client.clone())
             .ipc_endpoint_rx(ipc_endpoint_rx.clone())
             .pod_name(args.pod_name())
             .gateway_namespace(args.pod_namespace())
@@ -70,11 +108,15 @@ async fn main() {
         poll_gateway_events(&task_builder, params)
     };
 
+    let (configuration_status_tx, configuration_status_rx) = signal("configuration_status");
+
     let ipc_configuration_source_rx = {
         let params = FetchConfigurationParams::builder()
-            .client(client.clone())
+            .client(ipc_client.clone())
             .ipc_endpoint_rx(ipc_endpoint_rx.clone())
             .gateway_events_rx(gateway_events_tx.subscribe())
+            .encoding(args.ipc_configuration_encoding())
+            .configuration_status_tx(configuration_status_tx.clone())
             .pod_name(args.pod_name())
             .gateway_namespace(args.pod_namespace())
             .gateway_name(args.gateway_name())
@@ -83,42 +125,176 @@ async fn main() {
         fetch_configuration(&task_builder, params)
     };
 
-    let fs_configuration_source_rx = {
-        let params = WatchConfigurationFileParams::builder()
-            .file_path(args.config_file_path())
+    {
+        let params = ReportConfigurationStatusParams::builder()
+            .client(ipc_client.clone())
+            .ipc_endpoint_rx(ipc_endpoint_rx.clone())
+            .configuration_status_rx(configuration_status_rx.clone())
+            .pod_name(args.pod_name())
+            .gateway_namespace(args.pod_namespace())
+            .gateway_name(args.gateway_name())
             .build();
 
-        watch_configuration_file(&task_builder, params)
+        report_configuration_status(&task_builder, params);
+    }
+
+    let fs_configuration_source_rx = match args.config_directory_path() {
+        Some(directory_path) => {
+            let params = WatchConfigurationDirectoryParams::builder()
+                .directory_path(directory_path)
+                .build();
+
+            watch_configuration_directory(&task_builder, params)
+        }
+        None => {
+            let params = WatchConfigurationFileParams::builder()
+                .file_path(args.config_file_path())
+                .build();
+
+            watch_configuration_file(&task_builder, params)
+        }
     };
 
-    let gateway_configuration_rx = {
+    let cache_configuration_source_rx = {
+        let params = CacheConfigurationParams::builder()
+            .file_path(args.configuration_cache_path())
+            .ipc_configuration_source_rx(ipc_configuration_source_rx.clone())
+            .configuration_status_rx(configuration_status_rx)
+            .build();
+
+        cache_configuration(&task_builder, params)
+    };
+
+    let (configuration_source_tx, configuration_source_rx) = signal("configuration_source");
+
+    let configuration_candidates_rx = {
         let params = SelectorParams::builder()
             .ipc_configuration_source_rx(ipc_configuration_source_rx)
             .fs_configuration_source_rx(fs_configuration_source_rx)
+            .cache_configuration_source_rx(cache_configuration_source_rx)
             .build();
 
         select_configuration(&task_builder, params)
     };
 
-    watch_ipc_endpoint(&task_builder, &gateway_configuration_rx, ipc_endpoint_tx);
+    let gateway_configuration_rx = {
+        let params = ValidateConfigurationParams::builder()
+            .configuration_candidates_rx(configuration_candidates_rx)
+            .configuration_source_tx(configuration_source_tx)
+            .configuration_status_tx(configuration_status_tx)
+            .build();
+
+        validate_configuration(&task_builder, params)
+    };
+
+    watch_ipc_endpoint(
+        &task_builder,
+        &gateway_configuration_rx,
+        ipc_ca_verifier,
+        ipc_endpoint_tx,
+    );
+
+    let resolved_hostnames_rx = resolve_endpoint_hostnames(
+        &task_builder,
+        SystemResolver::from_system_conf().expect("Failed to read the DNS configuration"),
+        &gateway_configuration_rx,
+    );
 
-    let router_rx =
-        synthesize_http_router(&task_builder, &gateway_configuration_rx, current_location);
+    let router_rx = synthesize_http_router(
+        &task_builder,
+        &gateway_configuration_rx,
+        &resolved_hostnames_rx,
+        current_location,
+    );
     let client_addr_filter_handler_rx =
         client_addr_filter_handler(&task_builder, &gateway_configuration_rx);
     let access_control_filters_handlers_rx =
         access_control_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let rate_limit_filters_handlers_rx =
+        rate_limit_filters_handlers(&task_builder, &gateway_configuration_rx);
+    sync_global_rate_limits(
+        &task_builder,
+        IpcRateLimitSyncClient::builder()
+            .client(ipc_client.clone())
+            .ipc_endpoint_rx(ipc_endpoint_rx.clone())
+            .pod_name(args.pod_name())
+            .gateway_namespace(args.pod_namespace())
+            .gateway_name(args.gateway_name())
+            .build(),
+        &rate_limit_filters_handlers_rx,
+    );
+    let jwt_auth_filters_handlers_rx =
+        jwt_auth_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let jwks_cache = ipc_secrets_cache(
+        &task_builder,
+        ipc_client.clone(),
+        &ipc_endpoint_rx,
+        args.pod_name(),
+        args.pod_namespace(),
+        args.gateway_name(),
+    );
+    let external_auth_filters_handlers_rx =
+        external_auth_filters_handlers(&task_builder, client, &gateway_configuration_rx);
+    let basic_auth_filters_handlers_rx =
+        basic_auth_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let htpasswd_cache = ipc_secrets_cache(
+        &task_builder,
+        ipc_client.clone(),
+        &ipc_endpoint_rx,
+        args.pod_name(),
+        args.pod_namespace(),
+        args.gateway_name(),
+    );
+    let client_certificates_cache = ipc_secrets_cache(
+        &task_builder,
+        ipc_client.clone(),
+        &ipc_endpoint_rx,
+        args.pod_name(),
+        args.pod_namespace(),
+        args.gateway_name(),
+    );
+    let cors_filters_handlers_rx = cors_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let compression_filters_handlers_rx =
+        compression_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let cache_filters_handlers_rx =
+        cache_filters_handlers(&task_builder, &gateway_configuration_rx);
+    let http_cache = HttpCache::new(args.http_cache_size_bytes());
+    purge_http_cache(
+        &task_builder,
+        gateway_events_tx.subscribe(),
+        http_cache.clone(),
+    );
+    let request_limits_filters_handlers_rx =
+        request_limits_filters_handlers(&task_builder, &gateway_configuration_rx);
     let error_responses_rx = error_responses(&task_builder, &gateway_configuration_rx);
     let static_responses_rx = static_responses(&task_builder, &gateway_configuration_rx);
     let static_response_bodies_cache = static_response_bodies_cache(
         &task_builder,
-        client.clone(),
+        ipc_client.clone(),
         &static_responses_rx,
         &ipc_endpoint_rx,
         args.pod_name(),
         args.pod_namespace(),
         args.gateway_name(),
     );
+    let access_logger_rx = access_logger(&task_builder, &gateway_configuration_rx);
+
+    {
+        let state = AdminEndpointState::builder()
+            .gateway_configuration_rx(gateway_configuration_rx.clone())
+            .configuration_source_rx(configuration_source_rx)
+            .router_rx(router_rx.clone())
+            .build();
+        let params = SpawnAdminEndpointParameters::builder()
+            .port(args.admin_port())
+            .prometheus_enabled(args.prometheus_enabled())
+            .state(state)
+            .build();
+
+        spawn_admin_endpoint(&task_builder, params)
+            .await
+            .expect("Failed to spawn admin endpoint");
+    }
 
     task_builder.new_task("server").spawn_blocking(move || {
         let mut server = Server::new(None).unwrap();
@@ -126,10 +302,23 @@ async fn main() {
         let proxy = Proxy::builder()
             .client_addr_filter_handler_rx(client_addr_filter_handler_rx)
             .access_control_filters_handlers_rx(access_control_filters_handlers_rx)
+            .rate_limit_filters_handlers_rx(rate_limit_filters_handlers_rx)
+            .jwt_auth_filters_handlers_rx(jwt_auth_filters_handlers_rx)
+            .jwks_cache(jwks_cache)
+            .external_auth_filters_handlers_rx(external_auth_filters_handlers_rx)
+            .basic_auth_filters_handlers_rx(basic_auth_filters_handlers_rx)
+            .htpasswd_cache(htpasswd_cache)
+            .client_certificates_cache(client_certificates_cache)
+            .cors_filters_handlers_rx(cors_filters_handlers_rx)
+            .compression_filters_handlers_rx(compression_filters_handlers_rx)
+            .cache_filters_handlers_rx(cache_filters_handlers_rx)
+            .http_cache(http_cache)
+            .request_limits_filters_handlers_rx(request_limits_filters_handlers_rx)
             .error_responses_rx(error_responses_rx)
             .router_rx(router_rx)
             .static_responses_rx(static_responses_rx)
             .static_response_bodies_cache(static_response_bodies_cache)
+            .access_logger_rx(access_logger_rx)
             .build();
         let mut service = http_proxy_service(&server.configuration, proxy);
         service.add_tcp("0.0.0.0:8080");
@@ -0,0 +1,232 @@
+use chrono::{DateTime, Utc};
+use getset::Getters;
+use opentelemetry::logs::AnyValue;
+use opentelemetry::Key;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+
+/// What is known about a request once its response has been sent
+#[derive(Debug, Clone, PartialEq, Getters, TypedBuilder)]
+pub struct AccessLogEntry {
+    #[getset(get = "pub")]
+    #[builder(default = Utc::now())]
+    timestamp: DateTime<Utc>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    client_addr: Option<IpAddr>,
+
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    method: String,
+
+    /// Path and query of the request
+    #[getset(get = "pub")]
+    #[builder(setter(into))]
+    path: String,
+
+    #[getset(get = "pub")]
+    protocol: &'static str,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    host: Option<String>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    user_agent: Option<String>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    referer: Option<String>,
+
+    /// Namespaced name of the matched `HTTPRoute`
+    #[getset(get = "pub")]
+    #[builder(default)]
+    route: Option<String>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    rule_id: Option<String>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    upstream_addr: Option<SocketAddr>,
+
+    /// Status sent to the client, missing when the connection was lost before
+    #[getset(get = "pub")]
+    #[builder(default)]
+    status: Option<u16>,
+
+    #[getset(get = "pub")]
+    duration: Duration,
+
+    /// Time spent waiting for the upstream to respond, missing when the request was
+    /// answered by the gateway itself
+    #[getset(get = "pub")]
+    #[builder(default)]
+    upstream_duration: Option<Duration>,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    bytes_received: usize,
+
+    #[getset(get = "pub")]
+    #[builder(default)]
+    bytes_sent: usize,
+}
+
+/// The fields of an entry, as named in JSON lines and template placeholders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessLogField {
+    Timestamp,
+    ClientAddr,
+    Method,
+    Path,
+    Protocol,
+    Host,
+    UserAgent,
+    Referer,
+    Route,
+    RuleId,
+    UpstreamAddr,
+    Status,
+    DurationMs,
+    UpstreamDurationMs,
+    BytesReceived,
+    BytesSent,
+}
+
+impl AccessLogField {
+    pub const ALL: [AccessLogField; 16] = [
+        Self::Timestamp,
+        Self::ClientAddr,
+        Self::Method,
+        Self::Path,
+        Self::Protocol,
+        Self::Host,
+        Self::UserAgent,
+        Self::Referer,
+        Self::Route,
+        Self::RuleId,
+        Self::UpstreamAddr,
+        Self::Status,
+        Self::DurationMs,
+        Self::UpstreamDurationMs,
+        Self::BytesReceived,
+        Self::BytesSent,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Timestamp => "timestamp",
+            Self::ClientAddr => "client_addr",
+            Self::Method => "method",
+            Self::Path => "path",
+            Self::Protocol => "protocol",
+            Self::Host => "host",
+            Self::UserAgent => "user_agent",
+            Self::Referer => "referer",
+            Self::Route => "route",
+            Self::RuleId => "rule_id",
+            Self::UpstreamAddr => "upstream_addr",
+            Self::Status => "status",
+            Self::DurationMs => "duration_ms",
+            Self::UpstreamDurationMs => "upstream_duration_ms",
+            Self::BytesReceived => "bytes_received",
+            Self::BytesSent => "bytes_sent",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.name() == name)
+    }
+}
+
+/// The value of a field of an entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessLogValue {
+    String(String),
+    Integer(u64),
+    Float(f64),
+}
+
+impl AccessLogValue {
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::String(value) => value.clone().into(),
+            Self::Integer(value) => (*value).into(),
+            Self::Float(value) => (*value).into(),
+        }
+    }
+}
+
+impl std::fmt::Display for AccessLogValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String(value) => f.write_str(value),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value:.3}"),
+        }
+    }
+}
+
+impl From<AccessLogValue> for AnyValue {
+    fn from(value: AccessLogValue) -> Self {
+        match value {
+            AccessLogValue::String(value) => value.into(),
+            AccessLogValue::Integer(value) => i64::try_from(value).unwrap_or(i64::MAX).into(),
+            AccessLogValue::Float(value) => value.into(),
+        }
+    }
+}
+
+impl AccessLogEntry {
+    /// The value of a field, or `None` when it is unknown for the request
+    pub fn value(&self, field: AccessLogField) -> Option<AccessLogValue> {
+        let string = |value: &str| Some(AccessLogValue::String(value.to_string()));
+        let millis =
+            |duration: &Duration| AccessLogValue::Float(duration.as_micros() as f64 / 1000.0);
+        match field {
+            AccessLogField::Timestamp => Some(AccessLogValue::String(
+                self.timestamp
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            )),
+            AccessLogField::ClientAddr => self
+                .client_addr
+                .map(|addr| AccessLogValue::String(addr.to_string())),
+            AccessLogField::Method => string(&self.method),
+            AccessLogField::Path => string(&self.path),
+            AccessLogField::Protocol => string(self.protocol),
+            AccessLogField::Host => self.host.as_deref().and_then(string),
+            AccessLogField::UserAgent => self.user_agent.as_deref().and_then(string),
+            AccessLogField::Referer => self.referer.as_deref().and_then(string),
+            AccessLogField::Route => self.route.as_deref().and_then(string),
+            AccessLogField::RuleId => self.rule_id.as_deref().and_then(string),
+            AccessLogField::UpstreamAddr => self
+                .upstream_addr
+                .map(|addr| AccessLogValue::String(addr.to_string())),
+            AccessLogField::Status => self
+                .status
+                .map(|status| AccessLogValue::Integer(u64::from(status))),
+            AccessLogField::DurationMs => Some(millis(&self.duration)),
+            AccessLogField::UpstreamDurationMs => self.upstream_duration.as_ref().map(millis),
+            AccessLogField::BytesReceived => {
+                Some(AccessLogValue::Integer(self.bytes_received as u64))
+            }
+            AccessLogField::BytesSent => Some(AccessLogValue::Integer(self.bytes_sent as u64)),
+        }
+    }
+
+    /// The known fields of the entry as OpenTelemetry log attributes
+    pub fn attributes(&self) -> Vec<(Key, AnyValue)> {
+        AccessLogField::ALL
+            .into_iter()
+            .filter_map(|field| {
+                self.value(field)
+                    .map(|value| (Key::from_static_str(field.name()), value.into()))
+            })
+            .collect()
+    }
+}
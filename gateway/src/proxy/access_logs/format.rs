@@ -0,0 +1,228 @@
+use super::entry::{AccessLogEntry, AccessLogField};
+use std::fmt::Write;
+use tracing::warn;
+use vg_core::config::gateway::types::net::AccessLogFormat;
+
+/// Renders access log entries as lines, with templates parsed once per configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessLogLineFormat {
+    Json,
+    Common,
+    Combined,
+    Template(Vec<TemplatePart>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    Literal(String),
+    Field(AccessLogField),
+    /// A placeholder naming no field, always rendered as `-`
+    Unknown,
+}
+
+impl AccessLogLineFormat {
+    pub fn new(format: &AccessLogFormat) -> Self {
+        match format {
+            AccessLogFormat::Json => Self::Json,
+            AccessLogFormat::Common => Self::Common,
+            AccessLogFormat::Combined => Self::Combined,
+            AccessLogFormat::Template(template) => Self::Template(parse_template(template)),
+        }
+    }
+
+    pub fn render(&self, entry: &AccessLogEntry) -> String {
+        match self {
+            Self::Json => render_json(entry),
+            Self::Common => render_common(entry),
+            Self::Combined => {
+                let mut line = render_common(entry);
+                let _ = write!(
+                    line,
+                    " \"{}\" \"{}\"",
+                    quoted(entry.referer().as_deref()),
+                    quoted(entry.user_agent().as_deref())
+                );
+                line
+            }
+            Self::Template(parts) => {
+                let mut line = String::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Literal(literal) => line.push_str(literal),
+                        TemplatePart::Field(field) => match entry.value(*field) {
+                            Some(value) => {
+                                let _ = write!(line, "{value}");
+                            }
+                            None => line.push('-'),
+                        },
+                        TemplatePart::Unknown => line.push('-'),
+                    }
+                }
+                line
+            }
+        }
+    }
+}
+
+/// Splits a template into literals and `${field}` placeholders. A `$` not followed by
+/// a closed placeholder is kept as is.
+fn parse_template(template: &str) -> Vec<TemplatePart> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+        literal.push_str(&rest[..start]);
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+        }
+
+        let name = rest[start + 2..start + 2 + length].trim();
+        match AccessLogField::from_name(name) {
+            Some(field) => parts.push(TemplatePart::Field(field)),
+            None => {
+                warn!("Unknown access log template field: {}", name);
+                parts.push(TemplatePart::Unknown);
+            }
+        }
+        rest = &rest[start + 3 + length..];
+    }
+
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    parts
+}
+
+fn render_json(entry: &AccessLogEntry) -> String {
+    let object: serde_json::Map<String, serde_json::Value> = AccessLogField::ALL
+        .into_iter()
+        .filter_map(|field| {
+            entry
+                .value(field)
+                .map(|value| (field.name().to_string(), value.to_json()))
+        })
+        .collect();
+    serde_json::Value::Object(object).to_string()
+}
+
+/// `client - - [timestamp] "request line" status bytes`, in the NCSA common log format
+fn render_common(entry: &AccessLogEntry) -> String {
+    format!(
+        "{} - - [{}] \"{} {} {}\" {} {}",
+        entry
+            .client_addr()
+            .map_or_else(|| "-".to_string(), |addr| addr.to_string()),
+        entry.timestamp().format("%d/%b/%Y:%H:%M:%S %z"),
+        entry.method(),
+        quoted(Some(entry.path())),
+        entry.protocol(),
+        entry
+            .status()
+            .map_or_else(|| "-".to_string(), |status| status.to_string()),
+        match entry.bytes_sent() {
+            0 => "-".to_string(),
+            bytes => bytes.to_string(),
+        }
+    )
+}
+
+/// Escapes a value written between double quotes, `-` when it is missing
+fn quoted(value: Option<&str>) -> String {
+    match value {
+        Some(value) => value.replace('\\', "\\\\").replace('"', "\\\""),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::time::Duration;
+
+    fn entry() -> AccessLogEntry {
+        AccessLogEntry::builder()
+            .timestamp(Utc.with_ymd_and_hms(2025, 3, 7, 14, 5, 9).unwrap())
+            .client_addr(Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))))
+            .method("GET")
+            .path("/echo?name=\"vale\"")
+            .protocol("HTTP/1.1")
+            .host(Some("echo.example.com".to_string()))
+            .user_agent(Some("curl/8.5.0".to_string()))
+            .route(Some("default/echo-route".to_string()))
+            .upstream_addr(Some(SocketAddr::from(([10, 0, 1, 2], 8080))))
+            .status(Some(200))
+            .duration(Duration::from_micros(12_500))
+            .upstream_duration(Some(Duration::from_millis(10)))
+            .bytes_received(0)
+            .bytes_sent(42)
+            .build()
+    }
+
+    #[test]
+    fn test_parse_template() {
+        assert_eq!(
+            parse_template("${method} ${ path } $x ${nope}${status"),
+            vec![
+                TemplatePart::Field(AccessLogField::Method),
+                TemplatePart::Literal(" ".to_string()),
+                TemplatePart::Field(AccessLogField::Path),
+                TemplatePart::Literal(" $x ".to_string()),
+                TemplatePart::Unknown,
+                TemplatePart::Literal("${status".to_string()),
+            ]
+        );
+        assert_eq!(parse_template(""), vec![]);
+    }
+
+    #[test]
+    fn test_render_json() {
+        let line = AccessLogLineFormat::Json.render(&entry());
+        let value: serde_json::Value = serde_json::from_str(&line).expect("json");
+
+        assert_eq!(value["timestamp"], "2025-03-07T14:05:09.000Z");
+        assert_eq!(value["client_addr"], "10.0.0.1");
+        assert_eq!(value["route"], "default/echo-route");
+        assert_eq!(value["upstream_addr"], "10.0.1.2:8080");
+        assert_eq!(value["status"], 200);
+        assert_eq!(value["duration_ms"], 12.5);
+        assert_eq!(value["bytes_sent"], 42);
+        // Unknown fields are left out
+        assert!(value.get("referer").is_none());
+        assert!(value.get("rule_id").is_none());
+    }
+
+    #[test]
+    fn test_render_common() {
+        assert_eq!(
+            AccessLogLineFormat::Common.render(&entry()),
+            r#"10.0.0.1 - - [07/Mar/2025:14:05:09 +0000] "GET /echo?name=\"vale\" HTTP/1.1" 200 42"#
+        );
+    }
+
+    #[test]
+    fn test_render_combined() {
+        assert_eq!(
+            AccessLogLineFormat::Combined.render(&entry()),
+            r#"10.0.0.1 - - [07/Mar/2025:14:05:09 +0000] "GET /echo?name=\"vale\" HTTP/1.1" 200 42 "-" "curl/8.5.0""#
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let format = AccessLogLineFormat::new(&AccessLogFormat::Template(
+            "${method} ${route} ${rule_id} ${status} ${duration_ms}ms ${unknown}".to_string(),
+        ));
+        assert_eq!(
+            format.render(&entry()),
+            "GET default/echo-route - 200 12.500ms -"
+        );
+    }
+}
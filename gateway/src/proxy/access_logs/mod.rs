@@ -0,0 +1,279 @@
+mod entry;
+mod format;
+mod writer;
+
+pub use entry::AccessLogEntry;
+
+use crate::instrumentation::get_meter;
+use format::AccessLogLineFormat;
+use opentelemetry::metrics::Counter;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use tokio::sync::mpsc;
+use vg_core::config::gateway::types::net::{AccessLogSink, AccessLogs, StatusClass};
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::instrumentation::emit_log_record;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::{await_ready, continue_on, ReadyState};
+use writer::{write_access_log_lines, AccessLogLine, AccessLogOutput};
+
+/// Lines waiting to be written before new ones are dropped, so a slow disk or
+/// terminal never holds back requests
+const ACCESS_LOG_QUEUE_SIZE: usize = 8192;
+
+static DROPPED_LINES: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    get_meter()
+        .u64_counter("vale_gateway.access_logs.dropped_lines")
+        .with_description("Number of access log lines dropped as the writer fell behind.")
+        .build()
+});
+
+pub fn access_logger(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<AccessLogger> {
+    let (tx, rx) = signal(stringify!(access_logger));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+    let (lines_tx, lines_rx) = mpsc::channel(ACCESS_LOG_QUEUE_SIZE);
+
+    task_builder
+        .new_task(stringify!(write_access_log_lines))
+        .spawn(write_access_log_lines(lines_rx));
+
+    task_builder
+        .new_task(stringify!(access_logger))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    match gateway_configuration.access_logs() {
+                        Some(access_logs) => {
+                            tx.set(AccessLogger::new(access_logs, lines_tx.clone()))
+                                .await;
+                        }
+                        None => tx.clear().await,
+                    }
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
+
+/// The class of a response status, from its first digit
+fn status_class(status: u16) -> Option<StatusClass> {
+    match status / 100 {
+        1 => Some(StatusClass::Informational),
+        2 => Some(StatusClass::Successful),
+        3 => Some(StatusClass::Redirection),
+        4 => Some(StatusClass::ClientError),
+        5 => Some(StatusClass::ServerError),
+        _ => None,
+    }
+}
+
+/// Which requests are logged, for the whole gateway or a route
+#[derive(Debug, Clone, PartialEq)]
+struct AccessLogPolicy {
+    sample_ratio: f64,
+    status_classes: Vec<StatusClass>,
+}
+
+impl AccessLogPolicy {
+    /// Whether a request answered with a status is logged, given a random sample
+    /// taken uniformly from `[0, 1)`
+    fn should_log(&self, status: Option<u16>, sample: f64) -> bool {
+        let status_matches = self.status_classes.is_empty()
+            || status
+                .and_then(status_class)
+                .is_some_and(|class| self.status_classes.contains(&class));
+        status_matches && sample < self.sample_ratio
+    }
+}
+
+#[derive(Debug, Clone)]
+enum AccessLogDestination {
+    Lines(mpsc::Sender<AccessLogLine>, AccessLogOutput),
+    OpenTelemetry,
+}
+
+impl PartialEq for AccessLogDestination {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Lines(_, lhs), Self::Lines(_, rhs)) => lhs == rhs,
+            (Self::OpenTelemetry, Self::OpenTelemetry) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Renders the entries of the sampled requests and hands them to the configured sink
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessLogger {
+    format: AccessLogLineFormat,
+    destination: AccessLogDestination,
+    policy: AccessLogPolicy,
+    route_policies: HashMap<String, AccessLogPolicy>,
+}
+
+impl AccessLogger {
+    fn new(access_logs: &AccessLogs, lines_tx: mpsc::Sender<AccessLogLine>) -> Self {
+        let destination = match access_logs.sink() {
+            AccessLogSink::Stdout => AccessLogDestination::Lines(lines_tx, AccessLogOutput::Stdout),
+            AccessLogSink::File(file) => {
+                AccessLogDestination::Lines(lines_tx, AccessLogOutput::File(Arc::new(file.clone())))
+            }
+            AccessLogSink::OpenTelemetry => AccessLogDestination::OpenTelemetry,
+        };
+
+        let policy = AccessLogPolicy {
+            sample_ratio: *access_logs.sample_ratio(),
+            status_classes: access_logs.status_classes().clone(),
+        };
+        let route_policies = access_logs
+            .routes()
+            .iter()
+            .map(|route| {
+                let route_policy = AccessLogPolicy {
+                    sample_ratio: route.sample_ratio().unwrap_or(policy.sample_ratio),
+                    status_classes: if route.status_classes().is_empty() {
+                        policy.status_classes.clone()
+                    } else {
+                        route.status_classes().clone()
+                    },
+                };
+                (route.key().clone(), route_policy)
+            })
+            .collect();
+
+        Self {
+            format: AccessLogLineFormat::new(access_logs.format()),
+            destination,
+            policy,
+            route_policies,
+        }
+    }
+
+    /// Whether the request matching a route and answered with a status is logged
+    pub fn should_log(&self, route: Option<&str>, status: Option<u16>) -> bool {
+        let policy = route
+            .and_then(|route| self.route_policies.get(route))
+            .unwrap_or(&self.policy);
+        policy.should_log(status, rand::random())
+    }
+
+    pub fn log(&self, entry: &AccessLogEntry) {
+        let line = self.format.render(entry);
+        match &self.destination {
+            AccessLogDestination::Lines(lines_tx, output) => {
+                let line = AccessLogLine {
+                    output: output.clone(),
+                    line,
+                };
+                if lines_tx.try_send(line).is_err() {
+                    DROPPED_LINES.add(1, &[]);
+                }
+            }
+            AccessLogDestination::OpenTelemetry => {
+                emit_log_record("vale_gateway.access_log", line, entry.attributes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use vg_core::config::gateway::types::net::AccessLogRoute;
+
+    fn access_logger() -> (AccessLogger, mpsc::Receiver<AccessLogLine>) {
+        let (lines_tx, lines_rx) = mpsc::channel(1);
+        let access_logs = AccessLogs::builder()
+            .status_classes(vec![StatusClass::ClientError, StatusClass::ServerError])
+            .routes(vec![
+                AccessLogRoute::builder().key("default/all").build(),
+                AccessLogRoute::builder()
+                    .key("default/errors")
+                    .sample_ratio(Some(0.0))
+                    .build(),
+                AccessLogRoute::builder()
+                    .key("default/successes")
+                    .status_classes(vec![StatusClass::Successful])
+                    .build(),
+            ])
+            .build();
+        (AccessLogger::new(&access_logs, lines_tx), lines_rx)
+    }
+
+    #[test]
+    fn test_status_class() {
+        assert_eq!(status_class(101), Some(StatusClass::Informational));
+        assert_eq!(status_class(204), Some(StatusClass::Successful));
+        assert_eq!(status_class(308), Some(StatusClass::Redirection));
+        assert_eq!(status_class(429), Some(StatusClass::ClientError));
+        assert_eq!(status_class(503), Some(StatusClass::ServerError));
+        assert_eq!(status_class(0), None);
+        assert_eq!(status_class(600), None);
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = AccessLogPolicy {
+            sample_ratio: 0.25,
+            status_classes: vec![StatusClass::ServerError],
+        };
+        assert!(policy.should_log(Some(500), 0.0));
+        assert!(policy.should_log(Some(502), 0.24));
+        assert!(!policy.should_log(Some(502), 0.25));
+        assert!(!policy.should_log(Some(404), 0.0));
+        assert!(!policy.should_log(None, 0.0));
+
+        let policy = AccessLogPolicy {
+            sample_ratio: 1.0,
+            status_classes: vec![],
+        };
+        assert!(policy.should_log(None, 0.99));
+        assert!(policy.should_log(Some(200), 0.99));
+    }
+
+    #[test]
+    fn test_route_policies() {
+        let (logger, _lines_rx) = access_logger();
+
+        // Gateway policy
+        assert!(logger.should_log(None, Some(500)));
+        assert!(!logger.should_log(None, Some(200)));
+        assert!(!logger.should_log(Some("default/other"), Some(200)));
+
+        // Routes inherit what they do not override
+        assert!(logger.should_log(Some("default/all"), Some(404)));
+        assert!(!logger.should_log(Some("default/all"), Some(200)));
+        assert!(!logger.should_log(Some("default/errors"), Some(500)));
+        assert!(logger.should_log(Some("default/successes"), Some(200)));
+        assert!(!logger.should_log(Some("default/successes"), Some(500)));
+    }
+
+    #[test]
+    fn test_log_drops_lines_when_full() {
+        let (logger, mut lines_rx) = access_logger();
+        let entry = AccessLogEntry::builder()
+            .method("GET")
+            .path("/")
+            .protocol("HTTP/1.1")
+            .status(Some(500))
+            .duration(Duration::from_millis(1))
+            .build();
+
+        logger.log(&entry);
+        logger.log(&entry);
+
+        let line = lines_rx.try_recv().expect("line");
+        assert_eq!(line.output, AccessLogOutput::Stdout);
+        assert!(line.line.contains("\"status\":500"));
+        assert!(lines_rx.try_recv().is_err());
+    }
+}
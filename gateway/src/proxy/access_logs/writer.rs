@@ -0,0 +1,221 @@
+use std::io::ErrorKind;
+use std::sync::Arc;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{self, AsyncWriteExt, BufWriter, Stdout};
+use tokio::sync::mpsc;
+use tracing::warn;
+use vg_core::config::gateway::types::net::AccessLogFile;
+
+/// Where a line is written to
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessLogOutput {
+    Stdout,
+    File(Arc<AccessLogFile>),
+}
+
+#[derive(Debug)]
+pub struct AccessLogLine {
+    pub output: AccessLogOutput,
+    pub line: String,
+}
+
+/// Writes the lines queued by the proxy, off the request path. Lines are flushed
+/// whenever the queue is drained.
+pub async fn write_access_log_lines(mut lines_rx: mpsc::Receiver<AccessLogLine>) {
+    let mut stdout = BufWriter::new(io::stdout());
+    let mut file: Option<RotatingFile> = None;
+
+    while let Some(AccessLogLine { output, mut line }) = lines_rx.recv().await {
+        line.push('\n');
+        let result = match output {
+            AccessLogOutput::Stdout => stdout.write_all(line.as_bytes()).await,
+            AccessLogOutput::File(config) => {
+                write_to_file(&mut file, config, line.as_bytes()).await
+            }
+        };
+        if let Err(e) = result {
+            warn!("Failed to write access log line: {}", e);
+        }
+
+        if lines_rx.is_empty() {
+            flush(&mut stdout, &mut file).await;
+        }
+    }
+
+    flush(&mut stdout, &mut file).await;
+}
+
+async fn write_to_file(
+    file: &mut Option<RotatingFile>,
+    config: Arc<AccessLogFile>,
+    line: &[u8],
+) -> io::Result<()> {
+    let current = match file.take() {
+        Some(current) if current.config == config => current,
+        previous => {
+            if let Some(mut previous) = previous {
+                previous.writer.flush().await?;
+            }
+            RotatingFile::open(config).await?
+        }
+    };
+    file.insert(current).write_line(line).await
+}
+
+async fn flush(stdout: &mut BufWriter<Stdout>, file: &mut Option<RotatingFile>) {
+    if let Err(e) = stdout.flush().await {
+        warn!("Failed to flush access logs to stdout: {}", e);
+    }
+    if let Some(file) = file
+        && let Err(e) = file.writer.flush().await
+    {
+        warn!(
+            "Failed to flush access logs to {}: {}",
+            file.config.path(),
+            e
+        );
+    }
+}
+
+/// An access log file, renamed to `<path>.1` once it reaches its maximum size while
+/// older files are shifted up to `<path>.<max_files>`
+struct RotatingFile {
+    config: Arc<AccessLogFile>,
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    async fn open(config: Arc<AccessLogFile>) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(config.path())
+            .await?;
+        let size = file.metadata().await?.len();
+        Ok(Self {
+            config,
+            writer: BufWriter::new(file),
+            size,
+        })
+    }
+
+    async fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > *self.config.max_size_bytes() {
+            self.rotate().await?;
+        }
+        self.writer.write_all(line).await?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush().await?;
+
+        let path = self.config.path();
+        let max_files = *self.config.max_files();
+        if max_files == 0 {
+            fs::remove_file(path).await?;
+        } else {
+            for index in (1..max_files).rev() {
+                match fs::rename(format!("{path}.{index}"), format!("{path}.{}", index + 1)).await {
+                    Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+            }
+            fs::rename(path, format!("{path}.1")).await?;
+        }
+
+        *self = Self::open(self.config.clone()).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_config(dir: &tempfile::TempDir, max_files: u32) -> Arc<AccessLogFile> {
+        Arc::new(
+            AccessLogFile::builder()
+                .path(dir.path().join("access.log").to_string_lossy())
+                .max_size_bytes(16)
+                .max_files(max_files)
+                .build(),
+        )
+    }
+
+    async fn read(path: String) -> Option<String> {
+        fs::read_to_string(path).await.ok()
+    }
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = file_config(&dir, 2);
+        let path = config.path().clone();
+
+        let (lines_tx, lines_rx) = mpsc::channel(16);
+        for line in ["first", "second", "third", "fourth", "fifth"] {
+            lines_tx
+                .send(AccessLogLine {
+                    output: AccessLogOutput::File(config.clone()),
+                    line: format!("{line} line"),
+                })
+                .await
+                .expect("send");
+        }
+        drop(lines_tx);
+        write_access_log_lines(lines_rx).await;
+
+        assert_eq!(read(path.clone()).await.as_deref(), Some("fifth line\n"));
+        assert_eq!(
+            read(format!("{path}.1")).await.as_deref(),
+            Some("fourth line\n")
+        );
+        assert_eq!(
+            read(format!("{path}.2")).await.as_deref(),
+            Some("third line\n")
+        );
+        assert_eq!(read(format!("{path}.3")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_rotation_without_rotated_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = file_config(&dir, 0);
+        let path = config.path().clone();
+
+        let mut file = None;
+        for line in ["first line\n", "second line\n"] {
+            write_to_file(&mut file, config.clone(), line.as_bytes())
+                .await
+                .expect("write");
+        }
+        flush(&mut BufWriter::new(io::stdout()), &mut file).await;
+
+        assert_eq!(read(path.clone()).await.as_deref(), Some("second line\n"));
+        assert_eq!(read(format!("{path}.1")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_appends_to_existing_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = file_config(&dir, 1);
+        let path = config.path().clone();
+        fs::write(&path, "existing\n").await.expect("write");
+
+        let mut file = None;
+        write_to_file(&mut file, config.clone(), b"new line\n")
+            .await
+            .expect("write");
+        flush(&mut BufWriter::new(io::stdout()), &mut file).await;
+
+        // The existing 9 bytes count towards the maximum size
+        assert_eq!(read(path.clone()).await.as_deref(), Some("new line\n"));
+        assert_eq!(
+            read(format!("{path}.1")).await.as_deref(),
+            Some("existing\n")
+        );
+    }
+}
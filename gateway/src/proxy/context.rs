@@ -1,18 +1,24 @@
+use crate::proxy::filters::cache::CacheFilterHandler;
+use crate::proxy::filters::compression::ResponseCompressor;
+use crate::proxy::filters::request_limits::RequestBodyLimiter;
 use crate::proxy::instrumentation::RequestInstrumentation;
 use crate::proxy::responses::error_responses::{ErrorResponseCode, ErrorResponseGenerators};
+use crate::proxy::router::circuit_breaker::{CircuitBreakerLimit, CircuitBreakerPermit};
 use crate::proxy::router::endpoints::EndpointsResolver;
-use crate::proxy::router::{HttpRoute, HttpRouteRule};
+use crate::proxy::router::{HttpBackend, HttpRoute, HttpRouteRule};
 use bytes::Bytes;
 use getset::Getters;
 use http::Response;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 use typed_builder::TypedBuilder;
 use vg_core::sync::signal::Receiver;
 
 #[derive(Debug)]
 pub enum UpstreamPeerResult {
     Addr(SocketAddr),
+    CircuitOpen(CircuitBreakerLimit),
     NotFound,
     ServiceUnavailable,
     MissingConfiguration,
@@ -22,6 +28,8 @@ pub enum UpstreamPeerResult {
 struct ContextState {
     route: MatchRouteResult,
     endpoint_resolver: Option<EndpointsResolver>,
+    circuit_breaker_permit: Option<CircuitBreakerPermit>,
+    upstream_addr: Option<SocketAddr>,
     #[allow(dead_code)] // Future use for client IP tracking
     client_addr: Option<IpAddr>,
 }
@@ -35,6 +43,22 @@ pub struct RequestContext {
 
     #[builder(default)]
     state: OnceLock<ContextState>,
+
+    /// Error response to send when proxying fails, instead of Pingora's default
+    #[builder(default)]
+    error_response_code: Option<ErrorResponseCode>,
+
+    /// Compressor of the response body, when a compression filter negotiated one
+    #[builder(default)]
+    response_compressor: Option<ResponseCompressor>,
+
+    /// Cache filter of the matched route, when the request may be served from the cache
+    #[builder(default)]
+    cache_filter: Option<CacheFilterHandler>,
+
+    /// Limiter of the request body, when a request limits filter applies to the route
+    #[builder(default)]
+    request_body_limiter: Option<RequestBodyLimiter>,
 }
 
 unsafe impl Send for RequestContext {}
@@ -57,11 +81,25 @@ impl RequestContext {
         if let Some(state) = self.state.get_mut()
             && let Some(resolver) = &mut state.endpoint_resolver
         {
-            return if let Some(addr) = resolver.next() {
-                UpstreamPeerResult::Addr(addr)
-            } else {
-                UpstreamPeerResult::NotFound
+            let Some(addr) = resolver.next() else {
+                return UpstreamPeerResult::NotFound;
             };
+
+            // Release the slot held by a previous attempt before reserving the next one
+            state.circuit_breaker_permit = None;
+            state.upstream_addr = Some(addr);
+            if let MatchRouteResult::Found(_, rule, _) = &state.route
+                && let Some(circuit_breaker) = rule
+                    .backend_for(addr)
+                    .and_then(|backend| backend.circuit_breaker().as_ref())
+            {
+                match circuit_breaker.try_acquire() {
+                    Ok(permit) => state.circuit_breaker_permit = Some(permit),
+                    Err(limit) => return UpstreamPeerResult::CircuitOpen(limit),
+                }
+            }
+
+            return UpstreamPeerResult::Addr(addr);
         }
         match self.route() {
             Some(MatchRouteResult::NotFound) => UpstreamPeerResult::NotFound,
@@ -72,6 +110,64 @@ impl RequestContext {
         }
     }
 
+    /// The backend of the matched rule that the current upstream peer belongs to
+    pub fn upstream_backend(&self) -> Option<&HttpBackend> {
+        let state = self.state.get()?;
+        let MatchRouteResult::Found(_, rule, _) = &state.route else {
+            return None;
+        };
+        rule.backend_for(state.upstream_addr?)
+    }
+
+    /// Marks the pending upstream connection as established for the circuit breaker
+    pub fn upstream_connected(&mut self) {
+        if let Some(state) = self.state.get_mut()
+            && let Some(permit) = &mut state.circuit_breaker_permit
+        {
+            permit.connected();
+        }
+    }
+
+    pub fn error_response_code(&self) -> Option<ErrorResponseCode> {
+        self.error_response_code
+    }
+
+    pub fn set_error_response_code(&mut self, code: ErrorResponseCode) {
+        self.error_response_code = Some(code);
+    }
+
+    pub fn set_response_compressor(&mut self, compressor: ResponseCompressor) {
+        self.response_compressor = Some(compressor);
+    }
+
+    pub fn response_compressor_mut(&mut self) -> Option<&mut ResponseCompressor> {
+        self.response_compressor.as_mut()
+    }
+
+    pub fn cache_filter(&self) -> Option<&CacheFilterHandler> {
+        self.cache_filter.as_ref()
+    }
+
+    pub fn set_cache_filter(&mut self, handler: CacheFilterHandler) {
+        self.cache_filter = Some(handler);
+    }
+
+    pub fn set_request_body_limiter(&mut self, limiter: RequestBodyLimiter) {
+        self.request_body_limiter = Some(limiter);
+    }
+
+    pub fn request_body_limiter_mut(&mut self) -> Option<&mut RequestBodyLimiter> {
+        self.request_body_limiter.as_mut()
+    }
+
+    /// Whether another upstream can be sent the request body after a failed attempt, which
+    /// only holds for bodies buffered in full or not read yet
+    pub fn is_request_body_replayable(&self) -> bool {
+        self.request_body_limiter
+            .as_ref()
+            .is_some_and(RequestBodyLimiter::is_replayable)
+    }
+
     #[allow(dead_code)] // Public API for future client IP tracking
     pub fn client_addr(&self) -> Option<IpAddr> {
         self.state.get().and_then(|x| x.client_addr)
@@ -90,10 +186,15 @@ impl RequestContext {
             MatchRouteResult::Found(route, rule, matched_prefix) => {
                 let mut resolver_builder = EndpointsResolver::builder(client_addr);
                 resolver_builder.unique_id(rule.unique_id());
+                let now = Instant::now();
                 for backend in rule.backends() {
                     for (location, endpoints) in backend.endpoints() {
                         for endpoint in endpoints {
-                            resolver_builder.insert(endpoint.addr(), *location);
+                            resolver_builder.insert_weighted(
+                                endpoint.addr(),
+                                *location,
+                                backend.endpoint_weight(endpoint, now),
+                            );
                         }
                     }
                 }
@@ -112,6 +213,8 @@ impl RequestContext {
         let _ = self.state.set(ContextState {
             route,
             endpoint_resolver,
+            circuit_breaker_permit: None,
+            upstream_addr: None,
             client_addr,
         });
     }
@@ -0,0 +1,42 @@
+use super::BasicAuthFilterHandler;
+use std::collections::HashMap;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::basic_auth::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type BasicAuthFilterHandlers = HashMap<Key, BasicAuthFilterHandler>;
+
+pub fn basic_auth_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<BasicAuthFilterHandlers> {
+    let (tx, rx) = signal(stringify!(basic_auth_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(basic_auth_filters_handlers))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    let handlers: BasicAuthFilterHandlers = gateway_configuration
+                        .basic_auth_filters()
+                        .iter()
+                        .map(|filter| {
+                            (
+                                filter.key().clone(),
+                                BasicAuthFilterHandler::new(filter.clone()),
+                            )
+                        })
+                        .collect();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
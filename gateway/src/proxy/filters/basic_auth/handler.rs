@@ -0,0 +1,312 @@
+use super::Htpasswd;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use http::header::AUTHORIZATION;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use std::str::FromStr;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{instrument, warn};
+use vg_core::config::gateway::types::net::BasicAuthFilter;
+
+/// Realm used in the `WWW-Authenticate` challenge when the filter does not set one,
+/// since RFC 7617 requires the parameter
+const DEFAULT_REALM: &str = "Restricted";
+
+#[derive(Debug, Error)]
+pub enum BasicAuthError {
+    #[error("Missing credentials")]
+    MissingCredentials,
+    #[error("Malformed credentials")]
+    MalformedCredentials,
+    #[error("Credentials are unavailable")]
+    CredentialsUnavailable,
+    #[error("Invalid username or password")]
+    InvalidCredentials,
+}
+
+#[derive(Debug)]
+pub enum BasicAuthEvaluationResult {
+    /// The credentials are valid. The username header is set, or removed when the
+    /// username is not a valid header value, so clients cannot supply it themselves.
+    Authenticated {
+        upstream_headers: Vec<(HeaderName, Option<HeaderValue>)>,
+    },
+    Unauthenticated(BasicAuthError),
+}
+
+/// Decoded `Authorization: Basic` credentials, deliberately not `Debug` so the
+/// password cannot end up in logs
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicCredentials {
+    username: String,
+    password: String,
+}
+
+impl BasicCredentials {
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicAuthFilterHandler {
+    filter: BasicAuthFilter,
+    username_header: Option<HeaderName>,
+}
+
+impl BasicAuthFilterHandler {
+    pub fn new(filter: BasicAuthFilter) -> Self {
+        let username_header = filter.username_header().as_ref().and_then(|header| {
+            match HeaderName::from_str(header) {
+                Ok(header) => Some(header),
+                Err(err) => {
+                    warn!("Ignoring invalid username header {}: {}", header, err);
+                    None
+                }
+            }
+        });
+
+        Self {
+            filter,
+            username_header,
+        }
+    }
+
+    pub fn filter(&self) -> &BasicAuthFilter {
+        &self.filter
+    }
+
+    /// Decodes the credentials of an `Authorization: Basic` header
+    pub fn credentials(headers: &HeaderMap) -> Result<BasicCredentials, BasicAuthError> {
+        let value = headers
+            .get(AUTHORIZATION)
+            .ok_or(BasicAuthError::MissingCredentials)?
+            .to_str()
+            .map_err(|_| BasicAuthError::MalformedCredentials)?;
+        let (scheme, encoded) = value
+            .split_once(' ')
+            .ok_or(BasicAuthError::MissingCredentials)?;
+        if !scheme.eq_ignore_ascii_case("basic") {
+            return Err(BasicAuthError::MissingCredentials);
+        }
+
+        let decoded = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|_| BasicAuthError::MalformedCredentials)?;
+        let decoded =
+            String::from_utf8(decoded).map_err(|_| BasicAuthError::MalformedCredentials)?;
+        let (username, password) = decoded
+            .split_once(':')
+            .ok_or(BasicAuthError::MalformedCredentials)?;
+
+        Ok(BasicCredentials {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+    }
+
+    /// Verifies the credentials on a blocking thread, as bcrypt and argon2 take
+    /// tens of milliseconds by design
+    #[instrument(name = "BasicAuthFilterHandler::evaluate", skip_all)]
+    pub async fn evaluate(
+        &self,
+        credentials: Result<BasicCredentials, BasicAuthError>,
+        htpasswd: Option<Arc<Htpasswd>>,
+    ) -> BasicAuthEvaluationResult {
+        let credentials = match credentials {
+            Ok(credentials) => credentials,
+            Err(err) => return BasicAuthEvaluationResult::Unauthenticated(err),
+        };
+        let Some(htpasswd) = htpasswd else {
+            return BasicAuthEvaluationResult::Unauthenticated(
+                BasicAuthError::CredentialsUnavailable,
+            );
+        };
+
+        let username = credentials.username.clone();
+        let verified = tokio::task::spawn_blocking(move || {
+            htpasswd.verify(&credentials.username, &credentials.password)
+        })
+        .await
+        .unwrap_or(false);
+
+        if verified {
+            BasicAuthEvaluationResult::Authenticated {
+                upstream_headers: self.upstream_headers(&username),
+            }
+        } else {
+            BasicAuthEvaluationResult::Unauthenticated(BasicAuthError::InvalidCredentials)
+        }
+    }
+
+    /// The `WWW-Authenticate` challenge for a rejected request, as described by RFC 7617
+    pub fn www_authenticate(&self) -> HeaderValue {
+        let realm = self.filter.realm().as_deref().unwrap_or(DEFAULT_REALM);
+        let challenge = format!("Basic realm=\"{}\", charset=\"UTF-8\"", quote(realm));
+        HeaderValue::from_str(&challenge)
+            .unwrap_or_else(|_| HeaderValue::from_static("Basic realm=\"Restricted\""))
+    }
+
+    fn upstream_headers(&self, username: &str) -> Vec<(HeaderName, Option<HeaderValue>)> {
+        let mut headers = Vec::new();
+        if let Some(username_header) = &self.username_header {
+            headers.push((
+                username_header.clone(),
+                HeaderValue::from_str(username).ok(),
+            ));
+        }
+        if *self.filter.strip_authorization() {
+            headers.push((AUTHORIZATION, None));
+        }
+        headers
+    }
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `htpasswd -s` hash of "secret"
+    const HTPASSWD: &str = "alice:{SHA}5en6G6MezRroT3XKqkdPOmY/BfQ=";
+
+    fn filter_handler(strip_authorization: bool) -> BasicAuthFilterHandler {
+        BasicAuthFilterHandler::new(
+            BasicAuthFilter::builder()
+                .key("default/basic")
+                .version_key("1")
+                .htpasswd_identifier("uid")
+                .strip_authorization(strip_authorization)
+                .username_header(Some("x-remote-user".to_string()))
+                .build(),
+        )
+    }
+
+    fn headers(authorization: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(authorization));
+        headers
+    }
+
+    fn htpasswd() -> Option<Arc<Htpasswd>> {
+        Some(Arc::new(Htpasswd::parse(HTPASSWD)))
+    }
+
+    #[test]
+    fn test_credentials() {
+        // alice:secret
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U6c2VjcmV0"))
+            .expect("credentials");
+        assert_eq!(credentials.username(), "alice");
+        assert_eq!(credentials.password, "secret");
+
+        // Passwords may contain colons
+        let credentials = BasicAuthFilterHandler::credentials(&headers("basic YWxpY2U6YTpi"))
+            .expect("credentials");
+        assert_eq!(credentials.password, "a:b");
+
+        assert!(matches!(
+            BasicAuthFilterHandler::credentials(&HeaderMap::new()),
+            Err(BasicAuthError::MissingCredentials)
+        ));
+        assert!(matches!(
+            BasicAuthFilterHandler::credentials(&headers("Bearer abc.def.ghi")),
+            Err(BasicAuthError::MissingCredentials)
+        ));
+        assert!(matches!(
+            BasicAuthFilterHandler::credentials(&headers("Basic !!!")),
+            Err(BasicAuthError::MalformedCredentials)
+        ));
+        // "alice" without a password separator
+        assert!(matches!(
+            BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U=")),
+            Err(BasicAuthError::MalformedCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_valid_credentials_set_upstream_headers() {
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U6c2VjcmV0"));
+        let result = filter_handler(true).evaluate(credentials, htpasswd()).await;
+
+        let BasicAuthEvaluationResult::Authenticated { upstream_headers } = result else {
+            panic!("credentials were rejected");
+        };
+        assert_eq!(
+            upstream_headers,
+            vec![
+                (
+                    HeaderName::from_static("x-remote-user"),
+                    Some(HeaderValue::from_static("alice"))
+                ),
+                (AUTHORIZATION, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorization_is_kept_by_default() {
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U6c2VjcmV0"));
+        let result = filter_handler(false)
+            .evaluate(credentials, htpasswd())
+            .await;
+
+        let BasicAuthEvaluationResult::Authenticated { upstream_headers } = result else {
+            panic!("credentials were rejected");
+        };
+        assert_eq!(upstream_headers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_credentials() {
+        let handler = filter_handler(false);
+
+        // alice:wrong
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U6d3Jvbmc="));
+        let result = handler.evaluate(credentials, htpasswd()).await;
+        assert!(matches!(
+            result,
+            BasicAuthEvaluationResult::Unauthenticated(BasicAuthError::InvalidCredentials)
+        ));
+
+        // bob:secret
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic Ym9iOnNlY3JldA=="));
+        let result = handler.evaluate(credentials, htpasswd()).await;
+        assert!(matches!(
+            result,
+            BasicAuthEvaluationResult::Unauthenticated(BasicAuthError::InvalidCredentials)
+        ));
+
+        let credentials = BasicAuthFilterHandler::credentials(&headers("Basic YWxpY2U6c2VjcmV0"));
+        let result = handler.evaluate(credentials, None).await;
+        assert!(matches!(
+            result,
+            BasicAuthEvaluationResult::Unauthenticated(BasicAuthError::CredentialsUnavailable)
+        ));
+    }
+
+    #[test]
+    fn test_www_authenticate() {
+        assert_eq!(
+            filter_handler(false).www_authenticate(),
+            "Basic realm=\"Restricted\", charset=\"UTF-8\""
+        );
+
+        let handler = BasicAuthFilterHandler::new(
+            BasicAuthFilter::builder()
+                .key("default/basic")
+                .version_key("1")
+                .htpasswd_identifier("uid")
+                .realm(Some("internal \"tools\"".to_string()))
+                .build(),
+        );
+        assert_eq!(
+            handler.www_authenticate(),
+            "Basic realm=\"internal \\\"tools\\\"\", charset=\"UTF-8\""
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use argon2::password_hash::PasswordHash;
+use argon2::{Argon2, PasswordVerifier};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use subtle::ConstantTimeEq;
+use tracing::warn;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HashedPassword {
+    Bcrypt(String),
+    Sha1([u8; 20]),
+    Argon2(String),
+}
+
+impl HashedPassword {
+    fn parse(hash: &str) -> Option<Self> {
+        if ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            Some(Self::Bcrypt(hash.to_string()))
+        } else if let Some(digest) = hash.strip_prefix("{SHA}") {
+            let digest = BASE64_STANDARD.decode(digest).ok()?;
+            Some(Self::Sha1(digest.try_into().ok()?))
+        } else if hash.starts_with("$argon2") {
+            PasswordHash::new(hash).ok()?;
+            Some(Self::Argon2(hash.to_string()))
+        } else {
+            None
+        }
+    }
+
+    fn verify(&self, password: &str) -> bool {
+        match self {
+            Self::Bcrypt(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+            Self::Sha1(digest) => Sha1::digest(password.as_bytes())
+                .as_slice()
+                .ct_eq(digest)
+                .into(),
+            Self::Argon2(hash) => PasswordHash::new(hash).is_ok_and(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            }),
+        }
+    }
+}
+
+/// The users of an htpasswd file. Only bcrypt, `{SHA}` and argon2 hashes are
+/// supported, lines using any other scheme are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Htpasswd {
+    users: HashMap<String, HashedPassword>,
+}
+
+impl Htpasswd {
+    pub fn parse(contents: &str) -> Self {
+        let users = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let Some((username, hash)) = line.split_once(':') else {
+                    warn!("Ignoring malformed htpasswd line");
+                    return None;
+                };
+                match HashedPassword::parse(hash) {
+                    Some(hash) => Some((username.to_string(), hash)),
+                    None => {
+                        warn!(
+                            "Ignoring htpasswd entry for user {} with an unsupported hash",
+                            username
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { users }
+    }
+
+    pub fn contains(&self, username: &str) -> bool {
+        self.users.contains_key(username)
+    }
+
+    /// Checks a password against the hash of the user. bcrypt and argon2 are slow
+    /// by design, so this should not be called on an async worker thread.
+    pub fn verify(&self, username: &str, password: &str) -> bool {
+        self.users
+            .get(username)
+            .is_some_and(|hash| hash.verify(password))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `htpasswd -s` hash of "secret"
+    const SHA: &str = "{SHA}5en6G6MezRroT3XKqkdPOmY/BfQ=";
+
+    fn argon2_hash(password: &str) -> String {
+        use argon2::password_hash::{PasswordHasher, SaltString};
+        let salt = SaltString::encode_b64(b"vale-gateway-salt").expect("salt");
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .expect("hash")
+            .to_string()
+    }
+
+    fn bcrypt_hash(password: &str) -> String {
+        bcrypt::hash(password, 4).expect("hash")
+    }
+
+    #[test]
+    fn test_parse_skips_unsupported_entries() {
+        let htpasswd = Htpasswd::parse(&format!(
+            "# users\nalice:{}\n\nbob:{SHA}\nmallory:$apr1$abc$def\nplain:secret\nbroken\n",
+            bcrypt_hash("secret")
+        ));
+
+        assert!(htpasswd.contains("alice"));
+        assert!(htpasswd.contains("bob"));
+        assert!(!htpasswd.contains("mallory"));
+        assert!(!htpasswd.contains("plain"));
+    }
+
+    #[test]
+    fn test_verify_sha() {
+        let htpasswd = Htpasswd::parse(&format!("bob:{SHA}"));
+        assert!(htpasswd.verify("bob", "secret"));
+        assert!(!htpasswd.verify("bob", "Secret"));
+        assert!(!htpasswd.verify("alice", "secret"));
+    }
+
+    #[test]
+    fn test_verify_bcrypt() {
+        let htpasswd = Htpasswd::parse(&format!("alice:{}", bcrypt_hash("secret")));
+        assert!(htpasswd.verify("alice", "secret"));
+        assert!(!htpasswd.verify("alice", "wrong"));
+    }
+
+    #[test]
+    fn test_verify_argon2() {
+        let htpasswd = Htpasswd::parse(&format!("carol:{}", argon2_hash("secret")));
+        assert!(htpasswd.verify("carol", "secret"));
+        assert!(!htpasswd.verify("carol", "wrong"));
+    }
+}
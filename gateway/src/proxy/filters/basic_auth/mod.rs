@@ -0,0 +1,7 @@
+mod controllers;
+mod handler;
+mod htpasswd;
+
+pub use controllers::*;
+pub use handler::*;
+pub use htpasswd::*;
@@ -0,0 +1,76 @@
+use super::{CacheFilterHandler, HttpCache};
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver as BroadcastReceiver;
+use tracing::{info, warn};
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::ipc::GatewayEvent;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::cache::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type CacheFilterHandlers = HashMap<Key, CacheFilterHandler>;
+
+pub fn cache_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<CacheFilterHandlers> {
+    let (tx, rx) = signal(stringify!(cache_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(cache_filters_handlers))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    let handlers: CacheFilterHandlers = gateway_configuration
+                        .cache_filters()
+                        .iter()
+                        .map(|filter| (filter.key().clone(), CacheFilterHandler::new(filter)))
+                        .collect();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
+
+/// Purges the response cache when the control plane asks for it
+pub fn purge_http_cache(
+    task_builder: &TaskBuilder,
+    gateway_events_rx: BroadcastReceiver<GatewayEvent>,
+    http_cache: HttpCache,
+) {
+    task_builder
+        .new_task(stringify!(purge_http_cache))
+        .spawn(async move {
+            let mut gateway_events = gateway_events_rx;
+            loop {
+                match gateway_events.recv().await {
+                    Ok(GatewayEvent::CachePurge(purge)) => {
+                        info!(
+                            "Purging cached responses for {}",
+                            purge.hostname().as_deref().unwrap_or("all hostnames")
+                        );
+                        http_cache.purge(purge.hostname().as_deref());
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        // A missed purge cannot be told apart from other events, so the
+                        // whole cache is dropped to be safe
+                        warn!(
+                            "Missed {} gateway events, purging all cached responses",
+                            skipped
+                        );
+                        http_cache.purge(None);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+}
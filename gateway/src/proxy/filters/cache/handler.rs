@@ -0,0 +1,206 @@
+use http::header::{SET_COOKIE, VARY};
+use http::{Method, StatusCode};
+use pingora::cache::cache_control::CacheControl;
+use pingora::cache::filters::resp_cacheable;
+use pingora::cache::key::HashBinary;
+use pingora::cache::{CacheMeta, CacheMetaDefaults, NoCacheReason, RespCacheable, VarianceBuilder};
+use pingora::http::{RequestHeader, ResponseHeader};
+use vg_core::config::gateway::types::net::CacheFilter;
+use vg_core::types::filters::cache::Key;
+
+/// Responses are only cached when the upstream gives them a lifetime itself
+fn no_default_freshness(_status: StatusCode) -> Option<u32> {
+    None
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheFilterHandler {
+    key: Key,
+    max_object_size_bytes: usize,
+    stale_while_revalidate_seconds: u32,
+    stale_if_error_seconds: u32,
+}
+
+impl CacheFilterHandler {
+    pub fn new(filter: &CacheFilter) -> Self {
+        Self {
+            key: filter.key().clone(),
+            max_object_size_bytes: *filter.max_object_size_bytes() as usize,
+            stale_while_revalidate_seconds: *filter.stale_while_revalidate_seconds(),
+            stale_if_error_seconds: *filter.stale_if_error_seconds(),
+        }
+    }
+
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    pub fn max_object_size_bytes(&self) -> usize {
+        self.max_object_size_bytes
+    }
+
+    /// Only `GET` requests are looked up in and stored to the cache
+    pub fn is_cacheable_request(method: &Method) -> bool {
+        method == Method::GET
+    }
+
+    /// Decides from `Cache-Control` whether a response may be stored in a shared cache.
+    /// Responses to authenticated requests are only stored when the upstream explicitly
+    /// allows it, and responses setting cookies or varying on everything never are.
+    pub fn response_cacheable(
+        &self,
+        response: &ResponseHeader,
+        authenticated: bool,
+    ) -> RespCacheable {
+        let varies_on_everything = response
+            .headers
+            .get_all(VARY)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|name| name.trim() == "*");
+        if varies_on_everything || response.headers.contains_key(SET_COOKIE) {
+            return RespCacheable::Uncacheable(NoCacheReason::OriginNotCache);
+        }
+
+        let defaults = CacheMetaDefaults::new(
+            no_default_freshness,
+            self.stale_while_revalidate_seconds,
+            self.stale_if_error_seconds,
+        );
+        resp_cacheable(
+            CacheControl::from_resp_headers(response).as_ref(),
+            response.clone(),
+            authenticated,
+            &defaults,
+        )
+    }
+
+    /// The variant of a cached response a request maps to, from the request headers
+    /// listed in the `Vary` header of the response
+    pub fn variance(meta: &CacheMeta, request: &RequestHeader) -> Option<HashBinary> {
+        let names: Vec<String> = meta
+            .response_header()
+            .headers
+            .get_all(VARY)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim().to_ascii_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        let mut variance = VarianceBuilder::new();
+        for name in &names {
+            let value = request
+                .headers
+                .get(name.as_str())
+                .map(|value| value.as_bytes())
+                .unwrap_or_default();
+            variance.add_value(name, value);
+        }
+        variance.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_handler() -> CacheFilterHandler {
+        CacheFilterHandler::new(
+            &CacheFilter::builder()
+                .key("default/cache")
+                .max_object_size_bytes(1024 * 1024)
+                .stale_while_revalidate_seconds(30)
+                .build(),
+        )
+    }
+
+    fn response(headers: &[(&'static str, &'static str)]) -> ResponseHeader {
+        let mut response = ResponseHeader::build(StatusCode::OK, None).expect("response");
+        for (name, value) in headers {
+            response.append_header(*name, *value).expect("header");
+        }
+        response
+    }
+
+    fn request(headers: &[(&'static str, &'static str)]) -> RequestHeader {
+        let mut request = RequestHeader::build(Method::GET, b"/", None).expect("request");
+        for (name, value) in headers {
+            request.insert_header(*name, *value).expect("header");
+        }
+        request
+    }
+
+    fn is_cacheable(headers: &[(&'static str, &'static str)], authenticated: bool) -> bool {
+        matches!(
+            filter_handler().response_cacheable(&response(headers), authenticated),
+            RespCacheable::Cacheable(_)
+        )
+    }
+
+    #[test]
+    fn test_is_cacheable_request() {
+        assert!(CacheFilterHandler::is_cacheable_request(&Method::GET));
+        assert!(!CacheFilterHandler::is_cacheable_request(&Method::HEAD));
+        assert!(!CacheFilterHandler::is_cacheable_request(&Method::POST));
+    }
+
+    #[test]
+    fn test_response_cacheable() {
+        assert!(is_cacheable(&[("cache-control", "max-age=60")], false));
+        assert!(is_cacheable(&[("cache-control", "s-maxage=60")], false));
+
+        // Without an explicit lifetime
+        assert!(!is_cacheable(&[], false));
+        assert!(!is_cacheable(
+            &[("cache-control", "max-age=60, private")],
+            false
+        ));
+        assert!(!is_cacheable(&[("cache-control", "no-store")], false));
+        assert!(!is_cacheable(
+            &[("cache-control", "max-age=60"), ("vary", "Origin, *")],
+            false
+        ));
+        assert!(!is_cacheable(
+            &[("cache-control", "max-age=60"), ("set-cookie", "session=1")],
+            false
+        ));
+    }
+
+    #[test]
+    fn test_authenticated_responses_must_be_public() {
+        assert!(!is_cacheable(&[("cache-control", "max-age=60")], true));
+        assert!(is_cacheable(
+            &[("cache-control", "public, max-age=60")],
+            true
+        ));
+    }
+
+    #[test]
+    fn test_variance() {
+        let RespCacheable::Cacheable(meta) = filter_handler().response_cacheable(
+            &response(&[("cache-control", "max-age=60"), ("vary", "Accept-Encoding")]),
+            false,
+        ) else {
+            panic!("response should be cacheable");
+        };
+
+        let gzip = CacheFilterHandler::variance(&meta, &request(&[("accept-encoding", "gzip")]));
+        let br = CacheFilterHandler::variance(&meta, &request(&[("accept-encoding", "br")]));
+        assert!(gzip.is_some());
+        assert_ne!(gzip, br);
+        assert_eq!(
+            gzip,
+            CacheFilterHandler::variance(&meta, &request(&[("accept-encoding", "gzip")]))
+        );
+
+        let RespCacheable::Cacheable(meta) = filter_handler()
+            .response_cacheable(&response(&[("cache-control", "max-age=60")]), false)
+        else {
+            panic!("response should be cacheable");
+        };
+        assert_eq!(CacheFilterHandler::variance(&meta, &request(&[])), None);
+    }
+}
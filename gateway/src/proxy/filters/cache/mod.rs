@@ -0,0 +1,7 @@
+mod controllers;
+mod handler;
+mod storage;
+
+pub use controllers::*;
+pub use handler::*;
+pub use storage::*;
@@ -0,0 +1,189 @@
+use super::CacheFilterHandler;
+use dashmap::DashMap;
+use pingora::cache::eviction::simple_lru::Manager;
+use pingora::cache::lock::CacheLock;
+use pingora::cache::{CacheKey, MemCache};
+use pingora::http::RequestHeader;
+use pingora::proxy::Session;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long concurrent misses for the same response wait for the first one to fill
+/// the cache before going upstream themselves
+const CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Generations of the cache keys, bumped to purge every response or those of a
+/// hostname at once. Purged responses can no longer be looked up and are evicted
+/// as the cache fills up.
+#[derive(Debug, Default)]
+struct Generations {
+    all: AtomicU64,
+    hostnames: DashMap<String, u64>,
+}
+
+/// The in-memory response cache shared by all cache filters of the gateway, bounded
+/// by a memory budget with least recently used eviction
+#[derive(Clone)]
+pub struct HttpCache {
+    storage: &'static MemCache,
+    eviction: &'static Manager,
+    lock: &'static CacheLock,
+    generations: Arc<Generations>,
+}
+
+impl HttpCache {
+    /// Pingora requires the cache backends to live as long as the process, so they
+    /// are leaked. Only one cache is created per gateway.
+    pub fn new(size_bytes: usize) -> Self {
+        Self {
+            storage: Box::leak(Box::new(MemCache::new())),
+            eviction: Box::leak(Box::new(Manager::new(size_bytes))),
+            lock: Box::leak(Box::new(CacheLock::new(CACHE_LOCK_TIMEOUT))),
+            generations: Arc::default(),
+        }
+    }
+
+    /// Enables the cache for a request matching a cache filter
+    pub fn enable(&self, session: &mut Session, handler: &CacheFilterHandler) {
+        session.cache.enable(
+            self.storage,
+            Some(self.eviction),
+            None,
+            Some(self.lock),
+            None,
+        );
+        session
+            .cache
+            .set_max_file_size_bytes(handler.max_object_size_bytes());
+    }
+
+    /// Responses are keyed by filter, so routes never share cached responses, and by
+    /// the host, path and query of the request
+    pub fn cache_key(&self, handler: &CacheFilterHandler, request: &RequestHeader) -> CacheKey {
+        let host = host(request);
+        let hostname_generation = self
+            .generations
+            .hostnames
+            .get(&host)
+            .map(|generation| *generation)
+            .unwrap_or_default();
+        let primary = format!(
+            "{}.{}|{}{}",
+            self.generations.all.load(Ordering::Acquire),
+            hostname_generation,
+            host,
+            request
+                .uri
+                .path_and_query()
+                .map_or("/", |path_and_query| path_and_query.as_str())
+        );
+
+        CacheKey::new(handler.key().as_ref().to_string(), primary, String::new())
+    }
+
+    /// Purges the responses cached for a hostname, or every response when none is given
+    pub fn purge(&self, hostname: Option<&str>) {
+        match hostname {
+            Some(hostname) => {
+                *self
+                    .generations
+                    .hostnames
+                    .entry(hostname.to_ascii_lowercase())
+                    .or_default() += 1;
+            }
+            None => {
+                self.generations.all.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+    }
+}
+
+/// The hostname of a request without its port, from the URI of HTTP/2 requests or
+/// the `Host` header
+fn host(request: &RequestHeader) -> String {
+    let host = request.uri.host().or_else(|| {
+        request
+            .headers
+            .get(http::header::HOST)
+            .and_then(|value| value.to_str().ok())
+            .map(|host| match host.rsplit_once(':') {
+                Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+                _ => host,
+            })
+    });
+    host.unwrap_or_default().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use pingora::cache::key::CacheHashKey;
+    use vg_core::config::gateway::types::net::CacheFilter;
+
+    fn filter_handler(key: &str) -> CacheFilterHandler {
+        CacheFilterHandler::new(
+            &CacheFilter::builder()
+                .key(key)
+                .max_object_size_bytes(1024)
+                .build(),
+        )
+    }
+
+    fn request(host: &'static str, path: &str) -> RequestHeader {
+        let mut request =
+            RequestHeader::build(Method::GET, path.as_bytes(), None).expect("request");
+        request.insert_header("host", host).expect("host");
+        request
+    }
+
+    #[test]
+    fn test_host() {
+        let host_of = |host_header| host(&request(host_header, "/"));
+        assert_eq!(host_of("Echo.Example.com:8080"), "echo.example.com");
+        assert_eq!(host_of("echo.example.com"), "echo.example.com");
+        assert_eq!(host_of("[::1]:8080"), "[::1]");
+        assert_eq!(host_of("[::1]"), "[::1]");
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let cache = HttpCache::new(1024 * 1024);
+        let handler = filter_handler("default/cache");
+        let key = |host, path| cache.cache_key(&handler, &request(host, path)).combined();
+
+        assert_eq!(
+            key("a.example.com", "/x?y=1"),
+            key("A.example.com:80", "/x?y=1")
+        );
+        assert_ne!(
+            key("a.example.com", "/x?y=1"),
+            key("a.example.com", "/x?y=2")
+        );
+        assert_ne!(key("a.example.com", "/x"), key("b.example.com", "/x"));
+
+        let other_handler = filter_handler("default/other");
+        let other = cache.cache_key(&other_handler, &request("a.example.com", "/x"));
+        assert_ne!(key("a.example.com", "/x"), other.combined());
+    }
+
+    #[test]
+    fn test_purge() {
+        let cache = HttpCache::new(1024 * 1024);
+        let handler = filter_handler("default/cache");
+        let key = |host, path| cache.cache_key(&handler, &request(host, path)).combined();
+
+        let a = key("a.example.com", "/x");
+        let b = key("b.example.com", "/x");
+
+        cache.purge(Some("A.example.com"));
+        assert_ne!(key("a.example.com", "/x"), a);
+        assert_eq!(key("b.example.com", "/x"), b);
+
+        let a = key("a.example.com", "/x");
+        cache.purge(None);
+        assert_ne!(key("a.example.com", "/x"), a);
+        assert_ne!(key("b.example.com", "/x"), b);
+    }
+}
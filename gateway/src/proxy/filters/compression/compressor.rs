@@ -0,0 +1,229 @@
+use crate::instrumentation::get_meter;
+use brotli::CompressorWriter;
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use opentelemetry::metrics::Counter;
+use opentelemetry::KeyValue;
+use std::io::{self, Write};
+use std::sync::LazyLock;
+use vg_core::config::gateway::types::net::CompressionAlgorithm;
+
+/// Levels favouring speed, as responses are compressed while they are streamed
+const GZIP_LEVEL: u32 = 6;
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const ZSTD_LEVEL: i32 = 3;
+
+static SAVED_BYTES: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    get_meter()
+        .u64_counter("vale_gateway.compression.saved_bytes")
+        .with_description("Number of response body bytes saved by compression.")
+        .with_unit("By")
+        .build()
+});
+
+/// The `Content-Encoding` token of an algorithm
+pub fn content_coding(algorithm: CompressionAlgorithm) -> &'static str {
+    match algorithm {
+        CompressionAlgorithm::Gzip => "gzip",
+        CompressionAlgorithm::Brotli => "br",
+        CompressionAlgorithm::Zstd => "zstd",
+    }
+}
+
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Brotli(Box<CompressorWriter<Vec<u8>>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(algorithm: CompressionAlgorithm) -> io::Result<Self> {
+        Ok(match algorithm {
+            CompressionAlgorithm::Gzip => {
+                Self::Gzip(GzEncoder::new(Vec::new(), Compression::new(GZIP_LEVEL)))
+            }
+            CompressionAlgorithm::Brotli => Self::Brotli(Box::new(CompressorWriter::new(
+                Vec::new(),
+                BROTLI_BUFFER_SIZE,
+                BROTLI_QUALITY,
+                BROTLI_WINDOW,
+            ))),
+            CompressionAlgorithm::Zstd => {
+                Self::Zstd(zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL)?)
+            }
+        })
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Gzip(encoder) => encoder.write_all(data),
+            Self::Brotli(encoder) => encoder.write_all(data),
+            Self::Zstd(encoder) => encoder.write_all(data),
+        }
+    }
+
+    /// Takes the output produced so far, which the encoders buffer until they have
+    /// enough input to emit a block
+    fn take_output(&mut self) -> Vec<u8> {
+        match self {
+            Self::Gzip(encoder) => std::mem::take(encoder.get_mut()),
+            Self::Brotli(encoder) => std::mem::take(encoder.get_mut()),
+            Self::Zstd(encoder) => std::mem::take(encoder.get_mut()),
+        }
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip(encoder) => encoder.finish(),
+            Self::Brotli(encoder) => Ok(encoder.into_inner()),
+            Self::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// Compresses a response body as it is streamed to the client
+pub struct ResponseCompressor {
+    algorithm: CompressionAlgorithm,
+    encoder: Option<Encoder>,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+impl ResponseCompressor {
+    pub fn new(algorithm: CompressionAlgorithm) -> io::Result<Self> {
+        Ok(Self {
+            algorithm,
+            encoder: Some(Encoder::new(algorithm)?),
+            bytes_in: 0,
+            bytes_out: 0,
+        })
+    }
+
+    pub fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+
+    /// Compresses the next chunk of the body. `None` is returned while the encoder has
+    /// nothing to emit yet, as an empty chunk would end a chunked response early.
+    pub fn compress(&mut self, data: Option<&Bytes>, end: bool) -> io::Result<Option<Bytes>> {
+        let Some(encoder) = self.encoder.as_mut() else {
+            return Ok(None);
+        };
+
+        if let Some(data) = data {
+            encoder.write_all(data)?;
+            self.bytes_in += data.len() as u64;
+        }
+
+        let output = if end {
+            let output = self.encoder.take().map(Encoder::finish).transpose()?;
+            output.unwrap_or_default()
+        } else {
+            encoder.take_output()
+        };
+        self.bytes_out += output.len() as u64;
+
+        if end {
+            SAVED_BYTES.add(
+                self.bytes_in.saturating_sub(self.bytes_out),
+                &[KeyValue::new("algorithm", content_coding(self.algorithm))],
+            );
+        }
+
+        Ok((!output.is_empty()).then(|| Bytes::from(output)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn compress(algorithm: CompressionAlgorithm, chunks: &[&str]) -> Vec<u8> {
+        let mut compressor = ResponseCompressor::new(algorithm).expect("compressor");
+        let mut output = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let data = Bytes::from(chunk.to_string());
+            let end = i == chunks.len() - 1;
+            if let Some(compressed) = compressor.compress(Some(&data), end).expect("compress") {
+                output.extend_from_slice(&compressed);
+            }
+        }
+        output
+    }
+
+    fn body() -> Vec<String> {
+        (0..64)
+            .map(|i| format!("{{\"id\":{i},\"message\":\"hello from the echo service\"}}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let body = body();
+        let chunks: Vec<&str> = body.iter().map(String::as_str).collect();
+        let compressed = compress(CompressionAlgorithm::Gzip, &chunks);
+
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut decompressed)
+            .expect("decompress");
+        assert_eq!(decompressed, body.concat());
+        assert!(compressed.len() < decompressed.len());
+    }
+
+    #[test]
+    fn test_brotli_round_trip() {
+        let body = body();
+        let chunks: Vec<&str> = body.iter().map(String::as_str).collect();
+        let compressed = compress(CompressionAlgorithm::Brotli, &chunks);
+
+        let mut decompressed = String::new();
+        brotli::Decompressor::new(compressed.as_slice(), 4096)
+            .read_to_string(&mut decompressed)
+            .expect("decompress");
+        assert_eq!(decompressed, body.concat());
+    }
+
+    #[test]
+    fn test_zstd_round_trip() {
+        let body = body();
+        let chunks: Vec<&str> = body.iter().map(String::as_str).collect();
+        let compressed = compress(CompressionAlgorithm::Zstd, &chunks);
+
+        let decompressed = zstd::decode_all(compressed.as_slice()).expect("decompress");
+        assert_eq!(
+            String::from_utf8(decompressed).expect("utf-8"),
+            body.concat()
+        );
+    }
+
+    #[test]
+    fn test_end_without_data_finishes_stream() {
+        let mut compressor = ResponseCompressor::new(CompressionAlgorithm::Gzip).expect("gzip");
+        let data = Bytes::from_static(b"hello");
+        let mut output = compressor
+            .compress(Some(&data), false)
+            .expect("compress")
+            .map(|bytes| bytes.to_vec())
+            .unwrap_or_default();
+        output.extend_from_slice(
+            &compressor
+                .compress(None, true)
+                .expect("finish")
+                .expect("trailer"),
+        );
+
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(output.as_slice())
+            .read_to_string(&mut decompressed)
+            .expect("decompress");
+        assert_eq!(decompressed, "hello");
+
+        // Nothing is emitted once the stream is finished
+        assert_eq!(compressor.compress(None, true).expect("finished"), None);
+    }
+}
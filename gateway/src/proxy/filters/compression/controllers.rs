@@ -0,0 +1,37 @@
+use super::CompressionFilterHandler;
+use std::collections::HashMap;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::compression::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type CompressionFilterHandlers = HashMap<Key, CompressionFilterHandler>;
+
+pub fn compression_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<CompressionFilterHandlers> {
+    let (tx, rx) = signal(stringify!(compression_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(compression_filters_handlers))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    let handlers: CompressionFilterHandlers = gateway_configuration
+                        .compression_filters()
+                        .iter()
+                        .map(|filter| (filter.key().clone(), CompressionFilterHandler::new(filter)))
+                        .collect();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
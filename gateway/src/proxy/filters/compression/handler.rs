@@ -0,0 +1,374 @@
+use super::{content_coding, ResponseCompressor};
+use http::header::{
+    ACCEPT_ENCODING, ACCEPT_RANGES, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE,
+    CONTENT_TYPE, ETAG, TRANSFER_ENCODING, VARY,
+};
+use http::{HeaderMap, Method, StatusCode};
+use pingora::http::ResponseHeader;
+use tracing::warn;
+use vg_core::config::gateway::types::net::{CompressionAlgorithm, CompressionFilter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MediaRange {
+    type_: String,
+    /// `None` matches any subtype
+    subtype: Option<String>,
+}
+
+impl MediaRange {
+    fn parse(value: &str) -> Option<Self> {
+        let (type_, subtype) = value.trim().split_once('/')?;
+        Some(Self {
+            type_: type_.to_ascii_lowercase(),
+            subtype: (subtype != "*").then(|| subtype.to_ascii_lowercase()),
+        })
+    }
+
+    fn matches(&self, content_type: &str) -> bool {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        let Some((type_, subtype)) = media_type.split_once('/') else {
+            return false;
+        };
+        self.type_.eq_ignore_ascii_case(type_)
+            && self
+                .subtype
+                .as_ref()
+                .is_none_or(|expected| expected.eq_ignore_ascii_case(subtype))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionFilterHandler {
+    algorithms: Vec<CompressionAlgorithm>,
+    content_types: Vec<MediaRange>,
+    min_size_bytes: u64,
+}
+
+impl CompressionFilterHandler {
+    pub fn new(filter: &CompressionFilter) -> Self {
+        let content_types = filter
+            .content_types()
+            .iter()
+            .filter_map(|content_type| {
+                let media_range = MediaRange::parse(content_type);
+                if media_range.is_none() {
+                    warn!(
+                        "Ignoring invalid compressible content type {}",
+                        content_type
+                    );
+                }
+                media_range
+            })
+            .collect();
+
+        Self {
+            algorithms: filter.algorithms().clone(),
+            content_types,
+            min_size_bytes: u64::from(*filter.min_size_bytes()),
+        }
+    }
+
+    /// Prepares the response headers for compression and returns the compressor for its
+    /// body, or `None` when the response is sent as is
+    pub fn compress_response(
+        &self,
+        method: &Method,
+        request: &HeaderMap,
+        response: &mut ResponseHeader,
+    ) -> pingora::Result<Option<ResponseCompressor>> {
+        if !self.is_compressible(response) {
+            return Ok(None);
+        }
+
+        // The representation depends on Accept-Encoding even when this client gets it
+        // uncompressed, so shared caches must not hand it to other clients as is
+        if !varies_on_accept_encoding(&response.headers) {
+            response.append_header(VARY, "Accept-Encoding")?;
+        }
+
+        if method == Method::HEAD
+            || is_too_small(&response.headers, self.min_size_bytes)
+            || is_no_transform(&response.headers)
+        {
+            return Ok(None);
+        }
+        let Some(algorithm) = self.negotiate(request) else {
+            return Ok(None);
+        };
+        let compressor = match ResponseCompressor::new(algorithm) {
+            Ok(compressor) => compressor,
+            Err(err) => {
+                warn!("Failed to create {:?} compressor: {}", algorithm, err);
+                return Ok(None);
+            }
+        };
+
+        response.insert_header(CONTENT_ENCODING, content_coding(algorithm))?;
+        response.remove_header(&CONTENT_LENGTH);
+        response.remove_header(&ACCEPT_RANGES);
+        response.insert_header(TRANSFER_ENCODING, "chunked")?;
+        // The compressed body is no longer byte-for-byte identical to the one the
+        // upstream tagged
+        if let Some(etag) = response.headers.get(ETAG).cloned()
+            && !etag.as_bytes().starts_with(b"W/")
+        {
+            let mut weak_etag = b"W/".to_vec();
+            weak_etag.extend_from_slice(etag.as_bytes());
+            response.insert_header(ETAG, weak_etag)?;
+        }
+
+        Ok(Some(compressor))
+    }
+
+    /// Responses without a body, partial content and bodies the upstream already
+    /// encoded are never compressed
+    fn is_compressible(&self, response: &ResponseHeader) -> bool {
+        let status = response.status;
+        if status.is_informational()
+            || status == StatusCode::NO_CONTENT
+            || status == StatusCode::PARTIAL_CONTENT
+            || status == StatusCode::NOT_MODIFIED
+            || response.headers.contains_key(CONTENT_RANGE)
+        {
+            return false;
+        }
+
+        let is_encoded = response
+            .headers
+            .get(CONTENT_ENCODING)
+            .is_some_and(|encoding| !encoding.as_bytes().eq_ignore_ascii_case(b"identity"));
+        if is_encoded {
+            return false;
+        }
+
+        response
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| {
+                self.content_types
+                    .iter()
+                    .any(|media_range| media_range.matches(content_type))
+            })
+    }
+
+    /// Picks the algorithm the client prefers, falling back to the order of the filter
+    /// when it accepts several equally
+    fn negotiate(&self, request: &HeaderMap) -> Option<CompressionAlgorithm> {
+        let accepted: Vec<(String, f32)> = request
+            .get_all(ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(parse_coding)
+            .collect();
+        let quality = |coding: &str| {
+            accepted
+                .iter()
+                .find(|(accepted, _)| accepted.eq_ignore_ascii_case(coding))
+                .or_else(|| accepted.iter().find(|(accepted, _)| accepted == "*"))
+                .map_or(0.0, |(_, quality)| *quality)
+        };
+
+        let mut selected: Option<(CompressionAlgorithm, f32)> = None;
+        for algorithm in &self.algorithms {
+            let quality = quality(content_coding(*algorithm));
+            if quality > 0.0 && selected.is_none_or(|(_, best)| quality > best) {
+                selected = Some((*algorithm, quality));
+            }
+        }
+        selected.map(|(algorithm, _)| algorithm)
+    }
+}
+
+/// Parses one `coding;q=value` element of `Accept-Encoding`
+fn parse_coding(element: &str) -> Option<(String, f32)> {
+    let mut parts = element.split(';');
+    let coding = parts.next()?.trim();
+    if coding.is_empty() {
+        return None;
+    }
+    let quality = parts
+        .filter_map(|parameter| parameter.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+        .map_or(Some(1.0), |(_, value)| value.trim().parse::<f32>().ok())?;
+    Some((coding.to_ascii_lowercase(), quality))
+}
+
+fn varies_on_accept_encoding(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|name| name == "*" || name.eq_ignore_ascii_case(ACCEPT_ENCODING.as_str()))
+}
+
+fn is_too_small(headers: &HeaderMap, min_size_bytes: u64) -> bool {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok())
+        .and_then(|length| length.parse::<u64>().ok())
+        .is_some_and(|length| length < min_size_bytes)
+}
+
+fn is_no_transform(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| directive.trim().eq_ignore_ascii_case("no-transform"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn filter_handler() -> CompressionFilterHandler {
+        CompressionFilterHandler::new(
+            &CompressionFilter::builder()
+                .key("default/compression")
+                .algorithms(vec![
+                    CompressionAlgorithm::Zstd,
+                    CompressionAlgorithm::Brotli,
+                    CompressionAlgorithm::Gzip,
+                ])
+                .content_types(vec!["text/*".to_string(), "application/json".to_string()])
+                .min_size_bytes(1024)
+                .build(),
+        )
+    }
+
+    fn request(accept_encoding: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT_ENCODING,
+            HeaderValue::from_str(accept_encoding).expect("accept-encoding"),
+        );
+        headers
+    }
+
+    fn response(content_type: &str, content_length: usize) -> ResponseHeader {
+        let mut response = ResponseHeader::build(200, None).expect("response");
+        response
+            .insert_header(CONTENT_TYPE, content_type)
+            .expect("content-type");
+        response
+            .insert_header(CONTENT_LENGTH, content_length.to_string())
+            .expect("content-length");
+        response
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let handler = filter_handler();
+        for (accept_encoding, expected) in [
+            ("gzip, deflate, br, zstd", Some(CompressionAlgorithm::Zstd)),
+            ("gzip, br", Some(CompressionAlgorithm::Brotli)),
+            ("gzip;q=1.0, br;q=0.5", Some(CompressionAlgorithm::Gzip)),
+            ("GZIP", Some(CompressionAlgorithm::Gzip)),
+            ("*", Some(CompressionAlgorithm::Zstd)),
+            (
+                "*;q=0.5, zstd;q=0, br;q=0",
+                Some(CompressionAlgorithm::Gzip),
+            ),
+            ("identity", None),
+            ("gzip;q=0", None),
+            ("deflate", None),
+        ] {
+            assert_eq!(
+                handler.negotiate(&request(accept_encoding)),
+                expected,
+                "{accept_encoding}"
+            );
+        }
+        assert_eq!(handler.negotiate(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_compress_response_headers() {
+        let mut response = response("application/json; charset=utf-8", 4096);
+        response.insert_header(ETAG, "\"abc\"").expect("etag");
+        response
+            .insert_header(ACCEPT_RANGES, "bytes")
+            .expect("accept-ranges");
+
+        let compressor = filter_handler()
+            .compress_response(&Method::GET, &request("gzip"), &mut response)
+            .expect("headers");
+
+        assert_eq!(
+            compressor.map(|compressor| compressor.algorithm()),
+            Some(CompressionAlgorithm::Gzip)
+        );
+        assert_eq!(response.headers[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers[VARY], "Accept-Encoding");
+        assert_eq!(response.headers[ETAG], "W/\"abc\"");
+        assert!(!response.headers.contains_key(CONTENT_LENGTH));
+        assert!(!response.headers.contains_key(ACCEPT_RANGES));
+    }
+
+    #[test]
+    fn test_skips_responses() {
+        let handler = filter_handler();
+        let gzip = request("gzip");
+        let compress = |response: &mut ResponseHeader, request: &HeaderMap| {
+            handler
+                .compress_response(&Method::GET, request, response)
+                .expect("headers")
+        };
+
+        // Too small
+        let mut small = response("text/html", 512);
+        assert!(compress(&mut small, &gzip).is_none());
+        assert_eq!(small.headers[VARY], "Accept-Encoding");
+        assert_eq!(small.headers[CONTENT_LENGTH], "512");
+
+        // Not an allowed content type
+        let mut image = response("image/png", 4096);
+        assert!(compress(&mut image, &gzip).is_none());
+        assert!(!image.headers.contains_key(VARY));
+
+        // Already encoded
+        let mut encoded = response("text/html", 4096);
+        encoded
+            .insert_header(CONTENT_ENCODING, "br")
+            .expect("content-encoding");
+        assert!(compress(&mut encoded, &gzip).is_none());
+        assert_eq!(encoded.headers[CONTENT_ENCODING], "br");
+
+        // The upstream forbids transformations
+        let mut no_transform = response("text/html", 4096);
+        no_transform
+            .insert_header(CACHE_CONTROL, "public, no-transform")
+            .expect("cache-control");
+        assert!(compress(&mut no_transform, &gzip).is_none());
+
+        // Not accepted by the client
+        let mut identity = response("text/html", 4096);
+        assert!(compress(&mut identity, &request("identity")).is_none());
+        assert_eq!(identity.headers[VARY], "Accept-Encoding");
+    }
+
+    #[test]
+    fn test_streamed_responses_are_compressed() {
+        let mut response = ResponseHeader::build(200, None).expect("response");
+        response
+            .insert_header(CONTENT_TYPE, "text/plain")
+            .expect("content-type");
+        response
+            .insert_header(VARY, "Origin, Accept-Encoding")
+            .expect("vary");
+
+        let compressor = filter_handler()
+            .compress_response(&Method::GET, &request("br"), &mut response)
+            .expect("headers");
+
+        assert!(compressor.is_some());
+        assert_eq!(response.headers[CONTENT_ENCODING], "br");
+        assert_eq!(response.headers.get_all(VARY).iter().count(), 1);
+    }
+}
@@ -0,0 +1,7 @@
+mod compressor;
+mod controllers;
+mod handler;
+
+pub use compressor::*;
+pub use controllers::*;
+pub use handler::*;
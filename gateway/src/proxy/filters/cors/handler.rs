@@ -0,0 +1,357 @@
+use crate::proxy::filters::headers::HeaderOperations;
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue, Method};
+use regex::Regex;
+use tracing::warn;
+use vg_core::config::gateway::types::net::{CorsFilter, CorsOrigin};
+
+#[derive(Debug, Clone)]
+enum OriginMatcher {
+    Any,
+    Exact(String),
+    Pattern(Regex),
+}
+
+impl OriginMatcher {
+    fn new(origin: &CorsOrigin) -> Option<Self> {
+        let pattern = match origin {
+            CorsOrigin::Exact(origin) => return Some(Self::Exact(origin.clone())),
+            CorsOrigin::Wildcard(origin) if origin == "*" => return Some(Self::Any),
+            CorsOrigin::Wildcard(origin) => regex::escape(origin).replace(r"\*", "[^/]*"),
+            CorsOrigin::Regex(pattern) => pattern.clone(),
+        };
+
+        match Regex::new(&format!("^(?:{pattern})$")) {
+            Ok(regex) => Some(Self::Pattern(regex)),
+            Err(err) => {
+                warn!("Ignoring invalid CORS origin pattern {}: {}", pattern, err);
+                None
+            }
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Exact(exact) => exact.eq_ignore_ascii_case(origin),
+            Self::Pattern(regex) => regex.is_match(origin),
+        }
+    }
+}
+
+/// Methods or headers allowed in preflight requests
+#[derive(Debug, Clone, PartialEq)]
+enum Allowed {
+    /// Whatever the preflight request asks for, since a literal `*` is not honored by
+    /// browsers for requests with credentials
+    Any,
+    List(Option<HeaderValue>),
+}
+
+impl Allowed {
+    fn new(values: &[String]) -> Self {
+        if values.iter().any(|value| value == "*") {
+            Self::Any
+        } else {
+            Self::List(join(values))
+        }
+    }
+
+    fn value<'a>(&'a self, requested: Option<&'a HeaderValue>) -> Option<&'a HeaderValue> {
+        match self {
+            Self::Any => requested,
+            Self::List(value) => value.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsFilterHandler {
+    origins: Vec<OriginMatcher>,
+    allow_methods: Allowed,
+    allow_headers: Allowed,
+    expose_headers: Option<HeaderValue>,
+    allow_credentials: bool,
+    max_age: Option<HeaderValue>,
+}
+
+impl CorsFilterHandler {
+    pub fn new(filter: &CorsFilter) -> Self {
+        Self {
+            origins: filter
+                .allow_origins()
+                .iter()
+                .filter_map(OriginMatcher::new)
+                .collect(),
+            allow_methods: Allowed::new(filter.allow_methods()),
+            allow_headers: Allowed::new(filter.allow_headers()),
+            expose_headers: join(filter.expose_headers()),
+            allow_credentials: *filter.allow_credentials(),
+            max_age: filter.max_age_seconds().map(HeaderValue::from),
+        }
+    }
+
+    /// Whether a request is a CORS preflight, which the gateway answers itself
+    pub fn is_preflight(method: &Method, headers: &HeaderMap) -> bool {
+        method == Method::OPTIONS
+            && headers.contains_key(ORIGIN)
+            && headers.contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+    }
+
+    /// Adds the headers answering a preflight request. A request from an origin that is
+    /// not allowed gets none, which makes the browser fail it.
+    pub fn apply_to_preflight<H: HeaderOperations>(
+        &self,
+        request: &HeaderMap,
+        response: &mut H,
+    ) -> Result<(), H::Error> {
+        response.append_header(
+            VARY.as_str(),
+            HeaderValue::from_static(
+                "Origin, Access-Control-Request-Method, Access-Control-Request-Headers",
+            ),
+        )?;
+
+        let Some(origin) = self.allowed_origin(request) else {
+            return Ok(());
+        };
+        self.apply_origin(origin, response)?;
+
+        if let Some(methods) = self
+            .allow_methods
+            .value(request.get(ACCESS_CONTROL_REQUEST_METHOD))
+        {
+            response.insert_header(ACCESS_CONTROL_ALLOW_METHODS.as_str(), methods.clone())?;
+        }
+        if let Some(headers) = self
+            .allow_headers
+            .value(request.get(ACCESS_CONTROL_REQUEST_HEADERS))
+        {
+            response.insert_header(ACCESS_CONTROL_ALLOW_HEADERS.as_str(), headers.clone())?;
+        }
+        if let Some(max_age) = &self.max_age {
+            response.insert_header(ACCESS_CONTROL_MAX_AGE.as_str(), max_age.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Adds the headers allowing the origin of an actual request to read the response,
+    /// replacing any the upstream set itself
+    pub fn apply_to_response<H: HeaderOperations>(
+        &self,
+        request: &HeaderMap,
+        response: &mut H,
+    ) -> Result<(), H::Error> {
+        response.append_header(VARY.as_str(), HeaderValue::from_static("Origin"))?;
+
+        let Some(origin) = self.allowed_origin(request) else {
+            return Ok(());
+        };
+        self.apply_origin(origin, response)?;
+
+        if let Some(expose_headers) = &self.expose_headers {
+            response.insert_header(
+                ACCESS_CONTROL_EXPOSE_HEADERS.as_str(),
+                expose_headers.clone(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn allowed_origin<'a>(&self, request: &'a HeaderMap) -> Option<&'a HeaderValue> {
+        let origin = request.get(ORIGIN)?;
+        let value = origin.to_str().ok()?;
+        self.origins
+            .iter()
+            .any(|matcher| matcher.matches(value))
+            .then_some(origin)
+    }
+
+    /// The origin is always echoed rather than answered with `*`, so the same response
+    /// works for requests with and without credentials
+    fn apply_origin<H: HeaderOperations>(
+        &self,
+        origin: &HeaderValue,
+        response: &mut H,
+    ) -> Result<(), H::Error> {
+        response.insert_header(ACCESS_CONTROL_ALLOW_ORIGIN.as_str(), origin.clone())?;
+        if self.allow_credentials {
+            response.insert_header(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS.as_str(),
+                HeaderValue::from_static("true"),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn join(values: &[String]) -> Option<HeaderValue> {
+    if values.is_empty() {
+        return None;
+    }
+    HeaderValue::from_str(&values.join(", "))
+        .inspect_err(|err| warn!("Ignoring invalid CORS header list {:?}: {}", values, err))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_handler(allow_credentials: bool) -> CorsFilterHandler {
+        CorsFilterHandler::new(
+            &CorsFilter::builder()
+                .key("default/cors")
+                .allow_origins(vec![
+                    CorsOrigin::Exact("https://app.example.com".to_string()),
+                    CorsOrigin::Wildcard("https://*.example.org".to_string()),
+                    CorsOrigin::Regex(r"https://preview-\d+\.example\.dev".to_string()),
+                ])
+                .allow_methods(vec!["GET".to_string(), "PUT".to_string()])
+                .allow_headers(vec!["*".to_string()])
+                .expose_headers(vec!["x-request-id".to_string()])
+                .allow_credentials(allow_credentials)
+                .max_age_seconds(Some(600))
+                .build(),
+        )
+    }
+
+    fn request(origin: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ORIGIN, HeaderValue::from_str(origin).expect("origin"));
+        headers
+    }
+
+    fn preflight(origin: &str) -> HeaderMap {
+        let mut headers = request(origin);
+        headers.insert(
+            ACCESS_CONTROL_REQUEST_METHOD,
+            HeaderValue::from_static("PUT"),
+        );
+        headers.insert(
+            ACCESS_CONTROL_REQUEST_HEADERS,
+            HeaderValue::from_static("content-type, x-trace"),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_origin_matching() {
+        let handler = filter_handler(false);
+        for origin in [
+            "https://app.example.com",
+            "https://APP.example.com",
+            "https://admin.example.org",
+            "https://preview-42.example.dev",
+        ] {
+            assert!(
+                handler.allowed_origin(&request(origin)).is_some(),
+                "{origin} should be allowed"
+            );
+        }
+        for origin in [
+            "http://app.example.com",
+            "https://app.example.com.evil.com",
+            "https://example.org",
+            "https://evil.com/.example.org",
+            "https://preview-x.example.dev",
+        ] {
+            assert!(
+                handler.allowed_origin(&request(origin)).is_none(),
+                "{origin} should not be allowed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_any_origin() {
+        let handler = CorsFilterHandler::new(
+            &CorsFilter::builder()
+                .key("default/cors")
+                .allow_origins(vec![CorsOrigin::Wildcard("*".to_string())])
+                .build(),
+        );
+        assert!(handler
+            .allowed_origin(&request("https://anything.example"))
+            .is_some());
+    }
+
+    #[test]
+    fn test_is_preflight() {
+        assert!(CorsFilterHandler::is_preflight(
+            &Method::OPTIONS,
+            &preflight("https://app.example.com")
+        ));
+        assert!(!CorsFilterHandler::is_preflight(
+            &Method::OPTIONS,
+            &request("https://app.example.com")
+        ));
+        assert!(!CorsFilterHandler::is_preflight(
+            &Method::PUT,
+            &preflight("https://app.example.com")
+        ));
+    }
+
+    #[test]
+    fn test_apply_to_preflight() {
+        let mut response = HeaderMap::new();
+        filter_handler(true)
+            .apply_to_preflight(&preflight("https://app.example.com"), &mut response)
+            .expect("headers");
+
+        assert_eq!(
+            response[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        assert_eq!(response[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(response[ACCESS_CONTROL_ALLOW_METHODS], "GET, PUT");
+        assert_eq!(
+            response[ACCESS_CONTROL_ALLOW_HEADERS],
+            "content-type, x-trace"
+        );
+        assert_eq!(response[ACCESS_CONTROL_MAX_AGE], "600");
+        assert!(response.contains_key(VARY));
+    }
+
+    #[test]
+    fn test_preflight_from_disallowed_origin() {
+        let mut response = HeaderMap::new();
+        filter_handler(true)
+            .apply_to_preflight(&preflight("https://evil.com"), &mut response)
+            .expect("headers");
+
+        assert!(!response.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!response.contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+        assert!(response.contains_key(VARY));
+    }
+
+    #[test]
+    fn test_apply_to_response() {
+        let mut response = HeaderMap::new();
+        response.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+        response.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+
+        filter_handler(false)
+            .apply_to_response(&request("https://admin.example.org"), &mut response)
+            .expect("headers");
+
+        assert_eq!(
+            response[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://admin.example.org"
+        );
+        assert_eq!(response[ACCESS_CONTROL_EXPOSE_HEADERS], "x-request-id");
+        assert!(!response.contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+        assert_eq!(
+            response
+                .get_all(VARY)
+                .iter()
+                .map(|value| value.to_str().expect("vary"))
+                .collect::<Vec<_>>(),
+            vec!["Accept-Encoding", "Origin"]
+        );
+    }
+}
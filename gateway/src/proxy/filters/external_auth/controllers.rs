@@ -0,0 +1,52 @@
+use super::ExternalAuthFilterHandler;
+use reqwest_middleware::ClientWithMiddleware;
+use std::collections::HashMap;
+use std::sync::Arc;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::external_auth::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type ExternalAuthFilterHandlers = HashMap<Key, ExternalAuthFilterHandler>;
+
+pub fn external_auth_filters_handlers(
+    task_builder: &TaskBuilder,
+    client: Arc<ClientWithMiddleware>,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<ExternalAuthFilterHandlers> {
+    let (tx, rx) = signal(stringify!(external_auth_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(external_auth_filters_handlers))
+        .spawn(async move {
+            let mut previous = ExternalAuthFilterHandlers::new();
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    // Handlers of unchanged filters are carried over so their caches survive
+                    let handlers: ExternalAuthFilterHandlers = gateway_configuration
+                        .external_auth_filters()
+                        .iter()
+                        .map(|filter| {
+                            let handler = previous
+                                .get(filter.key())
+                                .filter(|handler| handler.filter() == filter)
+                                .cloned()
+                                .unwrap_or_else(|| {
+                                    ExternalAuthFilterHandler::new(filter.clone(), client.clone())
+                                });
+                            (filter.key().clone(), handler)
+                        })
+                        .collect();
+                    previous = handlers.clone();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
@@ -0,0 +1,539 @@
+use dashmap::DashMap;
+use http::header::{CONTENT_LENGTH, HOST};
+use http::request::Parts;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use reqwest_middleware::ClientWithMiddleware;
+use reqwest_tracing::OtelName;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+use vg_core::config::gateway::types::net::{ExternalAuthFailureMode, ExternalAuthFilter};
+
+/// Header the client address is passed to the authorization service in
+pub const EXTERNAL_AUTH_CLIENT_ADDR_HEADER: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// Number of cached decisions above which expired entries are swept
+const MAX_CACHED_DECISIONS: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum ExternalAuthError {
+    #[error("No endpoints available for the authorization service")]
+    NoEndpoints,
+    #[error("Error calling the authorization service: {0}")]
+    Request(#[source] reqwest_middleware::Error),
+    #[error("Authorization service failed with status {0}")]
+    Status(StatusCode),
+}
+
+#[derive(Debug)]
+pub enum ExternalAuthEvaluationResult {
+    /// The authorization service allowed the request. Each upstream header is set to the
+    /// value returned by the service, or removed when the service did not return it, so
+    /// clients cannot supply it themselves.
+    Allowed {
+        upstream_headers: Vec<(HeaderName, Option<HeaderValue>)>,
+    },
+    /// The authorization service denied the request with the given status
+    Denied { status: StatusCode },
+    /// The authorization service could not be consulted and the filter fails closed
+    Unavailable(ExternalAuthError),
+}
+
+#[derive(Debug, Clone)]
+enum ExternalAuthDecision {
+    Allowed(HeaderMap),
+    Denied(StatusCode),
+}
+
+#[derive(Debug, Clone)]
+struct CachedDecision {
+    expires_at: Instant,
+    decision: ExternalAuthDecision,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExternalAuthFilterHandler {
+    filter: ExternalAuthFilter,
+    client: Arc<ClientWithMiddleware>,
+    endpoints: Vec<SocketAddr>,
+    next_endpoint: Arc<AtomicUsize>,
+    request_headers: Vec<HeaderName>,
+    upstream_headers: Vec<HeaderName>,
+    cache_key_header: Option<HeaderName>,
+    cache: Arc<DashMap<HeaderValue, CachedDecision>>,
+}
+
+impl ExternalAuthFilterHandler {
+    pub fn new(filter: ExternalAuthFilter, client: Arc<ClientWithMiddleware>) -> Self {
+        let port = filter.backend().port().map_or(80, u16::from);
+        let endpoints = filter
+            .backend()
+            .endpoints()
+            .iter()
+            .filter_map(|endpoint| endpoint.address().ip())
+            .map(|address| SocketAddr::new(address, port))
+            .collect();
+
+        let request_headers = parse_header_names(filter.request_headers());
+        let upstream_headers = parse_header_names(filter.upstream_headers());
+        let cache_key_header = filter
+            .cache()
+            .as_ref()
+            .and_then(|cache| parse_header_names(std::slice::from_ref(cache.key_header())).pop());
+
+        Self {
+            filter,
+            client,
+            endpoints,
+            next_endpoint: Arc::new(AtomicUsize::new(0)),
+            request_headers,
+            upstream_headers,
+            cache_key_header,
+            cache: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn filter(&self) -> &ExternalAuthFilter {
+        &self.filter
+    }
+
+    #[instrument(name = "ExternalAuthFilterHandler::evaluate", skip_all)]
+    pub async fn evaluate(
+        &self,
+        request: &Parts,
+        client_addr: Option<IpAddr>,
+        now: Instant,
+    ) -> ExternalAuthEvaluationResult {
+        let cache_key = self
+            .cache_key_header
+            .as_ref()
+            .and_then(|header| request.headers.get(header))
+            .cloned();
+
+        if let Some(cache_key) = &cache_key {
+            let cached = self
+                .cache
+                .get(cache_key)
+                .filter(|entry| entry.expires_at > now)
+                .map(|entry| entry.decision.clone());
+            if let Some(decision) = cached {
+                debug!("Using cached authorization decision");
+                return self.result(decision);
+            }
+        }
+
+        match self.check(request, client_addr).await {
+            Ok(decision) => {
+                if let (Some(cache_key), Some(cache)) = (cache_key, self.filter.cache()) {
+                    self.store(
+                        cache_key,
+                        decision.clone(),
+                        now + Duration::from_secs(u64::from(*cache.ttl_seconds())),
+                        now,
+                    );
+                }
+                self.result(decision)
+            }
+            Err(err) => match self.filter.failure_mode() {
+                ExternalAuthFailureMode::Open => {
+                    warn!("Allowing request without authorization: {}", err);
+                    self.result(ExternalAuthDecision::Allowed(HeaderMap::new()))
+                }
+                ExternalAuthFailureMode::Closed => ExternalAuthEvaluationResult::Unavailable(err),
+            },
+        }
+    }
+
+    /// Asks the authorization service about the request, with the method, path and
+    /// selected headers of the original request but no body
+    async fn check(
+        &self,
+        request: &Parts,
+        client_addr: Option<IpAddr>,
+    ) -> Result<ExternalAuthDecision, ExternalAuthError> {
+        let endpoint = self.next_endpoint().ok_or(ExternalAuthError::NoEndpoints)?;
+
+        let path_and_query = request
+            .uri
+            .path_and_query()
+            .map_or("/", |path_and_query| path_and_query.as_str());
+        let path_prefix = self
+            .filter
+            .path_prefix()
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/');
+        let url = format!("http://{endpoint}{path_prefix}{path_and_query}");
+
+        let mut headers = HeaderMap::new();
+        for name in &self.request_headers {
+            // The connection to the authorization service has its own host and length
+            if name == HOST || name == CONTENT_LENGTH {
+                continue;
+            }
+            for value in request.headers.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        if let Some(client_addr) = client_addr {
+            headers.insert(
+                EXTERNAL_AUTH_CLIENT_ADDR_HEADER,
+                HeaderValue::from_str(&client_addr.to_string())
+                    .expect("IP address is a valid header value"),
+            );
+        }
+
+        debug!("Checking authorization with {}", url);
+        let response = self
+            .client
+            .request(request.method.clone(), url)
+            .headers(headers)
+            .timeout(Duration::from_millis(u64::from(
+                *self.filter.timeout_milliseconds(),
+            )))
+            .with_extension(OtelName("external_auth".into()))
+            .send()
+            .await
+            .map_err(ExternalAuthError::Request)?;
+
+        let status = response.status();
+        if status.is_server_error() {
+            Err(ExternalAuthError::Status(status))
+        } else if status.is_success() {
+            Ok(ExternalAuthDecision::Allowed(response.headers().clone()))
+        } else {
+            Ok(ExternalAuthDecision::Denied(status))
+        }
+    }
+
+    fn next_endpoint(&self) -> Option<SocketAddr> {
+        if self.endpoints.is_empty() {
+            return None;
+        }
+        let index = self.next_endpoint.fetch_add(1, Ordering::Relaxed);
+        Some(self.endpoints[index % self.endpoints.len()])
+    }
+
+    fn store(
+        &self,
+        cache_key: HeaderValue,
+        decision: ExternalAuthDecision,
+        expires_at: Instant,
+        now: Instant,
+    ) {
+        if self.cache.len() >= MAX_CACHED_DECISIONS {
+            self.cache.retain(|_, entry| entry.expires_at > now);
+            if self.cache.len() >= MAX_CACHED_DECISIONS {
+                self.cache.clear();
+            }
+        }
+        self.cache.insert(
+            cache_key,
+            CachedDecision {
+                expires_at,
+                decision,
+            },
+        );
+    }
+
+    fn result(&self, decision: ExternalAuthDecision) -> ExternalAuthEvaluationResult {
+        match decision {
+            ExternalAuthDecision::Allowed(headers) => ExternalAuthEvaluationResult::Allowed {
+                upstream_headers: self
+                    .upstream_headers
+                    .iter()
+                    .map(|name| (name.clone(), headers.get(name).cloned()))
+                    .collect(),
+            },
+            ExternalAuthDecision::Denied(status) => ExternalAuthEvaluationResult::Denied { status },
+        }
+    }
+}
+
+fn parse_header_names(names: &[String]) -> Vec<HeaderName> {
+    names
+        .iter()
+        .filter_map(|name| match HeaderName::from_str(name) {
+            Ok(name) => Some(name),
+            Err(err) => {
+                warn!("Ignoring invalid header name {}: {}", name, err);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::AUTHORIZATION;
+    use http::{Method, Request};
+    use reqwest_middleware::ClientBuilder;
+    use vg_core::config::gateway::types::net::{BackendBuilder, ExternalAuthCache};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn filter_handler(
+        addr: Option<SocketAddr>,
+        failure_mode: ExternalAuthFailureMode,
+        cache: Option<ExternalAuthCache>,
+    ) -> ExternalAuthFilterHandler {
+        let mut backend = BackendBuilder::default();
+        backend.named("authz").with_namespace(Some("default"));
+        if let Some(addr) = addr {
+            backend
+                .with_port(Some(addr.port().into()))
+                .add_endpoint(addr.ip(), |_| {});
+        }
+
+        let filter = ExternalAuthFilter::builder()
+            .key("default/authz")
+            .backend(backend.build().expect("backend"))
+            .path_prefix(Some("/check/".to_string()))
+            .request_headers(vec!["authorization".to_string()])
+            .upstream_headers(vec!["x-user-id".to_string()])
+            .failure_mode(failure_mode)
+            .timeout_milliseconds(200)
+            .cache(cache)
+            .build();
+        let client = ClientBuilder::new(reqwest::Client::new()).build();
+        ExternalAuthFilterHandler::new(filter, Arc::new(client))
+    }
+
+    fn request(authorization: Option<&str>) -> Parts {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri("/orders?id=1")
+            .header("x-user-id", "spoofed")
+            .header("cookie", "session=1");
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        request.body(()).expect("request").into_parts().0
+    }
+
+    fn client_addr() -> Option<IpAddr> {
+        Some("203.0.113.7".parse().expect("ip"))
+    }
+
+    #[tokio::test]
+    async fn test_allowed_request_copies_upstream_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/check/orders"))
+            .and(header("authorization", "Bearer alice"))
+            .and(header("x-forwarded-for", "203.0.113.7"))
+            .respond_with(ResponseTemplate::new(200).insert_header("x-user-id", "alice"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let handler = filter_handler(
+            Some(*server.address()),
+            ExternalAuthFailureMode::Closed,
+            None,
+        );
+        let result = handler
+            .evaluate(
+                &request(Some("Bearer alice")),
+                client_addr(),
+                Instant::now(),
+            )
+            .await;
+
+        let ExternalAuthEvaluationResult::Allowed { upstream_headers } = result else {
+            panic!("Expected request to be allowed");
+        };
+        assert_eq!(
+            upstream_headers,
+            vec![(
+                HeaderName::from_static("x-user-id"),
+                Some(HeaderValue::from_static("alice"))
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_upstream_header_is_removed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let handler = filter_handler(
+            Some(*server.address()),
+            ExternalAuthFailureMode::Closed,
+            None,
+        );
+        let result = handler
+            .evaluate(
+                &request(Some("Bearer alice")),
+                client_addr(),
+                Instant::now(),
+            )
+            .await;
+
+        let ExternalAuthEvaluationResult::Allowed { upstream_headers } = result else {
+            panic!("Expected request to be allowed");
+        };
+        assert_eq!(
+            upstream_headers,
+            vec![(HeaderName::from_static("x-user-id"), None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_denied_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let handler = filter_handler(Some(*server.address()), ExternalAuthFailureMode::Open, None);
+        let result = handler
+            .evaluate(
+                &request(Some("Bearer mallory")),
+                client_addr(),
+                Instant::now(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            ExternalAuthEvaluationResult::Denied {
+                status: StatusCode::FORBIDDEN
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failure_modes() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let closed = filter_handler(
+            Some(*server.address()),
+            ExternalAuthFailureMode::Closed,
+            None,
+        );
+        let result = closed
+            .evaluate(&request(None), client_addr(), Instant::now())
+            .await;
+        assert!(matches!(
+            result,
+            ExternalAuthEvaluationResult::Unavailable(ExternalAuthError::Status(
+                StatusCode::SERVICE_UNAVAILABLE
+            ))
+        ));
+
+        let open = filter_handler(Some(*server.address()), ExternalAuthFailureMode::Open, None);
+        let result = open
+            .evaluate(&request(None), client_addr(), Instant::now())
+            .await;
+        let ExternalAuthEvaluationResult::Allowed { upstream_headers } = result else {
+            panic!("Expected request to be allowed");
+        };
+        assert_eq!(
+            upstream_headers,
+            vec![(HeaderName::from_static("x-user-id"), None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_missing_endpoints_are_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+
+        let handler = filter_handler(
+            Some(*server.address()),
+            ExternalAuthFailureMode::Closed,
+            None,
+        );
+        let result = handler
+            .evaluate(&request(None), client_addr(), Instant::now())
+            .await;
+        assert!(matches!(
+            result,
+            ExternalAuthEvaluationResult::Unavailable(ExternalAuthError::Request(_))
+        ));
+
+        let handler = filter_handler(None, ExternalAuthFailureMode::Closed, None);
+        let result = handler
+            .evaluate(&request(None), client_addr(), Instant::now())
+            .await;
+        assert!(matches!(
+            result,
+            ExternalAuthEvaluationResult::Unavailable(ExternalAuthError::NoEndpoints)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_decisions_are_cached_by_key_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("authorization", "Bearer alice"))
+            .respond_with(ResponseTemplate::new(200).insert_header("x-user-id", "alice"))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header("authorization", "Bearer mallory"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = ExternalAuthCache::builder()
+            .key_header("authorization")
+            .ttl_seconds(60)
+            .build();
+        let handler = filter_handler(
+            Some(*server.address()),
+            ExternalAuthFailureMode::Closed,
+            Some(cache),
+        );
+
+        let now = Instant::now();
+        for _ in 0..3 {
+            let result = handler
+                .evaluate(&request(Some("Bearer alice")), client_addr(), now)
+                .await;
+            assert!(matches!(
+                result,
+                ExternalAuthEvaluationResult::Allowed { .. }
+            ));
+
+            let result = handler
+                .evaluate(&request(Some("Bearer mallory")), client_addr(), now)
+                .await;
+            assert!(matches!(
+                result,
+                ExternalAuthEvaluationResult::Denied {
+                    status: StatusCode::UNAUTHORIZED
+                }
+            ));
+        }
+
+        // Expired decisions are checked again
+        let later = now + Duration::from_secs(61);
+        let result = handler
+            .evaluate(&request(Some("Bearer alice")), client_addr(), later)
+            .await;
+        assert!(matches!(
+            result,
+            ExternalAuthEvaluationResult::Allowed { .. }
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+mod controllers;
+mod handler;
+
+pub use controllers::*;
+pub use handler::*;
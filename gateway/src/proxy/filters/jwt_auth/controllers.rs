@@ -0,0 +1,42 @@
+use super::JwtAuthFilterHandler;
+use std::collections::HashMap;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::jwt_auth::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type JwtAuthFilterHandlers = HashMap<Key, JwtAuthFilterHandler>;
+
+pub fn jwt_auth_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<JwtAuthFilterHandlers> {
+    let (tx, rx) = signal(stringify!(jwt_auth_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(jwt_auth_filters_handlers))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    let handlers: JwtAuthFilterHandlers = gateway_configuration
+                        .jwt_auth_filters()
+                        .iter()
+                        .map(|filter| {
+                            (
+                                filter.key().clone(),
+                                JwtAuthFilterHandler::new(filter.clone()),
+                            )
+                        })
+                        .collect();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
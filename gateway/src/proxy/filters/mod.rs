@@ -1,7 +1,15 @@
 pub mod access_control;
+pub mod basic_auth;
+pub mod cache;
 pub mod client_addrs;
+pub mod compression;
+pub mod cors;
+pub mod external_auth;
 pub mod headers;
+pub mod jwt_auth;
+pub mod rate_limit;
 pub mod request_headers;
+pub mod request_limits;
 pub mod request_redirect;
 pub mod response_headers;
 pub mod static_responses;
@@ -0,0 +1,47 @@
+use super::RateLimitFilterHandler;
+use std::collections::HashMap;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::rate_limit::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type RateLimitFilterHandlers = HashMap<Key, RateLimitFilterHandler>;
+
+pub fn rate_limit_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<RateLimitFilterHandlers> {
+    let (tx, rx) = signal(stringify!(rate_limit_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(rate_limit_filters_handlers))
+        .spawn(async move {
+            let mut previous = RateLimitFilterHandlers::new();
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    // Handlers of unchanged filters are carried over so their buckets survive
+                    let handlers: RateLimitFilterHandlers = gateway_configuration
+                        .rate_limit_filters()
+                        .iter()
+                        .map(|filter| {
+                            let handler = previous
+                                .get(filter.key())
+                                .filter(|handler| handler.filter() == filter)
+                                .cloned()
+                                .unwrap_or_else(|| RateLimitFilterHandler::new(filter.clone()));
+                            (filter.key().clone(), handler)
+                        })
+                        .collect();
+                    previous = handlers.clone();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
@@ -0,0 +1,326 @@
+use super::{RateLimitFilterHandler, RateLimitFilterHandlers};
+use crate::controllers::config::ipc::IpcEndpoint;
+use async_trait::async_trait;
+use http::header::CONTENT_TYPE;
+use http::StatusCode;
+use reqwest_middleware::ClientWithMiddleware;
+use reqwest_tracing::{OtelName, OtelPathNames};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, warn};
+use typed_builder::TypedBuilder;
+use vg_core::ipc::rate_limits::{RateLimitSyncRequest, RateLimitSyncResponse};
+use vg_core::sync::signal::Receiver;
+use vg_core::task::Builder as TaskBuilder;
+
+/// How often the consumption of global rate limits is reported to the control plane
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the gateway keeps enforcing its share of global limits without a
+/// successful sync, before it falls back to enforcing the full limits locally
+const SYNC_STALENESS: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Error)]
+pub enum RateLimitSyncError {
+    #[error("IPC endpoint is not known yet")]
+    MissingEndpoint,
+    #[error("Error serializing rate limit usages: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Error syncing rate limits: {0}")]
+    Request(#[source] reqwest_middleware::Error),
+    #[error("Unexpected status syncing rate limits: {0}")]
+    Status(StatusCode),
+    #[error("Error reading rate limit sync response: {0}")]
+    Body(#[source] reqwest::Error),
+    #[error("Error parsing rate limit sync response: {0}")]
+    Parse(#[source] serde_json::Error),
+}
+
+/// Exchanges this pod's consumption of global rate limits for the cluster-wide state
+#[async_trait]
+pub trait RateLimitSyncClient: Send + Sync {
+    async fn sync(
+        &self,
+        request: &RateLimitSyncRequest,
+    ) -> Result<RateLimitSyncResponse, RateLimitSyncError>;
+}
+
+#[derive(Debug, TypedBuilder)]
+pub struct IpcRateLimitSyncClient {
+    client: Arc<ClientWithMiddleware>,
+    ipc_endpoint_rx: Receiver<IpcEndpoint>,
+    #[builder(setter(into))]
+    pod_name: String,
+    #[builder(setter(into))]
+    gateway_namespace: String,
+    #[builder(setter(into))]
+    gateway_name: String,
+}
+
+#[async_trait]
+impl RateLimitSyncClient for IpcRateLimitSyncClient {
+    async fn sync(
+        &self,
+        request: &RateLimitSyncRequest,
+    ) -> Result<RateLimitSyncResponse, RateLimitSyncError> {
+        let ipc_endpoint =
+            (*self.ipc_endpoint_rx.get().await).ok_or(RateLimitSyncError::MissingEndpoint)?;
+
+        let url = {
+            let mut url = ipc_endpoint.url();
+            url.set_path(&format!(
+                "/ipc/namespaces/{}/gateways/{}/rate_limits",
+                self.gateway_namespace, self.gateway_name
+            ));
+            url.set_query(Some(&format!("pod_name={}", self.pod_name)));
+            url
+        };
+
+        let body = serde_json::to_vec(request).map_err(RateLimitSyncError::Serialize)?;
+
+        let response = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .with_extension(OtelName("sync_rate_limits".into()))
+            .with_extension(
+                OtelPathNames::known_paths([
+                    "/ipc/namespaces/{namespace}/gateways/{gateway_name}/rate_limits",
+                ])
+                .expect("Failed to set known paths"),
+            )
+            .send()
+            .await
+            .map_err(RateLimitSyncError::Request)?;
+
+        if response.status() != StatusCode::OK {
+            return Err(RateLimitSyncError::Status(response.status()));
+        }
+
+        let bytes = response.bytes().await.map_err(RateLimitSyncError::Body)?;
+        serde_json::from_slice(&bytes).map_err(RateLimitSyncError::Parse)
+    }
+}
+
+/// Reports the consumption of every global rate limit handler and applies the
+/// cluster-wide state in return. Consumption that could not be reported is kept
+/// for the next attempt.
+pub async fn sync_global_rate_limits_once<C: RateLimitSyncClient + ?Sized>(
+    handlers: &[RateLimitFilterHandler],
+    client: &C,
+) -> Result<(), RateLimitSyncError> {
+    let usages: Vec<_> = handlers
+        .iter()
+        .map(|handler| (handler, handler.take_usages()))
+        .collect();
+
+    let request = RateLimitSyncRequest::builder()
+        .usages(
+            usages
+                .iter()
+                .flat_map(|(_, usages)| usages.iter().cloned())
+                .collect::<Vec<_>>(),
+        )
+        .build();
+
+    match client.sync(&request).await {
+        Ok(response) => {
+            let now = Instant::now();
+            for handler in handlers {
+                let states = response
+                    .buckets()
+                    .iter()
+                    .filter(|state| state.filter() == handler.filter().key());
+                handler.apply_sync(response.replicas(), states, now);
+            }
+            Ok(())
+        }
+        Err(err) => {
+            for (handler, usages) in &usages {
+                handler.restore_usages(usages);
+            }
+            Err(err)
+        }
+    }
+}
+
+pub fn sync_global_rate_limits(
+    task_builder: &TaskBuilder,
+    client: impl RateLimitSyncClient + 'static,
+    rate_limit_filters_handlers_rx: &Receiver<RateLimitFilterHandlers>,
+) {
+    let rate_limit_filters_handlers_rx = rate_limit_filters_handlers_rx.clone();
+
+    task_builder
+        .new_task(stringify!(sync_global_rate_limits))
+        .spawn(async move {
+            let mut last_synced_at = Instant::now();
+            loop {
+                tokio::time::sleep(SYNC_INTERVAL).await;
+
+                let handlers: Vec<_> = rate_limit_filters_handlers_rx
+                    .get()
+                    .await
+                    .iter()
+                    .flat_map(|handlers| handlers.values())
+                    .filter(|handler| handler.is_global())
+                    .cloned()
+                    .collect();
+                if handlers.is_empty() {
+                    last_synced_at = Instant::now();
+                    continue;
+                }
+
+                match sync_global_rate_limits_once(&handlers, &client).await {
+                    Ok(()) => {
+                        debug!("Synced {} global rate limit filters", handlers.len());
+                        last_synced_at = Instant::now();
+                    }
+                    Err(err) => {
+                        warn!("Error syncing global rate limits: {}", err);
+                        if last_synced_at.elapsed() >= SYNC_STALENESS {
+                            warn!("Falling back to local rate limits");
+                            for handler in &handlers {
+                                handler.degrade();
+                            }
+                        }
+                    }
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::filters::rate_limit::RateLimitEvaluationResult;
+    use vg_core::config::gateway::types::net::{
+        RateLimitFilter, RateLimitFilterKey, RateLimitMode,
+    };
+    use vg_core::ipc::rate_limits::GlobalRateLimits;
+    use vg_core::ipc::Ref;
+
+    /// Calls the control plane's aggregation directly, as if over IPC
+    struct InProcessClient {
+        global: Arc<GlobalRateLimits>,
+        pod_name: String,
+    }
+
+    #[async_trait]
+    impl RateLimitSyncClient for InProcessClient {
+        async fn sync(
+            &self,
+            request: &RateLimitSyncRequest,
+        ) -> Result<RateLimitSyncResponse, RateLimitSyncError> {
+            let gateway_ref = Ref::builder().namespace("default").name("gateway").build();
+            Ok(self
+                .global
+                .sync(&gateway_ref, &self.pod_name, request, Instant::now()))
+        }
+    }
+
+    struct FailingClient;
+
+    #[async_trait]
+    impl RateLimitSyncClient for FailingClient {
+        async fn sync(
+            &self,
+            _request: &RateLimitSyncRequest,
+        ) -> Result<RateLimitSyncResponse, RateLimitSyncError> {
+            Err(RateLimitSyncError::MissingEndpoint)
+        }
+    }
+
+    fn global_handler(requests: u32) -> RateLimitFilterHandler {
+        RateLimitFilterHandler::new(
+            RateLimitFilter::builder()
+                .key("default/limit")
+                .limit_key(RateLimitFilterKey::Route)
+                .requests(requests)
+                .period_seconds(3600)
+                .mode(RateLimitMode::Global)
+                .build(),
+        )
+    }
+
+    fn allowed(handler: &RateLimitFilterHandler, key: &str, attempts: u32) -> u32 {
+        let now = Instant::now();
+        (0..attempts)
+            .filter(|_| {
+                matches!(
+                    handler.evaluate(Some(key), now),
+                    RateLimitEvaluationResult::Allowed { .. }
+                )
+            })
+            .fold(0, |count, _| count + 1)
+    }
+
+    async fn sync_all(gateways: &[(RateLimitFilterHandler, InProcessClient)]) {
+        for (handler, client) in gateways {
+            sync_global_rate_limits_once(std::slice::from_ref(handler), client)
+                .await
+                .expect("sync");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gateways_share_global_limit() {
+        let global = Arc::new(GlobalRateLimits::default());
+        let gateways: Vec<_> = ["pod-a", "pod-b", "pod-c"]
+            .into_iter()
+            .map(|pod_name| {
+                let client = InProcessClient {
+                    global: global.clone(),
+                    pod_name: pod_name.to_string(),
+                };
+                (global_handler(30), client)
+            })
+            .collect();
+
+        // Every gateway registers as a replica and picks up its share of the bucket
+        let mut total = 0;
+        for (handler, _) in &gateways {
+            total += allowed(handler, "route", 1);
+        }
+        sync_all(&gateways).await;
+        sync_all(&gateways).await;
+
+        for _ in 0..3 {
+            for (handler, _) in &gateways {
+                total += allowed(handler, "route", 30);
+            }
+            // A second pass lets each gateway see the consumption of the others
+            sync_all(&gateways).await;
+            sync_all(&gateways).await;
+        }
+
+        assert_eq!(total, 30);
+    }
+
+    #[tokio::test]
+    async fn test_failed_sync_keeps_usage_and_degrade_restores_full_limit() {
+        let handler = global_handler(10);
+        let global = Arc::new(GlobalRateLimits::default());
+        for pod_name in ["pod-a", "pod-b"] {
+            let client = InProcessClient {
+                global: global.clone(),
+                pod_name: pod_name.to_string(),
+            };
+            sync_global_rate_limits_once(std::slice::from_ref(&handler), &client)
+                .await
+                .expect("sync");
+        }
+
+        assert_eq!(allowed(&handler, "route", 10), 5);
+
+        let result =
+            sync_global_rate_limits_once(std::slice::from_ref(&handler), &FailingClient).await;
+        assert!(result.is_err());
+        assert_eq!(handler.take_usages()[0].consumed(), 5);
+
+        handler.degrade();
+        assert_eq!(allowed(&handler, "other", 20), 10);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::proxy::router::HttpRouteRuleUniqueId;
+use dashmap::DashMap;
+use http::HeaderMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::instrument;
+use vg_core::config::gateway::types::net::{RateLimitFilter, RateLimitFilterKey, RateLimitMode};
+use vg_core::ipc::rate_limits::{RateLimitBucketLimit, RateLimitBucketState, RateLimitUsage};
+
+/// Number of buckets a handler keeps before it starts pruning idle ones
+const PRUNE_THRESHOLD: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitEvaluationResult {
+    /// The request had no value for the configured key, so it is not limited
+    Unkeyed,
+    Allowed {
+        limit: u32,
+        remaining: u32,
+        reset: Duration,
+    },
+    Limited {
+        limit: u32,
+        retry_after: Duration,
+        reset: Duration,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// Tokens taken since the consumption was last reported to the control plane
+    consumed: u32,
+}
+
+/// Enforces a token bucket per key. Clones share their buckets, so a handler can be
+/// carried over across configuration updates without resetting its limits.
+///
+/// In global mode the bucket's capacity and refill rate are divided by the number of
+/// replicas last reported by the control plane, and the tokens are periodically
+/// replaced by this pod's share of the cluster-wide tokens left.
+#[derive(Debug, Clone)]
+pub struct RateLimitFilterHandler {
+    filter: RateLimitFilter,
+    buckets: Arc<DashMap<String, TokenBucket>>,
+    replicas: Arc<AtomicU32>,
+    last_pruned_at: Arc<Mutex<Option<Instant>>>,
+}
+
+impl PartialEq for RateLimitFilterHandler {
+    fn eq(&self, other: &Self) -> bool {
+        self.filter == other.filter && Arc::ptr_eq(&self.buckets, &other.buckets)
+    }
+}
+
+impl RateLimitFilterHandler {
+    pub fn new(filter: RateLimitFilter) -> Self {
+        Self {
+            filter,
+            buckets: Arc::default(),
+            replicas: Arc::new(AtomicU32::new(1)),
+            last_pruned_at: Arc::default(),
+        }
+    }
+
+    pub fn is_global(&self) -> bool {
+        *self.filter.mode() == RateLimitMode::Global
+    }
+
+    /// The cluster-wide bucket parameters of the filter
+    pub fn limit(&self) -> RateLimitBucketLimit {
+        let requests = f64::from(*self.filter.requests());
+        let period = *self.filter.period_seconds();
+        RateLimitBucketLimit::builder()
+            .capacity(f64::from(
+                self.filter.burst().unwrap_or(*self.filter.requests()),
+            ))
+            .refill_per_second(if period == 0 {
+                0.0
+            } else {
+                requests / f64::from(period)
+            })
+            .build()
+    }
+
+    /// Takes the consumption of every bucket that has been used since the last sync,
+    /// so it can be reported to the control plane
+    pub fn take_usages(&self) -> Vec<RateLimitUsage> {
+        let limit = self.limit();
+        let capacity = self.capacity();
+        self.buckets
+            .iter_mut()
+            .filter(|bucket| bucket.consumed > 0 || bucket.tokens < capacity)
+            .map(|mut bucket| {
+                let consumed = std::mem::take(&mut bucket.consumed);
+                RateLimitUsage::builder()
+                    .filter(self.filter.key().clone())
+                    .key(bucket.key().clone())
+                    .limit(limit)
+                    .consumed(consumed)
+                    .build()
+            })
+            .collect()
+    }
+
+    /// Puts back consumption that could not be reported, so the next sync includes it
+    pub fn restore_usages(&self, usages: &[RateLimitUsage]) {
+        for usage in usages {
+            if let Some(mut bucket) = self.buckets.get_mut(usage.key()) {
+                bucket.consumed = bucket.consumed.saturating_add(usage.consumed());
+            }
+        }
+    }
+
+    /// Replaces the local tokens with this pod's share of the cluster-wide tokens,
+    /// less anything consumed while the sync was in flight
+    pub fn apply_sync<'a>(
+        &self,
+        replicas: u32,
+        states: impl IntoIterator<Item = &'a RateLimitBucketState>,
+        now: Instant,
+    ) {
+        let replicas = replicas.max(1);
+        self.replicas.store(replicas, Ordering::Release);
+
+        let capacity = self.capacity();
+        for state in states {
+            if let Some(mut bucket) = self.buckets.get_mut(state.key()) {
+                let share = state.tokens() / f64::from(replicas);
+                bucket.tokens = (share - f64::from(bucket.consumed)).clamp(0.0, capacity);
+                bucket.updated_at = now;
+            }
+        }
+    }
+
+    /// Falls back to enforcing the full limit on this pod alone
+    pub fn degrade(&self) {
+        self.replicas.store(1, Ordering::Release);
+    }
+
+    pub fn filter(&self) -> &RateLimitFilter {
+        &self.filter
+    }
+
+    /// Resolves the bucket key of a request according to the filter's key source
+    pub fn bucket_key(
+        &self,
+        client_addr: Option<IpAddr>,
+        headers: &HeaderMap,
+        rule_id: &HttpRouteRuleUniqueId,
+    ) -> Option<String> {
+        match self.filter.limit_key() {
+            RateLimitFilterKey::ClientAddr => client_addr.map(|addr| addr.to_string()),
+            RateLimitFilterKey::Header(name) => headers
+                .get(name.as_str())
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string),
+            RateLimitFilterKey::Route => Some(rule_id.as_ref().to_string()),
+        }
+    }
+
+    #[instrument(name = "RateLimitFilterHandler::evaluate", skip(self, key, now))]
+    pub fn evaluate(&self, key: Option<&str>, now: Instant) -> RateLimitEvaluationResult {
+        let Some(key) = key else {
+            return RateLimitEvaluationResult::Unkeyed;
+        };
+
+        self.prune_idle(now);
+
+        let capacity = self.capacity();
+        let rate = self.refill_rate();
+        let limit = *self.filter.requests();
+
+        let mut bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                updated_at: now,
+                consumed: 0,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            if self.is_global() {
+                bucket.consumed = bucket.consumed.saturating_add(1);
+            }
+            RateLimitEvaluationResult::Allowed {
+                limit,
+                remaining: bucket.tokens.floor() as u32,
+                reset: self.time_to_refill(capacity - bucket.tokens),
+            }
+        } else {
+            RateLimitEvaluationResult::Limited {
+                limit,
+                retry_after: self.time_to_refill(1.0 - bucket.tokens),
+                reset: self.time_to_refill(capacity - bucket.tokens),
+            }
+        }
+    }
+
+    fn replicas(&self) -> f64 {
+        f64::from(self.replicas.load(Ordering::Acquire))
+    }
+
+    fn capacity(&self) -> f64 {
+        self.limit().capacity() / self.replicas()
+    }
+
+    fn refill_rate(&self) -> f64 {
+        self.limit().refill_per_second() / self.replicas()
+    }
+
+    fn time_to_refill(&self, tokens: f64) -> Duration {
+        let rate = self.refill_rate();
+        if rate > 0.0 {
+            Duration::from_secs_f64(tokens.max(0.0) / rate)
+        } else {
+            Duration::from_secs(u64::from(*self.filter.period_seconds()))
+        }
+    }
+
+    /// Drops the buckets that have refilled completely, as they are indistinguishable
+    /// from new ones. Runs at most once per period, and only once there are many keys.
+    fn prune_idle(&self, now: Instant) {
+        if self.buckets.len() < PRUNE_THRESHOLD {
+            return;
+        }
+
+        let period = Duration::from_secs(u64::from(*self.filter.period_seconds()));
+        {
+            let Ok(mut last_pruned_at) = self.last_pruned_at.lock() else {
+                return;
+            };
+            if last_pruned_at.is_some_and(|at| now.saturating_duration_since(at) < period) {
+                return;
+            }
+            *last_pruned_at = Some(now);
+        }
+
+        let capacity = self.capacity();
+        let rate = self.refill_rate();
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.updated_at);
+            bucket.consumed > 0 || bucket.tokens + elapsed.as_secs_f64() * rate < capacity
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn handler(limit_key: RateLimitFilterKey, requests: u32) -> RateLimitFilterHandler {
+        let filter = RateLimitFilter::builder()
+            .key("test")
+            .limit_key(limit_key)
+            .requests(requests)
+            .period_seconds(10)
+            .build();
+        RateLimitFilterHandler::new(filter)
+    }
+
+    #[test]
+    fn test_limits_after_capacity_is_used() {
+        let handler = handler(RateLimitFilterKey::Route, 2);
+        let now = Instant::now();
+
+        assert_eq!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Allowed {
+                limit: 2,
+                remaining: 1,
+                reset: Duration::from_secs(5),
+            }
+        );
+        assert!(matches!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Allowed { remaining: 0, .. }
+        ));
+        assert_eq!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Limited {
+                limit: 2,
+                retry_after: Duration::from_secs(5),
+                reset: Duration::from_secs(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let handler = handler(RateLimitFilterKey::Route, 1);
+        let now = Instant::now();
+
+        assert!(matches!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Allowed { .. }
+        ));
+        assert!(matches!(
+            handler.evaluate(Some("a"), now + Duration::from_secs(5)),
+            RateLimitEvaluationResult::Limited { .. }
+        ));
+        assert!(matches!(
+            handler.evaluate(Some("a"), now + Duration::from_secs(10)),
+            RateLimitEvaluationResult::Allowed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_burst_sets_capacity() {
+        let filter = RateLimitFilter::builder()
+            .key("test")
+            .limit_key(RateLimitFilterKey::Route)
+            .requests(1)
+            .period_seconds(10)
+            .burst(3)
+            .build();
+        let handler = RateLimitFilterHandler::new(filter);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                handler.evaluate(Some("a"), now),
+                RateLimitEvaluationResult::Allowed { .. }
+            ));
+        }
+        assert!(matches!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn test_keys_have_separate_buckets() {
+        let handler = handler(RateLimitFilterKey::ClientAddr, 1);
+        let now = Instant::now();
+
+        assert!(matches!(
+            handler.evaluate(Some("10.0.0.1"), now),
+            RateLimitEvaluationResult::Allowed { .. }
+        ));
+        assert!(matches!(
+            handler.evaluate(Some("10.0.0.2"), now),
+            RateLimitEvaluationResult::Allowed { .. }
+        ));
+        assert!(matches!(
+            handler.evaluate(Some("10.0.0.1"), now),
+            RateLimitEvaluationResult::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn test_unkeyed_requests_are_not_limited() {
+        let handler = handler(RateLimitFilterKey::Route, 1);
+        let now = Instant::now();
+
+        assert_eq!(
+            handler.evaluate(None, now),
+            RateLimitEvaluationResult::Unkeyed
+        );
+    }
+
+    #[test]
+    fn test_clones_share_buckets() {
+        let handler = handler(RateLimitFilterKey::Route, 1);
+        let clone = handler.clone();
+        let now = Instant::now();
+
+        assert!(matches!(
+            handler.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Allowed { .. }
+        ));
+        assert!(matches!(
+            clone.evaluate(Some("a"), now),
+            RateLimitEvaluationResult::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn test_bucket_key() {
+        let rule_id = HttpRouteRuleUniqueId::new("rule-1");
+        let client_addr: Option<IpAddr> = Some("10.0.0.1".parse().unwrap());
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+
+        let by_client = handler(RateLimitFilterKey::ClientAddr, 1);
+        assert_eq!(
+            by_client.bucket_key(client_addr, &headers, &rule_id),
+            Some("10.0.0.1".to_string())
+        );
+        assert_eq!(by_client.bucket_key(None, &headers, &rule_id), None);
+
+        let by_header = handler(RateLimitFilterKey::Header("x-api-key".to_string()), 1);
+        assert_eq!(
+            by_header.bucket_key(client_addr, &headers, &rule_id),
+            Some("secret".to_string())
+        );
+        assert_eq!(
+            by_header.bucket_key(client_addr, &HeaderMap::new(), &rule_id),
+            None
+        );
+
+        let by_route = handler(RateLimitFilterKey::Route, 1);
+        assert_eq!(
+            by_route.bucket_key(None, &headers, &rule_id),
+            Some("rule-1".to_string())
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod controllers;
+mod global;
+mod handler;
+
+pub use controllers::*;
+pub use global::*;
+pub use handler::*;
+
+use http::HeaderName;
+
+pub const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
@@ -0,0 +1,42 @@
+use super::RequestLimitsFilterHandler;
+use std::collections::HashMap;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::sync::signal::{signal, Receiver};
+use vg_core::task::Builder as TaskBuilder;
+use vg_core::types::filters::request_limits::Key;
+use vg_core::{await_ready, continue_on, ReadyState};
+
+pub type RequestLimitsFilterHandlers = HashMap<Key, RequestLimitsFilterHandler>;
+
+pub fn request_limits_filters_handlers(
+    task_builder: &TaskBuilder,
+    gateway_configuration_rx: &Receiver<GatewayConfiguration>,
+) -> Receiver<RequestLimitsFilterHandlers> {
+    let (tx, rx) = signal(stringify!(request_limits_filters_handlers));
+    let gateway_configuration_rx = gateway_configuration_rx.clone();
+
+    task_builder
+        .new_task(stringify!(request_limits_filters_handlers))
+        .spawn(async move {
+            loop {
+                if let ReadyState::Ready(gateway_configuration) =
+                    await_ready!(gateway_configuration_rx)
+                {
+                    let handlers: RequestLimitsFilterHandlers = gateway_configuration
+                        .request_limits_filters()
+                        .iter()
+                        .map(|filter| {
+                            (
+                                filter.key().clone(),
+                                RequestLimitsFilterHandler::new(filter),
+                            )
+                        })
+                        .collect();
+                    tx.set(handlers).await;
+                }
+                continue_on!(gateway_configuration_rx.changed());
+            }
+        });
+
+    rx
+}
@@ -0,0 +1,251 @@
+use crate::proxy::responses::error_responses::ErrorResponseCode;
+use bytes::{Bytes, BytesMut};
+use http::header::CONTENT_LENGTH;
+use pingora::http::RequestHeader;
+use vg_core::config::gateway::types::net::{RequestBodyMode, RequestLimitsFilter};
+
+/// Bytes taken by the `: ` separator and the line break of each header field
+const HEADER_FIELD_OVERHEAD: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLimitsFilterHandler {
+    max_request_body_bytes: Option<u64>,
+    max_request_headers_bytes: Option<u32>,
+    body_mode: RequestBodyMode,
+}
+
+impl RequestLimitsFilterHandler {
+    pub fn new(filter: &RequestLimitsFilter) -> Self {
+        Self {
+            max_request_body_bytes: *filter.max_request_body_bytes(),
+            max_request_headers_bytes: *filter.max_request_headers_bytes(),
+            body_mode: *filter.body_mode(),
+        }
+    }
+
+    pub fn max_request_body_bytes(&self) -> Option<u64> {
+        self.max_request_body_bytes
+    }
+
+    pub fn max_request_headers_bytes(&self) -> Option<u32> {
+        self.max_request_headers_bytes
+    }
+
+    /// Rejects requests whose header fields are too large, or whose declared
+    /// `Content-Length` is over the body limit before any of the body is read
+    pub fn check_request_header(&self, request: &RequestHeader) -> Result<(), ErrorResponseCode> {
+        if let Some(max) = self.max_request_headers_bytes {
+            let size: usize = request
+                .headers
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len() + HEADER_FIELD_OVERHEAD)
+                .sum();
+            if size > max as usize {
+                return Err(ErrorResponseCode::RequestHeadersTooLarge);
+            }
+        }
+
+        if let Some(max) = self.max_request_body_bytes
+            && let Some(content_length) = request
+                .headers
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+            && content_length > max
+        {
+            return Err(ErrorResponseCode::PayloadTooLarge);
+        }
+
+        Ok(())
+    }
+
+    /// The limiter enforcing the body limit while the request body is received
+    pub fn body_limiter(&self) -> RequestBodyLimiter {
+        RequestBodyLimiter {
+            max_body_bytes: self.max_request_body_bytes,
+            buffer: (self.body_mode == RequestBodyMode::Buffered).then(BytesMut::new),
+            received_bytes: 0,
+            buffered_body: None,
+        }
+    }
+}
+
+/// Counts the request body against its limit as it is received, as chunked requests
+/// do not declare their size upfront, and holds it back when it is buffered
+#[derive(Debug)]
+pub struct RequestBodyLimiter {
+    max_body_bytes: Option<u64>,
+    buffer: Option<BytesMut>,
+    received_bytes: u64,
+    buffered_body: Option<Bytes>,
+}
+
+impl RequestBodyLimiter {
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes
+    }
+
+    /// Whether the body is buffered in full before it is forwarded
+    pub fn is_buffered(&self) -> bool {
+        self.buffer.is_some()
+    }
+
+    /// Whether the body can be sent to another upstream, as none of it was received yet or
+    /// it was buffered in full
+    pub fn is_replayable(&self) -> bool {
+        self.is_buffered() && (self.received_bytes == 0 || self.buffered_body.is_some())
+    }
+
+    /// The whole request body once it has been buffered
+    pub fn buffered_body(&self) -> Option<&Bytes> {
+        self.buffered_body.as_ref()
+    }
+
+    /// Counts the next chunk of the body. When buffering, chunks are taken out of the
+    /// stream and the whole body is put back in once its end is reached. Retries pass the
+    /// body through again, and are sent the buffered copy without counting it twice.
+    pub fn filter(
+        &mut self,
+        body: &mut Option<Bytes>,
+        end_of_stream: bool,
+    ) -> Result<(), ErrorResponseCode> {
+        if let Some(buffered_body) = &self.buffered_body {
+            *body = (end_of_stream && !buffered_body.is_empty()).then(|| buffered_body.clone());
+            return Ok(());
+        }
+
+        if let Some(data) = body.as_ref() {
+            self.received_bytes += data.len() as u64;
+            if self
+                .max_body_bytes
+                .is_some_and(|max| self.received_bytes > max)
+            {
+                return Err(ErrorResponseCode::PayloadTooLarge);
+            }
+        }
+
+        let Some(buffer) = self.buffer.as_mut() else {
+            return Ok(());
+        };
+        if let Some(data) = body.take() {
+            buffer.extend_from_slice(&data);
+        }
+        if end_of_stream {
+            let full_body = std::mem::take(buffer).freeze();
+            *body = (!full_body.is_empty()).then(|| full_body.clone());
+            self.buffered_body = Some(full_body);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+
+    fn filter_handler(body_mode: RequestBodyMode) -> RequestLimitsFilterHandler {
+        RequestLimitsFilterHandler::new(
+            &RequestLimitsFilter::builder()
+                .key("default/request-limits")
+                .max_request_body_bytes(Some(10))
+                .max_request_headers_bytes(Some(64))
+                .body_mode(body_mode)
+                .build(),
+        )
+    }
+
+    fn request(headers: &[(&'static str, &str)]) -> RequestHeader {
+        let mut request = RequestHeader::build(Method::POST, b"/", None).expect("request");
+        for (name, value) in headers {
+            request
+                .append_header(*name, value.to_string())
+                .expect("header");
+        }
+        request
+    }
+
+    #[test]
+    fn test_check_request_header() {
+        let handler = filter_handler(RequestBodyMode::Streaming);
+        let check =
+            |headers: &[(&'static str, &str)]| handler.check_request_header(&request(headers));
+
+        assert_eq!(check(&[("content-length", "10")]), Ok(()));
+        assert_eq!(check(&[]), Ok(()));
+        assert_eq!(
+            check(&[("content-length", "11")]),
+            Err(ErrorResponseCode::PayloadTooLarge)
+        );
+
+        let large = "x".repeat(64);
+        assert_eq!(
+            check(&[("x-large", large.as_str())]),
+            Err(ErrorResponseCode::RequestHeadersTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_streaming_body() {
+        let mut limiter = filter_handler(RequestBodyMode::Streaming).body_limiter();
+
+        let mut body = Some(Bytes::from_static(b"hello"));
+        assert_eq!(limiter.filter(&mut body, false), Ok(()));
+        assert_eq!(body, Some(Bytes::from_static(b"hello")));
+
+        let mut body = Some(Bytes::from_static(b"world!"));
+        assert_eq!(
+            limiter.filter(&mut body, true),
+            Err(ErrorResponseCode::PayloadTooLarge)
+        );
+        assert_eq!(limiter.received_bytes(), 11);
+        assert_eq!(limiter.buffered_body(), None);
+        assert!(!limiter.is_replayable());
+    }
+
+    #[test]
+    fn test_buffered_body() {
+        let mut limiter = filter_handler(RequestBodyMode::Buffered).body_limiter();
+        assert!(limiter.is_replayable());
+
+        let mut body = Some(Bytes::from_static(b"hello"));
+        assert_eq!(limiter.filter(&mut body, false), Ok(()));
+        assert_eq!(body, None);
+        assert!(!limiter.is_replayable());
+
+        let mut body = Some(Bytes::from_static(b"world"));
+        assert_eq!(limiter.filter(&mut body, true), Ok(()));
+        assert_eq!(body, Some(Bytes::from_static(b"helloworld")));
+        assert_eq!(
+            limiter.buffered_body(),
+            Some(&Bytes::from_static(b"helloworld"))
+        );
+        assert!(limiter.is_replayable());
+    }
+
+    #[test]
+    fn test_replayed_buffered_body() {
+        let mut limiter = filter_handler(RequestBodyMode::Buffered).body_limiter();
+        let mut body = Some(Bytes::from_static(b"helloworld"));
+        assert_eq!(limiter.filter(&mut body, true), Ok(()));
+
+        // A retry sends the buffered copy again, without counting it against the limit
+        let mut body = Some(Bytes::from_static(b"hello"));
+        assert_eq!(limiter.filter(&mut body, false), Ok(()));
+        assert_eq!(body, None);
+        let mut body = None;
+        assert_eq!(limiter.filter(&mut body, true), Ok(()));
+        assert_eq!(body, Some(Bytes::from_static(b"helloworld")));
+        assert_eq!(limiter.received_bytes(), 10);
+    }
+
+    #[test]
+    fn test_empty_buffered_body() {
+        let mut limiter = filter_handler(RequestBodyMode::Buffered).body_limiter();
+
+        let mut body = None;
+        assert_eq!(limiter.filter(&mut body, true), Ok(()));
+        assert_eq!(body, None);
+        assert_eq!(limiter.buffered_body(), Some(&Bytes::new()));
+    }
+}
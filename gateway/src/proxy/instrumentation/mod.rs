@@ -1,19 +1,21 @@
 use crate::instrumentation::get_meter;
+use crate::proxy::access_logs::AccessLogEntry;
+use chrono::{DateTime, Utc};
 use getset::Getters;
-use http::header::{HOST, USER_AGENT};
+use http::header::{HeaderName, HOST, REFERER, USER_AGENT};
 use http::uri::Authority;
-use http::{request, response, HeaderMap, StatusCode};
+use http::{request, response, HeaderMap, StatusCode, Version};
 use opentelemetry::global::get_text_map_propagator;
 use opentelemetry::metrics::{Histogram, UpDownCounter};
 use opentelemetry::KeyValue;
 use opentelemetry_http::{HeaderExtractor, HeaderInjector};
 use opentelemetry_semantic_conventions::metric::HTTP_SERVER_REQUEST_DURATION;
 use opentelemetry_semantic_conventions::trace::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::LazyLock;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::span::EnteredSpan;
 use tracing::{info_span, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
@@ -39,6 +41,7 @@ static ACTIVE_REQUESTS: LazyLock<UpDownCounter<i64>> = LazyLock::new(|| {
 #[derive(Debug, Getters)]
 pub struct RequestInstrumentation {
     start_time: Instant,
+    start_timestamp: DateTime<Utc>,
 
     duration_attributes: RefCell<Vec<KeyValue>>,
     active_requests_attributes: RefCell<Vec<KeyValue>>,
@@ -47,6 +50,12 @@ pub struct RequestInstrumentation {
     request_span: Span,
 
     upstream_request_spans: RefCell<VecDeque<EnteredSpan>>,
+
+    client_addr: Cell<Option<IpAddr>>,
+    upstream_addr: Cell<Option<SocketAddr>>,
+    upstream_call_start_time: Cell<Option<Instant>>,
+    upstream_duration: Cell<Option<Duration>>,
+    status: Cell<Option<StatusCode>>,
 }
 
 impl RequestInstrumentation {
@@ -56,10 +65,16 @@ impl RequestInstrumentation {
         let request_span = info_span!("request", otel.kind = "server");
         Self {
             start_time,
+            start_timestamp: Utc::now(),
             duration_attributes: RefCell::default(),
             active_requests_attributes: RefCell::default(),
             request_span,
             upstream_request_spans: RefCell::default(),
+            client_addr: Cell::default(),
+            upstream_addr: Cell::default(),
+            upstream_call_start_time: Cell::default(),
+            upstream_duration: Cell::default(),
+            status: Cell::default(),
         }
     }
 
@@ -136,6 +151,7 @@ impl RequestInstrumentation {
 
     #[track_caller]
     pub fn record_client_addr(&self, addr: Option<IpAddr>) {
+        self.client_addr.set(addr);
         if let Some(addr) = addr {
             self.request_span
                 .set_attribute(CLIENT_ADDRESS, addr.to_string());
@@ -147,6 +163,7 @@ impl RequestInstrumentation {
 
     #[track_caller]
     pub fn record_upstream_peer(&self, addr: SocketAddr) {
+        self.upstream_addr.set(Some(addr));
         let span = Span::current();
         span.set_attribute(NETWORK_PEER_ADDRESS, addr.ip().to_string());
         span.set_attribute(NETWORK_PEER_PORT, addr.port() as i64);
@@ -165,10 +182,16 @@ impl RequestInstrumentation {
         self.upstream_request_spans
             .borrow_mut()
             .push_back(span.entered());
+        self.upstream_call_start_time.set(Some(Instant::now()));
     }
 
     #[track_caller]
     pub fn end_upstream_call(&self, upstream_res: &response::Parts) {
+        if let Some(start_time) = self.upstream_call_start_time.take() {
+            let duration = self.upstream_duration.get().unwrap_or_default();
+            self.upstream_duration
+                .set(Some(duration + start_time.elapsed()));
+        }
         if let Some(span) = self.upstream_request_spans.borrow_mut().pop_back() {
             self.request_span.set_attribute(
                 HTTP_RESPONSE_STATUS_CODE,
@@ -180,6 +203,7 @@ impl RequestInstrumentation {
 
     #[track_caller]
     pub fn record_status(&self, status: StatusCode) {
+        self.status.set(Some(status));
         let mut duration_attributes = self.duration_attributes.borrow_mut();
         let status = status.as_u16() as i64;
         self.request_span
@@ -187,6 +211,100 @@ impl RequestInstrumentation {
         self.request_span.set_attribute("http.status_code", status);
         duration_attributes.push(KeyValue::new(HTTP_RESPONSE_STATUS_CODE, status));
     }
+
+    /// Records how the response cache handled the request, such as `hit`, `miss` or `stale`
+    pub fn record_cache_status(&self, status: &'static str) {
+        let mut duration_attributes = self.duration_attributes.borrow_mut();
+        self.request_span
+            .set_attribute("vale_gateway.cache.status", status);
+        duration_attributes.push(KeyValue::new("vale_gateway.cache.status", status));
+    }
+
+    /// Records the body and header size limits the request is held to
+    pub fn record_request_limits(
+        &self,
+        max_body_bytes: Option<u64>,
+        max_headers_bytes: Option<u32>,
+    ) {
+        if let Some(max_body_bytes) = max_body_bytes {
+            self.request_span.set_attribute(
+                "vale_gateway.request.max_body_bytes",
+                i64::try_from(max_body_bytes).unwrap_or(i64::MAX),
+            );
+        }
+        if let Some(max_headers_bytes) = max_headers_bytes {
+            self.request_span.set_attribute(
+                "vale_gateway.request.max_headers_bytes",
+                i64::from(max_headers_bytes),
+            );
+        }
+    }
+
+    /// Records the size of the request body received from the client
+    pub fn record_request_body_size(&self, size: u64) {
+        self.request_span.set_attribute(
+            "http.request.body.size",
+            i64::try_from(size).unwrap_or(i64::MAX),
+        );
+    }
+}
+
+impl RequestInstrumentation {
+    /// The last response status recorded for the request
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status.get()
+    }
+
+    /// The access log entry of the request, once its response has been sent
+    pub fn access_log_entry(
+        &self,
+        req: &request::Parts,
+        route: Option<&str>,
+        rule_id: Option<&str>,
+        status: Option<StatusCode>,
+        bytes_received: usize,
+        bytes_sent: usize,
+    ) -> AccessLogEntry {
+        let header = |name: HeaderName| {
+            req.headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        AccessLogEntry::builder()
+            .timestamp(self.start_timestamp)
+            .client_addr(self.client_addr.get())
+            .method(req.method.as_str())
+            .path(
+                req.uri
+                    .path_and_query()
+                    .map_or_else(|| req.uri.path(), |path_and_query| path_and_query.as_str()),
+            )
+            .protocol(protocol(req.version))
+            .host(req.uri.host().map(str::to_string).or_else(|| header(HOST)))
+            .user_agent(header(USER_AGENT))
+            .referer(header(REFERER))
+            .route(route.map(str::to_string))
+            .rule_id(rule_id.map(str::to_string))
+            .upstream_addr(self.upstream_addr.get())
+            .status(status.map(|status| status.as_u16()))
+            .duration(self.start_time.elapsed())
+            .upstream_duration(self.upstream_duration.get())
+            .bytes_received(bytes_received)
+            .bytes_sent(bytes_sent)
+            .build()
+    }
+}
+
+fn protocol(version: Version) -> &'static str {
+    match version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_2 => "HTTP/2.0",
+        Version::HTTP_3 => "HTTP/3.0",
+        _ => "HTTP/1.1",
+    }
 }
 
 impl Drop for RequestInstrumentation {
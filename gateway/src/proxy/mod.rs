@@ -1,33 +1,60 @@
+pub mod access_logs;
 mod constants;
 mod context;
 pub mod filters;
 mod instrumentation;
 pub mod responses;
 pub mod router;
+pub mod upstream_tls;
 
+use crate::controllers::ipc_secrets_cache::{ClientCertificatesCache, HtpasswdCache, JwksCache};
 use crate::controllers::static_response_bodies_cache::StaticResponseBodiesCache;
+use crate::proxy::access_logs::AccessLogger;
 use crate::proxy::context::{MatchRouteResult, UpstreamPeerResult};
 
 use crate::proxy::filters::access_control::AccessControlFilterHandlers;
+use crate::proxy::filters::basic_auth::{
+    BasicAuthEvaluationResult, BasicAuthFilterHandler, BasicAuthFilterHandlers,
+};
+use crate::proxy::filters::cache::{CacheFilterHandler, CacheFilterHandlers, HttpCache};
+use crate::proxy::filters::compression::CompressionFilterHandlers;
+use crate::proxy::filters::cors::{CorsFilterHandler, CorsFilterHandlers};
+use crate::proxy::filters::external_auth::{
+    ExternalAuthEvaluationResult, ExternalAuthFilterHandlers,
+};
+use crate::proxy::filters::jwt_auth::{
+    JwtAuthEvaluationResult, JwtAuthFilterHandler, JwtAuthFilterHandlers,
+};
+use crate::proxy::filters::rate_limit::{
+    RateLimitEvaluationResult, RateLimitFilterHandlers, RATELIMIT_LIMIT, RATELIMIT_REMAINING,
+    RATELIMIT_RESET,
+};
+use crate::proxy::filters::request_limits::RequestLimitsFilterHandlers;
 use crate::proxy::filters::static_responses::StaticResponseFilter;
 use crate::proxy::instrumentation::RequestInstrumentation;
 use crate::proxy::responses::error_responses::{ErrorResponseCode, ErrorResponseGenerators};
 use async_trait::async_trait;
+use bytes::Bytes;
 use context::RequestContext;
 use filters::client_addrs::ClientAddrFilterHandler;
 use filters::request_headers::RequestHeaderFilter;
 use filters::request_redirect::RequestRedirectFilter;
 use filters::response_headers::ResponseHeaderFilter;
 use filters::url_rewrite::URLRewriteFilter;
-use http::header::SERVER;
-use http::{HeaderMap, StatusCode};
+use http::header::{AUTHORIZATION, RETRY_AFTER, SERVER, WWW_AUTHENTICATE};
+use http::{HeaderMap, Response, StatusCode};
 use itertools::Itertools;
+use pingora::cache::key::HashBinary;
+use pingora::cache::{CacheKey, CacheMeta, NoCacheReason, RespCacheable};
 use pingora::http::ResponseHeader;
 use pingora::prelude::*;
 use pingora::protocols::http::error_resp::gen_error_response;
+use pingora::proxy::FailToProxy;
 use router::HttpRouter;
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, instrument, warn};
 use typed_builder::TypedBuilder;
 use vg_core::config::gateway::types::net::StaticResponse;
@@ -39,9 +66,22 @@ pub struct Proxy {
     router_rx: Receiver<HttpRouter>,
     client_addr_filter_handler_rx: Receiver<ClientAddrFilterHandler>,
     access_control_filters_handlers_rx: Receiver<AccessControlFilterHandlers>,
+    rate_limit_filters_handlers_rx: Receiver<RateLimitFilterHandlers>,
+    jwt_auth_filters_handlers_rx: Receiver<JwtAuthFilterHandlers>,
+    jwks_cache: JwksCache,
+    external_auth_filters_handlers_rx: Receiver<ExternalAuthFilterHandlers>,
+    basic_auth_filters_handlers_rx: Receiver<BasicAuthFilterHandlers>,
+    htpasswd_cache: HtpasswdCache,
+    client_certificates_cache: ClientCertificatesCache,
+    cors_filters_handlers_rx: Receiver<CorsFilterHandlers>,
+    compression_filters_handlers_rx: Receiver<CompressionFilterHandlers>,
+    cache_filters_handlers_rx: Receiver<CacheFilterHandlers>,
+    http_cache: HttpCache,
+    request_limits_filters_handlers_rx: Receiver<RequestLimitsFilterHandlers>,
     error_responses_rx: Receiver<ErrorResponseGenerators>,
     static_responses_rx: Receiver<Arc<HashMap<String, StaticResponse>>>,
     static_response_bodies_cache: StaticResponseBodiesCache,
+    access_logger_rx: Receiver<AccessLogger>,
 }
 
 #[async_trait]
@@ -66,7 +106,55 @@ impl ProxyHttp for Proxy {
         match ctx.next_upstream_peer() {
             UpstreamPeerResult::Addr(addr) => {
                 ctx.instrumentation().record_upstream_peer(addr);
-                Ok(Box::new(HttpPeer::new(addr, false, "".to_string())))
+                let Some(tls) = ctx
+                    .upstream_backend()
+                    .and_then(|backend| backend.tls().clone())
+                else {
+                    return Ok(Box::new(HttpPeer::new(addr, false, "".to_string())));
+                };
+
+                let client_cert_key = match tls.client_certificate() {
+                    Some(client_certificate) => {
+                        match self
+                            .client_certificates_cache
+                            .get(
+                                client_certificate.identifier(),
+                                client_certificate.version_key(),
+                                None,
+                            )
+                            .await
+                        {
+                            Some(cert_key) => Some(cert_key),
+                            None => {
+                                // The backend requires the certificate, connecting without it
+                                // would only fail later
+                                warn!("Client certificate for upstream {} unavailable", addr);
+                                ctx.set_error_response_code(ErrorResponseCode::UpstreamUnavailable);
+                                ctx.instrumentation()
+                                    .record_status(StatusCode::SERVICE_UNAVAILABLE);
+                                return Err(Error::explain(
+                                    HTTPStatus(StatusCode::SERVICE_UNAVAILABLE.into()),
+                                    "Client certificate unavailable",
+                                ));
+                            }
+                        }
+                    }
+                    None => None,
+                };
+                Ok(Box::new(tls.peer(addr, client_cert_key.as_deref())))
+            }
+            UpstreamPeerResult::CircuitOpen(limit) => {
+                warn!(
+                    "Circuit breaker open for upstream, {:?} limit reached",
+                    limit
+                );
+                ctx.set_error_response_code(ErrorResponseCode::UpstreamUnavailable);
+                ctx.instrumentation()
+                    .record_status(StatusCode::SERVICE_UNAVAILABLE);
+                Err(Error::explain(
+                    HTTPStatus(StatusCode::SERVICE_UNAVAILABLE.into()),
+                    "Circuit breaker open",
+                ))
             }
             UpstreamPeerResult::NotFound => {
                 ctx.instrumentation().record_status(StatusCode::NOT_FOUND);
@@ -183,6 +271,343 @@ impl ProxyHttp for Proxy {
                 //     return Ok(true); // Request handled, don't proceed to upstream
                 // }
 
+                // Oversized requests are rejected before any other filter spends work on them
+                if let Some(ext_request_limits) = rule
+                    .filters()
+                    .iter()
+                    .find_map(|filter| filter.ext_request_limits.as_ref())
+                {
+                    let request_limits_filters_handlers_rx =
+                        self.request_limits_filters_handlers_rx.clone();
+                    let Some(handler) = request_limits_filters_handlers_rx
+                        .get()
+                        .await
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_request_limits.key()))
+                        .cloned()
+                    else {
+                        return self
+                            .reject_unknown_filter(
+                                session,
+                                ctx,
+                                "Request limits",
+                                ext_request_limits.key(),
+                            )
+                            .await;
+                    };
+
+                    ctx.instrumentation().record_request_limits(
+                        handler.max_request_body_bytes(),
+                        handler.max_request_headers_bytes(),
+                    );
+                    if let Err(code) = handler.check_request_header(session.req_header()) {
+                        debug!(
+                            "Request limits filter {:?} rejected request: {:?}",
+                            ext_request_limits.key(),
+                            code
+                        );
+                        let response = ctx.generate_error_response(code).await;
+                        ctx.instrumentation().record_status(response.status());
+                        self.write_error_response(session, &response).await?;
+                        return Ok(true);
+                    }
+                    let limiter = handler.body_limiter();
+                    if limiter.is_buffered() {
+                        // Retries only pass the body through `request_body_filter` again when
+                        // Pingora kept it, the limiter then sends its buffered copy instead
+                        session.enable_retry_buffering();
+                    }
+                    ctx.set_request_body_limiter(limiter);
+                }
+
+                // Preflight requests are answered before authentication, as browsers never
+                // send credentials with them
+                let req_header = session.req_header();
+                if CorsFilterHandler::is_preflight(&req_header.method, &req_header.headers) {
+                    let cors_filters_handlers_rx = self.cors_filters_handlers_rx.clone();
+                    let cors_filters_handlers = cors_filters_handlers_rx.get().await;
+                    for filter in rule.filters() {
+                        let Some(ext_cors) = &filter.ext_cors else {
+                            continue;
+                        };
+                        let Some(handler) = cors_filters_handlers
+                            .as_ref()
+                            .and_then(|handlers| handlers.get(ext_cors.key()))
+                        else {
+                            return self
+                                .reject_unknown_filter(session, ctx, "CORS", ext_cors.key())
+                                .await;
+                        };
+
+                        debug!(
+                            "Answering preflight request with CORS filter {:?} for route: {:?}",
+                            ext_cors.key(),
+                            route
+                        );
+                        let mut preflight_response =
+                            gen_error_response(StatusCode::NO_CONTENT.as_u16());
+                        self.set_response_server_header(&mut preflight_response)?;
+                        if let Err(e) = handler.apply_to_preflight(
+                            &session.req_header().headers,
+                            &mut preflight_response,
+                        ) {
+                            warn!("Failed to apply CORS filter to preflight response: {}", e);
+                        }
+                        ctx.instrumentation().record_status(StatusCode::NO_CONTENT);
+
+                        session
+                            .write_response_header_ref(&preflight_response)
+                            .await?;
+                        session
+                            .write_response_body(Some(Bytes::new()), true)
+                            .await?;
+                        return Ok(true);
+                    }
+                }
+
+                let jwt_auth_filters_handlers_rx = self.jwt_auth_filters_handlers_rx.clone();
+                let jwt_auth_filters_handlers = jwt_auth_filters_handlers_rx.get().await;
+                for filter in rule.filters() {
+                    let Some(ext_jwt_auth) = &filter.ext_jwt_auth else {
+                        continue;
+                    };
+                    let Some(handler) = jwt_auth_filters_handlers
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_jwt_auth.key()))
+                    else {
+                        return self
+                            .reject_unknown_filter(session, ctx, "JWT auth", ext_jwt_auth.key())
+                            .await;
+                    };
+
+                    let token = JwtAuthFilterHandler::bearer_token(&session.req_header().headers)
+                        .map(ToString::to_string);
+                    let jwks = match &token {
+                        Some(token) => {
+                            let key_id = JwtAuthFilterHandler::key_id(token);
+                            let filter = handler.filter();
+                            self.jwks_cache
+                                .get(
+                                    filter.jwks_identifier(),
+                                    filter.version_key(),
+                                    key_id.as_deref(),
+                                )
+                                .await
+                        }
+                        None => None,
+                    };
+
+                    match handler.evaluate(token.as_deref(), jwks.as_deref()) {
+                        JwtAuthEvaluationResult::Authenticated { claim_headers } => {
+                            let req_header = session.req_header_mut();
+                            for (name, value) in claim_headers {
+                                match value {
+                                    Some(value) => req_header.insert_header(name, value)?,
+                                    None => {
+                                        req_header.remove_header(&name);
+                                    }
+                                }
+                            }
+                        }
+                        JwtAuthEvaluationResult::Unauthenticated(err) => {
+                            debug!(
+                                "JWT auth filter {:?} rejected request for route: {:?}: {}",
+                                ext_jwt_auth.key(),
+                                route,
+                                err
+                            );
+                            let mut response = ctx
+                                .generate_error_response(ErrorResponseCode::Unauthorized)
+                                .await;
+                            response
+                                .headers_mut()
+                                .insert(WWW_AUTHENTICATE, handler.www_authenticate(&err));
+
+                            ctx.instrumentation().record_status(response.status());
+                            self.write_error_response(session, &response).await?;
+                            return Ok(true);
+                        }
+                    }
+                }
+
+                let basic_auth_filters_handlers_rx = self.basic_auth_filters_handlers_rx.clone();
+                let basic_auth_filters_handlers = basic_auth_filters_handlers_rx.get().await;
+                for filter in rule.filters() {
+                    let Some(ext_basic_auth) = &filter.ext_basic_auth else {
+                        continue;
+                    };
+                    let Some(handler) = basic_auth_filters_handlers
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_basic_auth.key()))
+                    else {
+                        return self
+                            .reject_unknown_filter(session, ctx, "Basic auth", ext_basic_auth.key())
+                            .await;
+                    };
+
+                    let credentials =
+                        BasicAuthFilterHandler::credentials(&session.req_header().headers);
+                    let htpasswd = match &credentials {
+                        Ok(credentials) => {
+                            let filter = handler.filter();
+                            self.htpasswd_cache
+                                .get(
+                                    filter.htpasswd_identifier(),
+                                    filter.version_key(),
+                                    Some(credentials.username()),
+                                )
+                                .await
+                        }
+                        Err(_) => None,
+                    };
+
+                    match handler.evaluate(credentials, htpasswd).await {
+                        BasicAuthEvaluationResult::Authenticated { upstream_headers } => {
+                            let req_header = session.req_header_mut();
+                            for (name, value) in upstream_headers {
+                                match value {
+                                    Some(value) => req_header.insert_header(name, value)?,
+                                    None => {
+                                        req_header.remove_header(&name);
+                                    }
+                                }
+                            }
+                        }
+                        BasicAuthEvaluationResult::Unauthenticated(err) => {
+                            debug!(
+                                "Basic auth filter {:?} rejected request for route: {:?}: {}",
+                                ext_basic_auth.key(),
+                                route,
+                                err
+                            );
+                            let mut response = ctx
+                                .generate_error_response(ErrorResponseCode::Unauthorized)
+                                .await;
+                            response
+                                .headers_mut()
+                                .insert(WWW_AUTHENTICATE, handler.www_authenticate());
+
+                            ctx.instrumentation().record_status(response.status());
+                            self.write_error_response(session, &response).await?;
+                            return Ok(true);
+                        }
+                    }
+                }
+
+                let external_auth_filters_handlers_rx =
+                    self.external_auth_filters_handlers_rx.clone();
+                let external_auth_filters_handlers = external_auth_filters_handlers_rx.get().await;
+                for filter in rule.filters() {
+                    let Some(ext_external_auth) = &filter.ext_external_auth else {
+                        continue;
+                    };
+                    let Some(handler) = external_auth_filters_handlers
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_external_auth.key()))
+                    else {
+                        return self
+                            .reject_unknown_filter(
+                                session,
+                                ctx,
+                                "External auth",
+                                ext_external_auth.key(),
+                            )
+                            .await;
+                    };
+
+                    let result = handler
+                        .evaluate(session.req_header(), client_addr, Instant::now())
+                        .await;
+                    let code = match result {
+                        ExternalAuthEvaluationResult::Allowed { upstream_headers } => {
+                            let req_header = session.req_header_mut();
+                            for (name, value) in upstream_headers {
+                                match value {
+                                    Some(value) => req_header.insert_header(name, value)?,
+                                    None => {
+                                        req_header.remove_header(&name);
+                                    }
+                                }
+                            }
+                            continue;
+                        }
+                        ExternalAuthEvaluationResult::Denied { status } => {
+                            debug!(
+                                "External auth filter {:?} denied request for route: {:?}: {}",
+                                ext_external_auth.key(),
+                                route,
+                                status
+                            );
+                            if status == StatusCode::UNAUTHORIZED {
+                                ErrorResponseCode::Unauthorized
+                            } else {
+                                ErrorResponseCode::AccessDenied
+                            }
+                        }
+                        ExternalAuthEvaluationResult::Unavailable(err) => {
+                            warn!(
+                                "External auth filter {:?} rejected request for route: {:?}: {}",
+                                ext_external_auth.key(),
+                                route,
+                                err
+                            );
+                            ErrorResponseCode::AuthorizationUnavailable
+                        }
+                    };
+
+                    let response = ctx.generate_error_response(code).await;
+                    ctx.instrumentation().record_status(response.status());
+                    self.write_error_response(session, &response).await?;
+                    return Ok(true);
+                }
+
+                let rate_limit_filters_handlers_rx = self.rate_limit_filters_handlers_rx.clone();
+                let rate_limit_filters_handlers = rate_limit_filters_handlers_rx.get().await;
+                let now = Instant::now();
+                for filter in rule.filters() {
+                    let Some(ext_rate_limit) = &filter.ext_rate_limit else {
+                        continue;
+                    };
+                    let Some(handler) = rate_limit_filters_handlers
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_rate_limit.key()))
+                    else {
+                        return self
+                            .reject_unknown_filter(session, ctx, "Rate limit", ext_rate_limit.key())
+                            .await;
+                    };
+
+                    let key = handler.bucket_key(
+                        client_addr,
+                        &session.req_header().headers,
+                        rule.unique_id(),
+                    );
+                    if let RateLimitEvaluationResult::Limited {
+                        limit,
+                        retry_after,
+                        reset,
+                    } = handler.evaluate(key.as_deref(), now)
+                    {
+                        debug!(
+                            "Rate limit filter {:?} limited request for route: {:?}",
+                            ext_rate_limit.key(),
+                            route
+                        );
+                        let mut response = ctx
+                            .generate_error_response(ErrorResponseCode::RateLimited)
+                            .await;
+                        let headers = response.headers_mut();
+                        headers.insert(RETRY_AFTER, ceil_secs(retry_after).into());
+                        headers.insert(RATELIMIT_LIMIT, limit.into());
+                        headers.insert(RATELIMIT_REMAINING, 0u32.into());
+                        headers.insert(RATELIMIT_RESET, ceil_secs(reset).into());
+
+                        ctx.instrumentation().record_status(response.status());
+                        self.write_error_response(session, &response).await?;
+                        return Ok(true);
+                    }
+                }
+
                 let static_responses_rx = self.static_responses_rx.clone();
                 for filter in rule.filters() {
                     if let Some(ext_static_response) = &filter.ext_static_response
@@ -294,6 +719,29 @@ impl ProxyHttp for Proxy {
                     }
                 }
 
+                // Only requests that passed the other filters are served from the cache
+                if let Some(ext_cache) = rule
+                    .filters()
+                    .iter()
+                    .find_map(|filter| filter.ext_cache.as_ref())
+                    && CacheFilterHandler::is_cacheable_request(&session.req_header().method)
+                {
+                    let cache_filters_handlers_rx = self.cache_filters_handlers_rx.clone();
+                    match cache_filters_handlers_rx
+                        .get()
+                        .await
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_cache.key()))
+                    {
+                        Some(handler) => ctx.set_cache_filter(handler.clone()),
+                        None => {
+                            return self
+                                .reject_unknown_filter(session, ctx, "Cache", ext_cache.key())
+                                .await;
+                        }
+                    }
+                }
+
                 ctx.set(
                     MatchRouteResult::Found(route, rule, matched_prefix),
                     client_addr,
@@ -307,16 +755,7 @@ impl ProxyHttp for Proxy {
         let response = ctx.generate_error_response(error_code).await;
 
         ctx.instrumentation().record_status(response.status());
-        let mut error_response = gen_error_response(response.status().into());
-        self.set_response_server_header(&mut error_response)?;
-        for (name, value) in response.headers() {
-            error_response.insert_header(name, value)?;
-        }
-
-        session.write_response_header_ref(&error_response).await?;
-        session
-            .write_response_body(response.body().clone(), true)
-            .await?;
+        self.write_error_response(session, &response).await?;
 
         Ok(true)
     }
@@ -331,6 +770,42 @@ impl ProxyHttp for Proxy {
         Ok(())
     }
 
+    #[instrument(name = "request_body_filter", parent = ctx.instrumentation().request_span(), skip(self, _session, body, ctx))]
+    async fn request_body_filter(
+        &self,
+        _session: &mut Session,
+        body: &mut Option<Bytes>,
+        end_of_stream: bool,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        let Some(limiter) = ctx.request_body_limiter_mut() else {
+            return Ok(());
+        };
+        let result = limiter.filter(body, end_of_stream);
+        let received_bytes = limiter.received_bytes();
+
+        if let Err(code) = result {
+            debug!(
+                "Request body exceeded its limit after {} bytes",
+                received_bytes
+            );
+            // The error response itself is written by `fail_to_proxy`
+            ctx.set_error_response_code(code);
+            let status = StatusCode::from(code);
+            ctx.instrumentation().record_status(status);
+            return Err(Error::explain(
+                HTTPStatus(status.as_u16()),
+                "Request body too large",
+            ));
+        }
+        if end_of_stream {
+            ctx.instrumentation()
+                .record_request_body_size(received_bytes);
+        }
+
+        Ok(())
+    }
+
     #[instrument(name = "upstream_request_filter", parent = ctx.instrumentation().request_span(), skip(self, _session, upstream_request, ctx))]
     async fn upstream_request_filter(
         &self,
@@ -338,6 +813,9 @@ impl ProxyHttp for Proxy {
         upstream_request: &mut RequestHeader,
         ctx: &mut Self::CTX,
     ) -> Result<()> {
+        // The upstream connection has been established by the time this filter runs
+        ctx.upstream_connected();
+
         // Apply backend-level header modifications from the matched route rule
         if let Some(MatchRouteResult::Found(route, rule, _)) = ctx.route() {
             if !rule.filters().is_empty() {
@@ -360,6 +838,17 @@ impl ProxyHttp for Proxy {
             debug!("No matched route found for upstream request header filter");
         }
 
+        // Then those of the selected backend, such as the Host header of an external service
+        if let Some(request_header_modifier) = ctx
+            .upstream_backend()
+            .and_then(|backend| backend.request_header_modifier().clone())
+        {
+            let header_filter = RequestHeaderFilter::new(request_header_modifier);
+            if let Err(e) = header_filter.apply_to_headers(upstream_request) {
+                warn!("Failed to apply backend request header filter: {}", e);
+            }
+        }
+
         let mut upstream_req_headers = HeaderMap::new();
         ctx.instrumentation()
             .begin_upstream_call(&mut upstream_req_headers);
@@ -385,10 +874,10 @@ impl ProxyHttp for Proxy {
         Ok(())
     }
 
-    #[instrument(name = "response_filter", parent = ctx.instrumentation().request_span(), skip(self, _session, upstream_response, ctx))]
+    #[instrument(name = "response_filter", parent = ctx.instrumentation().request_span(), skip(self, session, upstream_response, ctx))]
     async fn response_filter(
         &self,
-        _session: &mut Session,
+        session: &mut Session,
         upstream_response: &mut ResponseHeader,
         ctx: &mut Self::CTX,
     ) -> Result<()>
@@ -397,13 +886,42 @@ impl ProxyHttp for Proxy {
     {
         ctx.instrumentation()
             .record_status(upstream_response.status);
+        if ctx.cache_filter().is_some() {
+            ctx.instrumentation()
+                .record_cache_status(session.cache.phase().as_str());
+        }
 
         self.set_response_server_header(upstream_response)?;
 
         // Apply response header modifications from the matched route rule
+        let mut response_compressor = None;
         if let Some(context::MatchRouteResult::Found(route, rule, _)) = ctx.route() {
             if !rule.filters().is_empty() {
+                let cors_filters_handlers_rx = self.cors_filters_handlers_rx.clone();
+                let cors_filters_handlers = cors_filters_handlers_rx.get().await;
                 for filter in rule.filters() {
+                    if let Some(ext_cors) = &filter.ext_cors {
+                        match cors_filters_handlers
+                            .as_ref()
+                            .and_then(|handlers| handlers.get(ext_cors.key()))
+                        {
+                            Some(handler) => {
+                                if let Err(e) = handler.apply_to_response(
+                                    &session.req_header().headers,
+                                    upstream_response,
+                                ) {
+                                    warn!("Failed to apply CORS filter: {}", e);
+                                }
+                            }
+                            None => {
+                                warn!(
+                                    "CORS filter {:?} not found in configuration",
+                                    ext_cors.key()
+                                )
+                            }
+                        }
+                    }
+
                     if let Some(response_header_modifier) = &filter.response_header_modifier {
                         let header_filter =
                             ResponseHeaderFilter::new(response_header_modifier.clone());
@@ -414,18 +932,261 @@ impl ProxyHttp for Proxy {
                         }
                     }
                 }
+
+                // Compression comes last, so it sees the headers the client will get
+                if let Some(ext_compression) = rule
+                    .filters()
+                    .iter()
+                    .find_map(|filter| filter.ext_compression.as_ref())
+                {
+                    let compression_filters_handlers_rx =
+                        self.compression_filters_handlers_rx.clone();
+                    match compression_filters_handlers_rx
+                        .get()
+                        .await
+                        .as_ref()
+                        .and_then(|handlers| handlers.get(ext_compression.key()))
+                    {
+                        Some(handler) => {
+                            let req_header = session.req_header();
+                            response_compressor = handler.compress_response(
+                                &req_header.method,
+                                &req_header.headers,
+                                upstream_response,
+                            )?;
+                        }
+                        None => {
+                            warn!(
+                                "Compression filter {:?} not found in configuration",
+                                ext_compression.key()
+                            )
+                        }
+                    }
+                }
             }
         } else {
             debug!("No matched route found for response header filter");
         }
 
+        if let Some(compressor) = response_compressor {
+            debug!("Compressing response with {:?}", compressor.algorithm());
+            ctx.set_response_compressor(compressor);
+        }
+
+        Ok(())
+    }
+
+    fn response_body_filter(
+        &self,
+        _session: &mut Session,
+        body: &mut Option<Bytes>,
+        end_of_stream: bool,
+        ctx: &mut Self::CTX,
+    ) -> Result<Option<Duration>>
+    where
+        Self::CTX: Send + Sync,
+    {
+        if let Some(compressor) = ctx.response_compressor_mut() {
+            *body = compressor
+                .compress(body.as_ref(), end_of_stream)
+                .map_err(|e| Error::because(InternalError, "Failed to compress response", e))?;
+        }
+
+        Ok(None)
+    }
+
+    fn request_cache_filter(&self, session: &mut Session, ctx: &mut Self::CTX) -> Result<()> {
+        if let Some(handler) = ctx.cache_filter() {
+            self.http_cache.enable(session, handler);
+        }
+
         Ok(())
     }
+
+    fn cache_key_callback(&self, session: &Session, ctx: &mut Self::CTX) -> Result<CacheKey> {
+        match ctx.cache_filter() {
+            Some(handler) => Ok(self.http_cache.cache_key(handler, session.req_header())),
+            None => Err(Error::explain(
+                InternalError,
+                "Cache enabled without a cache filter",
+            )),
+        }
+    }
+
+    fn response_cache_filter(
+        &self,
+        session: &Session,
+        upstream_response: &ResponseHeader,
+        ctx: &mut Self::CTX,
+    ) -> Result<RespCacheable> {
+        let Some(handler) = ctx.cache_filter() else {
+            return Ok(RespCacheable::Uncacheable(NoCacheReason::NeverEnabled));
+        };
+
+        // Authentication filters may strip the credentials before the request is proxied
+        let route_authenticates = match ctx.route() {
+            Some(MatchRouteResult::Found(_, rule, _)) => rule.filters().iter().any(|filter| {
+                filter.ext_jwt_auth.is_some()
+                    || filter.ext_basic_auth.is_some()
+                    || filter.ext_external_auth.is_some()
+            }),
+            _ => false,
+        };
+        let authenticated =
+            route_authenticates || session.req_header().headers.contains_key(AUTHORIZATION);
+
+        Ok(handler.response_cacheable(upstream_response, authenticated))
+    }
+
+    fn cache_vary_filter(
+        &self,
+        meta: &CacheMeta,
+        _ctx: &mut Self::CTX,
+        req: &RequestHeader,
+    ) -> Option<HashBinary> {
+        CacheFilterHandler::variance(meta, req)
+    }
+
+    fn fail_to_connect(
+        &self,
+        _session: &mut Session,
+        _peer: &HttpPeer,
+        ctx: &mut Self::CTX,
+        mut e: Box<Error>,
+    ) -> Box<Error> {
+        // The next endpoint is tried when the body can be replayed to it
+        if ctx.is_request_body_replayable() {
+            debug!("Failed to connect to upstream, retrying on the next endpoint");
+            e.set_retry(true);
+        }
+        e
+    }
+
+    async fn fail_to_proxy(
+        &self,
+        session: &mut Session,
+        e: &Error,
+        ctx: &mut Self::CTX,
+    ) -> FailToProxy
+    where
+        Self::CTX: Send + Sync,
+    {
+        let error_code = if let Some(code) = ctx.error_response_code() {
+            let response = ctx.generate_error_response(code).await;
+            if let Err(e) = self.write_error_response(session, &response).await {
+                warn!("Failed to write error response: {}", e);
+            }
+            response.status().as_u16()
+        } else {
+            let code = match e.etype() {
+                HTTPStatus(code) => *code,
+                _ => match e.esource() {
+                    ErrorSource::Upstream => StatusCode::BAD_GATEWAY.as_u16(),
+                    ErrorSource::Downstream => match e.etype() {
+                        WriteError | ReadError | ConnectionClosed => 0,
+                        _ => StatusCode::BAD_REQUEST.as_u16(),
+                    },
+                    ErrorSource::Internal | ErrorSource::Unset => {
+                        StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+                    }
+                },
+            };
+            if code > 0 {
+                if let Err(e) = session.respond_error(code).await {
+                    warn!("Failed to write error response: {}", e);
+                }
+            }
+            code
+        };
+
+        FailToProxy {
+            error_code,
+            can_reuse_downstream: false,
+        }
+    }
+
+    async fn logging(&self, session: &mut Session, _e: Option<&Error>, ctx: &mut Self::CTX)
+    where
+        Self::CTX: Send + Sync,
+    {
+        let access_logger_rx = self.access_logger_rx.clone();
+        let access_logger = access_logger_rx.get().await;
+        let Some(access_logger) = access_logger.as_ref() else {
+            return;
+        };
+
+        let (route, rule_id) = match ctx.route() {
+            Some(MatchRouteResult::Found(route, rule, _)) => {
+                (route.key().as_deref(), Some(rule.unique_id().as_ref()))
+            }
+            _ => (None, None),
+        };
+        let status = session
+            .response_written()
+            .map(|response| response.status)
+            .or(ctx.instrumentation().status());
+        if !access_logger.should_log(route, status.map(|status| status.as_u16())) {
+            return;
+        }
+
+        let entry = ctx.instrumentation().access_log_entry(
+            session.req_header(),
+            route,
+            rule_id,
+            status,
+            session.body_bytes_read(),
+            session.body_bytes_sent(),
+        );
+        access_logger.log(&entry);
+    }
 }
 
 impl Proxy {
+    /// Rejects a request whose rule references a filter the gateway does not know, for
+    /// instance while its configuration is still being applied. Requests are never let
+    /// through such a filter, as that would serve the route unauthenticated or unlimited.
+    async fn reject_unknown_filter(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        filter: &str,
+        key: &(impl Debug + Sync),
+    ) -> Result<bool> {
+        warn!("{} filter {:?} not found in configuration", filter, key);
+        let response = ctx
+            .generate_error_response(ErrorResponseCode::InvalidConfiguration)
+            .await;
+        ctx.instrumentation().record_status(response.status());
+        self.write_error_response(session, &response).await?;
+        Ok(true)
+    }
+
+    async fn write_error_response(
+        &self,
+        session: &mut Session,
+        response: &Response<Option<Bytes>>,
+    ) -> Result<()> {
+        let mut error_response = gen_error_response(response.status().into());
+        self.set_response_server_header(&mut error_response)?;
+        for (name, value) in response.headers() {
+            error_response.insert_header(name, value)?;
+        }
+
+        session.write_response_header_ref(&error_response).await?;
+        session
+            .write_response_body(response.body().clone(), true)
+            .await?;
+
+        Ok(())
+    }
+
     fn set_response_server_header(&self, response: &mut ResponseHeader) -> Result<(), BError> {
         response.insert_header(SERVER, "Vale Gateway")?;
         Ok(())
     }
 }
+
+/// Rounds up to whole seconds, as `Retry-After` and the `RateLimit-*` headers require
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
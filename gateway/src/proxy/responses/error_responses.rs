@@ -74,6 +74,11 @@ pub enum ErrorResponseCode {
     MissingConfiguration,
     UpstreamUnavailable,
     InvalidConfiguration,
+    RateLimited,
+    Unauthorized,
+    AuthorizationUnavailable,
+    PayloadTooLarge,
+    RequestHeadersTooLarge,
 }
 
 impl From<ErrorResponseCode> for StatusCode {
@@ -84,6 +89,13 @@ impl From<ErrorResponseCode> for StatusCode {
             ErrorResponseCode::MissingConfiguration => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorResponseCode::UpstreamUnavailable => StatusCode::SERVICE_UNAVAILABLE,
             ErrorResponseCode::InvalidConfiguration => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorResponseCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorResponseCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorResponseCode::AuthorizationUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorResponseCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorResponseCode::RequestHeadersTooLarge => {
+                StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+            }
         }
     }
 }
@@ -96,6 +108,11 @@ impl From<ErrorResponseCode> for Cow<'static, str> {
             ErrorResponseCode::MissingConfiguration => "Missing configuration".into(),
             ErrorResponseCode::UpstreamUnavailable => "Upstream unavailable".into(),
             ErrorResponseCode::InvalidConfiguration => "Invalid configuration".into(),
+            ErrorResponseCode::RateLimited => "Too many requests".into(),
+            ErrorResponseCode::Unauthorized => "Unauthorized".into(),
+            ErrorResponseCode::AuthorizationUnavailable => "Authorization unavailable".into(),
+            ErrorResponseCode::PayloadTooLarge => "Request body too large".into(),
+            ErrorResponseCode::RequestHeadersTooLarge => "Request header fields too large".into(),
         }
     }
 }
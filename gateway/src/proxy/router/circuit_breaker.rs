@@ -0,0 +1,280 @@
+use crate::instrumentation::get_meter;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, UpDownCounter};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock};
+use strum::IntoStaticStr;
+use tracing::debug;
+use vg_core::config::gateway::types::GatewayConfiguration;
+use vg_core::config::gateway::types::net::{Backend, CircuitBreaker as CircuitBreakerThresholds};
+
+static TRIPS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    get_meter()
+        .u64_counter("vale_gateway.backend.circuit_breaker.trips")
+        .with_description("Number of requests rejected by a backend circuit breaker.")
+        .build()
+});
+
+static ACTIVE_REQUESTS: LazyLock<UpDownCounter<i64>> = LazyLock::new(|| {
+    get_meter()
+        .i64_up_down_counter("vale_gateway.backend.active_requests")
+        .with_description("Number of in-flight requests to a backend.")
+        .build()
+});
+
+static PENDING_CONNECTIONS: LazyLock<UpDownCounter<i64>> = LazyLock::new(|| {
+    get_meter()
+        .i64_up_down_counter("vale_gateway.backend.pending_connections")
+        .with_description("Number of requests waiting for a connection to a backend.")
+        .build()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum CircuitBreakerLimit {
+    Requests,
+    PendingConnections,
+}
+
+#[derive(Debug, Default)]
+struct Occupancy {
+    active_requests: AtomicU32,
+    pending_connections: AtomicU32,
+}
+
+/// A backend's circuit breaker. The thresholds come from the current configuration,
+/// while the occupancy counters are shared across router rebuilds.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    thresholds: CircuitBreakerThresholds,
+    occupancy: Arc<Occupancy>,
+    attributes: Arc<[KeyValue]>,
+}
+
+impl PartialEq for CircuitBreaker {
+    fn eq(&self, other: &Self) -> bool {
+        self.thresholds == other.thresholds && Arc::ptr_eq(&self.occupancy, &other.occupancy)
+    }
+}
+
+impl Eq for CircuitBreaker {}
+
+impl CircuitBreaker {
+    /// Reserves a request and a pending connection against the thresholds, or reports
+    /// which limit tripped the breaker.
+    pub fn try_acquire(&self) -> Result<CircuitBreakerPermit, CircuitBreakerLimit> {
+        let occupancy = &self.occupancy;
+
+        if !try_increment(&occupancy.active_requests, *self.thresholds.max_requests()) {
+            return Err(self.trip(CircuitBreakerLimit::Requests));
+        }
+
+        if !try_increment(
+            &occupancy.pending_connections,
+            *self.thresholds.max_pending_connections(),
+        ) {
+            occupancy.active_requests.fetch_sub(1, Ordering::AcqRel);
+            return Err(self.trip(CircuitBreakerLimit::PendingConnections));
+        }
+
+        ACTIVE_REQUESTS.add(1, &self.attributes);
+        PENDING_CONNECTIONS.add(1, &self.attributes);
+
+        Ok(CircuitBreakerPermit {
+            circuit_breaker: self.clone(),
+            pending: true,
+        })
+    }
+
+    fn trip(&self, limit: CircuitBreakerLimit) -> CircuitBreakerLimit {
+        debug!("Circuit breaker tripped on {:?} limit", limit);
+        let mut attributes = self.attributes.to_vec();
+        attributes.push(KeyValue::new(
+            "vale_gateway.circuit_breaker.limit",
+            <&'static str>::from(limit),
+        ));
+        TRIPS.add(1, &attributes);
+        limit
+    }
+}
+
+fn try_increment(counter: &AtomicU32, limit: Option<u32>) -> bool {
+    counter
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| match limit {
+            Some(limit) if current >= limit => None,
+            _ => Some(current.saturating_add(1)),
+        })
+        .is_ok()
+}
+
+/// Holds a request's place in a backend's circuit breaker until it is dropped.
+#[derive(Debug)]
+pub struct CircuitBreakerPermit {
+    circuit_breaker: CircuitBreaker,
+    pending: bool,
+}
+
+impl CircuitBreakerPermit {
+    /// Releases the pending connection slot once the upstream connection is established.
+    pub fn connected(&mut self) {
+        if self.pending {
+            self.pending = false;
+            let circuit_breaker = &self.circuit_breaker;
+            circuit_breaker
+                .occupancy
+                .pending_connections
+                .fetch_sub(1, Ordering::AcqRel);
+            PENDING_CONNECTIONS.add(-1, &circuit_breaker.attributes);
+        }
+    }
+}
+
+impl Drop for CircuitBreakerPermit {
+    fn drop(&mut self) {
+        self.connected();
+        let circuit_breaker = &self.circuit_breaker;
+        circuit_breaker
+            .occupancy
+            .active_requests
+            .fetch_sub(1, Ordering::AcqRel);
+        ACTIVE_REQUESTS.add(-1, &circuit_breaker.attributes);
+    }
+}
+
+type BackendKey = (Option<String>, String);
+
+/// Keeps the occupancy counters of each backend alive across configuration updates,
+/// so in-flight requests still count against the limits after the router is rebuilt.
+#[derive(Debug, Default)]
+pub struct CircuitBreakerRegistry {
+    occupancy: HashMap<BackendKey, Arc<Occupancy>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn observe(&mut self, gateway_config: &GatewayConfiguration) {
+        let keys: HashSet<_> = gateway_config
+            .http_routes()
+            .iter()
+            .flat_map(|route| route.rules())
+            .flat_map(|rule| rule.backends())
+            .filter(|backend| backend.circuit_breaker().is_some())
+            .map(backend_key)
+            .collect();
+
+        self.occupancy.retain(|key, _| keys.contains(key));
+        for key in keys {
+            self.occupancy.entry(key).or_default();
+        }
+    }
+
+    pub fn circuit_breaker(&self, backend: &Backend) -> Option<CircuitBreaker> {
+        let thresholds = (*backend.circuit_breaker())?;
+        let occupancy = self.occupancy.get(&backend_key(backend))?.clone();
+
+        let mut attributes = vec![KeyValue::new(
+            "vale_gateway.backend.name",
+            backend.name().clone(),
+        )];
+        if let Some(namespace) = backend.namespace() {
+            attributes.push(KeyValue::new(
+                "vale_gateway.backend.namespace",
+                namespace.clone(),
+            ));
+        }
+
+        Some(CircuitBreaker {
+            thresholds,
+            occupancy,
+            attributes: attributes.into(),
+        })
+    }
+}
+
+fn backend_key(backend: &Backend) -> BackendKey {
+    (backend.namespace().clone(), backend.name().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circuit_breaker(thresholds: CircuitBreakerThresholds) -> CircuitBreaker {
+        CircuitBreaker {
+            thresholds,
+            occupancy: Arc::default(),
+            attributes: Arc::new([]),
+        }
+    }
+
+    fn active_requests(circuit_breaker: &CircuitBreaker) -> u32 {
+        circuit_breaker
+            .occupancy
+            .active_requests
+            .load(Ordering::Acquire)
+    }
+
+    fn pending_connections(circuit_breaker: &CircuitBreaker) -> u32 {
+        circuit_breaker
+            .occupancy
+            .pending_connections
+            .load(Ordering::Acquire)
+    }
+
+    #[test]
+    fn test_max_requests() {
+        let circuit_breaker = circuit_breaker(
+            CircuitBreakerThresholds::builder()
+                .max_requests(Some(2))
+                .build(),
+        );
+
+        let mut first = circuit_breaker.try_acquire().expect("first request");
+        first.connected();
+        let second = circuit_breaker.try_acquire().expect("second request");
+
+        assert_eq!(
+            circuit_breaker.try_acquire().err(),
+            Some(CircuitBreakerLimit::Requests)
+        );
+        assert_eq!(active_requests(&circuit_breaker), 2);
+
+        drop(second);
+        assert!(circuit_breaker.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_max_pending_connections() {
+        let circuit_breaker = circuit_breaker(
+            CircuitBreakerThresholds::builder()
+                .max_pending_connections(Some(1))
+                .build(),
+        );
+
+        let mut first = circuit_breaker.try_acquire().expect("first request");
+        assert_eq!(
+            circuit_breaker.try_acquire().err(),
+            Some(CircuitBreakerLimit::PendingConnections)
+        );
+        assert_eq!(active_requests(&circuit_breaker), 1);
+
+        first.connected();
+        assert_eq!(pending_connections(&circuit_breaker), 0);
+
+        let _second = circuit_breaker.try_acquire().expect("second request");
+        assert_eq!(active_requests(&circuit_breaker), 2);
+    }
+
+    #[test]
+    fn test_permit_drop_releases_counters() {
+        let circuit_breaker = circuit_breaker(CircuitBreakerThresholds::default());
+
+        let permit = circuit_breaker.try_acquire().expect("request");
+        assert_eq!(active_requests(&circuit_breaker), 1);
+        assert_eq!(pending_connections(&circuit_breaker), 1);
+
+        drop(permit);
+        assert_eq!(active_requests(&circuit_breaker), 0);
+        assert_eq!(pending_connections(&circuit_breaker), 0);
+    }
+}
@@ -1,13 +1,18 @@
 use crate::proxy::router::topology::TopologyLocationMatch;
 use enumflags2::BitFlags;
 use getset::Getters;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand::seq::SliceRandom;
 use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
 use std::hash::{DefaultHasher, Hasher};
 use std::net::{IpAddr, SocketAddr};
 use tracing::{debug, warn};
 
+/// The smallest share of the total endpoints a local tier must hold to keep all of the traffic.
+/// Below this the tier is widened into the next one for a proportional share of requests.
+const MIN_LOCAL_CAPACITY_RATIO: f64 = 0.25;
+
 #[derive(Debug, Getters, Clone, PartialEq, Eq)]
 pub struct EndpointsResolver {
     endpoints: Vec<SocketAddr>,
@@ -40,6 +45,7 @@ pub struct EndpointsResolverBuilder {
     node_local: Vec<SocketAddr>,
     zone_local: Vec<SocketAddr>,
     fallback: Vec<SocketAddr>,
+    weights: HashMap<SocketAddr, f64>,
 }
 
 impl EndpointsResolverBuilder {
@@ -50,6 +56,7 @@ impl EndpointsResolverBuilder {
             node_local: Vec::new(),
             zone_local: Vec::new(),
             fallback: Vec::new(),
+            weights: HashMap::new(),
         }
     }
 
@@ -74,6 +81,21 @@ impl EndpointsResolverBuilder {
         self
     }
 
+    /// Inserts an endpoint that should be selected less often than its peers, such as one
+    /// still ramping up during slow start. A weight of 1.0 behaves the same as [`Self::insert`].
+    pub fn insert_weighted(
+        &mut self,
+        addr: SocketAddr,
+        location_match: BitFlags<TopologyLocationMatch>,
+        weight: f64,
+    ) -> &mut Self {
+        if weight < 1.0 {
+            self.weights.insert(addr, weight);
+        }
+
+        self.insert(addr, location_match)
+    }
+
     pub fn build(self) -> EndpointsResolver {
         let mut node_local = self.node_local.clone();
         let mut zone_local = self.zone_local.clone();
@@ -100,6 +122,31 @@ impl EndpointsResolverBuilder {
         zone_local.shuffle(&mut rng);
         fallback.shuffle(&mut rng);
 
+        let total = node_local.len() + zone_local.len() + fallback.len();
+
+        // The order of the endpoints sticks to the client, but whether a tier spills over is
+        // decided for each request, or the same clients would always be sent outside of it
+        let mut spill_over_rng = rand::rng();
+        if should_spill_over(node_local.len(), total, &mut spill_over_rng) {
+            debug!("Node local tier is under capacity, spilling over into zone local tier");
+            zone_local.append(&mut node_local);
+            zone_local.shuffle(&mut rng);
+        }
+
+        let local = node_local.len() + zone_local.len();
+        if should_spill_over(local, total, &mut spill_over_rng) {
+            debug!("Local tiers are under capacity, spilling over into fallback tier");
+            fallback.append(&mut zone_local);
+            fallback.append(&mut node_local);
+            fallback.shuffle(&mut rng);
+        }
+
+        if !self.weights.is_empty() {
+            order_by_weight(&mut node_local, &self.weights, &mut rng);
+            order_by_weight(&mut zone_local, &self.weights, &mut rng);
+            order_by_weight(&mut fallback, &self.weights, &mut rng);
+        }
+
         EndpointsResolver {
             endpoints: node_local
                 .into_iter()
@@ -111,6 +158,50 @@ impl EndpointsResolverBuilder {
     }
 }
 
+/// Decides whether a local tier holding `local` of the `total` endpoints should be widened.
+/// The tier keeps traffic with a probability proportional to its share of the capacity, so a
+/// single local endpoint is not sent every request while the rest of the backend sits idle.
+#[allow(clippy::cast_precision_loss)]
+fn should_spill_over(local: usize, total: usize, rng: &mut impl Rng) -> bool {
+    if local == 0 || local == total {
+        return false;
+    }
+
+    let ratio = local as f64 / total as f64;
+    if ratio >= MIN_LOCAL_CAPACITY_RATIO {
+        return false;
+    }
+
+    !rng.random_bool(ratio / MIN_LOCAL_CAPACITY_RATIO)
+}
+
+/// Reorders a tier with a weighted random sample, so endpoints with a lower weight are less
+/// likely to be tried first. Tiers without weighted endpoints keep their shuffled order.
+fn order_by_weight(
+    endpoints: &mut Vec<SocketAddr>,
+    weights: &HashMap<SocketAddr, f64>,
+    rng: &mut impl Rng,
+) {
+    if !endpoints.iter().any(|addr| weights.contains_key(addr)) {
+        return;
+    }
+
+    let mut keyed: Vec<_> = endpoints
+        .iter()
+        .map(|addr| {
+            let weight = weights
+                .get(addr)
+                .copied()
+                .unwrap_or(1.0)
+                .max(f64::MIN_POSITIVE);
+            (rng.random::<f64>().powf(1.0 / weight), *addr)
+        })
+        .collect();
+    keyed.sort_by(|(lhs, _), (rhs, _)| rhs.total_cmp(lhs));
+
+    *endpoints = keyed.into_iter().map(|(_, addr)| addr).collect();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,4 +245,102 @@ mod tests {
         // Assert that fallback addresses come last
         assert!(endpoints.ends_with(&[fallback_ip1, fallback_ip2]));
     }
+
+    #[test]
+    fn test_spill_over_only_below_min_ratio() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        assert!(!should_spill_over(0, 10, &mut rng));
+        assert!(!should_spill_over(10, 10, &mut rng));
+        assert!(!should_spill_over(3, 10, &mut rng));
+    }
+
+    #[test]
+    fn test_spill_over_is_proportional() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // One local endpoint out of eight holds half of the minimum ratio, so roughly
+        // half of the resolvers should spill over into the wider tier.
+        let spilled = (0..10_000)
+            .filter(|_| should_spill_over(1, 8, &mut rng))
+            .count();
+
+        assert!((4_500..5_500).contains(&spilled), "spilled {spilled}");
+    }
+
+    #[test]
+    fn test_single_node_local_endpoint_does_not_take_all_traffic() {
+        let node_ip: SocketAddr = "192.168.1.1:8080".parse().unwrap();
+        let zone_ips: Vec<SocketAddr> = (1..=7)
+            .map(|i| format!("192.168.2.{i}:8080").parse().unwrap())
+            .collect();
+
+        let first_node_local = (0..1_000)
+            .filter(|_| {
+                let mut resolver_builder = EndpointsResolver::builder(None);
+                resolver_builder.insert(node_ip, BitFlags::from(TopologyLocationMatch::Node));
+                for zone_ip in &zone_ips {
+                    resolver_builder.insert(*zone_ip, BitFlags::from(TopologyLocationMatch::Zone));
+                }
+                resolver_builder.build().endpoints[0] == node_ip
+            })
+            .count();
+
+        assert!(first_node_local > 0);
+        assert!(first_node_local < 1_000);
+    }
+
+    #[test]
+    fn test_spill_over_is_decided_per_request() {
+        let client_addr = IpAddr::from_str("10.0.0.1").unwrap();
+        let node_ip: SocketAddr = "192.168.1.1:8080".parse().unwrap();
+        let zone_ips: Vec<SocketAddr> = (1..=7)
+            .map(|i| format!("192.168.2.{i}:8080").parse().unwrap())
+            .collect();
+
+        let mut orderings: HashMap<Vec<SocketAddr>, usize> = HashMap::new();
+        for _ in 0..1_000 {
+            let mut resolver_builder = EndpointsResolver::builder(Some(client_addr));
+            resolver_builder.unique_id("default/echo/0");
+            resolver_builder.insert(node_ip, BitFlags::from(TopologyLocationMatch::Node));
+            for zone_ip in &zone_ips {
+                resolver_builder.insert(*zone_ip, BitFlags::from(TopologyLocationMatch::Zone));
+            }
+            let endpoints = resolver_builder.build().endpoints;
+            *orderings.entry(endpoints).or_default() += 1;
+        }
+
+        // Requests of the same client get the same order whether the node local tier keeps
+        // them or spills over, and it spills over for about half of them
+        assert_eq!(orderings.len(), 2, "{orderings:?}");
+        for count in orderings.values() {
+            assert!((400..600).contains(count), "{orderings:?}");
+        }
+    }
+
+    #[test]
+    fn test_weighted_endpoint_is_chosen_less_often() {
+        let warm_ip: SocketAddr = "192.168.1.1:8080".parse().unwrap();
+        let ramping_ip: SocketAddr = "192.168.1.2:8080".parse().unwrap();
+
+        let ramping_first = (0..1_000)
+            .filter(|_| {
+                let mut resolver_builder = EndpointsResolver::builder(None);
+                resolver_builder.insert(warm_ip, BitFlags::from(TopologyLocationMatch::Node));
+                resolver_builder.insert_weighted(
+                    ramping_ip,
+                    BitFlags::from(TopologyLocationMatch::Node),
+                    0.1,
+                );
+                resolver_builder.build().endpoints[0] == ramping_ip
+            })
+            .count();
+
+        // With a weight of 0.1 against 1.0 the ramping endpoint should lead about 9% of the time
+        assert!(ramping_first > 0);
+        assert!(
+            ramping_first < 250,
+            "ramping endpoint first {ramping_first} times"
+        );
+    }
 }
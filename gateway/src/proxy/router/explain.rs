@@ -0,0 +1,311 @@
+use crate::proxy::router::matches::{MatchCriterion, ScoreCriterion};
+use crate::proxy::router::HttpRouter;
+use http::request::Parts;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Why a request would, or would not, be routed to each rule of a router
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HttpRouterExplanation {
+    /// Whether the host of the request is accepted by the router at all
+    pub host_matched: bool,
+
+    /// Index of the winning candidate in `candidates`
+    pub winner: Option<usize>,
+
+    /// Every route, rule and match tested, in declaration order
+    pub candidates: Vec<HttpRouteCandidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HttpRouteCandidate {
+    pub route_index: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches_index: Option<usize>,
+
+    pub outcome: HttpRouteCandidateOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum HttpRouteCandidateOutcome {
+    /// The route does not accept the host of the request
+    HostMismatch,
+
+    /// The rule has no matches, so it never matches
+    NoMatches,
+
+    /// The request failed one of the criteria of the matches
+    Mismatch { criterion: MatchCriterion },
+
+    /// The request matched the rule
+    Matched {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        matched_prefix: Option<String>,
+    },
+
+    /// The request matched the rule, but the winner is more specific. Without a
+    /// criterion, both are as specific and the winner is declared first.
+    Outranked {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        criterion: Option<ScoreCriterion>,
+    },
+}
+
+impl HttpRouter {
+    /// Tests a request against every rule, reporting the outcome of each along with
+    /// the rule `match_route` selects
+    pub fn explain(&self, parts: &Parts) -> HttpRouterExplanation {
+        if !self.host_matches.matches(&parts.headers) {
+            return HttpRouterExplanation {
+                host_matched: false,
+                winner: None,
+                candidates: vec![],
+            };
+        }
+
+        let best = self.best_route(parts);
+
+        let mut winner = None;
+        let mut candidates = vec![];
+        for (route_index, route) in self.routes.iter().enumerate() {
+            let candidate = |rule_id: Option<String>, matches_index, outcome| HttpRouteCandidate {
+                route_index,
+                route: route.key().clone(),
+                rule_id,
+                matches_index,
+                outcome,
+            };
+
+            if !route.host_header_match().matches(&parts.headers) {
+                candidates.push(candidate(
+                    None,
+                    None,
+                    HttpRouteCandidateOutcome::HostMismatch,
+                ));
+                continue;
+            }
+
+            for rule in route.rules() {
+                let rule_id = Some(rule.unique_id().as_ref().to_string());
+                if rule.matches().is_empty() {
+                    candidates.push(candidate(
+                        rule_id,
+                        None,
+                        HttpRouteCandidateOutcome::NoMatches,
+                    ));
+                    continue;
+                }
+
+                for (matches_index, matches) in rule.matches().iter().enumerate() {
+                    let outcome = match matches.evaluate(parts) {
+                        Err(criterion) => HttpRouteCandidateOutcome::Mismatch { criterion },
+                        Ok(result) => {
+                            let score = result.score().expect("matched result has a score");
+                            match &best {
+                                Some((best_index, best_result))
+                                    if winner.is_none()
+                                        && *best_index == route_index
+                                        && best_result.rule().is_some_and(|best_rule| {
+                                            Arc::ptr_eq(best_rule, rule)
+                                        })
+                                        && best_result.score() == Some(score) =>
+                                {
+                                    winner = Some(candidates.len());
+                                    HttpRouteCandidateOutcome::Matched {
+                                        matched_prefix: result.matched_prefix().cloned(),
+                                    }
+                                }
+                                Some((_, best_result)) => {
+                                    let best_score =
+                                        best_result.score().expect("matched result has a score");
+                                    let criterion = match best_score.compare(score) {
+                                        (Ordering::Equal, _) => None,
+                                        (_, criterion) => criterion,
+                                    };
+                                    HttpRouteCandidateOutcome::Outranked { criterion }
+                                }
+                                None => HttpRouteCandidateOutcome::Matched {
+                                    matched_prefix: result.matched_prefix().cloned(),
+                                },
+                            }
+                        }
+                    };
+                    candidates.push(candidate(rule_id.clone(), Some(matches_index), outcome));
+                }
+            }
+        }
+
+        HttpRouterExplanation {
+            host_matched: true,
+            winner,
+            candidates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::router::topology::TopologyLocation;
+    use crate::proxy::router::{HttpRouteRuleUniqueId, HttpRouterBuilder};
+    use http::{HeaderName, HeaderValue, Method, Request};
+    use vg_core::net::Hostname;
+
+    fn router() -> HttpRouter {
+        let mut builder = HttpRouterBuilder::new(Arc::new(TopologyLocation::default()));
+        builder
+            .add_route(|route| {
+                route.with_key("default/other-host");
+                route.add_exact_host(&Hostname::new("other.example.com"));
+                route.add_rule(HttpRouteRuleUniqueId::new("other"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/");
+                    });
+                });
+            })
+            .add_route(|route| {
+                route.with_key("default/api");
+                route.add_exact_host(&Hostname::new("example.com"));
+                route.add_rule(HttpRouteRuleUniqueId::new("catch-all"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/");
+                    });
+                });
+                route.add_rule(HttpRouteRuleUniqueId::new("api"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/api");
+                    });
+                });
+                route.add_rule(HttpRouteRuleUniqueId::new("canary"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/api").with_exact_header(
+                            HeaderName::from_static("x-canary"),
+                            HeaderValue::from_static("true"),
+                        );
+                    });
+                });
+                route.add_rule(HttpRouteRuleUniqueId::new("writes"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/api").with_method(Method::POST);
+                    });
+                });
+            })
+            .add_route(|route| {
+                route.with_key("default/duplicate");
+                route.add_rule(HttpRouteRuleUniqueId::new("duplicate"), |rule| {
+                    rule.add_matches(|matches| {
+                        matches.with_path_prefix("/api");
+                    });
+                });
+            });
+        builder.build()
+    }
+
+    fn parts(path: &str) -> Parts {
+        Request::builder()
+            .uri(path)
+            .header("host", "example.com")
+            .body(())
+            .expect("request")
+            .into_parts()
+            .0
+    }
+
+    fn outcomes(
+        explanation: &HttpRouterExplanation,
+    ) -> Vec<(Option<&str>, &HttpRouteCandidateOutcome)> {
+        explanation
+            .candidates
+            .iter()
+            .map(|candidate| (candidate.rule_id.as_deref(), &candidate.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn test_explain_winner_and_outranked_rules() {
+        let router = router();
+        let parts = parts("/api/users");
+        let explanation = router.explain(&parts);
+
+        assert!(explanation.host_matched);
+        assert_eq!(
+            outcomes(&explanation),
+            vec![
+                (None, &HttpRouteCandidateOutcome::HostMismatch),
+                (
+                    Some("catch-all"),
+                    &HttpRouteCandidateOutcome::Outranked {
+                        criterion: Some(ScoreCriterion::PathLength)
+                    }
+                ),
+                (
+                    Some("api"),
+                    &HttpRouteCandidateOutcome::Matched {
+                        matched_prefix: Some("/api".to_string())
+                    }
+                ),
+                (
+                    Some("canary"),
+                    &HttpRouteCandidateOutcome::Mismatch {
+                        criterion: MatchCriterion::Headers
+                    }
+                ),
+                (
+                    Some("writes"),
+                    &HttpRouteCandidateOutcome::Mismatch {
+                        criterion: MatchCriterion::Method
+                    }
+                ),
+                (
+                    Some("duplicate"),
+                    &HttpRouteCandidateOutcome::Outranked { criterion: None }
+                ),
+            ]
+        );
+
+        // The explanation agrees with the router
+        let winner = &explanation.candidates[explanation.winner.expect("winner")];
+        let matched = router.match_route(&parts).expect("matched");
+        assert_eq!(
+            winner.route.as_ref(),
+            matched.route().and_then(|r| r.key().as_ref())
+        );
+        assert_eq!(
+            winner.rule_id.as_deref(),
+            matched.rule().map(|r| r.unique_id().as_ref())
+        );
+    }
+
+    #[test]
+    fn test_explain_without_winner() {
+        let mut parts = parts("/");
+        parts
+            .headers
+            .insert("host", HeaderValue::from_static("unknown.example.com"));
+        let explanation = router().explain(&parts);
+
+        assert_eq!(explanation.winner, None);
+        assert_eq!(
+            outcomes(&explanation),
+            vec![
+                (None, &HttpRouteCandidateOutcome::HostMismatch),
+                (None, &HttpRouteCandidateOutcome::HostMismatch),
+                (
+                    Some("duplicate"),
+                    &HttpRouteCandidateOutcome::Mismatch {
+                        criterion: MatchCriterion::Path
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -2,6 +2,7 @@ use super::Match;
 use super::score::HttpRouteRuleMatchesScore;
 use crate::util::get_regex;
 use http::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt::{Display, Formatter};
 use tracing::{debug, instrument};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -65,6 +66,18 @@ impl HeaderMatch {
     }
 }
 
+impl Display for HeaderMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = &self.name_match.0;
+        match &self.value_match {
+            HeaderValueMatch::Exact(value) => {
+                write!(f, "{name}: {}", String::from_utf8_lossy(value.as_bytes()))
+            }
+            HeaderValueMatch::RegularExpression(pattern) => write!(f, "{name} ~ {pattern}"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct HeadersMatch {
     pub(crate) header_matches: Vec<HeaderMatch>,
@@ -1,4 +1,5 @@
 use http::{HeaderMap, header::HOST};
+use std::fmt::{Display, Formatter};
 use tracing::{debug, instrument};
 use vg_core::net::Hostname;
 
@@ -24,12 +25,29 @@ impl HostValueMatch {
     }
 }
 
+impl Display for HostValueMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(host) => write!(f, "{}", host.as_ref()),
+            Self::Suffix(suffix) => write!(f, "*{}", suffix.as_ref()),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct HostMatch {
     pub host_value_matches: Vec<HostValueMatch>,
 }
 
 impl HostMatch {
+    /// The accepted hosts in readable form, empty when any host is accepted
+    pub fn describe(&self) -> Vec<String> {
+        self.host_value_matches
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
     #[instrument(skip(self, headers), name = "HostMatch::matches")]
     pub fn matches(&self, headers: &HeaderMap) -> bool {
         // If no host matches are defined, accept all requests
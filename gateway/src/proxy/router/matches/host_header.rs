@@ -1,4 +1,5 @@
 use http::{HeaderMap, HeaderValue};
+use std::fmt::{Display, Formatter};
 use tracing::{debug, instrument};
 use vg_core::net::Hostname;
 
@@ -25,6 +26,15 @@ impl HostHeaderValueMatch {
     }
 }
 
+impl Display for HostHeaderValueMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(host) => write!(f, "{}", host.as_ref()),
+            Self::Suffix(suffix) => write!(f, "*{}", suffix.as_ref()),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct HostHeaderMatch {
     host_header_value_matches: Vec<HostHeaderValueMatch>,
@@ -35,6 +45,14 @@ impl HostHeaderMatch {
         HostHeaderMatchBuilder::new()
     }
 
+    /// The accepted hosts in readable form, empty when any host is accepted
+    pub fn describe(&self) -> Vec<String> {
+        self.host_header_value_matches
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
     #[instrument(skip(self, headers), name = "HostHeaderMatch::matches")]
     pub fn matches(&self, headers: &HeaderMap) -> bool {
         if self.host_header_value_matches.is_empty() {
@@ -14,7 +14,8 @@ use http::{HeaderName, HeaderValue};
 use method::*;
 use path::*;
 use query_params::*;
-pub use score::HttpRouteRuleMatchesScore;
+pub use score::{HttpRouteRuleMatchesScore, ScoreCriterion};
+use serde::Serialize;
 use std::borrow::Cow;
 use tracing::{debug, instrument, trace};
 
@@ -30,6 +31,32 @@ pub struct HttpRouteRuleMatches {
     query_params: Option<QueryParamsMatch>,
 }
 
+/// The part of a request that failed to match, in the order they are tested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchCriterion {
+    Method,
+    Path,
+    Headers,
+    QueryParams,
+}
+
+/// The matches of a rule in readable form
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HttpRouteRuleMatchesDescription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    query_params: Vec<String>,
+}
+
 /// Enhanced result that includes matched prefix context
 #[derive(Debug, Clone)]
 pub enum HttpRouteRuleMatchesResult {
@@ -77,6 +104,12 @@ impl HttpRouteRuleMatchesResult {
 impl HttpRouteRuleMatches {
     #[instrument(skip(self, parts), name = "HttpRouteRuleMatches::matches")]
     pub fn matches(&self, parts: &Parts) -> HttpRouteRuleMatchesResult {
+        self.evaluate(parts)
+            .unwrap_or_else(|_| HttpRouteRuleMatchesResult::not_matched())
+    }
+
+    /// Matches a request, reporting the first criterion that failed when it does not
+    pub fn evaluate(&self, parts: &Parts) -> Result<HttpRouteRuleMatchesResult, MatchCriterion> {
         let score = HttpRouteRuleMatchesScore::default();
         let mut matched_prefix = None;
 
@@ -84,7 +117,7 @@ impl HttpRouteRuleMatches {
             trace!("Testing method for match");
             if !method_matcher.matches(&score, &parts.method) {
                 debug!("Method did not match");
-                return HttpRouteRuleMatchesResult::not_matched();
+                return Err(MatchCriterion::Method);
             }
         }
 
@@ -93,7 +126,7 @@ impl HttpRouteRuleMatches {
             let path_result = path_matcher.matches_with_result(&score, &parts.uri.path());
             if !path_result.matched {
                 debug!("Path did not match");
-                return HttpRouteRuleMatchesResult::not_matched();
+                return Err(MatchCriterion::Path);
             }
             matched_prefix = path_result.matched_prefix;
         }
@@ -102,7 +135,7 @@ impl HttpRouteRuleMatches {
             trace!("Testing headers for match");
             if !headers_matcher.matches(&score, &parts.headers) {
                 debug!("Headers did not match");
-                return HttpRouteRuleMatchesResult::not_matched();
+                return Err(MatchCriterion::Headers);
             }
         }
 
@@ -115,12 +148,31 @@ impl HttpRouteRuleMatches {
                 .unwrap_or_default();
             if !query_params_matcher.matches(&score, &query_params) {
                 debug!("Query parameters did not match");
-                return HttpRouteRuleMatchesResult::not_matched();
+                return Err(MatchCriterion::QueryParams);
             }
         }
 
         debug!("All route rule matches succeeded");
-        HttpRouteRuleMatchesResult::matched(score, matched_prefix)
+        Ok(HttpRouteRuleMatchesResult::matched(score, matched_prefix))
+    }
+
+    pub fn describe(&self) -> HttpRouteRuleMatchesDescription {
+        HttpRouteRuleMatchesDescription {
+            path: self.path.as_ref().map(ToString::to_string),
+            method: self.method.as_ref().map(|m| m.method.to_string()),
+            headers: self
+                .headers
+                .iter()
+                .flat_map(|h| &h.header_matches)
+                .map(ToString::to_string)
+                .collect(),
+            query_params: self
+                .query_params
+                .iter()
+                .flat_map(|q| &q.query_param_matches)
+                .map(ToString::to_string)
+                .collect(),
+        }
     }
 
     /// Get the path matcher for this rule (used for extracting prefix information)
@@ -1,6 +1,7 @@
 use super::Match;
 use super::score::HttpRouteRuleMatchesScore;
 use crate::util::get_regex;
+use std::fmt::{Display, Formatter};
 use tracing::{debug, instrument};
 
 /// Path matching strategies for HTTP routes.
@@ -56,6 +57,16 @@ impl Default for PathMatch {
 ///     matched_prefix: None, // Exact matches don't provide prefix info
 /// }
 /// ```
+impl Display for PathMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathMatch::Exact(path) => write!(f, "Exact {path}"),
+            PathMatch::Prefix(prefix) => write!(f, "PathPrefix {prefix}"),
+            PathMatch::RegularExpression(pattern) => write!(f, "RegularExpression {pattern}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PathMatchResult {
     /// Whether the path matched
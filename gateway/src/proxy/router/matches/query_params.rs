@@ -3,6 +3,7 @@ use super::score::HttpRouteRuleMatchesScore;
 use crate::util::get_regex;
 use getset::Getters;
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 use tracing::{debug, instrument};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -63,6 +64,16 @@ impl QueryParamMatch {
     }
 }
 
+impl Display for QueryParamMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = &self.name_match.0;
+        match &self.value_match {
+            QueryParamValueMatch::Exact(value) => write!(f, "{name}={value}"),
+            QueryParamValueMatch::RegularExpression(pattern) => write!(f, "{name} ~ {pattern}"),
+        }
+    }
+}
+
 #[derive(Debug, Getters, PartialEq, Default, Clone)]
 pub struct QueryParamsMatch {
     pub(crate) query_param_matches: Vec<QueryParamMatch>,
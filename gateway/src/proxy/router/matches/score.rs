@@ -2,6 +2,7 @@ use super::headers::HeadersMatch;
 use super::method::MethodMatch;
 use super::path::PathMatch;
 use super::query_params::QueryParamsMatch;
+use serde::Serialize;
 use std::cell::Cell;
 use std::cmp::Ordering;
 use tracing::instrument;
@@ -24,39 +25,54 @@ impl PartialOrd for HttpRouteRuleMatchesScore {
 impl Ord for HttpRouteRuleMatchesScore {
     #[instrument(skip(self, other), name = "Score::cmp")]
     fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other).0
+    }
+}
+
+/// The criterion deciding between two matching rules, in order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCriterion {
+    ExactPath,
+    PathLength,
+    Method,
+    HeadersCount,
+    QueryParamsCount,
+}
+
+impl HttpRouteRuleMatchesScore {
+    /// Compares two scores, the lesser being the more specific match, along with the
+    /// criterion that decided. No criterion is returned for equal scores.
+    pub fn compare(&self, other: &Self) -> (Ordering, Option<ScoreCriterion>) {
         if self == other {
-            return Ordering::Equal;
+            return (Ordering::Equal, None);
         }
 
         match (self.path_exact.get(), other.path_exact.get()) {
-            (true, false) => return Ordering::Less,
-            (false, true) => return Ordering::Greater,
+            (true, false) => return (Ordering::Less, Some(ScoreCriterion::ExactPath)),
+            (false, true) => return (Ordering::Greater, Some(ScoreCriterion::ExactPath)),
             _ => {}
         };
 
         match (self.path_length.get(), other.path_length.get()) {
-            (Some(len1), Some(len2)) => match len1.cmp(&len2) {
-                Ordering::Less => return Ordering::Greater,
-                Ordering::Greater => return Ordering::Less,
-                Ordering::Equal => {}
-            },
-            (Some(_), None) => return Ordering::Less,
+            (Some(len1), Some(len2)) if len1 != len2 => {
+                return (len2.cmp(&len1), Some(ScoreCriterion::PathLength));
+            }
+            (Some(_), None) => return (Ordering::Less, Some(ScoreCriterion::PathLength)),
             _ => {}
         };
 
         match (self.method.get(), other.method.get()) {
-            (true, false) => return Ordering::Less,
-            (false, true) => return Ordering::Greater,
+            (true, false) => return (Ordering::Less, Some(ScoreCriterion::Method)),
+            (false, true) => return (Ordering::Greater, Some(ScoreCriterion::Method)),
             _ => {}
         };
 
         match (self.headers_count.get(), other.headers_count.get()) {
-            (Some(count1), Some(count2)) => match count1.cmp(&count2) {
-                Ordering::Less => return Ordering::Greater,
-                Ordering::Greater => return Ordering::Less,
-                Ordering::Equal => {}
-            },
-            (Some(_), None) => return Ordering::Less,
+            (Some(count1), Some(count2)) if count1 != count2 => {
+                return (count2.cmp(&count1), Some(ScoreCriterion::HeadersCount));
+            }
+            (Some(_), None) => return (Ordering::Less, Some(ScoreCriterion::HeadersCount)),
             _ => {}
         };
 
@@ -64,20 +80,16 @@ impl Ord for HttpRouteRuleMatchesScore {
             self.query_params_count.get(),
             other.query_params_count.get(),
         ) {
-            (Some(count1), Some(count2)) => match count1.cmp(&count2) {
-                Ordering::Less => return Ordering::Greater,
-                Ordering::Greater => return Ordering::Less,
-                Ordering::Equal => {}
-            },
-            (Some(_), None) => return Ordering::Less,
+            (Some(count1), Some(count2)) if count1 != count2 => {
+                return (count2.cmp(&count1), Some(ScoreCriterion::QueryParamsCount));
+            }
+            (Some(_), None) => return (Ordering::Less, Some(ScoreCriterion::QueryParamsCount)),
             _ => {}
         };
 
-        Ordering::Equal
+        (Ordering::Equal, None)
     }
-}
 
-impl HttpRouteRuleMatchesScore {
     pub fn path(&self, path_match: &PathMatch) {
         match path_match {
             PathMatch::Exact(_) => {
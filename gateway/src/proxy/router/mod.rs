@@ -1,23 +1,31 @@
+pub mod circuit_breaker;
 pub mod endpoints;
+mod explain;
 mod matches;
 mod routes;
 pub mod topology;
 
+use crate::proxy::router::circuit_breaker::CircuitBreaker;
 use crate::proxy::router::matches::{HostMatch, HostValueMatch};
-use crate::proxy::router::routes::HttpRouteBuilder;
+use crate::proxy::router::routes::{HttpRouteBuilder, HttpRouteMatchResult};
 use crate::proxy::router::topology::{TopologyLocation, TopologyLocationMatch};
+use crate::proxy::upstream_tls::UpstreamTls;
 use enumflags2::BitFlags;
 use getset::{CopyGetters, Getters};
 use http::request::Parts;
-use itertools::Itertools;
-pub use matches::HttpRouteRuleMatches;
+use itertools::{Either, Itertools};
+pub use matches::{HttpRouteRuleMatches, HttpRouteRuleMatchesDescription};
 pub use routes::HttpRoute;
 pub use routes::HttpRouteRule;
+pub use routes::HttpRouteRuleUniqueId;
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, instrument};
 use typed_builder::TypedBuilder;
+use vg_core::config::gateway::types::http::filters::RequestHeaderModifier;
+use vg_core::config::gateway::types::net::{BackendTls, SlowStart};
 use vg_core::net::Hostname;
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -85,7 +93,7 @@ impl HttpRouterMatchResult {
 pub struct HttpRouterBuilder {
     current_location: Arc<TopologyLocation>,
     host_value_matches: Vec<HostValueMatch>,
-    routes_builders: Vec<HttpRouteBuilder>,
+    routes: Vec<Either<HttpRouteBuilder, Arc<HttpRoute>>>,
 }
 
 impl HttpRouterBuilder {
@@ -93,7 +101,7 @@ impl HttpRouterBuilder {
         HttpRouterBuilder {
             current_location,
             host_value_matches: Vec::new(),
-            routes_builders: Vec::new(),
+            routes: Vec::new(),
         }
     }
 
@@ -105,9 +113,9 @@ impl HttpRouterBuilder {
         HttpRouter {
             host_matches: hosts,
             routes: self
-                .routes_builders
+                .routes
                 .into_iter()
-                .map(|b| Arc::new(b.build()))
+                .map(|route| route.either(|b| Arc::new(b.build()), |route| route))
                 .collect(),
         }
     }
@@ -118,11 +126,17 @@ impl HttpRouterBuilder {
     {
         let mut builder = HttpRouteBuilder::new(&self.current_location);
         factory(&mut builder);
-        self.routes_builders.push(builder);
+        self.routes.push(Either::Left(builder));
 
         self
     }
 
+    /// Adds a route built for a previous router, so it is shared rather than rebuilt
+    pub fn add_built_route(&mut self, route: Arc<HttpRoute>) -> &mut Self {
+        self.routes.push(Either::Right(route));
+        self
+    }
+
     #[allow(dead_code)] // Public API for exact host matching
     pub fn add_exact_host(&mut self, host: &Hostname) -> &mut Self {
         let host_value_match = HostValueMatch::Exact(host.clone());
@@ -139,12 +153,33 @@ impl HttpRouterBuilder {
 }
 
 impl HttpRouter {
+    pub fn routes(&self) -> &[Arc<HttpRoute>] {
+        &self.routes
+    }
+
+    /// The hosts accepted by the router in readable form, empty when any host is accepted
+    pub fn hosts(&self) -> Vec<String> {
+        self.host_matches.describe()
+    }
+
     #[instrument("match_route", skip(self, parts))]
     pub fn match_route(&self, parts: &Parts) -> Option<HttpRouterMatchResult> {
         if !self.host_matches.matches(&parts.headers) {
             return None;
         }
 
+        self.best_route(parts).map(|(i, match_result)| {
+            debug!("Returning matched route at index {}", i);
+            HttpRouterMatchResult::new(
+                self.routes[i].clone(),
+                match_result.rule().unwrap().clone(),
+                match_result.matched_prefix().cloned(),
+            )
+        })
+    }
+
+    /// The index of the route with the best matching rule, the first declared on ties
+    fn best_route(&self, parts: &Parts) -> Option<(usize, HttpRouteMatchResult)> {
         self.routes
             .iter()
             .enumerate()
@@ -152,20 +187,12 @@ impl HttpRouter {
                 let match_result = route.matches(parts);
                 if match_result.is_matched() {
                     debug!("Route {} matched with rule", i);
-                    Some((i, route, match_result))
+                    Some((i, match_result))
                 } else {
                     None
                 }
             })
-            .min_by(|(_, _, lhs), (_, _, rhs)| lhs.score().unwrap().cmp(rhs.score().unwrap()))
-            .map(|(i, route, match_result)| {
-                debug!("Returning matched route at index {}", i);
-                HttpRouterMatchResult::new(
-                    route.clone(),
-                    match_result.rule().unwrap().clone(),
-                    match_result.matched_prefix().cloned(),
-                )
-            })
+            .min_by(|(_, lhs), (_, rhs)| lhs.score().unwrap().cmp(rhs.score().unwrap()))
     }
 }
 
@@ -176,13 +203,59 @@ pub struct HttpBackend {
 
     #[getset(get = "pub")]
     endpoints: HashMap<BitFlags<TopologyLocationMatch>, Vec<HttpBackendEndpoint>>,
+
+    slow_start: Option<SlowStart>,
+
+    #[getset(get = "pub")]
+    circuit_breaker: Option<CircuitBreaker>,
+
+    /// Applied to upstream requests sent to this backend, after the filters of the rule
+    #[getset(get = "pub")]
+    request_header_modifier: Option<RequestHeaderModifier>,
+
+    /// Upstream requests are sent plain HTTP without it
+    #[getset(get = "pub")]
+    tls: Option<Arc<UpstreamTls>>,
+}
+
+impl HttpBackend {
+    pub fn contains_endpoint(&self, addr: SocketAddr) -> bool {
+        self.endpoints
+            .values()
+            .flatten()
+            .any(|endpoint| endpoint.addr == addr)
+    }
+
+    /// The relative selection weight of an endpoint, between the slow start floor and 1.0.
+    /// Endpoints without a readiness time, or backends without slow start, are always at 1.0.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn endpoint_weight(&self, endpoint: &HttpBackendEndpoint, now: Instant) -> f64 {
+        let (Some(slow_start), Some(ready_since)) = (&self.slow_start, endpoint.ready_since) else {
+            return 1.0;
+        };
+
+        let window = Duration::from_secs(u64::from(*slow_start.window_seconds()));
+        let elapsed = now.saturating_duration_since(ready_since);
+        if window.is_zero() || elapsed >= window {
+            return 1.0;
+        }
+
+        let floor = f64::from((*slow_start.min_weight_percent()).min(100)) / 100.0;
+        let progress = elapsed.as_secs_f64() / window.as_secs_f64();
+        floor + (1.0 - floor) * progress
+    }
 }
 
 pub struct HttpBackendBuilder {
     current_location: Arc<TopologyLocation>,
     weight: i32,
     port: Option<u16>,
-    endpoints: Vec<(TopologyLocation, HttpBackendEndpoint)>,
+    slow_start: Option<SlowStart>,
+    circuit_breaker: Option<CircuitBreaker>,
+    request_header_modifier: Option<RequestHeaderModifier>,
+    tls: Option<Arc<UpstreamTls>>,
+    endpoints: Vec<(TopologyLocation, Vec<String>, HttpBackendEndpoint)>,
+    external_endpoints: Vec<HttpBackendEndpoint>,
 }
 
 impl HttpBackendBuilder {
@@ -191,7 +264,12 @@ impl HttpBackendBuilder {
             current_location: current_location.clone(),
             weight: 1,
             port: None,
+            slow_start: None,
+            circuit_breaker: None,
+            request_header_modifier: None,
+            tls: None,
             endpoints: Vec::new(),
+            external_endpoints: Vec::new(),
         }
     }
 
@@ -201,11 +279,15 @@ impl HttpBackendBuilder {
         let endpoints: HashMap<_, _> = self
             .endpoints
             .into_iter()
-            .map(|(location, mut endpoint)| {
+            .map(|(location, zone_hints, mut endpoint)| {
                 // Overwrite the port in the endpoint's SocketAddr
                 let addr = SocketAddr::new(endpoint.addr.ip(), port);
                 endpoint.addr = addr;
-                let score = TopologyLocationMatch::matches(&self.current_location, &location);
+                let score = TopologyLocationMatch::matches_with_hints(
+                    &self.current_location,
+                    &location,
+                    &zone_hints,
+                );
                 let score = if score.contains(TopologyLocationMatch::Node) {
                     BitFlags::from(TopologyLocationMatch::Node)
                 } else if score.contains(TopologyLocationMatch::Zone) {
@@ -215,11 +297,19 @@ impl HttpBackendBuilder {
                 };
                 (score, endpoint)
             })
+            .chain(self.external_endpoints.into_iter().map(|mut endpoint| {
+                endpoint.addr = SocketAddr::new(endpoint.addr.ip(), port);
+                (BitFlags::empty(), endpoint)
+            }))
             .into_group_map();
 
         HttpBackend {
             weight: self.weight,
             endpoints,
+            slow_start: self.slow_start,
+            circuit_breaker: self.circuit_breaker,
+            request_header_modifier: self.request_header_modifier,
+            tls: self.tls,
         }
     }
 
@@ -233,11 +323,51 @@ impl HttpBackendBuilder {
         self
     }
 
-    pub fn add_endpoint(&mut self, ip_addr: IpAddr, location: TopologyLocation) -> &mut Self {
+    pub fn with_slow_start(&mut self, slow_start: Option<SlowStart>) -> &mut Self {
+        self.slow_start = slow_start;
+        self
+    }
+
+    pub fn with_circuit_breaker(&mut self, circuit_breaker: Option<CircuitBreaker>) -> &mut Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    pub fn with_request_header_modifier(
+        &mut self,
+        request_header_modifier: Option<RequestHeaderModifier>,
+    ) -> &mut Self {
+        self.request_header_modifier = request_header_modifier;
+        self
+    }
+
+    pub fn with_tls(&mut self, tls: Option<&BackendTls>) -> &mut Self {
+        self.tls = tls.map(|tls| Arc::new(UpstreamTls::new(tls)));
+        self
+    }
+
+    pub fn add_endpoint(
+        &mut self,
+        ip_addr: IpAddr,
+        location: TopologyLocation,
+        zone_hints: Vec<String>,
+        ready_since: Option<Instant>,
+    ) -> &mut Self {
         let endpoint = HttpBackendEndpoint::builder()
             .addr(SocketAddr::new(ip_addr, 0))
+            .ready_since(ready_since)
             .build();
-        self.endpoints.push((location, endpoint));
+        self.endpoints.push((location, zone_hints, endpoint));
+        self
+    }
+
+    /// Adds an endpoint resolved from a hostname. It is outside of the cluster topology, so it is
+    /// never preferred as node or zone local
+    pub fn add_external_endpoint(&mut self, ip_addr: IpAddr) -> &mut Self {
+        let endpoint = HttpBackendEndpoint::builder()
+            .addr(SocketAddr::new(ip_addr, 0))
+            .build();
+        self.external_endpoints.push(endpoint);
         self
     }
 }
@@ -246,4 +376,94 @@ impl HttpBackendBuilder {
 pub struct HttpBackendEndpoint {
     #[getset(get_copy = "pub")]
     addr: SocketAddr,
+
+    /// When the endpoint was first seen after startup, `None` for endpoints that are warmed up
+    #[builder(default)]
+    ready_since: Option<Instant>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(slow_start: Option<SlowStart>) -> HttpBackend {
+        let mut builder = HttpBackendBuilder::new(&Arc::new(TopologyLocation::default()));
+        builder.with_slow_start(slow_start);
+        builder.build()
+    }
+
+    fn endpoint(ready_since: Option<Instant>) -> HttpBackendEndpoint {
+        HttpBackendEndpoint::builder()
+            .addr("10.0.0.1:80".parse().unwrap())
+            .ready_since(ready_since)
+            .build()
+    }
+
+    #[test]
+    fn test_external_endpoints_outside_topology() {
+        let mut builder = HttpBackendBuilder::new(&Arc::new(TopologyLocation::default()));
+        builder
+            .with_port(443)
+            .add_endpoint(
+                "10.0.0.1".parse().unwrap(),
+                TopologyLocation::default(),
+                Vec::new(),
+                None,
+            )
+            .add_external_endpoint("192.0.2.1".parse().unwrap());
+        let backend = builder.build();
+
+        let addrs = |location_match: BitFlags<TopologyLocationMatch>| {
+            backend.endpoints()[&location_match]
+                .iter()
+                .map(HttpBackendEndpoint::addr)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            addrs(TopologyLocationMatch::Node.into()),
+            vec!["10.0.0.1:443".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(
+            addrs(BitFlags::empty()),
+            vec!["192.0.2.1:443".parse::<SocketAddr>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_endpoint_weight_without_slow_start() {
+        let now = Instant::now();
+        let backend = backend(None);
+
+        assert!((backend.endpoint_weight(&endpoint(Some(now)), now) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_endpoint_weight_ramps_linearly() {
+        let ready_since = Instant::now();
+        let backend = backend(Some(
+            SlowStart::builder()
+                .window_seconds(100)
+                .min_weight_percent(20)
+                .build(),
+        ));
+        let endpoint = endpoint(Some(ready_since));
+
+        let weight = backend.endpoint_weight(&endpoint, ready_since);
+        assert!((weight - 0.2).abs() < 1e-9);
+
+        let weight = backend.endpoint_weight(&endpoint, ready_since + Duration::from_secs(50));
+        assert!((weight - 0.6).abs() < 1e-9);
+
+        let weight = backend.endpoint_weight(&endpoint, ready_since + Duration::from_secs(100));
+        assert!((weight - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_endpoint_weight_for_warmed_endpoint() {
+        let backend = backend(Some(SlowStart::builder().window_seconds(100).build()));
+
+        assert!(
+            (backend.endpoint_weight(&endpoint(None), Instant::now()) - 1.0).abs() < f64::EPSILON
+        );
+    }
 }
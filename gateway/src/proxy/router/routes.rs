@@ -5,6 +5,7 @@ use crate::proxy::router::topology::TopologyLocation;
 use crate::proxy::router::{HttpBackend, HttpBackendBuilder, HttpRouteRuleMatches};
 use getset::Getters;
 use http::request::Parts;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::{debug, instrument};
 use vg_core::config::gateway::types::http::filters::HttpRouteFilter;
@@ -111,6 +112,10 @@ impl HttpRouteMatchResult {
 /// context information that can be used by filters (e.g., matched prefix for redirects).
 #[derive(Debug, Getters, Clone, PartialEq)]
 pub struct HttpRoute {
+    /// Namespaced name of the `HTTPRoute` the route was generated from
+    #[getset(get = "pub")]
+    key: Option<String>,
+
     #[getset(get = "pub")]
     host_header_match: HostHeaderMatch,
 
@@ -215,6 +220,7 @@ impl HttpRoute {
 }
 
 pub struct HttpRouteBuilder {
+    key: Option<String>,
     current_location: Arc<TopologyLocation>,
     host_header_match_builder: HostHeaderMatchBuilder,
     rule_builders: Vec<HttpRouteRuleBuilder>,
@@ -223,6 +229,7 @@ pub struct HttpRouteBuilder {
 impl HttpRouteBuilder {
     pub fn new(current_location: &Arc<TopologyLocation>) -> Self {
         HttpRouteBuilder {
+            key: None,
             current_location: current_location.clone(),
             host_header_match_builder: HostHeaderMatch::builder(),
             rule_builders: Vec::new(),
@@ -231,6 +238,7 @@ impl HttpRouteBuilder {
 
     pub fn build(self) -> HttpRoute {
         HttpRoute {
+            key: self.key,
             host_header_match: self.host_header_match_builder.build(),
             rules: self
                 .rule_builders
@@ -240,6 +248,11 @@ impl HttpRouteBuilder {
         }
     }
 
+    pub fn with_key<S: AsRef<str>>(&mut self, key: S) -> &mut Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
     pub fn add_exact_host(&mut self, host: &Hostname) -> &mut Self {
         self.host_header_match_builder.with_exact_host(host);
         self
@@ -307,6 +320,15 @@ pub struct HttpRouteRule {
     filters: Vec<HttpRouteFilter>,
 }
 
+impl HttpRouteRule {
+    /// Finds the backend that owns an upstream endpoint address
+    pub fn backend_for(&self, addr: SocketAddr) -> Option<&HttpBackend> {
+        self.backends
+            .iter()
+            .find(|backend| backend.contains_endpoint(addr))
+    }
+}
+
 pub struct HttpRouteRuleBuilder {
     unique_id: HttpRouteRuleUniqueId,
     current_location: Arc<TopologyLocation>,
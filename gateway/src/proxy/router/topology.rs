@@ -21,6 +21,30 @@ impl TopologyLocationMatch {
         }
         score
     }
+
+    /// Like [`TopologyLocationMatch::matches`], but respects the zone hints published on the
+    /// `EndpointSlice`. When hints are present they decide whether the endpoint is zone local,
+    /// and an endpoint hinted away from the current zone is never treated as local.
+    pub fn matches_with_hints(
+        current: &TopologyLocation,
+        endpoint: &TopologyLocation,
+        zone_hints: &[String],
+    ) -> BitFlags<Self> {
+        if zone_hints.is_empty() {
+            return Self::matches(current, endpoint);
+        }
+
+        let hinted = current
+            .zone
+            .as_ref()
+            .is_some_and(|zone| zone_hints.contains(zone));
+
+        if hinted {
+            Self::matches(current, endpoint) | Self::Zone
+        } else {
+            BitFlags::empty()
+        }
+    }
 }
 
 #[derive(Default, Getters, Debug, Clone, PartialEq, Eq, TypedBuilder, Hash)]
@@ -31,3 +55,47 @@ pub struct TopologyLocation {
     #[getset(get = "pub")]
     zone: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(node: &str, zone: &str) -> TopologyLocation {
+        TopologyLocation::builder()
+            .node(Some(node.to_string()))
+            .zone(Some(zone.to_string()))
+            .build()
+    }
+
+    #[test]
+    fn test_matches_with_hints_without_hints() {
+        let current = location("node1", "zone1");
+        let endpoint = location("node2", "zone1");
+
+        assert_eq!(
+            TopologyLocationMatch::matches_with_hints(&current, &endpoint, &[]),
+            BitFlags::from(TopologyLocationMatch::Zone)
+        );
+    }
+
+    #[test]
+    fn test_matches_with_hints_for_other_zone() {
+        let current = location("node1", "zone1");
+        let endpoint = location("node3", "zone2");
+        let hints = vec!["zone1".to_string()];
+
+        assert_eq!(
+            TopologyLocationMatch::matches_with_hints(&current, &endpoint, &hints),
+            BitFlags::from(TopologyLocationMatch::Zone)
+        );
+    }
+
+    #[test]
+    fn test_matches_with_hints_excluding_current_zone() {
+        let current = location("node1", "zone1");
+        let endpoint = location("node1", "zone1");
+        let hints = vec!["zone2".to_string()];
+
+        assert!(TopologyLocationMatch::matches_with_hints(&current, &endpoint, &hints).is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+use pingora::upstreams::peer::HttpPeer;
+use pingora::utils::tls::{CertKey, WrappedX509};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::warn;
+use vg_core::config::gateway::types::net::{BackendTls, ClientCertificate};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// TLS settings of a backend, with its CA certificates parsed once when the router is built
+/// rather than for every connection
+#[derive(Debug)]
+pub struct UpstreamTls {
+    settings: BackendTls,
+    ca: Option<Arc<[WrappedX509]>>,
+    group_key: u64,
+}
+
+impl PartialEq for UpstreamTls {
+    fn eq(&self, other: &Self) -> bool {
+        // The CA certificates are parsed from the settings
+        self.settings == other.settings
+    }
+}
+
+impl Eq for UpstreamTls {}
+
+/// A client certificate chain and its private key, with a fingerprint telling a rotated
+/// certificate apart from the one it replaces
+pub struct ClientCertKey {
+    cert_key: Arc<CertKey>,
+    fingerprint: u64,
+}
+
+impl UpstreamTls {
+    pub fn new(tls: &BackendTls) -> Self {
+        // Connections are only reused for the same TLS settings, even between the same addresses
+        let mut hasher = DefaultHasher::new();
+        tls.hash(&mut hasher);
+
+        UpstreamTls {
+            settings: tls.clone(),
+            ca: tls
+                .ca_certificates()
+                .as_deref()
+                .map(|pem| parse_ca_certificates(pem).into()),
+            group_key: hasher.finish(),
+        }
+    }
+
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.settings.client_certificate().as_ref()
+    }
+
+    /// A peer verifying the certificate of the endpoint for the SNI, and presenting the client
+    /// certificate when the backend requires one
+    pub fn peer(&self, addr: SocketAddr, client_cert_key: Option<&ClientCertKey>) -> HttpPeer {
+        let mut peer = HttpPeer::new(addr, true, self.settings.sni().as_ref().to_string());
+        peer.options.verify_cert = true;
+        peer.options.verify_hostname = true;
+        peer.options.ca = self.ca.clone();
+        peer.group_key = self.group_key;
+        if let Some(client_cert_key) = client_cert_key {
+            // Connections presenting a certificate that has since been rotated are not reused
+            let mut hasher = DefaultHasher::new();
+            self.group_key.hash(&mut hasher);
+            client_cert_key.fingerprint.hash(&mut hasher);
+            peer.group_key = hasher.finish();
+            peer.client_cert_key = Some(client_cert_key.cert_key.clone());
+        }
+        peer
+    }
+}
+
+/// Parses PEM encoded CA certificates. Invalid certificates are left out, which only ever makes
+/// fewer endpoints trusted
+fn parse_ca_certificates(pem: &str) -> Vec<WrappedX509> {
+    CertificateDer::pem_slice_iter(pem.as_bytes())
+        .filter_map(|certificate| match certificate {
+            Ok(certificate) => Some(certificate.to_vec()),
+            Err(err) => {
+                warn!("Skipping invalid CA certificate: {}", err);
+                None
+            }
+        })
+        .filter(|der| X509Certificate::from_der(der).is_ok())
+        .map(|der| {
+            WrappedX509::new(der, |der| {
+                X509Certificate::from_der(der)
+                    .expect("certificate was validated")
+                    .1
+            })
+        })
+        .collect()
+}
+
+/// Parses a client certificate chain followed by its private key, as served by the control plane
+pub fn parse_client_certificate(pem: &[u8]) -> Option<ClientCertKey> {
+    let certificates: Vec<_> = CertificateDer::pem_slice_iter(pem)
+        .filter_map(Result::ok)
+        .map(|certificate| certificate.to_vec())
+        .collect();
+    let key = PrivateKeyDer::from_pem_slice(pem).ok()?;
+    if certificates.is_empty() {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    pem.hash(&mut hasher);
+
+    Some(ClientCertKey {
+        cert_key: Arc::new(CertKey::new(certificates, key.secret_der().to_vec())),
+        fingerprint: hasher.finish(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pingora::connectors::TransportConnector;
+    use std::net::TcpListener;
+    use vg_core::net::Hostname;
+
+    const CA: &str = include_str!("./testcases/ca.pem");
+    const BACKEND: &[u8] = include_bytes!("./testcases/backend.pem");
+    const CLIENT1: &[u8] = include_bytes!("./testcases/client1.pem");
+    const CLIENT2: &[u8] = include_bytes!("./testcases/client2.pem");
+
+    fn backend_tls(sni: &str, ca_certificates: Option<&str>) -> BackendTls {
+        BackendTls::builder()
+            .sni(Hostname::new(sni))
+            .ca_certificates(ca_certificates.map(str::to_string))
+            .build()
+    }
+
+    #[test]
+    fn test_group_key_depends_on_tls_settings() {
+        let addr = SocketAddr::from(([10, 0, 0, 1], 443));
+        let peer = |tls: &BackendTls| UpstreamTls::new(tls).peer(addr, None);
+
+        let a = peer(&backend_tls("a.example.com", None));
+        let b = peer(&backend_tls("b.example.com", None));
+        let a_with_ca = peer(&backend_tls("a.example.com", Some("")));
+
+        assert_eq!(
+            a.group_key,
+            peer(&backend_tls("a.example.com", None)).group_key
+        );
+        assert_ne!(a.group_key, b.group_key);
+        assert_ne!(a.group_key, a_with_ca.group_key);
+        assert_eq!(a.sni, "a.example.com");
+        assert!(a.options.verify_cert && a.options.verify_hostname);
+    }
+
+    /// Accepts TLS connections for a.example.com, with a certificate issued by the test CA
+    fn serve_tls(connections: usize) -> SocketAddr {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let certificates = CertificateDer::pem_slice_iter(BACKEND)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to parse certificates");
+        let key = PrivateKeyDer::from_pem_slice(BACKEND).expect("Failed to parse key");
+        let config = Arc::new(
+            rustls::ServerConfig::builder()
+                .with_no_client_auth()
+                .with_single_cert(certificates, key)
+                .expect("Failed to build TLS config"),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+        let addr = listener.local_addr().expect("Failed to get address");
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let Ok(mut stream) = stream else { continue };
+                let mut connection =
+                    rustls::ServerConnection::new(config.clone()).expect("Failed to accept");
+                // The client aborts the handshake when it does not trust the certificate
+                let _ = connection.complete_io(&mut stream);
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_empty_ca_certificates_reject_peers() {
+        let addr = serve_tls(2);
+        let connector = TransportConnector::new(None);
+
+        let untrusted = UpstreamTls::new(&backend_tls("a.example.com", Some("")));
+        assert!(untrusted.ca.as_ref().is_some_and(|ca| ca.is_empty()));
+        assert!(
+            connector
+                .new_stream(&untrusted.peer(addr, None))
+                .await
+                .is_err()
+        );
+
+        let trusted = UpstreamTls::new(&backend_tls("a.example.com", Some(CA)));
+        assert!(
+            connector
+                .new_stream(&trusted.peer(addr, None))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_group_key_depends_on_client_certificate() {
+        let addr = SocketAddr::from(([10, 0, 0, 1], 443));
+        let tls = UpstreamTls::new(&backend_tls("a.example.com", None));
+        let client1 = parse_client_certificate(CLIENT1).expect("Failed to parse certificate");
+        let client2 = parse_client_certificate(CLIENT2).expect("Failed to parse certificate");
+
+        let with_client1 = tls.peer(addr, Some(&client1));
+        assert!(with_client1.client_cert_key.is_some());
+        assert_eq!(
+            with_client1.group_key,
+            tls.peer(addr, Some(&client1)).group_key
+        );
+        assert_ne!(
+            with_client1.group_key,
+            tls.peer(addr, Some(&client2)).group_key
+        );
+        assert_ne!(with_client1.group_key, tls.peer(addr, None).group_key);
+    }
+
+    #[test]
+    fn test_invalid_ca_certificates_trust_nothing() {
+        let tls = UpstreamTls::new(&backend_tls("a.example.com", Some("not a certificate")));
+
+        assert!(tls.ca.as_ref().is_some_and(|ca| ca.is_empty()));
+        assert!(
+            UpstreamTls::new(&backend_tls("a.example.com", None))
+                .ca
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_client_certificate_requires_key() {
+        assert!(parse_client_certificate(b"").is_none());
+    }
+}